@@ -0,0 +1,532 @@
+# German UI strings, anything missing here is shown in English
+
+## byte_stats.rs
+
+byte-statistics-title = Byte-Statistik
+counting-bytes = Bytes werden gezählt...
+byte-stats-summary = { $size }, Entropie { $entropy } Bit/Byte, { $printable } % druckbar: { $verdict }
+verdict-empty = leer
+verdict-compressed-or-encrypted = vermutlich komprimiert oder verschlüsselt
+verdict-text = vermutlich Text
+verdict-structured-binary = vermutlich strukturierte Binärdaten
+byte-histogram = Byte-Histogramm
+
+## converter.rs
+
+value-to-hex-pattern = Wert als Hex-Muster
+value-label = Wert:{" "}
+value-hint = z. B. 0x400000, -1, 3.14 oder Text
+type-label = Typ:{" "}
+little-endian = Little Endian
+big-endian = Big Endian
+insert = Einfügen
+append-to-the-search-pattern = An das Suchmuster anhängen
+replace = Ersetzen
+replace-the-search-pattern = Das Suchmuster ersetzen
+
+## diff.rs
+
+diff-target-hint = Gespiegelter Ordner, oder eine Datei beim Vergleich einer einzelnen Datei
+folder = Ordner
+file = Datei
+compare-with = Vergleichen mit:{" "}
+
+## dir_tree.rs
+
+directory-tree-title = Verzeichnisbaum
+directory-tree-intro = Verzeichnisse mit den meisten Treffern stehen oben.
+file-matches = { $name }  ({ $matches ->
+    [one] 1 Treffer
+   *[other] { $matches } Treffer
+})
+
+## elevated.rs
+
+retry-skipped-elevated = { $count } übersprungene Dateien mit erhöhten Rechten erneut versuchen
+retry-skipped-elevated-hint = Durchsucht die nicht lesbaren Dateien erneut in einem Hilfsprozess mit Administrator-/Root-Rechten und übernimmt dessen Treffer in die Ergebnisse
+waiting-for-elevated-helper = Warte auf den Hilfsprozess mit erhöhten Rechten…
+
+## export.rs
+
+exporting = Export läuft
+cancel = Abbrechen
+cancelling = Wird abgebrochen...
+writing-to = { $format } wird nach { $path } geschrieben
+written-of-total = { $written } / { $total } Ergebnisse
+
+## file_view.rs
+
+copy = Kopieren
+file-view-empty = Ein Ergebnis auswählen, um es hier anzuzeigen
+open-file-view = Dateiansicht öffnen
+file-view = Dateiansicht
+hex = Hex
+text = Text
+preview-at = Vorschau @ { $offset }
+matches-in-bytes = { $matches ->
+    [one] 1 Treffer
+   *[other] { $matches } Treffer
+} in { $size } Bytes
+minimap-hint = { $offset }, klicken, um zum nächsten Treffer zu springen
+entropy-block-hint = Block bei { $offset }: { $bits } Bit/Byte
+line-column = Zeile { $line }, Spalte { $column }
+match-minimap = Übersicht der Treffer
+entropy-heatmap = Entropie-Heatmap
+go-to-offset = Gehe zu Offset
+go-to-offset-hint = Hexadezimal mit 0x-Präfix, sonst dezimal
+go = Los
+previous-match = Vorheriger Treffer in dieser Datei
+next-match = Nächster Treffer in dieser Datei
+match-n-of-total = Treffer { $n } von { $total }
+
+## file_types.rs
+
+file-types = Dateitypen
+file-types-hint = Nur Dateien mit diesen Endungen durchsuchen, übernimmt auch die Standardoptionen des Typs
+file-type-defaults-title = Standardoptionen der Dateitypen
+file-type-defaults-intro = Optionen, die bei Auswahl eines Dateityps übernommen werden. Sie werden mit den Einstellungen gespeichert.
+category-any = Alle
+category-executables = Ausführbare Dateien
+category-text = Text
+category-documents = Dokumente
+category-archives = Archive
+category-images = Bilder
+
+## filters.rs
+
+filter-direct-children-hint = Nur Treffer direkt in diesem Verzeichnis anzeigen
+clear-filters = Filter zurücksetzen
+saved-filters = Gespeicherte Filter
+no-saved-filters = Noch keine gespeicherten Filter
+saved-filters-hint = Einen unter einem Namen gespeicherten Filterausdruck anwenden
+filter-name = Filtername
+save-filter = Filter speichern
+save-filter-hint = Den aktuellen Filterausdruck unter diesem Namen speichern, ein gleichnamiger wird ersetzt
+delete-filter = Filter löschen
+offsets-from = Offsets von
+offsets-to = bis
+offsets-to-hint = Dateiende
+offsets-to-exclusive = Funde ab diesem Offset werden ausgeblendet
+first-kib = Erstes KiB
+first-kib-hint = Nur Funde im ersten KiB jeder Datei anzeigen
+excluded-directories = Ausgeschlossene Verzeichnisse ({ $count })
+search-this-directory-again = Dieses Verzeichnis wieder durchsuchen
+
+## history.rs
+
+remember = Die letzten
+searches = Suchen merken
+history-pinned-limit-hint = Angeheftete Suchen zählen nicht zum Limit
+no-previous-searches-yet = Noch keine früheren Suchen
+history-pin-hint = Angeheftete Suchen bleiben oben
+re-run = Erneut ausführen
+history-rerun-hint = Diese Suche wiederherstellen und sofort starten
+clear = Leeren
+history-clear-hint = Alle nicht angehefteten Suchen vergessen
+
+## log_pane.rs
+clear-logs = Log leeren
+log-verbosity = Protokoll:
+log-verbosity-hint = Ausführlich protokolliert auch, wie lange jede Datei zum Durchsuchen brauchte
+log-errors-only = Nur Fehler
+log-normal = Normal
+log-verbose = Ausführlich
+filter-log = Protokoll filtern
+copy-all = Alles kopieren
+copy-all-hint = Alle Protokollzeilen mit ihrem Schweregrad kopieren, unabhängig von Ausführlichkeit und Filter
+save-log = Protokoll in Datei speichern…
+
+## main.rs
+
+mode-hex-hint = Für Daten als Paare zur Basis 16. Z. B. 'DE AD BE . 00 00'. '.' passt auf ein Byte.
+mode-text-hint = Für Textdaten. Z. B. 'Mary had a \w+ lamb.'
+auto = Auto
+mode-auto-hint = Text für Dateien, die nach Text aussehen, Hex für Binärdateien (falls das Muster gültiges Hex ist), entschieden anhand der ersten 4 KiB jeder Datei.
+content = Inhalt
+scan-content-hint = Das Muster auf den Dateiinhalt anwenden.
+metadata = Metadaten
+scan-metadata-hint = Das Muster auf EXIF-Felder, PE-Versionsinformationen und ID3-Tags anwenden.
+duplicates = Duplikate
+scan-duplicates-hint = Gruppen byte-identischer Dateien finden. Kein Muster nötig.
+count-only = Nur zählen
+scan-count-only-hint = Nur Treffer pro Datei zählen. Viel schneller, gut als erster Durchgang.
+diff = Vergleich
+scan-diff-hint = Bereiche melden, die sich von der gespiegelten Datei in einem anderen Ordner oder von einer anderen Datei unterscheiden. Kein Muster nötig.
+records = Datensätze
+scan-records-hint = Dateien als Datensätze fester Größe behandeln und das Muster nur an einem Feld jedes Datensatzes anwenden.
+padding = Füllbytes
+scan-padding-hint = Lange Folgen eines wiederholten Bytes oder kurzer sich wiederholender Füllung melden, z. B. 0x00/0xFF-Padding. Kein Muster nötig.
+clear-results-on-new-search = Ergebnisse bei neuer Suche leeren
+search-decoded-content = Dekodierten Inhalt durchsuchen (PDF, Mail, Disk-Images, APK, Firmware, Registry, Ereignisprotokolle)
+search-decoded-content-hint = Den extrahierten Text unterstützter Containerformate statt ihrer Rohbytes durchsuchen
+include-raw-pdf-streams = Rohe PDF-Streams einbeziehen
+all-files = Alle Dateien
+no-hidden-files = Keine versteckten Dateien
+no-hidden-or-system-files = Keine versteckten oder Systemdateien
+skip-system-dirs = Systemverzeichnisse auf Laufwerkswurzeln überspringen
+skip-system-dirs-hint = Bei der Suche ab einer Laufwerkswurzel die Pfade der Ausschlussliste weglassen
+skip-list-hint = Ein Pfad pro Zeile, Verzeichnisse werden samt Inhalt übersprungen
+restore-defaults = Standard wiederherstellen
+first-match-only = Beim ersten Treffer pro Datei aufhören
+first-match-only-hint = Nur melden, ob eine Datei überhaupt passt. Geht nach dem ersten Treffer zur nächsten Datei.
+alignment-hint = Nur Treffer an Offsets melden, die ein Vielfaches der Ausrichtung vom Basis-Offset entfernt sind, z. B. für Datensätze fester Größe nach einem Header
+match-length-hint = Nur Treffer innerhalb dieser Größe in Bytes melden, z. B. für druckbare Folgen
+anchors-hint = z. B. header_end = 0x200, table = 0x1000
+anchors-columns-hint = Benannte Offsets, jeder fügt eine Spalte mit dem Offset des Treffers relativ dazu hinzu
+records-layout-hint = Offset des ersten Datensatzes und Größe jedes Datensatzes in Bytes
+records-field-hint = Wo in jedem Datensatz das Muster passen muss
+padding-run-hint = Kleinste zu meldende Folge in Bytes und längste sich wiederholende Füllung in Bytes
+copy-as-bytes = Als Bytes kopieren
+copy-as-hex-bytes = Als Hex-Bytes kopieren
+find-similar-regions-menu = Ähnliche Bereiche finden...
+open-match-in-cyberchef = Treffer in CyberChef öffnen
+sort-ascending = Aufsteigend sortieren
+sort-descending = Absteigend sortieren
+copy-full-path = Vollständigen Pfad kopieren
+copy-filename = Dateinamen kopieren
+copy-enclosing-dir = Übergeordnetes Verzeichnis kopieren
+exclude-dir-from-results = Dieses Verzeichnis aus den Ergebnissen ausschließen
+exclude-dir-from-searches = … und aus künftigen Suchen
+exclude-dir-hint = Alles unterhalb von { $dir }
+copy-as-file-reference = Als Dateiverweis kopieren
+copy-as-file-reference-hint = Eine file://-URI, wird in Dateimanagern und Notiz-Programmen als die Datei eingefügt
+copy-other-paths-to-this-file = Andere Pfade zu dieser Datei kopieren
+export-imhex-bookmarks = Ergebnisse der Datei als .imhexbm exportieren...
+byte-statistics-menu = Byte-Statistik...
+hash-lookup = Hash-Abfrage
+copy-path-and-offset = Pfad und Offset kopieren
+copy-path-and-offset-hint = Als Pfad:0xOffset, um den Treffer an einen Hex-Editor oder Notizen weiterzugeben
+open-in-imhex-at-offset = In ImHex am Offset öffnen
+offset-inside-decoded-content = Der Offset liegt im aus der Datei dekodierten Inhalt (siehe Fundort), nicht in der Datei selbst
+copy-as-hexdump = Als Hexdump kopieren
+preview-pin-hint = Klicken, um eine angeheftete Vorschau zu öffnen
+disassembly = Disassemblierung
+references-from-code = Referenzen aus dem Code
+find-references = Referenzen suchen
+find-references-hint = Dekodiert den Code der ausführbaren Datei und listet die Befehle auf, die auf die Trefferadresse verweisen, z. B. wo ein String verwendet wird
+searching-references = Code wird nach Referenzen durchsucht...
+no-references-found = Kein Befehl verweist auf diese Adresse
+references-found = { $count } verweisende Befehle (Dateioffset, Adresse, Befehl)
+decoded-structure = Dekodierte Struktur
+decoded-as = Treffer als { $format } gelesen
+no-decoded-structure = Die getroffenen Bytes sind weder Protobuf noch DER
+match = Treffer
+column-match-hint = Inhalt des gefundenen Treffers
+count = Anzahl
+column-count-hint = Wie oft dieser Treffer gefunden wurde
+locations = Fundstellen
+column-locations-hint = Dateien und Offsets, an denen dieser Treffer gefunden wurde
+copy-match = Treffer kopieren
+file-path = Dateipfad
+column-file-path-hint = Pfad der Datei, in der ein Treffer gefunden wurde.
+offset = Offset
+column-offset-hint = Offset in der Datei, an dem der Treffer beginnt.
+preview = Vorschau
+visualize-column = Visualisierungsspalte
+location = Fundort
+column-location-hint = Wo in einem dekodierten Container (z. B. PDF-Seite, Mail-Teil) oder in welchem Metadatenfeld der Treffer gefunden wurde
+verdict = Bewertung
+column-verdict-hint = Ergebnis der letzten Hash-Abfrage für die Datei
+line-col = Zeile:Spalte
+column-line-col-hint = Zeile und Spalte (in Bytes), an der der Treffer beginnt
+encoding = Kodierung
+column-encoding-hint = Geschätzte Textkodierung um den Treffer, z. B. um breite von schmalen Strings zu unterscheiden
+va = VA
+column-va-hint = Virtuelle Adresse, an die der Treffer geladen würde, für PE/ELF/Mach-O-Dateien
+pattern = Muster
+column-pattern-hint = Zeile der Musterdatei, die gepasst hat
+capped-file-hint = Am Trefferlimit angehalten, diese Datei kann weitere Treffer enthalten
+copy-va = VA kopieren
+copy-relative-offset = Relativen Offset kopieren
+past-searches = Frühere Suchen
+presets = Vorlagen
+presets-hint = Gängige Muster und Vorlagen mit Platzhaltern
+pattern-syntax-reference = Referenz der Mustersyntax
+converter-hint = Eine Zahl oder einen String in ein Hex-Muster umwandeln
+enter-regex-here = Regex hier eingeben
+search-pattern-hint = Beispiele: abc.ef, ^hello world$, aa{"{"}3{"}"}h. Mehr in den Tooltips der Modi.
+search-within-results-hint = Das Muster nur auf die Dateien der aktuellen (gefilterten) Ergebnisse anwenden
+filter-results-hint = Wörter passen auf Pfad, Offset oder Vorschautext. Außerdem path:<Glob>, -path:<Glob> zum Ausblenden von Pfaden, offset:<Start>..<Ende> und re:<Regex> auf die Vorschau; alle Begriffe müssen passen.
+unique-matches-only = Nur eindeutige Treffer
+unique-matches-only-hint = Treffer mit identischem Inhalt zu einer Zeile zusammenfassen
+split-view = Geteilte Ansicht
+split-view-hint = Das ausgewählte Ergebnis in einer Hex-Ansicht unter der Tabelle zeigen. Mit Hoch/Runter durch die Ergebnisse blättern
+pop-out = Abdocken
+hide-to-tray-hint = Im Hintergrund weitersuchen, mit einer Benachrichtigung am Ende
+load-patterns-from-file = Muster aus Datei laden...
+load-patterns-hint = Ein Muster pro Zeile, '#' für Kommentare, 'hex:' oder 'text:' wählt den Modus
+paste-pattern-list = Musterliste einfügen
+paste-pattern-list-hint = Fügt je Zeile der Zwischenablage ein Muster zu den verwendeten hinzu, 'hex:' oder 'text:' wählt den Modus
+export = Export
+export-to-csv = Als CSV exportieren...
+quick-export = Schnellexport
+analysis = Analyse
+cluster-files-menu = Gefundene Dateien nach Ähnlichkeit gruppieren...
+byte-statistics-matched-menu = Byte-Statistik der gefundenen Dateien...
+pattern-statistics-menu = Musterstatistik...
+pattern-statistics-hint = Treffer und Dateien pro Muster einer Musterdatei-Suche
+directory-tree-menu = Verzeichnisbaum...
+pattern-tester-menu = Mustertester...
+scheduled-scans-menu = Geplante Suchen...
+scheduled-scans-title = Geplante Suchen
+scheduled-scans-intro = Jede Suche läuft einmal täglich zu ihrer Uhrzeit, solange quer läuft, nach einer gerade laufenden Suche. Ihre Treffer werden an eine JSON-Lines-Datei angehängt, markiert mit Suche und Lauf.
+no-scheduled-scans = Noch nichts geplant
+scheduled-scan-details = { $pattern } in { $root }
+scheduled-scan-export-hint = JSON-Lines-Datei, an die die Treffer jedes Laufs angehängt werden. ~ ist das Home-Verzeichnis, {"{"}date{"}"} beginnt jeden Tag eine neue Datei.
+scheduled-scan-outcome = { $matches } Treffer, { $new } neu ({ $files } neue Dateien) am { $time }
+alert-on-new-findings = Melden
+alert-on-new-findings-hint = Findet ein Lauf Treffer, die der vorige nicht hatte (neue Dateien oder neue Offsets), eine Benachrichtigung zeigen und sie an den Webhook senden, falls eingerichtet. Neue Treffer werden außerdem in der JSON-Lines-Datei als "new" markiert und in den Ergebnissen hervorgehoben.
+scheduled-scan-invalid-pattern = Das Muster ist ungültig
+run-now = Jetzt ausführen
+remove-scheduled-scan = Entfernen
+schedule-current-search = Aktuelle Suche planen
+schedule-current-search-hint = Fügt Muster, Ordner und Optionen wie jetzt eingestellt hinzu, Ausführung jede Nacht um 02:00
+settings = Einstellungen
+imhex-binary = ImHex-Programm:{" "}
+imhex-binary-hint = Pfad zur (oder Name der) ImHex-Programmdatei
+virustotal-api-key = VirusTotal-API-Schlüssel:{" "}
+virustotal-api-key-hint = Leer lassen, um Abfragen stattdessen im Browser zu öffnen
+malwarebazaar-api-key = MalwareBazaar-API-Schlüssel:{" "}
+quick-export-path = Pfad für Schnellexport:{" "}
+reset = Zurücksetzen
+quick-export-on-finish = Schnellexport nach jeder Suche
+export-meta-json = Eine .meta.json neben Exporte schreiben
+export-meta-json-hint = Muster, Modi, Suchordner, Filter, Programmversion und Suchzeiten
+defang-exports = URLs, E-Mail- und IP-Adressen in CSV-Exporten entschärfen
+defang-exports-hint = hxxp://example[.]com statt eines Links, der sich beim Ansehen des Berichts öffnen lässt
+cyberchef-recipe = CyberChef-Rezept:{" "}
+cyberchef-recipe-hint = Rezept für in CyberChef geöffnete Treffer, z. B. From_Hex('Auto')
+hexdump-width = Hexdump-Breite:{" "}
+hexdump-grouping = Hexdump-Gruppierung:{" "}
+hexdump-grouping-hint = Bytes pro Gruppe, 0 schaltet die Gruppierung ab
+hexdump-context-bytes = Hexdump-Kontextbytes:{" "}
+hexdump-context-hint = Bytes vor und nach einem Treffer in Vorschauen, bleibt zwischen Starts erhalten
+context-before = davor:{" "}
+context-after = danach:{" "}
+context-bytes = { $before } Bytes vor und { $after } nach dem Treffer, Änderung um eine Zeile
+hide-while-scanning = Ausblenden statt schließen, solange eine Suche läuft
+watch-clipboard = Kopierten Text nachschlagen
+watch-clipboard-hint = Durchsucht den Suchordner, sobald ein Hex-String oder eine kurze Textzeile kopiert wird, und zeigt die Treffer in einer Einblendung
+clipboard-lookup-title = Kopiert: { $snippet }
+clipboard-lookup-running = Suche läuft...
+clipboard-lookup-summary = { $matches } Treffer in { $files } Dateien
+search-with-quer = Mit quer durchsuchen
+register-shell-integration = Beim Desktop registrieren
+register-shell-integration-hint = Öffnet quer://search?dir=...&hex=...-Links in quer und fügt "Mit quer durchsuchen" zum Kontextmenü von Ordnern und Dateien hinzu
+unregister-shell-integration = Registrierung entfernen
+shell-integration-unsupported = Nur unter Linux und Windows unterstützt
+start-linked-search-title = Suche starten?
+start-linked-search-prompt = Ein Link möchte { $path } durchsuchen lassen nach
+single-instance = Nur eine Instanz
+single-instance-hint = Ein erneuter Start von quer, z. B. aus dem Kontextmenü eines Ordners oder über einen quer://-Link, übergibt die Suche an dieses Fenster, statt ein zweites quer zu starten. Übergebene Suchen warten, bis die laufende fertig ist.
+persist-results = Letzte Ergebnisse behalten
+persist-results-hint = Speichert die Ergebnisse der letzten Suche komprimiert neben den Einstellungen, damit sie nach einem Neustart von quer wiederhergestellt werden können
+restore-last-results-desc = Die letzte Suche fand { $count } Ergebnisse für „{ $pattern }“ in { $root } ({ $time }).
+restore-last-results = Letzte Ergebnisse wiederherstellen
+dismiss = Verwerfen
+worker-threads = Worker-Threads:{" "}
+worker-threads-hint = Threads, die Dateien lesen und durchsuchen, gilt ab der nächsten Suche
+low-priority-workers-hint = Suchen mit Leerlauf-Priorität für CPU und I/O ausführen, damit Hintergrundsuchen den Rechner nicht ausbremsen
+pin-workers-to-cores = Worker an Kerne binden:{" "}
+worker-cores-hint = z. B. 0-3, 6
+pin-workers-to-cores-hint = Worker verteilen sich reihum auf diese Kerne, leer lassen, um es dem Betriebssystem zu überlassen
+batch-small-file-reads-hint = Schneller auf NVMe-Laufwerken mit vielen kleinen Dateien
+post-findings-to-webhook = Treffer per POST an Webhook senden
+webhook-url = Webhook-URL:{" "}
+webhook-headers = Header (einer pro Zeile als 'Name: Wert'):
+webhook-headers-hint = Z. B. 'Authorization: Bearer <token>'
+help = Hilfe
+pattern-reference = Musterreferenz
+about = Über
+about-title = quer - Ein Werkzeug zum Finden von Daten
+results-popped-out = Die Ergebnisse werden in einem eigenen Fenster angezeigt.
+reattach = Wieder andocken
+similarity-clusters = Ähnlichkeitsgruppen
+min-similarity-score = Min. Ähnlichkeitswert:{" "}
+recompute = Neu berechnen
+hashing-files = Dateien werden gehasht...
+folder-to-search = Zu durchsuchender Ordner:{" "}
+open = Öffnen
+mode = Modus:
+scan = Suche:
+max-hits-per-file = Max. Treffer (pro Datei):{" "}
+alignment-0-to-disable = Ausrichtung (0 zum Abschalten):{" "}
+from-offset = {" "}ab Offset{" "}
+match-length-min = Trefferlänge: min{" "}
+max-0-for-no-limit = {" "}max (0 für kein Limit){" "}
+anchors = Anker:{" "}
+custom-column = Spalte:{" "}
+custom-column-placeholder = z. B. size = u16le(len) oder host = $host
+custom-column-hint = Fügt eine für jeden Treffer berechnete Spalte hinzu: $name nimmt eine benannte Gruppe, sonst ein Ausdruck über den Treffer mit len, offset, u8(i), u16le(i), u16be(i), u32le(i), u32be(i) und hex(x), wobei i ab dem Trefferanfang zählt und bis zu 256 Bytes hinter sein Ende reichen darf
+custom = Eigene
+custom-column-header-hint = Beim Suchen für jeden Treffer berechnet, für andere Werte die Spaltendefinition ändern und erneut suchen
+records-first-at = Datensätze: erster bei{" "}
+length = {" "}Länge{" "}
+field-offset = Feld: Offset{" "}
+length-0-for-rest-of-record = {" "}Länge (0 für den Rest des Datensatzes){" "}
+padding-min-run = Füllbytes: min. Folge{" "}
+max-period = {" "}max. Periode{" "}
+stop = Stopp
+search-within-results = In Ergebnissen suchen
+clear-results = Ergebnisse leeren
+searching-for = Suche nach:{" "}
+filter = Filter...
+language-label = Sprache:{" "}
+copy-as-hex = Als Hex kopieren: { $value }
+copy-as-decimal = Als Dezimalzahl kopieren: { $value }
+results-page = Ergebnisse { $start } - { $end } von { $total } (Seite { $page } von { $pages })
+n-more = ... { $more } weitere
+anchor-column-hint = Offset relativ zu { $name } ({ $offset })
+also-reachable-as = Auch erreichbar als:
+path-with-links = { $path } (+{ $links ->
+    [one] 1 Link
+   *[other] { $links } Links
+})
+found-results = { $count ->
+    [one] 1 Ergebnis
+   *[other] { $count } Ergebnisse
+} gefunden.
+quick-export-hint = CSV nach { $path }, der Pfad wird in den Einstellungen festgelegt
+cluster-files = Gruppe { $cluster } ({ $files ->
+    [one] 1 Datei
+   *[other] { $files } Dateien
+})
+high-contrast = Hoher Kontrast
+high-contrast-hint = Schwarzer Hintergrund, weißer Text und gelbe Umrandung um das fokussierte Element.
+skip-list = Ausschlussliste
+pattern-error = Fehler im Muster
+search-pattern = Suchmuster
+filter-results = Ergebnisse filtern
+low-priority-workers = Worker mit niedriger Priorität
+low-priority-workers-unsupported = Nur unter Linux und Windows unterstützt
+batch-small-file-reads = Kleine Dateien gebündelt lesen (io_uring)
+batch-small-file-reads-unsupported = Benötigt einen Build mit dem io-uring-Feature
+retry-failed-reads = Fehlgeschlagenes Lesen wiederholen
+retry-backoff-ms = mal, zuerst nach
+retry-failed-reads-hint = Das Öffnen oder Einblenden einer Datei wird so oft wiederholt, bevor sie übersprungen wird, mit doppelter Wartezeit vor jedem weiteren Versuch. Fehlende Dateien und verweigerter Zugriff werden nicht wiederholt.
+read-locked-files-raw = Gesperrte Dateien direkt vom Volume lesen (NTFS)
+read-locked-files-raw-hint = Dateien, die ein anderer Prozess exklusiv geöffnet hält, etwa Registry-Hives, werden direkt vom Volume gelesen statt übersprungen. Erfordert Administratorrechte.
+read-locked-files-raw-unsupported = Nur unter Windows nötig und verfügbar
+source-code = Quellcode
+result-row = { $path } bei { $offset }: { $match }
+row-colors = Zeilenfarben…
+row-colors-hint = Ergebniszeilen nach Pfad, Offset oder Mustername einfärben
+merge-adjacent-matches = Benachbarte Treffer zusammenfassen
+merge-gap = mit einem Abstand von höchstens
+merge-adjacent-matches-hint = Nach der Suche werden Treffer in derselben Datei, die sich überlappen oder höchstens so viele Bytes auseinanderliegen, zu einem Bereich zusammengefasst
+extract-context-before = Kontext davor
+extract-context-after = danach
+extract-matched-regions = Treffer als Dateien extrahieren…
+extract-matched-regions-hint = Jeden Treffer mit den Kontext-Bytes drumherum als eigene Datei in ein Verzeichnis schreiben, benannt nach Quellpfad und Offset
+export-manifest = Korpus-Manifest speichern...
+export-manifest-hint = Listet Pfad, Größe, Änderungszeit und SHA-256 jeder Datei auf, die die letzte Suche durchsucht hat, um später zu zeigen, was durchsucht wurde und ob es sich geändert hat
+check-against-manifest = Mit Manifest vergleichen...
+check-against-manifest-hint = Berechnet die Hashes der in einem Korpus-Manifest aufgeführten Dateien neu und listet die geänderten, fehlenden und die von der letzten Suche durchsuchten, aber nicht aufgeführten Dateien auf
+manifest-check = Manifest-Vergleich
+checked-of-total-files = { $checked } / { $total } Dateien
+manifest-unchanged = { $count } Dateien unverändert
+manifest-changed = { $count } Dateien geändert
+manifest-touched = { $count } Dateien geändert, Inhalt gleich
+manifest-missing = { $count } Dateien fehlen
+manifest-added = { $count } Dateien durchsucht, aber nicht aufgeführt
+copy-as-code = Als Code kopieren
+copy-as-c-array = Als C-Array kopieren
+copy-as-rust-slice = Als Rust-&[u8] kopieren
+copy-as-python-bytes = Als Python-Bytes-Literal kopieren
+copy-as-escaped-regex = Als maskierte Regex kopieren
+copy-as-escaped-regex-hint = Druckbarer Text wird für Regex maskiert, alles andere wird zu \xNN-Bytes für YARA, grep -P oder den Textmodus von quer (dort (?-u) für Bytes über 0x7F voranstellen)
+case-insensitive = Groß-/Kleinschreibung ignorieren
+case-insensitive-hint = Textmuster unabhängig von Groß- und Kleinschreibung finden
+file-type-defaults = Standardoptionen der Dateitypen…
+file-type-defaults-hint = Pro Dateityp übernommene Ausrichtungs- und Schreibweisenoptionen
+attribute-matches-to-files = Treffer in Rohvolumes Dateien zuordnen (NTFS, ext4)
+attribute-matches-to-files-hint = Liest bei Volume- oder Disk-Images die MFT bzw. Inode-Tabellen und zeigt, in welcher (ggf. gelöschten) Datei ein Treffer liegt, sonst "unallocated"
+search-shadow-copies = Auch Schattenkopien durchsuchen
+search-shadow-copies-hint = Durchsucht den Suchordner zusätzlich in den gewählten Snapshots, so werden gesperrte Dateien wie Registry-Hives und ältere Dateiversionen erreichbar. Das Auflisten erfordert Administratorrechte.
+shadow-copies-unsupported = Schattenkopien gibt es nur unter Windows
+listing-shadow-copies = Schattenkopien werden aufgelistet...
+no-shadow-copies = Keine Schattenkopien gefunden
+shadow-copy-entry = {$volume} vom {$created}
+refresh-shadow-copies = Aktualisieren
+
+## pattern_compare.rs
+
+compare-patterns = Muster vergleichen
+compare-patterns-hint = Treffer zweier Muster unterschiedlich einfärben, um ihre Lage zueinander zu sehen
+versus = gegen
+matched-by-both = beide
+
+## pattern_set.rs
+
+pattern-statistics-title = Musterstatistik
+searching-pattern-set = Suche nach { $count } Mustern aus { $sources } statt nach dem Muster oben.
+patterns-matched = { $fired } von { $count } Mustern haben getroffen. Zum Sortieren auf eine Spalte klicken.
+
+## pattern_tester.rs
+
+pattern-tester-title = Mustertester
+sample-is = Beispiel ist:{" "}
+pattern-tester-intro = Treffer des aktuellen Musters werden beim Tippen hervorgehoben.
+paste-sample-data-here = Beispieldaten hier einfügen
+n-matches = { $matches ->
+    [one] 1 Treffer
+   *[other] { $matches } Treffer
+}
+sample-data = Beispieldaten
+
+## presets.rs
+
+save-preset = Aktuelles Muster als Vorlage speichern
+save-preset-hint = Mit Platzhaltern {"{"}name{"}"} oder {"{"}name:art{"}"} wird daraus eine Schablone, Arten sind text, hexN, u8/u16/u32/u64 mit le oder be
+use-pattern = Muster verwenden
+template-title = Schablone: { $name }
+
+## progress.rs
+
+progress-bar-hint = Fertige, übersprungene, laufende und wartende Dateien
+queue-depths-hint = Warteschlangen der Lese- und Suchstufe
+progress-completed = Fertig
+progress-skipped = Übersprungen
+progress-scanning = Läuft
+progress-waiting = Wartend
+read-retries = { $failed } nach { $retried } Wiederholungen unlesbar
+read-retries-hint = Dateien, deren Lesen auch nach allen Wiederholungen fehlschlug, zählen als übersprungen
+locked-files-read = { $count } gesperrte Dateien direkt vom Volume gelesen
+queue-depths = ({ $queued } warten aufs Lesen, { $read } vorausgelesen)
+names-and-more = { $names } und { $more } weitere
+scanning-names = Durchsucht { $names }
+progress-summary = { $done } von { $total } Dateien fertig, { $scanning } werden durchsucht
+
+## regex_help.rs
+
+pattern-reference-title = Musterreferenz
+pattern-reference-intro = Ein Beispiel anklicken, um es ins Suchfeld zu übernehmen.
+hex-mode = Hex-Modus
+text-mode = Text-Modus
+text-mode-syntax = Der Text-Modus verwendet die Syntax des regex-Crates von Rust auf Rohbytes. Lookaround und Rückverweise werden nicht unterstützt.
+examples = Beispiele
+
+## row_colors.rs
+row-colors-title = Zeilenfarben
+row-colors-intro = Zeilen werden in der Farbe der ersten passenden Regel eingefärbt. Die Regeln werden mit den Einstellungen gespeichert.
+rule-path-contains = Pfad enthält
+rule-offset-below = Offset kleiner als
+rule-pattern-is = Mustername ist
+rule-value = Wert für „{ $rule }“
+remove-rule = Regel entfernen
+add-rule = + { $rule }
+
+## similar_regions.rs
+
+find-similar-regions-title = Ähnliche Bereiche finden
+bytes-before = Bytes davor:
+bytes-after = Bytes danach:
+similar-regions-intro = Bytes anklicken, um sie zu Platzhaltern zu machen.
+wildcard-all = Alle als Platzhalter
+wildcard-none = Keine Platzhalter
+wildcard-context = Kontext als Platzhalter
+pattern-label = Muster:
+search = Suchen
+similar-regions-search-hint = Den Bestand im Hex-Modus nach diesem Muster durchsuchen.
+use-as-pattern = Als Muster verwenden
+region-at = { $path } @ { $offset } ({ $size } Bytes)
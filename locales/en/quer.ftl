@@ -0,0 +1,532 @@
+# English UI strings, also the fallback for missing translations
+
+## byte_stats.rs
+
+byte-statistics-title = Byte Statistics
+counting-bytes = Counting bytes...
+byte-stats-summary = { $size }, entropy { $entropy } bits/byte, { $printable }% printable: { $verdict }
+verdict-empty = empty
+verdict-compressed-or-encrypted = likely compressed or encrypted
+verdict-text = likely text
+verdict-structured-binary = likely structured binary
+byte-histogram = Byte histogram
+
+## converter.rs
+
+value-to-hex-pattern = Value to Hex Pattern
+value-label = Value:{" "}
+value-hint = e.g. 0x400000, -1, 3.14 or text
+type-label = Type:{" "}
+little-endian = Little endian
+big-endian = Big endian
+insert = Insert
+append-to-the-search-pattern = Append to the search pattern
+replace = Replace
+replace-the-search-pattern = Replace the search pattern
+
+## diff.rs
+
+diff-target-hint = Mirrored folder, or a file when comparing a single file
+folder = Folder
+file = File
+compare-with = Compare with:{" "}
+
+## dir_tree.rs
+
+directory-tree-title = Directory Tree
+directory-tree-intro = Directories with the most matches are listed first.
+file-matches = { $name }  ({ $matches ->
+    [one] 1 match
+   *[other] { $matches } matches
+})
+
+## elevated.rs
+
+retry-skipped-elevated = Retry { $count } skipped files elevated
+retry-skipped-elevated-hint = Scans the files that couldn't be read again in a helper running with administrator/root rights and adds its matches to the results
+waiting-for-elevated-helper = Waiting for the elevated helper…
+
+## export.rs
+
+exporting = Exporting
+cancel = Cancel
+cancelling = Cancelling...
+writing-to = Writing { $format } to { $path }
+written-of-total = { $written } / { $total } results
+
+## file_view.rs
+
+copy = Copy
+file-view-empty = Select a result to view it here
+open-file-view = Open File View
+file-view = File View
+hex = Hex
+text = Text
+preview-at = Preview @ { $offset }
+matches-in-bytes = { $matches ->
+    [one] 1 match
+   *[other] { $matches } matches
+} in { $size } bytes
+minimap-hint = { $offset }, click to jump to the nearest match
+entropy-block-hint = Block at { $offset }: { $bits } bits/byte
+line-column = Line { $line }, column { $column }
+match-minimap = Match minimap
+entropy-heatmap = Entropy heatmap
+go-to-offset = Go to offset
+go-to-offset-hint = Hex with a 0x prefix, decimal otherwise
+go = Go
+previous-match = Previous match in this file
+next-match = Next match in this file
+match-n-of-total = match { $n } of { $total }
+
+## file_types.rs
+
+file-types = File types
+file-types-hint = Only search files with these extensions, also applies the type's default options
+file-type-defaults-title = File type defaults
+file-type-defaults-intro = Options applied when a file type is picked. They are saved with the settings.
+category-any = Any
+category-executables = Executables
+category-text = Text
+category-documents = Documents
+category-archives = Archives
+category-images = Images
+
+## filters.rs
+
+filter-direct-children-hint = Only show matches directly inside this directory
+clear-filters = Clear filters
+saved-filters = Saved filters
+no-saved-filters = No saved filters yet
+saved-filters-hint = Apply a filter expression saved under a name
+filter-name = Filter name
+save-filter = Save filter
+save-filter-hint = Save the current filter expression under this name, replacing one with the same name
+delete-filter = Delete filter
+offsets-from = Offsets from
+offsets-to = to
+offsets-to-hint = end of file
+offsets-to-exclusive = Findings at this offset and beyond are hidden
+first-kib = First 1 KiB
+first-kib-hint = Only show findings in the first 1 KiB of each file
+excluded-directories = Excluded directories ({ $count })
+search-this-directory-again = Search this directory again
+
+## history.rs
+
+remember = Remember
+searches = searches
+history-pinned-limit-hint = Pinned searches don't count towards the limit
+no-previous-searches-yet = No previous searches yet
+history-pin-hint = Pinned searches stay at the top
+re-run = Re-run
+history-rerun-hint = Restore this search and start it right away
+clear = Clear
+history-clear-hint = Forget all searches that aren't pinned
+
+## log_pane.rs
+clear-logs = Clear Logs
+log-verbosity = Log:
+log-verbosity-hint = Verbose also records how long each file took to scan
+log-errors-only = Errors only
+log-normal = Normal
+log-verbose = Verbose
+filter-log = Filter log
+copy-all = Copy all
+copy-all-hint = Copy every log line with its severity, regardless of the verbosity and filter
+save-log = Save log to file…
+
+## main.rs
+
+mode-hex-hint = Use this mode for data in base 16 pairs. E.g. 'DE AD BE . 00 00'. '.' matches one byte.
+mode-text-hint = Use this mode for textual data. E.g. 'Mary had a \w+ lamb.'
+auto = Auto
+mode-auto-hint = Text for files that look like text, hex for binaries (if the pattern is valid hex), decided from the first 4 KiB of each file.
+content = Content
+scan-content-hint = Match the pattern against file contents.
+metadata = Metadata
+scan-metadata-hint = Match the pattern against EXIF fields, PE version info strings and ID3 tags.
+duplicates = Duplicates
+scan-duplicates-hint = Find groups of byte-identical files. No pattern needed.
+count-only = Count Only
+scan-count-only-hint = Only count matches per file. Much faster, good as a first pass.
+diff = Diff
+scan-diff-hint = Report regions that differ from the mirrored file in another folder, or from another file. No pattern needed.
+records = Records
+scan-records-hint = Treat files as fixed-size records and match the pattern only at a field of each record.
+padding = Padding
+scan-padding-hint = Report long runs of a repeated byte or short repeating fill, e.g. 0x00/0xFF padding. No pattern needed.
+clear-results-on-new-search = Clear Results on New Search
+search-decoded-content = Search Decoded Content (PDF, mail, disk images, APK, firmware, registry, event logs)
+search-decoded-content-hint = Search the extracted text of supported container formats instead of their raw bytes
+include-raw-pdf-streams = Include raw PDF streams
+all-files = All Files
+no-hidden-files = No Hidden Files
+no-hidden-or-system-files = No Hidden or System Files
+skip-system-dirs = Skip System Directories on Drive Roots
+skip-system-dirs-hint = When searching from a drive root, leave out the paths in the skip list
+skip-list-hint = One path per line, directories are skipped with everything below them
+restore-defaults = Restore defaults
+first-match-only = Stop at First Match per File
+first-match-only-hint = Only report whether a file matches at all. Moves on as soon as one match is found.
+alignment-hint = Only report matches at offsets that are a multiple of the alignment away from the base offset, e.g. for fixed-size records after a header
+match-length-hint = Only report matches within this size in bytes, e.g. for printable runs
+anchors-hint = e.g. header_end = 0x200, table = 0x1000
+anchors-columns-hint = Named offsets, each one adds a column with the finding's offset relative to it
+records-layout-hint = Offset of the first record and the size of every record in bytes
+records-field-hint = Where inside each record the pattern has to match
+padding-run-hint = Smallest run in bytes to report, and the longest repeating fill in bytes
+copy-as-bytes = Copy as bytes
+copy-as-hex-bytes = Copy as hex bytes
+find-similar-regions-menu = Find similar regions...
+open-match-in-cyberchef = Open match in CyberChef
+sort-ascending = Sort ascending
+sort-descending = Sort descending
+copy-full-path = Copy full path
+copy-filename = Copy filename
+copy-enclosing-dir = Copy enclosing dir
+exclude-dir-from-results = Exclude this directory from results
+exclude-dir-from-searches = … and from future searches
+exclude-dir-hint = Everything below { $dir }
+copy-as-file-reference = Copy as file reference
+copy-as-file-reference-hint = A file:// URI, pastes as the file in file managers and note-taking tools
+copy-other-paths-to-this-file = Copy other paths to this file
+export-imhex-bookmarks = Export File results to .imhexbm...
+byte-statistics-menu = Byte statistics...
+hash-lookup = Hash lookup
+copy-path-and-offset = Copy path and offset
+copy-path-and-offset-hint = As path:0xoffset, for handing the match to a hex editor or notes
+open-in-imhex-at-offset = Open in ImHex at offset
+offset-inside-decoded-content = The offset is inside content decoded from the file (see Location), not into the file itself
+copy-as-hexdump = Copy as hexdump
+preview-pin-hint = Click to open a pinned preview
+disassembly = Disassembly
+references-from-code = References from code
+find-references = Find references
+find-references-hint = Decodes the executable's code and lists the instructions that refer to the matched address, e.g. where a string is used
+searching-references = Searching the code for references...
+no-references-found = No instruction refers to this address
+references-found = { $count } referencing instructions (file offset, address, instruction)
+decoded-structure = Decoded structure
+decoded-as = Matched bytes parsed as { $format }
+no-decoded-structure = The matched bytes are neither protobuf nor DER
+match = Match
+column-match-hint = Contents of the resulting match
+count = Count
+column-count-hint = Number of times this match was found
+locations = Locations
+column-locations-hint = Files and offsets this match was found at
+copy-match = Copy match
+file-path = File Path
+column-file-path-hint = File path to the file that a given match was found in.
+offset = Offset
+column-offset-hint = Offset into the file that the match starts at.
+preview = Preview
+visualize-column = Visualize column
+location = Location
+column-location-hint = Where inside a decoded container (e.g. PDF page, mail part) or which metadata field the match was found
+verdict = Verdict
+column-verdict-hint = Result of the last hash lookup for the file
+line-col = Line:Col
+column-line-col-hint = Line and column (in bytes) the match starts at
+encoding = Encoding
+column-encoding-hint = Guessed text encoding around the match, e.g. to tell wide strings from narrow ones
+va = VA
+column-va-hint = Virtual address the match would be loaded at, for PE/ELF/Mach-O files
+pattern = Pattern
+column-pattern-hint = Line of the pattern file that matched
+capped-file-hint = Stopped at the max hits limit, this file may have more matches
+copy-va = Copy VA
+copy-relative-offset = Copy relative offset
+past-searches = Past searches
+presets = Presets
+presets-hint = Common patterns and templates with placeholders
+pattern-syntax-reference = Pattern syntax reference
+converter-hint = Convert a number or string to a hex pattern
+enter-regex-here = Enter regex here
+search-pattern-hint = Examples: abc.ef, ^hello world$, aa{"{"}3{"}"}h. See mode tooltips for more info.
+search-within-results-hint = Run the pattern only over the files in the current (filtered) results
+filter-results-hint = Words match the path, offset or preview text. Also path:<glob>, -path:<glob> to hide paths, offset:<start>..<end> and re:<regex> on the preview; all terms have to match.
+unique-matches-only = Unique matches only
+unique-matches-only-hint = Collapse findings with identical match content into a single row
+split-view = Split view
+split-view-hint = Show the selected result in a hex view below the table. Use up/down to step through results
+pop-out = Pop out
+hide-to-tray-hint = Keep scanning in the background, with a notification when done
+load-patterns-from-file = Load patterns from file...
+load-patterns-hint = One pattern per line, '#' for comments, 'hex:' or 'text:' to pick the mode
+paste-pattern-list = Paste pattern list
+paste-pattern-list-hint = Adds one pattern per clipboard line to the patterns in use, 'hex:' or 'text:' pick the mode
+export = Export
+export-to-csv = Export to CSV...
+quick-export = Quick export
+analysis = Analysis
+cluster-files-menu = Cluster matched files by similarity...
+byte-statistics-matched-menu = Byte statistics of matched files...
+pattern-statistics-menu = Pattern statistics...
+pattern-statistics-hint = Hits and files per pattern of a pattern file search
+directory-tree-menu = Directory tree...
+pattern-tester-menu = Pattern tester...
+scheduled-scans-menu = Scheduled scans...
+scheduled-scans-title = Scheduled scans
+scheduled-scans-intro = Each scan runs once a day at its time while quer is running, after any search in progress. Its matches are appended to a JSON Lines file, tagged with the scan and the run.
+no-scheduled-scans = Nothing scheduled yet
+scheduled-scan-details = { $pattern } in { $root }
+scheduled-scan-export-hint = JSON Lines file the matches of every run are appended to. ~ is the home directory, {"{"}date{"}"} starts a new file every day.
+scheduled-scan-outcome = { $matches } matches, { $new } new ({ $files } new files) on { $time }
+alert-on-new-findings = Alert
+alert-on-new-findings-hint = When a run finds matches the previous run didn't (new files or new offsets), show a notification and post them to the webhook if one is set up. New matches are also marked "new" in the JSON Lines file and highlighted in the results.
+scheduled-scan-invalid-pattern = The pattern is invalid
+run-now = Run now
+remove-scheduled-scan = Remove
+schedule-current-search = Schedule the current search
+schedule-current-search-hint = Adds the pattern, folder and options as they are now, to run every night at 02:00
+settings = Settings
+imhex-binary = ImHex binary:{" "}
+imhex-binary-hint = Path to (or name of) the ImHex executable
+virustotal-api-key = VirusTotal API key:{" "}
+virustotal-api-key-hint = Leave empty to open lookups in the browser instead
+malwarebazaar-api-key = MalwareBazaar API key:{" "}
+quick-export-path = Quick export path:{" "}
+reset = Reset
+quick-export-on-finish = Quick export when a search finishes
+export-meta-json = Write a .meta.json next to exports
+export-meta-json-hint = Pattern, modes, search root, filters, app version and scan times
+defang-exports = Defang URLs, emails and IP addresses in CSV exports
+defang-exports-hint = hxxp://example[.]com instead of a link that opens when the report is viewed
+cyberchef-recipe = CyberChef recipe:{" "}
+cyberchef-recipe-hint = Recipe applied to matches opened in CyberChef, e.g. From_Hex('Auto')
+hexdump-width = Hexdump width:{" "}
+hexdump-grouping = Hexdump grouping:{" "}
+hexdump-grouping-hint = Bytes per group, 0 disables grouping
+hexdump-context-bytes = Hexdump context bytes:{" "}
+hexdump-context-hint = Bytes shown before and after a match in previews, kept between runs
+context-before = before:{" "}
+context-after = after:{" "}
+context-bytes = { $before } bytes before and { $after } after the match, changed by one row
+hide-while-scanning = Hide instead of closing while a scan is running
+watch-clipboard = Look up copied text
+watch-clipboard-hint = Searches the search folder whenever a hex string or a short line of text is copied, and shows the hits in a toast
+clipboard-lookup-title = Copied: { $snippet }
+clipboard-lookup-running = Searching...
+clipboard-lookup-summary = { $matches } matches in { $files } files
+search-with-quer = Search with quer
+register-shell-integration = Register with the desktop
+register-shell-integration-hint = Opens quer://search?dir=...&hex=... links in quer and adds "Search with quer" to the context menu of folders and files
+unregister-shell-integration = Unregister
+shell-integration-unsupported = Only supported on Linux and Windows
+start-linked-search-title = Start the search?
+start-linked-search-prompt = A link asked quer to search { $path } for
+single-instance = Single instance
+single-instance-hint = Launching quer again, e.g. from a folder's context menu or a quer:// link, hands the search to this window instead of starting a second quer. Searches handed over while one runs wait for it to finish.
+persist-results = Keep the last results
+persist-results-hint = Stores the results of the last search compressed next to the settings, so they can be brought back after restarting quer
+restore-last-results-desc = The last search found { $count } results for "{ $pattern }" in { $root } ({ $time }).
+restore-last-results = Restore last results
+dismiss = Dismiss
+worker-threads = Worker threads:{" "}
+worker-threads-hint = Threads reading and searching files, applied when the next search starts
+low-priority-workers-hint = Run searches at idle CPU and I/O priority so background sweeps don't slow down the machine
+pin-workers-to-cores = Pin workers to cores:{" "}
+worker-cores-hint = e.g. 0-3, 6
+pin-workers-to-cores-hint = Workers take turns on these cores, leave empty to let the OS decide
+batch-small-file-reads-hint = Faster on NVMe drives with many small files
+post-findings-to-webhook = POST findings to webhook
+webhook-url = Webhook URL:{" "}
+webhook-headers = Headers (one 'Name: value' per line):
+webhook-headers-hint = E.g. 'Authorization: Bearer <token>'
+help = Help
+pattern-reference = Pattern reference
+about = About
+about-title = quer - A data finding utility
+results-popped-out = Results are shown in a separate window.
+reattach = Reattach
+similarity-clusters = Similarity Clusters
+min-similarity-score = Min. similarity score:{" "}
+recompute = Recompute
+hashing-files = Hashing files...
+folder-to-search = Folder to search:{" "}
+open = Open
+mode = Mode:
+scan = Scan:
+max-hits-per-file = Max Hits (per File):{" "}
+alignment-0-to-disable = Alignment (0 to disable):{" "}
+from-offset = {" "}from offset{" "}
+match-length-min = Match Length: min{" "}
+max-0-for-no-limit = {" "}max (0 for no limit){" "}
+anchors = Anchors:{" "}
+custom-column = Column:{" "}
+custom-column-placeholder = e.g. size = u16le(len) or host = $host
+custom-column-hint = Adds a column computed for every match: $name takes a named capture group, otherwise an expression over the match with len, offset, u8(i), u16le(i), u16be(i), u32le(i), u32be(i) and hex(x), where i counts from the start of the match and may reach up to 256 bytes past its end
+custom = Custom
+custom-column-header-hint = Computed for each match while searching, edit the column definition and search again to change it
+records-first-at = Records: first at{" "}
+length = {" "}length{" "}
+field-offset = Field: offset{" "}
+length-0-for-rest-of-record = {" "}length (0 for rest of record){" "}
+padding-min-run = Padding: min run{" "}
+max-period = {" "}max period{" "}
+stop = Stop
+search-within-results = Search Within Results
+clear-results = Clear Results
+searching-for = Searching for:{" "}
+filter = Filter...
+language-label = Language:{" "}
+copy-as-hex = Copy as hex: { $value }
+copy-as-decimal = Copy as decimal: { $value }
+results-page = Showing { $start } - { $end } of { $total } results (page { $page } of { $pages })
+n-more = ... { $more } more
+anchor-column-hint = Offset relative to { $name } ({ $offset })
+also-reachable-as = Also reachable as:
+path-with-links = { $path } (+{ $links ->
+    [one] 1 link
+   *[other] { $links } links
+})
+found-results = Found { $count ->
+    [one] 1 result
+   *[other] { $count } results
+}.
+quick-export-hint = CSV to { $path }, the path is set in the settings
+cluster-files = Cluster { $cluster } ({ $files ->
+    [one] 1 file
+   *[other] { $files } files
+})
+high-contrast = High contrast
+high-contrast-hint = Black background, white text and yellow outlines around the focused control.
+skip-list = Skip list
+pattern-error = Pattern error
+search-pattern = Search pattern
+filter-results = Filter results
+low-priority-workers = Low priority workers
+low-priority-workers-unsupported = Only supported on Linux and Windows
+batch-small-file-reads = Batch small file reads (io_uring)
+batch-small-file-reads-unsupported = Needs a build with the io-uring feature
+retry-failed-reads = Retry failed reads
+retry-backoff-ms = times, first after
+retry-failed-reads-hint = Opening or mapping a file is tried again this often before it is skipped, waiting twice as long before each further attempt. Missing files and denied access aren't retried.
+read-locked-files-raw = Read locked files from the raw volume (NTFS)
+read-locked-files-raw-hint = Files another process keeps open exclusively, like registry hives, are read straight from the volume instead of being skipped. Needs admin rights.
+read-locked-files-raw-unsupported = Only needed and available on Windows
+source-code = Source Code
+result-row = { $path } at { $offset }: { $match }
+row-colors = Row colors…
+row-colors-hint = Color result rows by path, offset or pattern name
+merge-adjacent-matches = Merge adjacent matches
+merge-gap = within a gap of
+merge-adjacent-matches-hint = After the search, matches in the same file that overlap or are at most this many bytes apart become one region
+extract-context-before = Context before
+extract-context-after = after
+extract-matched-regions = Extract matched regions…
+extract-matched-regions-hint = Write each match, with the context bytes around it, to its own file in a directory, named by source path and offset
+export-manifest = Save corpus manifest...
+export-manifest-hint = Lists path, size, modification time and SHA-256 of every file the last search scanned, to show later what was scanned and whether it changed
+check-against-manifest = Check against manifest...
+check-against-manifest-hint = Hashes the files listed in a corpus manifest again and lists those that changed, are missing or were scanned by the last search without being listed
+manifest-check = Manifest check
+checked-of-total-files = { $checked } / { $total } files
+manifest-unchanged = { $count } files unchanged
+manifest-changed = { $count } files changed
+manifest-touched = { $count } files modified, content unchanged
+manifest-missing = { $count } files missing
+manifest-added = { $count } files scanned but not listed
+copy-as-code = Copy as code
+copy-as-c-array = Copy as C array
+copy-as-rust-slice = Copy as Rust &[u8]
+copy-as-python-bytes = Copy as Python bytes literal
+copy-as-escaped-regex = Copy as escaped regex
+copy-as-escaped-regex-hint = Printable text is regex-escaped, anything else becomes \xNN bytes for YARA, grep -P or quer's text mode (add (?-u) there for bytes above 0x7F)
+case-insensitive = Case insensitive
+case-insensitive-hint = Match text patterns regardless of letter case
+file-type-defaults = File type defaults…
+file-type-defaults-hint = Alignment and case options applied per file type
+attribute-matches-to-files = Attribute matches in raw volumes to files (NTFS, ext4)
+attribute-matches-to-files-hint = For volume or disk images, reads the MFT or inode tables and shows which file, possibly a deleted one, a match lies in, or "unallocated"
+search-shadow-copies = Also search volume shadow copies
+search-shadow-copies-hint = Walks the search folder inside the selected snapshots too, which reaches locked files such as registry hives and older versions of files. Listing them needs admin rights.
+shadow-copies-unsupported = Shadow copies are only available on Windows
+listing-shadow-copies = Listing shadow copies...
+no-shadow-copies = No shadow copies found
+shadow-copy-entry = {$volume} taken {$created}
+refresh-shadow-copies = Refresh
+
+## pattern_compare.rs
+
+compare-patterns = Compare patterns
+compare-patterns-hint = Color the matches of two patterns differently to see where they lie relative to each other
+versus = vs
+matched-by-both = both
+
+## pattern_set.rs
+
+pattern-statistics-title = Pattern Statistics
+searching-pattern-set = Searching for { $count } patterns from { $sources } instead of the pattern above.
+patterns-matched = { $fired } of { $count } patterns matched. Click a column to sort.
+
+## pattern_tester.rs
+
+pattern-tester-title = Pattern Tester
+sample-is = Sample is:{" "}
+pattern-tester-intro = Matches of the current pattern are highlighted as you type.
+paste-sample-data-here = Paste sample data here
+n-matches = { $matches ->
+    [one] 1 match
+   *[other] { $matches } matches
+}
+sample-data = Sample data
+
+## presets.rs
+
+save-preset = Save current pattern as preset
+save-preset-hint = Use {"{"}name{"}"} or {"{"}name:kind{"}"} placeholders to make it a template, kinds are text, hexN, u8/u16/u32/u64 with le or be
+use-pattern = Use pattern
+template-title = Template: { $name }
+
+## progress.rs
+
+progress-bar-hint = Completed, skipped, scanning and waiting files
+queue-depths-hint = Queue depths of the I/O and matching stages
+progress-completed = Completed
+progress-skipped = Skipped
+progress-scanning = Scanning
+progress-waiting = Waiting
+read-retries = { $failed } unreadable after { $retried } retries
+read-retries-hint = Files whose reads kept failing after every retry are counted as skipped
+locked-files-read = { $count } locked files read from the raw volume
+queue-depths = ({ $queued } queued for reading, { $read } read ahead)
+names-and-more = { $names } and { $more } more
+scanning-names = Scanning { $names }
+progress-summary = { $done } of { $total } files done, { $scanning } being scanned
+
+## regex_help.rs
+
+pattern-reference-title = Pattern Reference
+pattern-reference-intro = Click an example to put it in the search box.
+hex-mode = Hex mode
+text-mode = Text mode
+text-mode-syntax = Text mode uses the syntax of Rust's regex crate on raw bytes. Lookaround and backreferences aren't supported.
+examples = Examples
+
+## row_colors.rs
+row-colors-title = Row colors
+row-colors-intro = Rows are tinted with the color of the first rule they match. Rules are saved with the settings.
+rule-path-contains = Path contains
+rule-offset-below = Offset below
+rule-pattern-is = Pattern name is
+rule-value = { $rule } value
+remove-rule = Remove rule
+add-rule = + { $rule }
+
+## similar_regions.rs
+
+find-similar-regions-title = Find Similar Regions
+bytes-before = Bytes before:
+bytes-after = Bytes after:
+similar-regions-intro = Click bytes to wildcard them.
+wildcard-all = Wildcard all
+wildcard-none = Wildcard none
+wildcard-context = Wildcard context
+pattern-label = Pattern:
+search = Search
+similar-regions-search-hint = Search the corpus for this pattern in Hex mode.
+use-as-pattern = Use as pattern
+region-at = { $path } @ { $offset } ({ $size } bytes)
@@ -0,0 +1,58 @@
+use crate::QuerApp;
+use eframe::egui::{self, Color32, Stroke};
+
+// rows moved by PageUp/PageDown in the results table
+pub const ROWS_PER_KEY_PAGE: usize = 20;
+
+// names a widget for screen readers where no visible label sits next to it,
+// e.g. text boxes that only show a hint or custom-painted charts
+pub fn set_accessible_name(resp: &egui::Response, name: &str) {
+    resp.ctx.accesskit_node_builder(resp.id, |node| {
+        node.set_name(name);
+    });
+}
+
+// black background, white text and yellow focus/selection, with thicker outlines
+pub fn high_contrast_visuals() -> egui::Visuals {
+    let mut visuals = egui::Visuals::dark();
+    let yellow = Color32::from_rgb(0xff, 0xd7, 0x00);
+    visuals.override_text_color = Some(Color32::WHITE);
+    visuals.panel_fill = Color32::BLACK;
+    visuals.window_fill = Color32::BLACK;
+    visuals.extreme_bg_color = Color32::BLACK;
+    visuals.faint_bg_color = Color32::from_gray(0x20);
+    visuals.window_stroke = Stroke::new(2.0, Color32::WHITE);
+    visuals.hyperlink_color = Color32::from_rgb(0x7f, 0xdb, 0xff);
+    visuals.selection.bg_fill = Color32::from_rgb(0x00, 0x3f, 0xbf);
+    visuals.selection.stroke = Stroke::new(2.0, yellow);
+
+    let widgets = &mut visuals.widgets;
+    for state in [
+        &mut widgets.noninteractive,
+        &mut widgets.inactive,
+        &mut widgets.hovered,
+        &mut widgets.active,
+        &mut widgets.open,
+    ] {
+        state.bg_fill = Color32::BLACK;
+        state.weak_bg_fill = Color32::BLACK;
+        state.fg_stroke = Stroke::new(1.5, Color32::WHITE);
+    }
+    widgets.noninteractive.bg_stroke = Stroke::new(1.0, Color32::from_gray(0xc0));
+    widgets.inactive.bg_stroke = Stroke::new(1.5, Color32::WHITE);
+    widgets.hovered.bg_stroke = Stroke::new(2.0, yellow);
+    widgets.active.bg_stroke = Stroke::new(2.5, yellow);
+    widgets.open.bg_stroke = Stroke::new(2.0, yellow);
+    visuals
+}
+
+impl QuerApp {
+    pub(crate) fn apply_contrast(&mut self, ctx: &egui::Context) {
+        if self.high_contrast {
+            self.normal_visuals = Some(ctx.style().visuals.clone());
+            ctx.set_visuals(high_contrast_visuals());
+        } else if let Some(visuals) = self.normal_visuals.take() {
+            ctx.set_visuals(visuals);
+        }
+    }
+}
@@ -0,0 +1,369 @@
+use crate::QuerApp;
+use goblin::mach::Mach;
+use goblin::Object;
+use memmap2::Mmap;
+use std::fs::File;
+use std::sync::Arc;
+
+// a span of the file that gets loaded at `address`
+struct Segment {
+    file_start: u64,
+    file_end: u64,
+    address: u64,
+    executable: bool,
+}
+
+// a span of x86 code in an executable, 32 or 64-bit
+pub struct CodeSection {
+    pub file_start: u64,
+    pub file_end: u64,
+    pub address: u64,
+    pub bitness: u32,
+}
+
+// an image or mapped file occupying a range of a dumped process's address space
+struct Module {
+    start: u64,
+    end: u64,
+    name: Arc<str>,
+}
+
+pub struct AddressMap {
+    segments: Vec<Segment>,
+    modules: Vec<Module>, // only for memory dumps
+    bitness: Option<u32>, // for x86 executables, their code can be disassembled
+}
+
+const MINIDUMP_SIGNATURE: &[u8] = b"MDMP";
+const MINIDUMP_MODULE_LIST: u32 = 4;
+const MINIDUMP_MEMORY_LIST: u32 = 5;
+const MINIDUMP_MEMORY64_LIST: u32 = 9;
+const MINIDUMP_MODULE_SIZE: usize = 108;
+const NT_FILE: u32 = 0x4649_4c45;
+
+const PE_MACHINE_I386: u16 = 0x14c;
+const PE_MACHINE_AMD64: u16 = 0x8664;
+const PE_SCN_CNT_CODE: u32 = 0x20;
+const PE_SCN_MEM_EXECUTE: u32 = 0x2000_0000;
+const MACHO_CPU_X86: u32 = 7;
+const MACHO_CPU_X86_64: u32 = 0x0100_0007;
+const MACHO_PROT_EXECUTE: u32 = 4;
+
+fn le_u32(data: &[u8], pos: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(data.get(pos..pos + 4)?.try_into().ok()?))
+}
+
+fn le_u64(data: &[u8], pos: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(data.get(pos..pos + 8)?.try_into().ok()?))
+}
+
+// a MINIDUMP_STRING, its length in bytes followed by UTF-16
+fn minidump_string(data: &[u8], rva: usize) -> Option<String> {
+    let len = le_u32(data, rva)? as usize;
+    let units: Vec<u16> = data
+        .get(rva + 4..rva + 4 + len)?
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .collect();
+    Some(String::from_utf16_lossy(&units))
+}
+
+// Windows minidumps: memory ranges from the (64-bit) memory list, owners from the module list
+fn parse_minidump(data: &[u8]) -> Option<AddressMap> {
+    let streams = le_u32(data, 8)? as usize;
+    let directory = le_u32(data, 12)? as usize;
+    let mut segments = Vec::new();
+    let mut modules = Vec::new();
+    for idx in 0..streams {
+        let entry = directory + idx * 12;
+        let (Some(kind), Some(rva)) = (le_u32(data, entry), le_u32(data, entry + 8)) else {
+            break;
+        };
+        let rva = rva as usize;
+        match kind {
+            MINIDUMP_MEMORY64_LIST => {
+                let count = le_u64(data, rva)? as usize;
+                // the ranges' contents follow each other from the base on
+                let mut file_start = le_u64(data, rva + 8)?;
+                for range in 0..count {
+                    let desc = rva + 16 + range * 16;
+                    let (Some(address), Some(size)) = (le_u64(data, desc), le_u64(data, desc + 8))
+                    else {
+                        break;
+                    };
+                    segments.push(Segment {
+                        file_start,
+                        file_end: file_start + size,
+                        address,
+                        executable: false,
+                    });
+                    file_start += size;
+                }
+            }
+            MINIDUMP_MEMORY_LIST => {
+                let count = le_u32(data, rva)? as usize;
+                for range in 0..count {
+                    let desc = rva + 4 + range * 16;
+                    let (Some(address), Some(size), Some(file_start)) = (
+                        le_u64(data, desc),
+                        le_u32(data, desc + 8),
+                        le_u32(data, desc + 12),
+                    ) else {
+                        break;
+                    };
+                    segments.push(Segment {
+                        file_start: file_start as u64,
+                        file_end: file_start as u64 + size as u64,
+                        address,
+                        executable: false,
+                    });
+                }
+            }
+            MINIDUMP_MODULE_LIST => {
+                let count = le_u32(data, rva)? as usize;
+                for module in 0..count {
+                    let desc = rva + 4 + module * MINIDUMP_MODULE_SIZE;
+                    let (Some(base), Some(size), Some(name_rva)) = (
+                        le_u64(data, desc),
+                        le_u32(data, desc + 8),
+                        le_u32(data, desc + 20),
+                    ) else {
+                        break;
+                    };
+                    modules.push(Module {
+                        start: base,
+                        end: base + size as u64,
+                        name: Arc::from(minidump_string(data, name_rva as usize)?),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    Some(AddressMap {
+        segments,
+        modules,
+        bitness: None,
+    })
+}
+
+// the files mapped into a crashed process, from the NT_FILE note of an ELF core
+fn core_file_mappings(elf: &goblin::elf::Elf, data: &[u8]) -> Vec<Module> {
+    let mut modules = Vec::new();
+    let Some(notes) = elf.iter_note_headers(data) else {
+        return modules;
+    };
+    for note in notes.flatten() {
+        if note.n_type != NT_FILE {
+            continue;
+        }
+        let desc = note.desc;
+        let word = if elf.is_64 { 8 } else { 4 };
+        let read = |pos: usize| -> Option<u64> {
+            match word {
+                8 => le_u64(desc, pos),
+                _ => le_u32(desc, pos).map(u64::from),
+            }
+        };
+        let Some(count) = read(0) else {
+            continue;
+        };
+        let count = count as usize;
+        let names_start = word * 2 + count * word * 3;
+        let names = desc
+            .get(names_start..)
+            .unwrap_or_default()
+            .split(|byte| *byte == 0);
+        for (idx, name) in names.take(count).enumerate() {
+            let entry = word * 2 + idx * word * 3;
+            let (Some(start), Some(end)) = (read(entry), read(entry + word)) else {
+                break;
+            };
+            modules.push(Module {
+                start,
+                end,
+                name: Arc::from(String::from_utf8_lossy(name).as_ref()),
+            });
+        }
+    }
+    modules
+}
+
+impl AddressMap {
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        if data.starts_with(MINIDUMP_SIGNATURE) {
+            return parse_minidump(data);
+        }
+        let mut modules = Vec::new();
+        let bitness;
+        let segments = match Object::parse(data).ok()? {
+            Object::PE(pe) => {
+                bitness = match pe.header.coff_header.machine {
+                    PE_MACHINE_I386 => Some(32),
+                    PE_MACHINE_AMD64 => Some(64),
+                    _ => None,
+                };
+                let image_base = pe.image_base as u64;
+                let mut segments: Vec<Segment> = pe
+                    .sections
+                    .iter()
+                    .map(|section| Segment {
+                        file_start: section.pointer_to_raw_data as u64,
+                        file_end: section.pointer_to_raw_data as u64
+                            + section.size_of_raw_data as u64,
+                        address: image_base + section.virtual_address as u64,
+                        executable: section.characteristics
+                            & (PE_SCN_CNT_CODE | PE_SCN_MEM_EXECUTE)
+                            != 0,
+                    })
+                    .collect();
+                // the headers are mapped at the image base as well
+                let headers_end = segments
+                    .iter()
+                    .filter(|s| s.file_end > s.file_start)
+                    .map(|s| s.file_start)
+                    .min()
+                    .unwrap_or(0);
+                segments.push(Segment {
+                    file_start: 0,
+                    file_end: headers_end,
+                    address: image_base,
+                    executable: false,
+                });
+                segments
+            }
+            Object::Elf(elf) => {
+                if elf.header.e_type == goblin::elf::header::ET_CORE {
+                    modules = core_file_mappings(&elf, data);
+                }
+                bitness = match elf.header.e_machine {
+                    goblin::elf::header::EM_386 => Some(32),
+                    goblin::elf::header::EM_X86_64 => Some(64),
+                    _ => None,
+                };
+                elf.program_headers
+                    .iter()
+                    .filter(|ph| ph.p_type == goblin::elf::program_header::PT_LOAD)
+                    .map(|ph| Segment {
+                        file_start: ph.p_offset,
+                        file_end: ph.p_offset + ph.p_filesz,
+                        address: ph.p_vaddr,
+                        executable: ph.p_flags & goblin::elf::program_header::PF_X != 0,
+                    })
+                    .collect()
+            }
+            Object::Mach(Mach::Binary(macho)) => {
+                bitness = match macho.header.cputype {
+                    MACHO_CPU_X86 => Some(32),
+                    MACHO_CPU_X86_64 => Some(64),
+                    _ => None,
+                };
+                macho
+                    .segments
+                    .iter()
+                    .map(|segment| Segment {
+                        file_start: segment.fileoff,
+                        file_end: segment.fileoff + segment.filesize,
+                        address: segment.vmaddr,
+                        executable: segment.initprot & MACHO_PROT_EXECUTE != 0,
+                    })
+                    .collect()
+            }
+            // fat binaries hold several images, so an offset has no single address
+            _ => return None,
+        };
+
+        Some(Self {
+            segments,
+            modules,
+            bitness,
+        })
+    }
+
+    // all x86 code of the image
+    pub fn code_sections(&self) -> Vec<CodeSection> {
+        let Some(bitness) = self.bitness else {
+            return Vec::new();
+        };
+        self.segments
+            .iter()
+            .filter(|s| s.executable && s.file_end > s.file_start)
+            .map(|s| CodeSection {
+                file_start: s.file_start,
+                file_end: s.file_end,
+                address: s.address,
+                bitness,
+            })
+            .collect()
+    }
+
+    // the x86 code section an offset lies in
+    pub fn code_section(&self, offset: u64) -> Option<CodeSection> {
+        let bitness = self.bitness?;
+        self.segments
+            .iter()
+            .find(|s| s.executable && s.file_start <= offset && offset < s.file_end)
+            .map(|s| CodeSection {
+                file_start: s.file_start,
+                file_end: s.file_end,
+                address: s.address,
+                bitness,
+            })
+    }
+
+    pub fn virtual_address(&self, offset: u64) -> Option<u64> {
+        self.segments
+            .iter()
+            .find(|s| s.file_start <= offset && offset < s.file_end)
+            .map(|s| s.address + (offset - s.file_start))
+    }
+
+    // the module or mapped file of a memory dump that an address belongs to
+    pub fn module(&self, address: u64) -> Option<Arc<str>> {
+        self.modules
+            .iter()
+            .find(|module| module.start <= address && address < module.end)
+            .map(|module| Arc::clone(&module.name))
+    }
+}
+
+fn load_address_map(path: &str) -> Option<AddressMap> {
+    let file = File::open(path).ok()?;
+    let data = unsafe { Mmap::map(&file).ok()? };
+    AddressMap::parse(&data)
+}
+
+impl QuerApp {
+    // parsed the first time a file's addresses are needed, files that aren't executables are remembered too
+    fn address_map(&mut self, path: &str) -> Option<Arc<AddressMap>> {
+        match self.address_maps.get(path) {
+            Some(address_map) => address_map.clone(),
+            None => {
+                let address_map = load_address_map(path).map(Arc::new);
+                self.address_maps
+                    .insert(path.to_string(), address_map.clone());
+                address_map
+            }
+        }
+    }
+
+    // whether a match could be referenced from x86 code, its address being known
+    pub(crate) fn has_x86_code(&mut self, path: &str, offset: usize) -> bool {
+        self.address_map(path).is_some_and(|map| {
+            map.bitness.is_some() && map.virtual_address(offset as u64).is_some()
+        })
+    }
+
+    pub(crate) fn code_section(&mut self, path: &str, offset: usize) -> Option<CodeSection> {
+        self.address_map(path)?.code_section(offset as u64)
+    }
+
+    pub(crate) fn virtual_address(&mut self, path: &str, offset: usize) -> Option<u64> {
+        self.address_map(path)?.virtual_address(offset as u64)
+    }
+
+    // for matches in memory dumps, the module the address falls into
+    pub(crate) fn address_module(&mut self, path: &str, address: u64) -> Option<Arc<str>> {
+        self.address_map(path)?.module(address)
+    }
+}
@@ -0,0 +1,233 @@
+use memmap2::Mmap;
+use std::fs::File;
+use std::io;
+use std::ops::Range;
+use std::path::Path;
+
+pub struct EntropyMap {
+    pub filepath: String,
+    pub block_size: usize,
+    pub entropies: Vec<f32>, // normalized to 0.0..=1.0 (8 bits per byte)
+}
+
+pub fn shannon_entropy(data: &[u8]) -> f32 {
+    if data.is_empty() {
+        return 0.0;
+    }
+    let mut counts = [0_usize; 256];
+    for byte in data.iter() {
+        counts[*byte as usize] += 1;
+    }
+
+    let len = data.len() as f32;
+    counts
+        .iter()
+        .filter(|count| **count > 0)
+        .map(|count| {
+            let p = *count as f32 / len;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+pub fn entropy_map(filepath: &str, max_blocks: usize) -> io::Result<EntropyMap> {
+    let file = File::open(Path::new(filepath))?;
+    let size = file.metadata()?.len() as usize;
+    if size == 0 {
+        return Ok(EntropyMap {
+            filepath: filepath.to_string(),
+            block_size: 0,
+            entropies: Vec::new(),
+        });
+    }
+
+    let data = unsafe { Mmap::map(&file)? };
+    // small blocks are too noisy to be meaningful, so don't go below 256 bytes
+    let block_size = std::cmp::max((size + max_blocks - 1) / max_blocks, 256);
+    let entropies = data
+        .chunks(block_size)
+        .map(|block| shannon_entropy(block) / 8.0)
+        .collect();
+
+    Ok(EntropyMap {
+        filepath: filepath.to_string(),
+        block_size,
+        entropies,
+    })
+}
+
+// offsets at which each line starts, the first line always starts at 0
+pub fn line_starts(data: &[u8]) -> Vec<usize> {
+    let mut starts = vec![0];
+    starts.extend(
+        data.iter()
+            .enumerate()
+            .filter(|(_idx, byte)| **byte == b'\n')
+            .map(|(idx, _byte)| idx + 1),
+    );
+    starts
+}
+
+// 1-based (line, column) like grep reports them, the column counts bytes
+pub fn line_col(line_starts: &[usize], offset: usize) -> (usize, usize) {
+    let line = std::cmp::max(line_starts.partition_point(|start| *start <= offset), 1);
+    (line, offset - line_starts[line - 1] + 1)
+}
+
+pub struct ByteStats {
+    pub filepath: String,
+    pub size: usize,
+    pub counts: Vec<usize>, // one per byte value
+    pub printable_ratio: f32,
+    pub entropy: f32, // bits per byte
+}
+
+impl ByteStats {
+    // rough guess at what kind of data the file holds, as a UI string id
+    pub fn verdict(&self) -> &'static str {
+        if self.size == 0 {
+            "verdict-empty"
+        } else if self.entropy > 7.5 {
+            "verdict-compressed-or-encrypted"
+        } else if self.printable_ratio > 0.95 {
+            "verdict-text"
+        } else {
+            "verdict-structured-binary"
+        }
+    }
+}
+
+// how much of a file is sniffed to tell text from binary
+const SNIFF_LEN: usize = 4096;
+
+// no NUL bytes, and either UTF-8 or mostly printable ASCII (e.g. Latin-1 text)
+pub fn looks_like_text(data: &[u8]) -> bool {
+    let block = &data[..std::cmp::min(data.len(), SNIFF_LEN)];
+    if block.contains(&0) {
+        return false;
+    }
+    match std::str::from_utf8(block) {
+        Ok(_) => true,
+        Err(err) if err.error_len().is_none() => true, // cut off mid character
+        Err(_) => {
+            let printable = block
+                .iter()
+                .filter(|byte| byte.is_ascii_graphic() || byte.is_ascii_whitespace())
+                .count();
+            printable * 10 >= block.len() * 9
+        }
+    }
+}
+
+pub fn byte_stats(filepath: &str) -> io::Result<ByteStats> {
+    let file = File::open(Path::new(filepath))?;
+    let size = file.metadata()?.len() as usize;
+    let mut counts = vec![0_usize; 256];
+    if size > 0 {
+        let data = unsafe { Mmap::map(&file)? };
+        for byte in data.iter() {
+            counts[*byte as usize] += 1;
+        }
+    }
+
+    let printable: usize = counts
+        .iter()
+        .enumerate()
+        .filter(|(byte, _count)| {
+            let byte = *byte as u8;
+            byte.is_ascii_graphic() || byte.is_ascii_whitespace()
+        })
+        .map(|(_byte, count)| count)
+        .sum();
+    let len = size as f32;
+    let entropy = counts
+        .iter()
+        .filter(|count| **count > 0)
+        .map(|count| {
+            let p = *count as f32 / len;
+            -p * p.log2()
+        })
+        .sum();
+
+    Ok(ByteStats {
+        filepath: filepath.to_string(),
+        size,
+        counts,
+        printable_ratio: if size > 0 {
+            printable as f32 / len
+        } else {
+            0.0
+        },
+        entropy,
+    })
+}
+
+// runs of at least `min_run` bytes that repeat with a period of up to `max_period` bytes,
+// as (range, period), shorter periods win where runs overlap
+pub fn repeated_runs(data: &[u8], min_run: usize, max_period: usize) -> Vec<(Range<usize>, usize)> {
+    let mut candidates = Vec::new();
+    for period in 1..=max_period {
+        let min_run = std::cmp::max(min_run, period * 2);
+        let mut start = 0;
+        for idx in period..=data.len() {
+            // data[idx - period..idx] continues the run while bytes repeat one period back
+            if idx < data.len() && data[idx] == data[idx - period] {
+                continue;
+            }
+            if idx - start >= min_run {
+                candidates.push((start..idx, period));
+            }
+            start = idx + 1 - period;
+        }
+    }
+    candidates.sort_by_key(|(range, period)| (range.start, *period));
+
+    let mut runs: Vec<(Range<usize>, usize)> = Vec::new();
+    for (mut range, period) in candidates {
+        if let Some((last, _period)) = runs.last() {
+            range.start = std::cmp::max(range.start, last.end);
+        }
+        if range.end > range.start && range.end - range.start >= min_run {
+            runs.push((range, period));
+        }
+    }
+    runs
+}
+
+// bytes on either side of a match looked at when guessing its encoding
+const ENCODING_CONTEXT: usize = 32;
+
+// best guess at the encoding of the text around `range`
+pub fn detect_encoding(data: &[u8], range: Range<usize>) -> &'static str {
+    let start = range.start.saturating_sub(ENCODING_CONTEXT) & !1; // keep the UTF-16 byte phase
+    let end = std::cmp::min(range.end + ENCODING_CONTEXT, data.len());
+    let region = &data[start..end];
+
+    // mostly-ASCII wide strings have every other byte zeroed
+    let pairs = region.len() / 2;
+    if pairs >= 2 {
+        let zero_odd = region
+            .chunks_exact(2)
+            .filter(|p| p[1] == 0 && p[0] != 0)
+            .count();
+        let zero_even = region
+            .chunks_exact(2)
+            .filter(|p| p[0] == 0 && p[1] != 0)
+            .count();
+        if zero_odd * 4 >= pairs * 3 {
+            return "UTF-16LE";
+        }
+        if zero_even * 4 >= pairs * 3 {
+            return "UTF-16BE";
+        }
+    }
+
+    let matched = &data[range];
+    if matched.is_ascii() {
+        "ASCII"
+    } else if std::str::from_utf8(matched).is_ok() {
+        "UTF-8"
+    } else {
+        "Latin-1"
+    }
+}
@@ -0,0 +1,39 @@
+use crate::presets::parse_number;
+
+// a named offset that findings can be measured from, e.g. `header_end = 0x200`
+#[derive(Clone)]
+pub struct Anchor {
+    pub name: String,
+    pub offset: usize,
+}
+
+// comma or newline separated `name = offset` pairs
+pub fn parse_anchors(text: &str) -> Result<Vec<Anchor>, String> {
+    text.split([',', '\n'])
+        .map(str::trim)
+        .filter(|def| !def.is_empty())
+        .map(|def| {
+            let (name, offset) = def
+                .split_once('=')
+                .ok_or_else(|| format!("'{}' should look like 'name = 0x200'", def))?;
+            let name = name.trim();
+            if name.is_empty() {
+                return Err(format!("'{}' is missing a name", def));
+            }
+            Ok(Anchor {
+                name: name.to_string(),
+                offset: parse_number(offset)? as usize,
+            })
+        })
+        .collect()
+}
+
+impl Anchor {
+    pub fn relative(&self, offset: usize) -> String {
+        if offset >= self.offset {
+            format!("+0x{:x}", offset - self.offset)
+        } else {
+            format!("-0x{:x}", self.offset - offset)
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use crate::accessibility::set_accessible_name;
+use crate::analysis::{self, ByteStats};
+use crate::i18n::{tr, tr_args};
+use crate::{format_size, QuerApp};
+use eframe::egui::{self, Color32, Sense};
+use std::sync::Arc;
+use std::thread;
+
+const CHART_HEIGHT: f32 = 64.0;
+
+impl QuerApp {
+    pub(crate) fn request_byte_stats(&mut self, mut filepaths: Vec<String>, ctx: &egui::Context) {
+        filepaths.sort();
+        filepaths.dedup();
+        self.log(format!(
+            "Computing byte statistics of {} files",
+            filepaths.len()
+        ));
+        self.show_byte_stats_window = true;
+
+        if let Ok(mut byte_stats) = self.byte_stats.lock() {
+            *byte_stats = None;
+        }
+        let byte_stats = Arc::clone(&self.byte_stats);
+        let ctx = ctx.clone();
+        thread::spawn(move || {
+            let result = filepaths
+                .iter()
+                .filter_map(|path| analysis::byte_stats(path).ok())
+                .collect();
+            if let Ok(mut byte_stats) = byte_stats.lock() {
+                *byte_stats = Some(result);
+            }
+            ctx.request_repaint();
+        });
+    }
+
+    pub(crate) fn byte_stats_of_matched_files(&mut self, ctx: &egui::Context) {
+        let filepaths = self.findings.iter().map(|f| f.filepath.clone()).collect();
+        self.request_byte_stats(filepaths, ctx);
+    }
+
+    pub(crate) fn add_byte_stats_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_byte_stats_window;
+        egui::Window::new(tr("byte-statistics-title"))
+            .open(&mut open)
+            .resizable(true)
+            .default_width(560.0)
+            .show(ctx, |ui| {
+                let byte_stats = self.byte_stats.lock();
+                match byte_stats.as_deref() {
+                    Ok(Some(byte_stats)) => {
+                        egui::ScrollArea::vertical().show(ui, |ui| {
+                            for stats in byte_stats.iter() {
+                                egui::CollapsingHeader::new(&stats.filepath)
+                                    .default_open(byte_stats.len() == 1)
+                                    .show(ui, |ui| add_stats(ui, stats));
+                            }
+                        });
+                    }
+                    Ok(None) => {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label(tr("counting-bytes"));
+                        });
+                    }
+                    Err(_) => {}
+                }
+            });
+        self.show_byte_stats_window = open;
+    }
+}
+
+fn add_stats(ui: &mut egui::Ui, stats: &ByteStats) {
+    ui.label(tr_args(
+        "byte-stats-summary",
+        &[
+            ("size", format_size(stats.size).into()),
+            ("entropy", format!("{:.2}", stats.entropy).into()),
+            (
+                "printable",
+                format!("{:.1}", stats.printable_ratio * 100.0).into(),
+            ),
+            ("verdict", tr(stats.verdict()).into()),
+        ],
+    ));
+    add_histogram(ui, &stats.counts);
+}
+
+// one bar per byte value, scaled to the most frequent one
+fn add_histogram(ui: &mut egui::Ui, counts: &[usize]) {
+    let width = ui.available_width();
+    let (resp, painter) = ui.allocate_painter(egui::vec2(width, CHART_HEIGHT), Sense::hover());
+    set_accessible_name(&resp, &tr("byte-histogram"));
+    let rect = resp.rect;
+    painter.rect_filled(rect, 0.0, ui.visuals().extreme_bg_color);
+
+    let max_count = counts.iter().copied().max().unwrap_or(0);
+    if max_count == 0 {
+        return;
+    }
+    let bar_width = rect.width() / counts.len() as f32;
+    for (byte, count) in counts.iter().enumerate() {
+        let height = *count as f32 / max_count as f32 * rect.height();
+        let x = rect.left() + byte as f32 * bar_width;
+        let bar = egui::Rect::from_min_max(
+            egui::pos2(x, rect.bottom() - height),
+            egui::pos2(x + bar_width, rect.bottom()),
+        );
+        let color = if (byte as u8).is_ascii_graphic() {
+            Color32::from_rgb(0x2a, 0x7e, 0x43)
+        } else {
+            Color32::from_rgb(0x4a, 0x6f, 0xa5)
+        };
+        painter.rect_filled(bar, 0.0, color);
+    }
+
+    if let Some(pos) = resp.hover_pos() {
+        let byte = ((pos.x - rect.left()) / bar_width) as usize;
+        if let Some(count) = counts.get(byte) {
+            resp.on_hover_text(format!("0x{:02X}: {}", byte, count));
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use crate::i18n::{tr, tr_args};
+use crate::{tray, ContentEnum, QuerApp, ScanModeEnum};
+use eframe::egui;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+const TOAST_DURATION: Duration = Duration::from_secs(10);
+const MIN_HEX_DIGITS: usize = 4;
+const MIN_SNIPPET_LEN: usize = 3;
+const MAX_SNIPPET_LEN: usize = 256;
+const TOAST_FILES: usize = 5;
+
+// watches the clipboard from its own thread, stopped when dropped
+pub struct ClipboardMonitor {
+    copied: Arc<Mutex<Option<String>>>, // the latest new text, until it's looked up
+    stop: Arc<AtomicBool>,
+}
+
+impl ClipboardMonitor {
+    pub fn start(ctx: &egui::Context) -> Result<Self, String> {
+        let mut clipboard = arboard::Clipboard::new().map_err(|err| err.to_string())?;
+        let copied = Arc::new(Mutex::new(None));
+        let stop = Arc::new(AtomicBool::new(false));
+        let monitor = Self {
+            copied: Arc::clone(&copied),
+            stop: Arc::clone(&stop),
+        };
+        let ctx = ctx.clone();
+        thread::spawn(move || {
+            // whatever was copied before watching started isn't looked up
+            let mut last = clipboard.get_text().ok();
+            while !stop.load(Ordering::Relaxed) {
+                thread::sleep(POLL_INTERVAL);
+                let text = clipboard.get_text().ok();
+                if text.is_none() || text == last {
+                    continue;
+                }
+                last = text.clone();
+                if let Ok(mut copied) = copied.lock() {
+                    *copied = text;
+                }
+                ctx.request_repaint();
+            }
+        });
+        Ok(monitor)
+    }
+
+    fn take(&self) -> Option<String> {
+        self.copied.lock().ok()?.take()
+    }
+
+    // puts text back to be looked up once the running search is done
+    fn defer(&self, text: String) {
+        if let Ok(mut copied) = self.copied.lock() {
+            copied.get_or_insert(text);
+        }
+    }
+}
+
+impl Drop for ClipboardMonitor {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+// a lookup started from the clipboard, shown as a toast until it times out
+pub struct ClipboardLookup {
+    snippet: String,
+    finished_at: Option<Instant>,
+    notified: bool,
+}
+
+// hex strings ("de ad be ef", "0xdeadbeef", "de:ad:be:ef") are searched as bytes, other
+// single-line snippets as literal text. words like "decade" need a digit, prefix or
+// separator to count as hex.
+fn lookup_pattern(text: &str) -> Option<(String, ContentEnum)> {
+    let text = text.trim();
+    let looks_hex = text.starts_with("0x")
+        || text.contains(|c: char| c.is_ascii_digit() || matches!(c, ' ' | ':' | '-'));
+    let digits: String = text
+        .trim_start_matches("0x")
+        .chars()
+        .filter(|c| !matches!(c, ' ' | ':' | '-'))
+        .collect();
+    if looks_hex
+        && digits.len() >= MIN_HEX_DIGITS
+        && digits.len() % 2 == 0
+        && digits.chars().all(|c| c.is_ascii_hexdigit())
+    {
+        return Some((digits, ContentEnum::Hex));
+    }
+    let chars = text.chars().count();
+    if (MIN_SNIPPET_LEN..=MAX_SNIPPET_LEN).contains(&chars) && !text.contains('\n') {
+        return Some((regex::escape(text), ContentEnum::Text));
+    }
+    None
+}
+
+impl QuerApp {
+    pub(crate) fn set_clipboard_monitor(&mut self, ctx: &egui::Context, enabled: bool) {
+        self.clipboard_monitor = None;
+        if !enabled {
+            return;
+        }
+        match ClipboardMonitor::start(ctx) {
+            Ok(monitor) => self.clipboard_monitor = Some(Arc::new(monitor)),
+            Err(err) => {
+                self.log_error(format!("Couldn't watch the clipboard: {}", err));
+                self.watch_clipboard = false;
+            }
+        }
+    }
+
+    // searches the corpus for newly copied text, one lookup at a time
+    pub(crate) fn poll_clipboard_monitor(&mut self) {
+        let Some(monitor) = self.clipboard_monitor.clone() else {
+            return;
+        };
+        let Some(text) = monitor.take() else {
+            return;
+        };
+        let Some((pattern, content_type)) = lookup_pattern(&text) else {
+            return;
+        };
+        if !self.is_search_finished() {
+            monitor.defer(text);
+            return;
+        }
+        self.regex_str = pattern;
+        self.content_type = content_type;
+        self.scan_mode = ScanModeEnum::Content;
+        self.update_regex();
+        if self.regex_result.is_err() {
+            return;
+        }
+        self.log(format!("Looking up copied text: {}", text.trim()));
+        self.progress = 0.0;
+        self.search();
+        self.clipboard_lookup = Some(ClipboardLookup {
+            snippet: text.trim().chars().take(48).collect(),
+            finished_at: None,
+            notified: false,
+        });
+    }
+
+    pub(crate) fn add_clipboard_toast(&mut self, ctx: &egui::Context) {
+        let finished = self.is_search_finished();
+        let Some(lookup) = &mut self.clipboard_lookup else {
+            return;
+        };
+        if finished && lookup.finished_at.is_none() {
+            lookup.finished_at = Some(Instant::now());
+        }
+        if lookup
+            .finished_at
+            .is_some_and(|at| at.elapsed() > TOAST_DURATION)
+        {
+            self.clipboard_lookup = None;
+            return;
+        }
+
+        let findings = &self.findings[self.search_start_index.min(self.findings.len())..];
+        let mut files = Vec::new();
+        let mut seen = HashSet::new();
+        for finding in findings {
+            if seen.insert(&finding.filepath) {
+                files.push(finding.filepath.clone());
+            }
+        }
+        let summary = tr_args(
+            "clipboard-lookup-summary",
+            &[
+                ("matches", findings.len().into()),
+                ("files", files.len().into()),
+            ],
+        );
+        // the window may be out of sight while working in another tool
+        if lookup.finished_at.is_some() && !lookup.notified {
+            lookup.notified = true;
+            if !ctx.input(|i| i.viewport().focused.unwrap_or(true)) {
+                tray::notify(&format!("quer: {}", lookup.snippet), &summary);
+            }
+        }
+
+        let mut dismissed = false;
+        egui::Area::new(egui::Id::new("clipboard_toast"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, [-12.0, -12.0])
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.set_max_width(360.0);
+                    ui.horizontal(|ui| {
+                        ui.strong(tr_args(
+                            "clipboard-lookup-title",
+                            &[("snippet", lookup.snippet.clone().into())],
+                        ));
+                        dismissed = ui.small_button("x").clicked();
+                    });
+                    if lookup.finished_at.is_none() {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label(tr("clipboard-lookup-running"));
+                        });
+                    }
+                    ui.label(&summary);
+                    for file in files.iter().take(TOAST_FILES) {
+                        ui.small(file);
+                    }
+                });
+            });
+        if dismissed {
+            self.clipboard_lookup = None;
+        } else if let Some(at) = lookup.finished_at {
+            ctx.request_repaint_after(TOAST_DURATION.saturating_sub(at.elapsed()));
+        }
+    }
+}
@@ -0,0 +1,57 @@
+// match bytes formatted as literals and patterns for pasting into source code or rules
+
+const BYTES_PER_LINE: usize = 12;
+
+fn hex_list(bytes: &[u8], indent: &str) -> String {
+    bytes
+        .chunks(BYTES_PER_LINE)
+        .map(|line| {
+            let items: Vec<String> = line.iter().map(|byte| format!("0x{:02x}", byte)).collect();
+            format!("{}{},", indent, items.join(", "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// unsigned char match_400[4] = { ... };
+pub fn c_array(bytes: &[u8], offset: usize) -> String {
+    format!(
+        "unsigned char match_{:x}[{}] = {{\n{}\n}};",
+        offset,
+        bytes.len(),
+        hex_list(bytes, "    ")
+    )
+}
+
+// &[ ... ], usable wherever a &[u8] is expected
+pub fn rust_slice(bytes: &[u8]) -> String {
+    format!("&[\n{}\n]", hex_list(bytes, "    "))
+}
+
+// b"MZ\x90\x00", printable ASCII stays readable
+pub fn python_bytes(bytes: &[u8]) -> String {
+    let mut literal = String::from("b\"");
+    for byte in bytes {
+        match byte {
+            b'\\' => literal.push_str("\\\\"),
+            b'"' => literal.push_str("\\\""),
+            0x20..=0x7e => literal.push(*byte as char),
+            _ => literal.push_str(&format!("\\x{:02x}", byte)),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+// regex-escaped text when the match is printable ASCII, otherwise every byte as \xNN,
+// which YARA, grep -P and quer's text mode (with (?-u) for bytes above 0x7f) all accept
+pub fn escaped_regex(bytes: &[u8]) -> String {
+    if bytes.iter().all(|byte| (0x20..=0x7e).contains(byte)) {
+        regex::escape(&String::from_utf8_lossy(bytes))
+    } else {
+        bytes
+            .iter()
+            .map(|byte| format!("\\x{:02x}", byte))
+            .collect()
+    }
+}
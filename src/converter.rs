@@ -0,0 +1,219 @@
+use crate::i18n::tr;
+use crate::presets::parse_number;
+use crate::{ContentEnum, QuerApp};
+use eframe::egui;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ValueKind {
+    Int(usize, bool), // size in bytes, signed
+    F32,
+    F64,
+    Utf8,
+    Utf16,
+    Latin1,
+}
+
+impl ValueKind {
+    const ALL: [ValueKind; 13] = [
+        ValueKind::Int(1, false),
+        ValueKind::Int(1, true),
+        ValueKind::Int(2, false),
+        ValueKind::Int(2, true),
+        ValueKind::Int(4, false),
+        ValueKind::Int(4, true),
+        ValueKind::Int(8, false),
+        ValueKind::Int(8, true),
+        ValueKind::F32,
+        ValueKind::F64,
+        ValueKind::Utf8,
+        ValueKind::Utf16,
+        ValueKind::Latin1,
+    ];
+
+    fn name(&self) -> String {
+        match self {
+            ValueKind::Int(size, signed) => {
+                format!("{}{}", if *signed { "i" } else { "u" }, size * 8)
+            }
+            ValueKind::F32 => "f32".to_string(),
+            ValueKind::F64 => "f64".to_string(),
+            ValueKind::Utf8 => "UTF-8 string".to_string(),
+            ValueKind::Utf16 => "UTF-16 string".to_string(),
+            ValueKind::Latin1 => "Latin-1 string".to_string(),
+        }
+    }
+
+    fn has_byte_order(&self) -> bool {
+        !matches!(
+            self,
+            ValueKind::Utf8 | ValueKind::Latin1 | ValueKind::Int(1, _)
+        )
+    }
+}
+
+#[derive(Clone)]
+pub struct Converter {
+    pub kind: ValueKind,
+    pub little_endian: bool,
+    pub value: String,
+}
+
+impl Default for Converter {
+    fn default() -> Self {
+        Self {
+            kind: ValueKind::Int(4, false),
+            little_endian: true,
+            value: String::new(),
+        }
+    }
+}
+
+fn int_bytes(value: &str, size: usize, signed: bool) -> Result<Vec<u8>, String> {
+    let trimmed = value.trim();
+    let (negative, digits) = match trimmed.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, trimmed),
+    };
+    let magnitude = parse_number(digits)? as i128;
+    let number = if negative { -magnitude } else { magnitude };
+
+    let bits = size as u32 * 8;
+    let (min, max) = if signed {
+        (-(1_i128 << (bits - 1)), (1_i128 << (bits - 1)) - 1)
+    } else {
+        (0, (1_i128 << bits) - 1)
+    };
+    if number < min || number > max {
+        return Err(format!("{} doesn't fit in {} bytes", trimmed, size));
+    }
+    // two's complement, little endian
+    Ok((number as u64).to_le_bytes()[..size].to_vec())
+}
+
+impl Converter {
+    // bytes in little endian order where the value has a byte order
+    fn bytes(&self) -> Result<Vec<u8>, String> {
+        if self.value.is_empty() {
+            return Err("Enter a value to convert".to_string());
+        }
+        let float = || {
+            self.value
+                .trim()
+                .parse::<f64>()
+                .map_err(|_| format!("'{}' is not a number", self.value.trim()))
+        };
+        match self.kind {
+            ValueKind::Int(size, signed) => int_bytes(&self.value, size, signed),
+            ValueKind::F32 => Ok((float()? as f32).to_le_bytes().to_vec()),
+            ValueKind::F64 => Ok(float()?.to_le_bytes().to_vec()),
+            ValueKind::Utf8 => Ok(self.value.as_bytes().to_vec()),
+            ValueKind::Utf16 => Ok(self
+                .value
+                .encode_utf16()
+                .flat_map(|unit| unit.to_le_bytes())
+                .collect()),
+            ValueKind::Latin1 => self
+                .value
+                .chars()
+                .map(|c| u8::try_from(c).map_err(|_| format!("'{}' isn't in Latin-1", c)))
+                .collect(),
+        }
+    }
+
+    // simplified hex pattern for the value
+    pub fn pattern(&self) -> Result<String, String> {
+        let mut bytes = self.bytes()?;
+        if !self.little_endian && self.kind.has_byte_order() {
+            match self.kind {
+                // swap each code unit, not the whole string
+                ValueKind::Utf16 => bytes.chunks_mut(2).for_each(|unit| unit.reverse()),
+                _ => bytes.reverse(),
+            }
+        }
+        Ok(bytes
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<Vec<_>>()
+            .join(" "))
+    }
+}
+
+impl QuerApp {
+    pub(crate) fn add_converter_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_converter;
+        let mut insert = None;
+        egui::Window::new(tr("value-to-hex-pattern"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let converter = &mut self.converter;
+                ui.horizontal(|ui| {
+                    let label = ui.label(tr("value-label"));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut converter.value)
+                            .hint_text(tr("value-hint")),
+                    )
+                    .labelled_by(label.id);
+                });
+                ui.horizontal(|ui| {
+                    let label = ui.label(tr("type-label"));
+                    egui::ComboBox::from_id_source("converter_kind")
+                        .selected_text(converter.kind.name())
+                        .show_ui(ui, |ui| {
+                            for kind in ValueKind::ALL.iter() {
+                                ui.selectable_value(&mut converter.kind, *kind, kind.name());
+                            }
+                        })
+                        .response
+                        .labelled_by(label.id);
+                    ui.add_enabled_ui(converter.kind.has_byte_order(), |ui| {
+                        ui.selectable_value(
+                            &mut converter.little_endian,
+                            true,
+                            tr("little-endian"),
+                        );
+                        ui.selectable_value(&mut converter.little_endian, false, tr("big-endian"));
+                    });
+                });
+                ui.separator();
+
+                let result = converter.pattern();
+                match &result {
+                    Ok(pattern) => {
+                        ui.monospace(pattern);
+                    }
+                    Err(err) => {
+                        ui.colored_label(egui::Color32::from_rgb(0x8f, 0x0, 0x0), err);
+                    }
+                }
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(result.is_ok(), egui::Button::new(tr("insert")))
+                        .on_hover_text(tr("append-to-the-search-pattern"))
+                        .clicked()
+                    {
+                        insert = result.clone().ok().map(|pattern| (pattern, false));
+                    }
+                    if ui
+                        .add_enabled(result.is_ok(), egui::Button::new(tr("replace")))
+                        .on_hover_text(tr("replace-the-search-pattern"))
+                        .clicked()
+                    {
+                        insert = result.clone().ok().map(|pattern| (pattern, true));
+                    }
+                });
+            });
+        self.show_converter = open;
+
+        if let Some((pattern, replace)) = insert {
+            // text patterns can't be mixed with hex bytes, so start over
+            if replace || self.content_type != ContentEnum::Hex || self.regex_str.trim().is_empty()
+            {
+                self.regex_str = pattern;
+            } else {
+                self.regex_str = format!("{} {}", self.regex_str.trim_end(), pattern);
+            }
+            self.content_type = ContentEnum::Hex;
+        }
+    }
+}
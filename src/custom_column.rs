@@ -0,0 +1,129 @@
+use evalexpr::{build_operator_tree, Context, EvalexprError, EvalexprResult, Node, Value};
+use regex::bytes::Regex as BytesRegex;
+use std::ops::Range;
+use std::sync::Arc;
+
+// bytes past the end of a match an expression can read, e.g. a length field after it
+const BYTES_AFTER_MATCH: usize = 256;
+
+#[derive(Clone)]
+pub enum ColumnSource {
+    Group(String), // a named capture group of the pattern
+    Expression(Node),
+}
+
+// a user defined column computed for every match, e.g. `size = u16le(len)`
+#[derive(Clone)]
+pub struct CustomColumn {
+    pub name: String,
+    pub source: ColumnSource,
+}
+
+// `name = $group` or `name = expression`
+pub fn parse_custom_column(text: &str) -> Result<Option<CustomColumn>, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(None);
+    }
+    let (name, source) = text
+        .split_once('=')
+        .ok_or_else(|| format!("'{}' should look like 'size = u16le(len)'", text))?;
+    let (name, source) = (name.trim(), source.trim());
+    if name.is_empty() {
+        return Err(format!("'{}' is missing a name", text));
+    }
+    let source = match source.strip_prefix('$') {
+        Some(group) => ColumnSource::Group(group.to_string()),
+        None => ColumnSource::Expression(
+            build_operator_tree(source).map_err(|err| format!("{}: {}", source, err))?,
+        ),
+    };
+    Ok(Some(CustomColumn {
+        name: name.to_string(),
+        source,
+    }))
+}
+
+// the match and what follows it, as seen by an expression. `len` and `offset` are the
+// match's, the read functions take an index relative to the start of the match.
+struct MatchContext<'a> {
+    bytes: &'a [u8],
+    len: Value,
+    offset: Value,
+}
+
+impl MatchContext<'_> {
+    fn read<const N: usize>(&self, argument: &Value) -> EvalexprResult<[u8; N]> {
+        let index = argument.as_int()?;
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| self.bytes.get(index..index + N))
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| EvalexprError::CustomMessage(format!("{} is out of range", index)))
+    }
+}
+
+impl Context for MatchContext<'_> {
+    fn get_value(&self, identifier: &str) -> Option<&Value> {
+        match identifier {
+            "len" => Some(&self.len),
+            "offset" => Some(&self.offset),
+            _ => None,
+        }
+    }
+
+    fn call_function(&self, identifier: &str, argument: &Value) -> EvalexprResult<Value> {
+        let int = match identifier {
+            "u8" => self.read::<1>(argument)?[0] as i64,
+            "u16le" => u16::from_le_bytes(self.read(argument)?) as i64,
+            "u16be" => u16::from_be_bytes(self.read(argument)?) as i64,
+            "u32le" => u32::from_le_bytes(self.read(argument)?) as i64,
+            "u32be" => u32::from_be_bytes(self.read(argument)?) as i64,
+            "hex" => return Ok(Value::String(format!("0x{:x}", argument.as_int()?))),
+            _ => {
+                return Err(EvalexprError::FunctionIdentifierNotFound(
+                    identifier.to_string(),
+                ))
+            }
+        };
+        Ok(Value::Int(int))
+    }
+
+    fn are_builtin_functions_disabled(&self) -> bool {
+        false
+    }
+
+    fn set_builtin_functions_disabled(&mut self, _disabled: bool) -> EvalexprResult<()> {
+        Err(EvalexprError::ContextNotMutable)
+    }
+}
+
+impl CustomColumn {
+    // the column's value for the match at `m` in `data`, found by `regex`. a group that
+    // didn't take part in the match or an expression reading past the data leave it empty.
+    pub fn value(&self, regex: &BytesRegex, data: &[u8], m: Range<usize>) -> Option<Arc<str>> {
+        let value = match &self.source {
+            ColumnSource::Group(group) => {
+                let captures = regex.captures_at(data, m.start)?;
+                let bytes = captures.name(group)?.as_bytes();
+                match std::str::from_utf8(bytes) {
+                    Ok(text) if !text.chars().any(char::is_control) => text.to_string(),
+                    _ => bytes.iter().map(|b| format!("{:02x}", b)).collect(),
+                }
+            }
+            ColumnSource::Expression(node) => {
+                let end = std::cmp::min(m.end.saturating_add(BYTES_AFTER_MATCH), data.len());
+                let context = MatchContext {
+                    bytes: &data[m.start..end],
+                    len: Value::Int(m.len() as i64),
+                    offset: Value::Int(m.start as i64),
+                };
+                match node.eval_with_context(&context).ok()? {
+                    Value::String(text) => text,
+                    value => value.to_string(),
+                }
+            }
+        };
+        Some(value.into())
+    }
+}
@@ -0,0 +1,205 @@
+use crate::i18n::tr;
+use crate::{Finding, QuerApp, ScanOutcome, SearchOptions};
+use eframe::egui::{self, RichText, TextStyle};
+use memmap2::Mmap;
+use std::fs::File;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use walkdir::DirEntry;
+
+// differing runs closer than this are reported as one region
+const MERGE_GAP: usize = 8;
+// bytes of each side shown in the match column
+const CONTENT_PREVIEW: usize = 16;
+
+// regions where `old` and `new` differ, the tail of the longer one is a region of its own
+pub fn diff_regions(old: &[u8], new: &[u8]) -> Vec<Range<usize>> {
+    let common = std::cmp::min(old.len(), new.len());
+    let mut regions: Vec<Range<usize>> = Vec::new();
+    let mut idx = 0;
+    while idx < common {
+        if old[idx] == new[idx] {
+            idx += 1;
+            continue;
+        }
+        let start = idx;
+        while idx < common && old[idx] != new[idx] {
+            idx += 1;
+        }
+        match regions.last_mut() {
+            Some(last) if start - last.end < MERGE_GAP => last.end = idx,
+            _ => regions.push(start..idx),
+        }
+    }
+
+    let longest = std::cmp::max(old.len(), new.len());
+    if common < longest {
+        regions.push(common..longest);
+    }
+    regions
+}
+
+// the file in `compare_root` at the same relative location as `path` in `base_root`
+pub fn mirrored_path(base_root: &Path, compare_root: &Path, path: &Path) -> PathBuf {
+    match path.strip_prefix(base_root) {
+        Ok(rel) if !rel.as_os_str().is_empty() => compare_root.join(rel),
+        _ => compare_root.to_path_buf(), // comparing two single files
+    }
+}
+
+fn side_preview(data: &[u8], region: &Range<usize>) -> String {
+    let start = std::cmp::min(region.start, data.len());
+    let end = std::cmp::min(region.end, data.len());
+    if start == end {
+        return "(none)".to_string();
+    }
+    let mut preview: Vec<String> = data[start..std::cmp::min(end, start + CONTENT_PREVIEW)]
+        .iter()
+        .map(|byte| format!("{byte:02X}"))
+        .collect();
+    if end - start > CONTENT_PREVIEW {
+        preview.push("..".to_string());
+    }
+    preview.join(" ")
+}
+
+fn map_file(path: &Path) -> Option<Mmap> {
+    let file = File::open(path).ok()?;
+    // same caveat as the regular search, the file may change underneath us
+    unsafe { Mmap::map(&file) }.ok()
+}
+
+// compares a base file against its mirror, skipped if the base file couldn't be read
+pub fn diff_file(
+    entry: &DirEntry,
+    tx: &mpsc::Sender<Finding>,
+    search_opts: &SearchOptions,
+) -> ScanOutcome {
+    let Some(base_data) = map_file(entry.path()) else {
+        return ScanOutcome::Skipped;
+    };
+    let base = &base_data[..];
+    let filepath = entry.path().to_string_lossy().to_string();
+
+    let compare_path = mirrored_path(
+        &search_opts.search_root,
+        &search_opts.compare_root,
+        entry.path(),
+    );
+    let Some(compare_data) = map_file(&compare_path) else {
+        let _ = tx.send(Finding {
+            filepath,
+            offset: 0,
+            match_size: base.len(),
+            match_content: format!("{} bytes", base.len()).into(),
+            location: Some("only in base".to_string()),
+            decoded: false,
+            hit_count: 1,
+            encoding: None,
+            virtual_address: None,
+            pattern: None,
+            aliases: None,
+            owner: None,
+            custom: None,
+        });
+        return ScanOutcome::Scanned;
+    };
+    let compare = &compare_data[..];
+
+    for (hits, region) in diff_regions(base, compare).into_iter().enumerate() {
+        if hits as u32 >= search_opts.max_hits {
+            return ScanOutcome::Capped;
+        }
+        let location = if region.start >= compare.len() {
+            "base longer"
+        } else if region.start >= base.len() {
+            "compare longer"
+        } else {
+            "changed"
+        };
+        let _ = tx.send(Finding {
+            filepath: filepath.clone(),
+            offset: region.start,
+            match_size: region.len(),
+            match_content: format!(
+                "{} -> {}",
+                side_preview(base, &region),
+                side_preview(compare, &region)
+            )
+            .into(),
+            location: Some(location.to_string()),
+            decoded: false,
+            hit_count: 1,
+            encoding: None,
+            virtual_address: None,
+            pattern: None,
+            aliases: None,
+            owner: None,
+            custom: None,
+        });
+    }
+    ScanOutcome::Scanned
+}
+
+impl QuerApp {
+    pub(crate) fn add_compare_path(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        ui.horizontal(|ui| {
+            let path_label =
+                ui.label(RichText::new(tr("compare-with")).text_style(TextStyle::Small));
+            let path_edit = egui::TextEdit::singleline(&mut self.compare_path_str)
+                .font(TextStyle::Small)
+                .hint_text(tr("diff-target-hint"));
+            ui.add_sized(
+                [f32::max(ui.available_width() - 110.0, 24.0), 24.0],
+                path_edit,
+            )
+            .labelled_by(path_label.id);
+
+            if ui.button(tr("folder")).clicked() {
+                let mut dialog = self.compare_file_dialog();
+                dialog.select_directory();
+                self.compare_dialog = Some(dialog);
+            }
+            if ui.button(tr("file")).clicked() {
+                let mut dialog = self.compare_file_dialog();
+                dialog.select_file();
+                self.compare_dialog = Some(dialog);
+            }
+        });
+
+        if let Some(dialog) = &mut self.compare_dialog {
+            let (selected, done) = crate::update_dialog_viewport(
+                ctx,
+                dialog,
+                "compare_dialog",
+                "Open Path to Compare",
+            );
+            if let Some(path) = selected {
+                self.compare_path_str = path.to_string_lossy().to_string();
+            }
+            if done {
+                self.compare_dialog = None;
+            }
+        }
+    }
+
+    fn compare_file_dialog(&self) -> egui_file_dialog::FileDialog {
+        let initial = Path::new(&self.compare_path_str);
+        let initial = if initial.is_dir() {
+            initial.to_path_buf()
+        } else {
+            initial
+                .parent()
+                .map(|parent| parent.to_path_buf())
+                .unwrap_or_else(|| self.root_folder_path.clone())
+        };
+        egui_file_dialog::FileDialog::new()
+            .initial_directory(initial)
+            .as_modal(false)
+            .title_bar(false)
+            .movable(false)
+            .resizable(false)
+            .min_size([800., 600.])
+    }
+}
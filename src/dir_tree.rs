@@ -0,0 +1,158 @@
+use crate::i18n::{tr, tr_args};
+use crate::QuerApp;
+use eframe::egui;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+#[derive(Default, Clone)]
+pub struct DirNode {
+    pub scanned: usize,
+    pub skipped: usize,
+    pub matches: usize,
+    pub dirs: BTreeMap<String, DirNode>,
+    // files with at least one match: (matches, first offset)
+    pub files: BTreeMap<String, (usize, usize)>,
+}
+
+#[derive(Clone)]
+pub struct DirTree {
+    pub root: PathBuf,
+    pub node: DirNode,
+}
+
+impl DirTree {
+    pub fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            node: DirNode::default(),
+        }
+    }
+
+    pub fn add_scanned(&mut self, path: &Path) {
+        self.update(path, |node| node.scanned += 1);
+    }
+
+    pub fn add_skipped(&mut self, path: &Path) {
+        self.update(path, |node| node.skipped += 1);
+    }
+
+    pub fn add_match(&mut self, path: &Path, hits: usize, offset: usize) {
+        self.update(path, |node| node.matches += hits);
+
+        if let Some(parent) = self.parent_node(path) {
+            if let Some(name) = path.file_name() {
+                let entry = parent
+                    .files
+                    .entry(name.to_string_lossy().to_string())
+                    .or_insert((0, offset));
+                entry.0 += hits;
+                entry.1 = std::cmp::min(entry.1, offset);
+            }
+        }
+    }
+
+    // applies `f` to every directory from the root down to the file's parent
+    fn update<F: Fn(&mut DirNode)>(&mut self, path: &Path, f: F) {
+        let Some(rel_dir) = path
+            .parent()
+            .and_then(|parent| parent.strip_prefix(&self.root).ok())
+        else {
+            return;
+        };
+
+        let mut node = &mut self.node;
+        f(node);
+        for component in rel_dir.components() {
+            node = node
+                .dirs
+                .entry(component.as_os_str().to_string_lossy().to_string())
+                .or_default();
+            f(node);
+        }
+    }
+
+    fn parent_node(&mut self, path: &Path) -> Option<&mut DirNode> {
+        let rel_dir = path.parent()?.strip_prefix(&self.root).ok()?;
+        let mut node = &mut self.node;
+        for component in rel_dir.components() {
+            node = node
+                .dirs
+                .get_mut(component.as_os_str().to_string_lossy().as_ref())?;
+        }
+        Some(node)
+    }
+}
+
+impl QuerApp {
+    pub(crate) fn add_dir_tree_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_dir_tree_window;
+        let mut clicked = None;
+        egui::Window::new(tr("directory-tree-title"))
+            .open(&mut open)
+            .resizable(true)
+            .default_width(480.0)
+            .show(ctx, |ui| {
+                ui.label(tr("directory-tree-intro"));
+                ui.separator();
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    let root = self.dir_tree.root.clone();
+                    add_dir_node(
+                        ui,
+                        &root.to_string_lossy(),
+                        &root,
+                        &self.dir_tree.node,
+                        &mut clicked,
+                    );
+                });
+            });
+        self.show_dir_tree_window = open;
+
+        if let Some((path, offset)) = clicked {
+            self.select_finding(&path, offset, 0);
+        }
+    }
+}
+
+fn add_dir_node(
+    ui: &mut egui::Ui,
+    name: &str,
+    path: &Path,
+    node: &DirNode,
+    clicked: &mut Option<(String, usize)>,
+) {
+    let header = format!(
+        "{}  ({} matches, {} scanned, {} skipped)",
+        name, node.matches, node.scanned, node.skipped
+    );
+    egui::CollapsingHeader::new(header)
+        .id_source(path)
+        .default_open(path.parent().is_none() || node.matches > 0 && node.dirs.len() == 1)
+        .show(ui, |ui| {
+            let mut dirs: Vec<_> = node.dirs.iter().collect();
+            dirs.sort_by_key(|(_name, child)| std::cmp::Reverse(child.matches));
+            for (child_name, child) in dirs {
+                add_dir_node(ui, child_name, &path.join(child_name), child, clicked);
+            }
+
+            let mut files: Vec<_> = node.files.iter().collect();
+            files.sort_by_key(|(_name, (matches, _offset))| std::cmp::Reverse(*matches));
+            for (file_name, (matches, offset)) in files {
+                if ui
+                    .selectable_label(
+                        false,
+                        tr_args(
+                            "file-matches",
+                            &[
+                                ("name", file_name.as_str().into()),
+                                ("matches", (*matches).into()),
+                            ],
+                        ),
+                    )
+                    .clicked()
+                {
+                    let file_path = path.join(file_name).to_string_lossy().to_string();
+                    *clicked = Some((file_path, *offset));
+                }
+            }
+        });
+}
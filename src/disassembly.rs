@@ -0,0 +1,105 @@
+use crate::address_map::CodeSection;
+use iced_x86::{Decoder, DecoderOptions, Formatter, Instruction, IntelFormatter};
+
+// how far before the match decoding starts, it has to land on an instruction boundary there
+const LEAD_IN: usize = 64;
+const LINES_BEFORE: usize = 8;
+const LINES_AFTER: usize = 12;
+const MAX_INSTRUCTION: usize = 15;
+const MAX_MATCH: usize = 4096;
+
+pub struct DisassemblyLine {
+    pub address: u64,
+    pub bytes: String,
+    pub text: String,
+    pub in_match: bool,
+}
+
+// the bytes of the section to read for a listing around a match, as (file offset, length)
+pub fn window(section: &CodeSection, match_offset: u64, match_size: usize) -> (u64, usize) {
+    let start = std::cmp::max(
+        section.file_start,
+        match_offset.saturating_sub(LEAD_IN as u64),
+    );
+    let end = match_offset
+        + std::cmp::min(match_size, MAX_MATCH) as u64
+        + (LINES_AFTER * MAX_INSTRUCTION) as u64;
+    let end = std::cmp::min(end, section.file_end);
+    (start, end.saturating_sub(start) as usize)
+}
+
+fn decode(code: &[u8], address: u64, bitness: u32) -> Vec<Instruction> {
+    let mut decoder = Decoder::with_ip(bitness, code, address, DecoderOptions::NONE);
+    let mut instructions = Vec::new();
+    while decoder.can_decode() {
+        instructions.push(decoder.decode());
+    }
+    instructions
+}
+
+// instructions around the match, `code` being the section's bytes starting at `code_offset`
+// in the file. x86 can't be decoded backwards, so decoding starts as far before the match as
+// still stays in step with an instruction starting right at it.
+pub fn disassemble(
+    section: &CodeSection,
+    code: &[u8],
+    code_offset: u64,
+    match_offset: u64,
+    match_size: usize,
+) -> Vec<DisassemblyLine> {
+    let Some(match_pos) = match_offset
+        .checked_sub(code_offset)
+        .map(|pos| pos as usize)
+        .filter(|pos| *pos < code.len())
+    else {
+        return Vec::new();
+    };
+    let address_of = |pos: usize| section.address + (code_offset - section.file_start) + pos as u64;
+    let match_address = address_of(match_pos);
+    let match_end = match_address + match_size.max(1) as u64;
+
+    let mut instructions = Vec::new();
+    for lead_in in (0..=std::cmp::min(LEAD_IN, match_pos)).rev() {
+        let start = match_pos - lead_in;
+        let decoded = decode(&code[start..], address_of(start), section.bitness);
+        if decoded
+            .iter()
+            .any(|instruction| instruction.ip() == match_address)
+        {
+            instructions = decoded;
+            break;
+        }
+    }
+
+    let first_in_match = instructions
+        .iter()
+        .position(|instruction| instruction.ip() == match_address)
+        .unwrap_or(0);
+    let mut formatter = IntelFormatter::new();
+    instructions
+        .iter()
+        .enumerate()
+        .skip(first_in_match.saturating_sub(LINES_BEFORE))
+        .take_while(|(idx, instruction)| {
+            instruction.ip() < match_end || *idx < first_in_match + LINES_AFTER
+        })
+        .map(|(_, instruction)| {
+            let pos = (instruction.ip() - address_of(0)) as usize;
+            let bytes = code
+                .get(pos..pos + instruction.len())
+                .unwrap_or_default()
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect::<Vec<String>>()
+                .join(" ");
+            let mut text = String::new();
+            formatter.format(instruction, &mut text);
+            DisassemblyLine {
+                address: instruction.ip(),
+                bytes,
+                text,
+                in_match: instruction.ip() < match_end && match_address < instruction.next_ip(),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,455 @@
+use crate::custom_column::parse_custom_column;
+use crate::file_read::ReadRetry;
+use crate::i18n::{tr, tr_args};
+use crate::pattern_set::SearchPattern;
+use crate::{
+    prefetch_file, search_file, Finding, HandlerOptions, MatchContent, QuerApp, RecordLayout,
+    RegexEnum, ScanModeEnum, ScanOutcome, SearchOptions,
+};
+use base64::Engine;
+use eframe::egui;
+use regex::bytes::Regex as BytesRegex;
+use regex::bytes::RegexBuilder as BytesRegexBuilder;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+#[cfg(unix)]
+use std::os::unix::fs::{DirBuilderExt, MetadataExt};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
+use walkdir::WalkDir;
+
+// turns the binary into the privileged helper, followed by the path of a request file
+pub const HELPER_ARG: &str = "--elevated-scan";
+// where the helper leaves its response on Windows, next to the request. elsewhere it is
+// printed, the elevation prompt can't pass the helper's output back there.
+#[cfg(windows)]
+const RESPONSE_FILE: &str = "response.json";
+
+// how a retry went, sent back from the thread waiting on the helper
+pub struct ElevatedOutcome {
+    pub scanned: usize,
+    pub findings: usize,
+    pub still_skipped: Vec<PathBuf>,
+}
+
+fn pattern_to_json(pattern: &SearchPattern) -> json::JsonValue {
+    let mut obj = json::JsonValue::new_object();
+    if let Some(name) = &pattern.name {
+        obj["name"] = name.as_ref().into();
+    }
+    // the compiled sources, the case insensitivity option is sent along with the request
+    match &pattern.regex {
+        RegexEnum::Hex(re) => obj["hex"] = re.as_str().into(),
+        RegexEnum::Text(re) => obj["text"] = re.as_str().into(),
+        RegexEnum::Auto(text_re, hex_re) => {
+            obj["text"] = text_re.as_str().into();
+            if let Some(hex_re) = hex_re {
+                obj["hex"] = hex_re.as_str().into();
+            }
+            obj["auto"] = true.into();
+        }
+    }
+    obj
+}
+
+fn pattern_from_json(obj: &json::JsonValue, case_insensitive: bool) -> Option<SearchPattern> {
+    let compile = |key: &str| obj[key].as_str().and_then(|src| BytesRegex::new(src).ok());
+    let text_re = obj["text"].as_str().and_then(|src| {
+        BytesRegexBuilder::new(src)
+            .case_insensitive(case_insensitive)
+            .build()
+            .ok()
+    });
+    let regex = match (obj["auto"].as_bool().unwrap_or(false), text_re) {
+        (true, Some(text_re)) => RegexEnum::Auto(text_re, compile("hex")),
+        (false, Some(text_re)) => RegexEnum::Text(text_re),
+        (_, None) => RegexEnum::Hex(compile("hex")?),
+    };
+    Some(SearchPattern {
+        name: obj["name"].as_str().map(Arc::from),
+        regex,
+    })
+}
+
+pub(crate) fn finding_to_json(finding: &Finding) -> json::JsonValue {
+    let mut obj = json::JsonValue::new_object();
+    obj["filepath"] = finding.filepath.as_str().into();
+    obj["offset"] = finding.offset.into();
+    obj["match_size"] = finding.match_size.into();
+    obj["hit_count"] = finding.hit_count.into();
+    match &finding.match_content {
+        MatchContent::Text(text) => obj["text"] = text.as_str().into(),
+        MatchContent::Bytes(bytes) => {
+            obj["bytes"] = base64::engine::general_purpose::STANDARD
+                .encode(bytes)
+                .into()
+        }
+    }
+    if let Some(location) = &finding.location {
+        obj["location"] = location.as_str().into();
+    }
+    if finding.decoded {
+        obj["decoded"] = true.into();
+    }
+    if let Some(pattern) = &finding.pattern {
+        obj["pattern"] = pattern.as_ref().into();
+    }
+    if let Some(owner) = &finding.owner {
+        obj["owner"] = owner.as_ref().into();
+    }
+    if let Some(custom) = &finding.custom {
+        obj["custom"] = custom.as_ref().into();
+    }
+    obj
+}
+
+pub(crate) fn finding_from_json(obj: &json::JsonValue) -> Option<Finding> {
+    let match_content = match obj["text"].as_str() {
+        Some(text) => MatchContent::Text(text.to_string()),
+        None => MatchContent::Bytes(
+            base64::engine::general_purpose::STANDARD
+                .decode(obj["bytes"].as_str()?)
+                .ok()?
+                .into(),
+        ),
+    };
+    Some(Finding {
+        filepath: obj["filepath"].as_str()?.to_string(),
+        offset: obj["offset"].as_usize()?,
+        match_size: obj["match_size"].as_usize()?,
+        match_content,
+        location: obj["location"].as_str().map(str::to_string),
+        decoded: obj["decoded"].as_bool().unwrap_or(false),
+        hit_count: obj["hit_count"].as_usize().unwrap_or(1),
+        encoding: None,
+        virtual_address: None,
+        pattern: obj["pattern"].as_str().map(Arc::from),
+        aliases: None,
+        owner: obj["owner"].as_str().map(Arc::from),
+        custom: obj["custom"].as_str().map(Arc::from),
+    })
+}
+
+// only a regular file in a directory nobody else can write to, anything else could have been
+// put there by another user to make the helper scan what they choose
+fn read_request(path: &Path) -> Option<json::JsonValue> {
+    let metadata = fs::symlink_metadata(path).ok()?;
+    if !metadata.file_type().is_file() {
+        return None;
+    }
+    #[cfg(unix)]
+    {
+        let dir = fs::symlink_metadata(path.parent()?).ok()?;
+        if !dir.is_dir() || dir.mode() & 0o022 != 0 || dir.uid() != metadata.uid() {
+            return None;
+        }
+    }
+    json::parse(&fs::read_to_string(path).ok()?).ok()
+}
+
+#[cfg(windows)]
+fn write_response(request_path: &Path, response: &str) -> std::io::Result<()> {
+    // never an existing file, the unprivileged side only reads what the helper created
+    let path = request_path.with_file_name(RESPONSE_FILE);
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)?
+        .write_all(response.as_bytes())
+}
+
+#[cfg(not(windows))]
+fn write_response(_request_path: &Path, response: &str) -> std::io::Result<()> {
+    let mut stdout = std::io::stdout().lock();
+    writeln!(stdout, "{}", response)?;
+    stdout.flush()
+}
+
+// the helper side: scans the listed files with the given options and passes what it found
+// back to the unprivileged side, which never hands it a path to write to
+pub fn run_helper(request_path: &Path) -> i32 {
+    let Some(request) = read_request(request_path) else {
+        eprintln!("Couldn't read the request {}", request_path.display());
+        return 2;
+    };
+    let case_insensitive = request["case_insensitive"].as_bool().unwrap_or(false);
+    let search_opts = Arc::new(SearchOptions {
+        min_match_len: request["min_match_len"].as_usize().unwrap_or(0),
+        max_match_len: request["max_match_len"].as_usize().unwrap_or(0),
+        alignment: request["alignment"].as_i32().unwrap_or(0),
+        alignment_base: request["alignment_base"].as_usize().unwrap_or(0),
+        patterns: request["patterns"]
+            .members()
+            .filter_map(|pattern| pattern_from_json(pattern, case_insensitive))
+            .collect(),
+        max_hits: request["max_hits"].as_u32().unwrap_or(0),
+        decode_containers: false,
+        handler_options: HandlerOptions::default(),
+        scan_mode: ScanModeEnum::Content,
+        first_match_only: request["first_match_only"].as_bool().unwrap_or(false),
+        attribute_fs: request["attribute_fs"].as_bool().unwrap_or(false),
+        custom_column: request["custom_column"]
+            .as_str()
+            .and_then(|text| parse_custom_column(text).ok().flatten())
+            .map(Arc::new),
+        search_root: PathBuf::new(),
+        compare_root: PathBuf::new(),
+        padding_min_run: 0,
+        padding_max_period: 0,
+        record_layout: RecordLayout::default(),
+    });
+    let retry = ReadRetry {
+        locked_files: request["read_locked_files"]
+            .as_bool()
+            .unwrap_or(false)
+            .then(Default::default),
+        ..ReadRetry::new(
+            request["read_retries"].as_u32().unwrap_or(0),
+            Duration::from_millis(request["read_retry_backoff_ms"].as_u64().unwrap_or(0)),
+        )
+    };
+
+    let (tx, rx) = mpsc::channel();
+    let mut still_skipped = json::JsonValue::new_array();
+    for path in request["files"].members().filter_map(|path| path.as_str()) {
+        let entry = WalkDir::new(path).max_depth(0).into_iter().next();
+        let outcome = match entry {
+            Some(Ok(entry)) => {
+                let file_data = prefetch_file(&entry, &ScanModeEnum::Content, &retry);
+                search_file(&entry, file_data.as_deref(), &tx, Arc::clone(&search_opts))
+            }
+            _ => ScanOutcome::Skipped,
+        };
+        if matches!(outcome, ScanOutcome::Skipped) {
+            let _ = still_skipped.push(path);
+        }
+    }
+    drop(tx);
+
+    let mut response = json::JsonValue::new_object();
+    response["findings"] = json::JsonValue::Array(rx.iter().map(|f| finding_to_json(&f)).collect());
+    response["skipped"] = still_skipped;
+    match write_response(request_path, &response.dump()) {
+        Ok(_) => 0,
+        Err(err) => {
+            eprintln!("Couldn't pass the results back: {}", err);
+            1
+        }
+    }
+}
+
+// runs the helper with admin/root rights, prompting for them the platform's way
+fn elevated_command(exe: &Path, request: &Path) -> Command {
+    #[cfg(windows)]
+    {
+        let quote = |path: &Path| format!("'{}'", path.to_string_lossy().replace('\'', "''"));
+        let mut cmd = Command::new("powershell");
+        cmd.args(["-NoProfile", "-Command"]).arg(format!(
+            "Start-Process -FilePath {} -ArgumentList '{}','\"{}\"' -Verb RunAs -Wait",
+            quote(exe),
+            HELPER_ARG,
+            request.to_string_lossy().replace('\'', "''")
+        ));
+        cmd
+    }
+    #[cfg(target_os = "macos")]
+    {
+        let quote = |path: &Path| format!("'{}'", path.to_string_lossy().replace('\'', "'\\''"));
+        let script = format!("{} {} {}", quote(exe), HELPER_ARG, quote(request))
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"");
+        let mut cmd = Command::new("osascript");
+        cmd.arg("-e").arg(format!(
+            "do shell script \"{}\" with administrator privileges",
+            script
+        ));
+        cmd
+    }
+    #[cfg(not(any(windows, target_os = "macos")))]
+    {
+        let mut cmd = Command::new("pkexec");
+        cmd.arg(exe).arg(HELPER_ARG).arg(request);
+        cmd
+    }
+}
+
+// a new directory only the user can enter, with a name nobody can guess ahead of time
+fn private_dir() -> Result<PathBuf, String> {
+    let mut random = [0_u8; 16];
+    getrandom::getrandom(&mut random).map_err(|err| err.to_string())?;
+    let name: String = random.iter().map(|byte| format!("{:02x}", byte)).collect();
+    let dir = std::env::temp_dir().join(format!("quer-elevated-{}", name));
+    let mut builder = fs::DirBuilder::new();
+    #[cfg(unix)]
+    builder.mode(0o700);
+    // fails if anything, a link included, is already there
+    builder.create(&dir).map_err(|err| err.to_string())?;
+    Ok(dir)
+}
+
+fn ask_helper(
+    exe: &Path,
+    dir: &Path,
+    request: &json::JsonValue,
+) -> Result<json::JsonValue, String> {
+    let request_path = dir.join("request.json");
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&request_path)
+        .and_then(|mut file| file.write_all(request.dump().as_bytes()))
+        .map_err(|err| err.to_string())?;
+
+    let output = elevated_command(exe, &request_path)
+        .output()
+        .map_err(|err| err.to_string())?;
+    #[cfg(windows)]
+    let response = fs::read_to_string(dir.join(RESPONSE_FILE)).unwrap_or_default();
+    #[cfg(not(windows))]
+    let response = String::from_utf8_lossy(&output.stdout).to_string();
+    // the response is the last line, in case anything else was printed before it
+    response
+        .lines()
+        .rev()
+        .find(|line| !line.trim().is_empty())
+        .and_then(|line| json::parse(line).ok())
+        .filter(|response| response.has_key("findings"))
+        .ok_or_else(|| format!("the helper didn't finish ({})", output.status))
+}
+
+fn run_elevated(
+    request: json::JsonValue,
+    files: usize,
+    tx: &mpsc::Sender<Finding>,
+) -> Result<ElevatedOutcome, String> {
+    let exe = std::env::current_exe().map_err(|err| err.to_string())?;
+    let dir = private_dir()?;
+    let response = ask_helper(&exe, &dir, &request);
+    let _ = fs::remove_dir_all(&dir);
+    let response = response?;
+
+    let mut findings = 0;
+    for finding in response["findings"].members().filter_map(finding_from_json) {
+        findings += 1;
+        if tx.send(finding).is_err() {
+            break;
+        }
+    }
+    let still_skipped: Vec<PathBuf> = response["skipped"]
+        .members()
+        .filter_map(|path| path.as_str().map(PathBuf::from))
+        .collect();
+    Ok(ElevatedOutcome {
+        scanned: files.saturating_sub(still_skipped.len()),
+        findings,
+        still_skipped,
+    })
+}
+
+impl QuerApp {
+    fn elevated_request(&self) -> json::JsonValue {
+        let search_opts = self.get_search_options();
+        let mut request = json::JsonValue::new_object();
+        request["min_match_len"] = search_opts.min_match_len.into();
+        request["max_match_len"] = search_opts.max_match_len.into();
+        request["alignment"] = search_opts.alignment.into();
+        request["alignment_base"] = search_opts.alignment_base.into();
+        request["max_hits"] = search_opts.max_hits.into();
+        request["first_match_only"] = search_opts.first_match_only.into();
+        request["attribute_fs"] = search_opts.attribute_fs.into();
+        request["custom_column"] = self.custom_column_str.as_str().into();
+        request["read_retries"] = self.read_retry.retries.into();
+        request["read_retry_backoff_ms"] = (self.read_retry.backoff.as_millis() as u64).into();
+        request["read_locked_files"] = self.read_retry.locked_files.is_some().into();
+        // pattern files are compiled without it
+        request["case_insensitive"] = (self.case_insensitive && self.pattern_set.is_none()).into();
+        request["patterns"] =
+            json::JsonValue::Array(search_opts.patterns.iter().map(pattern_to_json).collect());
+        request["files"] = self
+            .skipped_files
+            .iter()
+            .map(|path| path.to_string_lossy().to_string())
+            .collect::<Vec<String>>()
+            .into();
+        request
+    }
+
+    fn retry_skipped_elevated(&mut self, ctx: &egui::Context) {
+        let request = self.elevated_request();
+        let files = self.skipped_files.len();
+        self.log(format!(
+            "Retrying {} skipped files with elevated rights",
+            files
+        ));
+
+        // findings come in through the results channels like those of a search
+        let (tx, rx) = mpsc::channel();
+        self.rx_handles.push(rx);
+        self.elevated_running = true;
+        let outcome = Arc::clone(&self.elevated_outcome);
+        let ctx = ctx.clone();
+        thread::spawn(move || {
+            let result = run_elevated(request, files, &tx);
+            if let Ok(mut outcome) = outcome.lock() {
+                *outcome = Some(result);
+            }
+            ctx.request_repaint();
+        });
+    }
+
+    fn poll_elevated_rescan(&mut self) {
+        let result = match self.elevated_outcome.lock() {
+            Ok(mut outcome) => outcome.take(),
+            Err(_) => None,
+        };
+        let Some(result) = result else {
+            return;
+        };
+        self.elevated_running = false;
+        match result {
+            Ok(outcome) => {
+                self.files_scanned += outcome.scanned;
+                self.files_skipped = self.files_skipped.saturating_sub(outcome.scanned);
+                self.skipped_files = outcome.still_skipped;
+                self.log(format!(
+                    "Elevated retry scanned {} files and found {} matches, {} still unreadable",
+                    outcome.scanned,
+                    outcome.findings,
+                    self.skipped_files.len()
+                ));
+            }
+            Err(err) => self.log_error(format!("Elevated retry failed: {}", err)),
+        }
+    }
+
+    // offered once a content search is done and left files it couldn't read
+    pub(crate) fn add_elevated_retry(&mut self, ui: &mut egui::Ui) {
+        self.poll_elevated_rescan();
+        if self.skipped_files.is_empty()
+            || self.active_scan_mode != ScanModeEnum::Content
+            || !self.is_search_finished()
+        {
+            return;
+        }
+        ui.horizontal(|ui| {
+            let button = egui::Button::new(tr_args(
+                "retry-skipped-elevated",
+                &[("count", self.skipped_files.len().into())],
+            ));
+            if ui
+                .add_enabled(!self.elevated_running, button)
+                .on_hover_text(tr("retry-skipped-elevated-hint"))
+                .clicked()
+            {
+                self.retry_skipped_elevated(ui.ctx());
+            }
+            if self.elevated_running {
+                ui.spinner();
+                ui.label(tr("waiting-for-elevated-helper"));
+            }
+        });
+    }
+}
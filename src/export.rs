@@ -0,0 +1,540 @@
+use crate::hash_lookup::sha256_bytes;
+use crate::history::SearchHistoryEntry;
+use crate::i18n::{tr, tr_args};
+use crate::manifest::write_manifest;
+use crate::{ContentEnum, FilterTypeEnum, Finding, QuerApp, ScanModeEnum};
+use eframe::egui::{self, Color32};
+use std::fs::{self, File};
+use std::io::{BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
+
+// rows written between checks for cancellation and progress updates
+const EXPORT_CHUNK: usize = 4096;
+
+// URLs, email addresses and IPv4 addresses, the indicators defanged in exports
+static IOC_REGEX: OnceLock<regex::Regex> = OnceLock::new();
+
+// characters of the source path kept in the name of an extracted region
+const MAX_REGION_NAME_LENGTH: usize = 120;
+
+// where quick exports go until changed in the settings
+pub(crate) const DEFAULT_QUICK_EXPORT_TEMPLATE: &str = "~/quer/{date}_{pattern-hash}.csv";
+pub(crate) const QUICK_EXPORT_PLACEHOLDERS: &str = "{date} and {time} of the export, {pattern-hash} (first 8 hex digits of the pattern's SHA-256), {mode} content mode, ~ for the home directory";
+
+// fills in the placeholders of a quick export path template
+pub(crate) fn expand_export_template(template: &str, pattern: &str, mode: &str) -> PathBuf {
+    let now = chrono::Local::now();
+    let expanded = template
+        .replace("{date}", &now.format("%Y-%m-%d").to_string())
+        .replace("{time}", &now.format("%H%M%S").to_string())
+        .replace("{pattern-hash}", &sha256_bytes(pattern.as_bytes())[..8])
+        .replace("{mode}", mode);
+    match expanded.strip_prefix("~/").or(expanded.strip_prefix("~\\")) {
+        Some(rest) => match std::env::var_os("HOME").or(std::env::var_os("USERPROFILE")) {
+            Some(home) => Path::new(&home).join(rest),
+            None => PathBuf::from(expanded),
+        },
+        None => PathBuf::from(expanded),
+    }
+}
+
+#[derive(Clone)]
+pub(crate) enum ExportFormat {
+    Csv,
+    ImHexBookmarks(String), // bookmarks of the matches in this file only
+    // one file per match in a directory, with this many bytes of context before and after
+    Regions(usize, usize),
+    // path, size, mtime and hash of every file the search scanned
+    Manifest,
+}
+
+impl ExportFormat {
+    fn name(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "CSV",
+            ExportFormat::ImHexBookmarks(_) => "ImHex bookmarks",
+            ExportFormat::Regions(_, _) => "matched regions",
+            ExportFormat::Manifest => "corpus manifest",
+        }
+    }
+
+    // exported into a directory instead of a single file
+    pub(crate) fn is_directory(&self) -> bool {
+        matches!(self, ExportFormat::Regions(_, _))
+    }
+}
+
+pub(crate) struct ExportJob {
+    path: PathBuf,
+    format_name: &'static str,
+    total: usize,
+    written: Arc<AtomicUsize>,
+    cancel: Arc<AtomicBool>,
+    result: Arc<Mutex<Option<Result<usize, String>>>>,
+}
+
+impl QuerApp {
+    // snapshots the findings and writes them out on a background thread
+    pub(crate) fn start_export(
+        &mut self,
+        format: ExportFormat,
+        path: PathBuf,
+        ctx: &egui::Context,
+    ) {
+        if self.export_job.is_some() {
+            self.log_error("An export is already running".to_string());
+            return;
+        }
+        let findings: Vec<Finding> = match &format {
+            ExportFormat::Csv | ExportFormat::Regions(_, _) => self.findings.clone(),
+            ExportFormat::Manifest => Vec::new(),
+            ExportFormat::ImHexBookmarks(filepath) => self
+                .findings
+                .iter()
+                // bookmarks point into the file, decoded offsets don't
+                .filter(|finding| finding.filepath == *filepath && !finding.decoded)
+                .cloned()
+                .collect(),
+        };
+        let files = match &format {
+            ExportFormat::Manifest => self.scanned_files.clone(),
+            _ => Vec::new(),
+        };
+        let total = match &format {
+            ExportFormat::Manifest => files.len(),
+            _ => findings.len(),
+        };
+        self.log(format!(
+            "Exporting {} results as {} to {}",
+            total,
+            format.name(),
+            path.display()
+        ));
+
+        let job = ExportJob {
+            path: path.clone(),
+            format_name: format.name(),
+            total,
+            written: Arc::new(AtomicUsize::new(0)),
+            cancel: Arc::new(AtomicBool::new(false)),
+            result: Arc::new(Mutex::new(None)),
+        };
+        let defang = self.defang_exports;
+        let metadata = match self.write_export_metadata {
+            true => Some(self.export_metadata(&format, total)),
+            false => None,
+        };
+        let written = Arc::clone(&job.written);
+        let cancel = Arc::clone(&job.cancel);
+        let result = Arc::clone(&job.result);
+        let ctx = ctx.clone();
+        thread::spawn(move || {
+            let outcome = match format {
+                ExportFormat::Csv => write_csv(&findings, &path, defang, &written, &cancel),
+                ExportFormat::ImHexBookmarks(_) => {
+                    write_imhexbm(&findings, &path, &written, &cancel)
+                }
+                ExportFormat::Regions(before, after) => {
+                    write_regions(&findings, &path, before, after, &written, &cancel)
+                }
+                ExportFormat::Manifest => write_manifest(&files, &path, &written, &cancel),
+            };
+            // don't leave half written files behind, regions already extracted are kept
+            if outcome.is_err() && !format.is_directory() {
+                let _ = fs::remove_file(&path);
+            }
+            let meta_path = match format.is_directory() {
+                true => path.join("regions.meta.json"),
+                false => metadata_path(&path),
+            };
+            let outcome = match (outcome, metadata) {
+                (Ok(rows), Some(metadata)) => {
+                    fs::write(meta_path, json::stringify_pretty(metadata, 4))
+                        .map(|_| rows)
+                        .map_err(|err| format!("results written, but not the metadata: {}", err))
+                }
+                (outcome, _) => outcome,
+            };
+            if let Ok(mut result) = result.lock() {
+                *result = Some(outcome);
+            }
+            ctx.request_repaint();
+        });
+        self.export_job = Some(job);
+    }
+
+    // CSV export of all results to the templated path, skipping the file dialog
+    pub(crate) fn quick_export(&mut self, ctx: &egui::Context) {
+        let mode = content_mode_name(&self.content_type);
+        let mut path = expand_export_template(&self.quick_export_template, &self.regex_str, mode);
+        // {date} alone would overwrite earlier exports of the same pattern that day
+        if path.exists() {
+            let stem = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let extension = path
+                .extension()
+                .map(|ext| format!(".{}", ext.to_string_lossy()))
+                .unwrap_or_default();
+            let suffix = chrono::Local::now().format("%H%M%S");
+            path.set_file_name(format!("{}_{}{}", stem, suffix, extension));
+        }
+        if let Some(parent) = path.parent() {
+            if let Err(err) = fs::create_dir_all(parent) {
+                self.log_error(format!(
+                    "Quick export failed, can't create {}: {}",
+                    parent.display(),
+                    err
+                ));
+                return;
+            }
+        }
+        self.start_export(ExportFormat::Csv, path, ctx);
+    }
+
+    // what produced the exported results, so they can be interpreted later on
+    fn export_metadata(&self, format: &ExportFormat, rows: usize) -> json::JsonValue {
+        let mut obj = json::JsonValue::new_object();
+        obj["app"] = "quer".into();
+        obj["app_version"] = env!("CARGO_PKG_VERSION").into();
+        obj["exported_at"] = chrono::Local::now().to_rfc3339().into();
+        obj["format"] = format.name().into();
+        obj["results"] = rows.into();
+        if matches!(format, ExportFormat::Csv) {
+            obj["defanged"] = self.defang_exports.into();
+        }
+        match format {
+            ExportFormat::ImHexBookmarks(filepath) => {
+                obj["source_file"] = filepath.as_str().into();
+            }
+            ExportFormat::Regions(before, after) => {
+                obj["context_before"] = (*before).into();
+                obj["context_after"] = (*after).into();
+            }
+            ExportFormat::Csv | ExportFormat::Manifest => {}
+        }
+        if let Some(search) = &self.last_search {
+            obj["search"] = search_metadata(search);
+        }
+        if let Some(started) = self.search_started_at {
+            obj["scan_started"] = started.to_rfc3339().into();
+        }
+        if let Some(finished) = self.search_finished_at {
+            obj["scan_finished"] = finished.to_rfc3339().into();
+        }
+        obj
+    }
+
+    pub(crate) fn add_export_progress_window(&mut self, ctx: &egui::Context) {
+        let Some(job) = &self.export_job else {
+            return;
+        };
+        let finished = job.result.lock().ok().and_then(|mut result| result.take());
+        if let Some(outcome) = finished {
+            let path = job.path.display().to_string();
+            let format_name = job.format_name;
+            self.export_job = None;
+            match outcome {
+                Ok(rows) => self.log(format!(
+                    "Exported {} results as {} to {}",
+                    rows, format_name, path
+                )),
+                Err(err) => self.log_error(format!("Export to {} failed: {}", path, err)),
+            }
+            return;
+        }
+
+        let written = job.written.load(Ordering::Relaxed);
+        let cancelling = job.cancel.load(Ordering::Relaxed);
+        egui::Window::new(tr("exporting"))
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(tr_args(
+                    "writing-to",
+                    &[
+                        ("format", job.format_name.into()),
+                        ("path", job.path.display().to_string().into()),
+                    ],
+                ));
+                let fraction = if job.total > 0 {
+                    written as f32 / job.total as f32
+                } else {
+                    1.0
+                };
+                ui.add(egui::ProgressBar::new(fraction).text(tr_args(
+                    "written-of-total",
+                    &[("written", written.into()), ("total", job.total.into())],
+                )));
+                ui.horizontal(|ui| {
+                    if cancelling {
+                        ui.spinner();
+                        ui.colored_label(Color32::from_rgb(0x8f, 0, 0), tr("cancelling"));
+                    } else if ui.button(tr("cancel")).clicked() {
+                        job.cancel.store(true, Ordering::Relaxed);
+                    }
+                });
+            });
+        // progress is polled, the worker only wakes the UI when it's done
+        ctx.request_repaint_after(std::time::Duration::from_millis(100));
+    }
+}
+
+pub(crate) fn content_mode_name(content_type: &ContentEnum) -> &'static str {
+    match content_type {
+        ContentEnum::Hex => "hex",
+        ContentEnum::Text => "text",
+        ContentEnum::Auto => "auto",
+    }
+}
+
+pub(crate) fn scan_mode_name(scan_mode: &ScanModeEnum) -> &'static str {
+    match scan_mode {
+        ScanModeEnum::Content => "content",
+        ScanModeEnum::Metadata => "metadata",
+        ScanModeEnum::Duplicates => "duplicates",
+        ScanModeEnum::CountOnly => "count_only",
+        ScanModeEnum::Diff => "diff",
+        ScanModeEnum::Padding => "padding",
+        ScanModeEnum::Records => "records",
+    }
+}
+
+pub(crate) fn hidden_files_name(hidden_files: &FilterTypeEnum) -> &'static str {
+    match hidden_files {
+        FilterTypeEnum::AllFiles => "included",
+        FilterTypeEnum::NoHidden => "excluded",
+        FilterTypeEnum::NoHiddenOrSystem => "hidden and system excluded",
+    }
+}
+
+fn search_metadata(search: &SearchHistoryEntry) -> json::JsonValue {
+    let mut obj = json::JsonValue::new_object();
+    obj["pattern"] = search.regex_str.as_str().into();
+    obj["content_mode"] = content_mode_name(&search.content_type).into();
+    obj["scan_mode"] = scan_mode_name(&search.scan_mode).into();
+    obj["root"] = search.root_folder_path.to_string_lossy().to_string().into();
+    obj["filter"] = search.filter_str.as_str().into();
+    obj["hidden_files"] = hidden_files_name(&search.file_walk_options.hidden_files).into();
+    obj["skipped_paths"] = search
+        .file_walk_options
+        .skip_paths
+        .iter()
+        .map(|path| path.to_string_lossy().to_string())
+        .collect::<Vec<String>>()
+        .into();
+    obj["alignment"] = search.alignment.into();
+    obj["alignment_base"] = search.alignment_base.into();
+    obj["max_hits"] = search.max_hits.into();
+    obj
+}
+
+// results.csv gets results.meta.json next to it
+fn metadata_path(path: &Path) -> PathBuf {
+    path.with_extension("meta.json")
+}
+
+fn check_cancel(cancel: &AtomicBool) -> Result<(), String> {
+    if cancel.load(Ordering::Relaxed) {
+        Err("cancelled".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+// quotes fields holding separators, quotes or line breaks
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+// hxxp://example[.]com, user[@]example[.]com and 10[.]0[.]0[.]1, so a shared report
+// doesn't turn them into clickable links. only the host part of a URL is touched.
+pub(crate) fn defang(text: &str) -> String {
+    let ioc = IOC_REGEX.get_or_init(|| {
+        regex::Regex::new(
+            r#"(?i)\b(?:(?:https?|ftp)://[^\s"'<>]+|[a-z0-9._%+-]+@[a-z0-9-]+(?:\.[a-z0-9-]+)+|(?:\d{1,3}\.){3}\d{1,3}\b)"#,
+        )
+        .unwrap()
+    });
+    ioc.replace_all(text, |caps: &regex::Captures| {
+        let ioc = &caps[0];
+        let Some(scheme_end) = ioc.find("://") else {
+            return ioc.replace('.', "[.]").replace('@', "[@]");
+        };
+        let (scheme, rest) = ioc.split_at(scheme_end + 3);
+        let host_end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+        let scheme = match scheme.to_ascii_lowercase().as_str() {
+            "ftp://" => format!("fxp{}", &scheme[3..]),
+            _ => format!("hxxp{}", &scheme[4..]),
+        };
+        format!(
+            "{}{}{}",
+            scheme,
+            rest[..host_end].replace('.', "[.]").replace('@', "[@]"),
+            &rest[host_end..]
+        )
+    })
+    .into_owned()
+}
+
+fn write_csv(
+    findings: &[Finding],
+    path: &Path,
+    defang_iocs: bool,
+    written: &AtomicUsize,
+    cancel: &AtomicBool,
+) -> Result<usize, String> {
+    let file = File::create(path).map_err(|err| err.to_string())?;
+    let mut out = BufWriter::new(file);
+    writeln!(
+        out,
+        "filepath,offset,match_size,match_content,location,encoding,virtual_address,pattern,owner,custom"
+    )
+    .map_err(|err| err.to_string())?;
+
+    for (idx, chunk) in findings.chunks(EXPORT_CHUNK).enumerate() {
+        check_cancel(cancel)?;
+        for finding in chunk {
+            writeln!(
+                out,
+                "{},{},{},{},{},{},{},{},{},{}",
+                csv_field(&finding.filepath),
+                finding.offset,
+                finding.match_size,
+                csv_field(&match defang_iocs {
+                    true => defang(&finding.match_content.to_string()),
+                    false => finding.match_content.to_string(),
+                }),
+                csv_field(finding.location.as_deref().unwrap_or("")),
+                finding.encoding.unwrap_or(""),
+                finding
+                    .virtual_address
+                    .map(|address| format!("0x{:x}", address))
+                    .unwrap_or_default(),
+                csv_field(finding.pattern.as_deref().unwrap_or("")),
+                csv_field(finding.owner.as_deref().unwrap_or("")),
+                csv_field(finding.custom.as_deref().unwrap_or("")),
+            )
+            .map_err(|err| err.to_string())?;
+        }
+        written.store(idx * EXPORT_CHUNK + chunk.len(), Ordering::Relaxed);
+    }
+    out.flush().map_err(|err| err.to_string())?;
+    Ok(findings.len())
+}
+
+fn write_imhexbm(
+    findings: &[Finding],
+    path: &Path,
+    written: &AtomicUsize,
+    cancel: &AtomicBool,
+) -> Result<usize, String> {
+    let mut bookmarks_vec: Vec<json::JsonValue> = Vec::new();
+    let mut json_data = json::JsonValue::new_object();
+
+    for (idx, finding) in findings.iter().enumerate() {
+        if idx % EXPORT_CHUNK == 0 {
+            check_cancel(cancel)?;
+            written.store(idx, Ordering::Relaxed);
+        }
+        let mut bookmark_obj = json::JsonValue::new_object();
+        bookmark_obj["color"] = 1341756994.into();
+        bookmark_obj["comment"] = match finding.virtual_address {
+            Some(address) => format!("VA 0x{:x}\n", address).into(),
+            None => "\n".into(),
+        };
+        bookmark_obj["id"] = (idx + 1).into();
+        bookmark_obj["locked"] = true.into();
+        bookmark_obj["name"] = format!("{} @ 0x{:x}", finding.match_content, finding.offset).into();
+
+        let mut region_obj = json::JsonValue::new_object();
+        region_obj["address"] = finding.offset.into();
+        region_obj["size"] = finding.match_size.into();
+        bookmark_obj["region"] = region_obj;
+
+        bookmarks_vec.push(bookmark_obj);
+    }
+    check_cancel(cancel)?;
+    written.store(findings.len(), Ordering::Relaxed);
+    json_data["bookmarks"] = bookmarks_vec.into();
+
+    fs::write(path, json::stringify_pretty(json_data, 4)).map_err(|err| err.to_string())?;
+    Ok(findings.len())
+}
+
+// e.g. /data/fw.bin and offset 0x400 become data_fw.bin_0x400.bin
+fn region_file_name(finding: &Finding) -> String {
+    let source: String = finding
+        .filepath
+        .chars()
+        .map(|c| match c.is_alphanumeric() || c == '.' || c == '-' {
+            true => c,
+            false => '_',
+        })
+        .collect();
+    let source = source.trim_start_matches('_');
+    // the end of the path is what tells the files apart
+    let skip = source
+        .chars()
+        .count()
+        .saturating_sub(MAX_REGION_NAME_LENGTH);
+    let source: String = source.chars().skip(skip).collect();
+    format!("{}_0x{:x}.bin", source, finding.offset)
+}
+
+// carves each match plus its context out of the source file, matches inside decoded
+// containers are skipped as their offsets don't point into the file itself
+fn write_regions(
+    findings: &[Finding],
+    dir: &Path,
+    before: usize,
+    after: usize,
+    written: &AtomicUsize,
+    cancel: &AtomicBool,
+) -> Result<usize, String> {
+    fs::create_dir_all(dir).map_err(|err| err.to_string())?;
+    let mut extracted = 0;
+    for (idx, finding) in findings.iter().enumerate() {
+        if idx % EXPORT_CHUNK == 0 {
+            check_cancel(cancel)?;
+        }
+        written.store(idx, Ordering::Relaxed);
+        let mut out_path = dir.join(region_file_name(finding));
+        // different paths can end up with the same name once sanitized
+        if out_path.exists() {
+            out_path = dir.join(format!("{}_{}.bin", region_file_name(finding), idx));
+        }
+        // the file holds decoded content encoded, there's only the match itself to extract
+        if finding.decoded {
+            fs::write(&out_path, finding.match_content.to_bytes())
+                .map_err(|err| format!("{}: {}", out_path.display(), err))?;
+            extracted += 1;
+            continue;
+        }
+
+        let start = finding.offset.saturating_sub(before);
+        let len = finding.offset - start + finding.match_size + after;
+        let mut source = File::open(&finding.filepath)
+            .map_err(|err| format!("{}: {}", finding.filepath, err))?;
+        source
+            .seek(SeekFrom::Start(start as u64))
+            .map_err(|err| format!("{}: {}", finding.filepath, err))?;
+        let mut bytes = Vec::with_capacity(len);
+        source
+            .take(len as u64)
+            .read_to_end(&mut bytes)
+            .map_err(|err| format!("{}: {}", finding.filepath, err))?;
+        fs::write(&out_path, &bytes).map_err(|err| format!("{}: {}", out_path.display(), err))?;
+        extracted += 1;
+    }
+    written.store(findings.len(), Ordering::Relaxed);
+    Ok(extracted)
+}
@@ -0,0 +1,201 @@
+use crate::raw_volume::LockedFileReader;
+use memmap2::Mmap;
+use std::io;
+use std::ops::Deref;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+pub const IO_URING_SUPPORTED: bool = cfg!(all(feature = "io-uring", target_os = "linux"));
+
+// file contents handed from the I/O stage to the matcher
+pub enum FileData {
+    Mapped(Mmap),
+    Read(Vec<u8>),
+}
+
+impl Deref for FileData {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            FileData::Mapped(mapped) => mapped,
+            FileData::Read(bytes) => bytes,
+        }
+    }
+}
+
+// how often a failed open or map is tried again, network filesystems fail reads now and then.
+// shared by the I/O workers of one search, which count into the same totals.
+#[derive(Clone, Default)]
+pub struct ReadRetry {
+    pub retries: u32,
+    pub backoff: Duration,         // doubled after every attempt
+    pub retried: Arc<AtomicUsize>, // attempts repeated during the search
+    pub failed: Arc<AtomicUsize>,  // files still unreadable after all retries
+    // files other processes keep locked are read from the raw volume instead, if set
+    pub locked_files: Option<Arc<LockedFileReader>>,
+}
+
+impl ReadRetry {
+    pub fn new(retries: u32, backoff: Duration) -> Self {
+        Self {
+            retries,
+            backoff,
+            ..Default::default()
+        }
+    }
+
+    // missing files and denied access won't change by waiting, those fail right away
+    pub fn run<T>(&self, mut attempt: impl FnMut() -> io::Result<T>) -> Option<T> {
+        let mut delay = self.backoff;
+        for retry in 0..=self.retries {
+            if retry > 0 {
+                self.retried.fetch_add(1, Ordering::Relaxed);
+                thread::sleep(delay);
+                delay = delay.saturating_mul(2);
+            }
+            match attempt() {
+                Ok(value) => return Some(value),
+                Err(err)
+                    if matches!(
+                        err.kind(),
+                        io::ErrorKind::NotFound | io::ErrorKind::PermissionDenied
+                    ) =>
+                {
+                    return None
+                }
+                Err(_) => {}
+            }
+        }
+        self.failed.fetch_add(1, Ordering::Relaxed);
+        None
+    }
+}
+
+#[cfg(all(feature = "io-uring", target_os = "linux"))]
+pub use uring::{prefetch_batched, UringReader};
+
+#[cfg(all(feature = "io-uring", target_os = "linux"))]
+mod uring {
+    use super::{FileData, ReadRetry};
+    use crate::{prefetch_file, ScanModeEnum};
+    use concurrent_queue::ConcurrentQueue;
+    use io_uring::{opcode, types, IoUring};
+    use std::fs::File;
+    use std::os::unix::io::AsRawFd;
+    use std::path::Path;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::mpsc;
+    use walkdir::DirEntry;
+
+    const BATCH: usize = 64;
+    // bigger files are mapped as usual, the ring only pays off for many small reads
+    const MAX_FILE_SIZE: u64 = 1024 * 1024;
+
+    pub struct UringReader {
+        ring: IoUring,
+    }
+
+    impl UringReader {
+        // None if the kernel doesn't support io_uring or it's blocked
+        pub fn new() -> Option<Self> {
+            IoUring::new(BATCH as u32).ok().map(|ring| Self { ring })
+        }
+
+        // reads whole files with one submission, None for files that weren't read completely
+        fn read_files(&mut self, paths: &[&Path]) -> Vec<Option<Vec<u8>>> {
+            let mut results = vec![None; paths.len()];
+            let mut reads: Vec<(usize, File, Vec<u8>)> = Vec::new();
+            for (idx, path) in paths.iter().enumerate().take(BATCH) {
+                let Ok(file) = File::open(path) else {
+                    continue;
+                };
+                let Ok(metadata) = file.metadata() else {
+                    continue;
+                };
+                reads.push((idx, file, vec![0; metadata.len() as usize]));
+            }
+
+            let mut submitted = 0;
+            {
+                let mut submission = self.ring.submission();
+                for (read_idx, (_idx, file, buffer)) in reads.iter_mut().enumerate() {
+                    let read = opcode::Read::new(
+                        types::Fd(file.as_raw_fd()),
+                        buffer.as_mut_ptr(),
+                        buffer.len() as u32,
+                    )
+                    .offset(0)
+                    .build()
+                    .user_data(read_idx as u64);
+                    // the buffers and files outlive the reads, we wait for all of them below
+                    if unsafe { submission.push(&read) }.is_err() {
+                        break;
+                    }
+                    submitted += 1;
+                }
+            }
+            if submitted == 0 || self.ring.submit_and_wait(submitted).is_err() {
+                return results;
+            }
+
+            for completion in self.ring.completion() {
+                let (idx, _file, buffer) = &mut reads[completion.user_data() as usize];
+                if usize::try_from(completion.result()).ok() == Some(buffer.len()) {
+                    results[*idx] = Some(std::mem::take(buffer));
+                }
+            }
+            results
+        }
+    }
+
+    // the I/O stage, reading small files in batches through the ring
+    pub fn prefetch_batched(
+        reader: &mut UringReader,
+        file_entry_q: &ConcurrentQueue<DirEntry>,
+        prefetch_tx: &mpsc::SyncSender<(DirEntry, Option<FileData>)>,
+        prefetched: &AtomicUsize,
+        scan_mode: &ScanModeEnum,
+        retry: &ReadRetry,
+    ) {
+        let send = |entry, file_data| {
+            prefetched.fetch_add(1, Ordering::Relaxed);
+            prefetch_tx.send((entry, file_data)).is_ok()
+        };
+        loop {
+            let mut small = Vec::new();
+            while small.len() < BATCH {
+                let Ok(entry) = file_entry_q.pop() else {
+                    break;
+                };
+                let size = entry.metadata().map(|m| m.len()).unwrap_or(u64::MAX);
+                if *scan_mode == ScanModeEnum::Diff || size > MAX_FILE_SIZE {
+                    let file_data = prefetch_file(&entry, scan_mode, retry);
+                    if !send(entry, file_data) {
+                        return;
+                    }
+                } else {
+                    small.push(entry);
+                }
+            }
+            if small.is_empty() {
+                return;
+            }
+
+            let paths: Vec<&Path> = small.iter().map(|entry| entry.path()).collect();
+            let contents = reader.read_files(&paths);
+            for (entry, bytes) in small.into_iter().zip(contents) {
+                // short reads and files that changed size are mapped instead
+                let file_data = match bytes {
+                    Some(bytes) => Some(FileData::Read(bytes)),
+                    None => prefetch_file(&entry, scan_mode, retry),
+                };
+                if !send(entry, file_data) {
+                    return;
+                }
+            }
+        }
+    }
+}
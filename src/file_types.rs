@@ -0,0 +1,211 @@
+use crate::i18n::tr;
+use crate::settings::save_setting;
+use crate::QuerApp;
+use eframe::egui;
+use std::path::Path;
+
+// files to search by extension, picking a category also applies its default options
+#[derive(Clone, Copy, PartialEq)]
+pub enum FileCategory {
+    Any,
+    Executables,
+    Text,
+    Documents,
+    Archives,
+    Images,
+}
+
+impl FileCategory {
+    pub const ALL: [FileCategory; 6] = [
+        FileCategory::Any,
+        FileCategory::Executables,
+        FileCategory::Text,
+        FileCategory::Documents,
+        FileCategory::Archives,
+        FileCategory::Images,
+    ];
+
+    fn key(&self) -> &'static str {
+        match self {
+            FileCategory::Any => "any",
+            FileCategory::Executables => "executables",
+            FileCategory::Text => "text",
+            FileCategory::Documents => "documents",
+            FileCategory::Archives => "archives",
+            FileCategory::Images => "images",
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            FileCategory::Any => tr("category-any"),
+            FileCategory::Executables => tr("category-executables"),
+            FileCategory::Text => tr("category-text"),
+            FileCategory::Documents => tr("category-documents"),
+            FileCategory::Archives => tr("category-archives"),
+            FileCategory::Images => tr("category-images"),
+        }
+    }
+
+    pub fn extensions(&self) -> &'static [&'static str] {
+        match self {
+            FileCategory::Any => &[],
+            FileCategory::Executables => &[
+                "exe", "dll", "sys", "scr", "ocx", "efi", "so", "ko", "o", "elf", "dylib", "bin",
+            ],
+            FileCategory::Text => &[
+                "txt", "log", "csv", "json", "xml", "yml", "yaml", "ini", "cfg", "conf", "md",
+                "html", "htm", "js", "py", "sh", "ps1", "bat", "c", "h", "cpp", "rs",
+            ],
+            FileCategory::Documents => &[
+                "pdf", "doc", "docx", "xls", "xlsx", "ppt", "pptx", "odt", "ods", "rtf", "eml",
+                "msg",
+            ],
+            FileCategory::Archives => &[
+                "zip", "7z", "rar", "tar", "gz", "tgz", "bz2", "xz", "zst", "cab", "jar", "apk",
+            ],
+            FileCategory::Images => &[
+                "png", "jpg", "jpeg", "gif", "bmp", "tif", "tiff", "webp", "ico", "heic",
+            ],
+        }
+    }
+
+    pub fn includes(&self, path: &Path) -> bool {
+        if *self == FileCategory::Any {
+            return true;
+        }
+        let Some(ext) = path.extension() else {
+            return false;
+        };
+        let ext = ext.to_string_lossy().to_lowercase();
+        self.extensions().contains(&ext.as_str())
+    }
+
+    fn builtin_options(&self) -> CategoryOptions {
+        match self {
+            FileCategory::Executables => CategoryOptions {
+                alignment: 4,
+                case_insensitive: false,
+            },
+            FileCategory::Text => CategoryOptions {
+                alignment: 0,
+                case_insensitive: true,
+            },
+            _ => CategoryOptions::default(),
+        }
+    }
+}
+
+// search options applied when a category gets picked
+#[derive(Clone, Copy, Default)]
+pub struct CategoryOptions {
+    pub alignment: i32,
+    pub case_insensitive: bool,
+}
+
+// one entry per category (in the order of `FileCategory::ALL`), edited ones from the settings
+pub fn load_category_options(settings: &json::JsonValue) -> Vec<CategoryOptions> {
+    FileCategory::ALL
+        .iter()
+        .map(|category| {
+            let saved = &settings["file_type_options"][category.key()];
+            let builtin = category.builtin_options();
+            CategoryOptions {
+                alignment: saved["alignment"].as_i32().unwrap_or(builtin.alignment),
+                case_insensitive: saved["case_insensitive"]
+                    .as_bool()
+                    .unwrap_or(builtin.case_insensitive),
+            }
+        })
+        .collect()
+}
+
+impl QuerApp {
+    fn category_index(category: FileCategory) -> usize {
+        FileCategory::ALL
+            .iter()
+            .position(|other| *other == category)
+            .unwrap_or(0)
+    }
+
+    fn apply_category_options(&mut self) {
+        if self.file_category == FileCategory::Any {
+            return;
+        }
+        let options = self.category_options[Self::category_index(self.file_category)];
+        self.alignment = options.alignment;
+        self.case_insensitive = options.case_insensitive;
+        self.update_regex();
+    }
+
+    pub(crate) fn add_file_category_selector(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            let label =
+                ui.label(egui::RichText::new(tr("file-types")).text_style(egui::TextStyle::Small));
+            let before = self.file_category;
+            egui::ComboBox::from_id_source("file_category")
+                .selected_text(self.file_category.name())
+                .show_ui(ui, |ui| {
+                    for category in FileCategory::ALL {
+                        ui.selectable_value(&mut self.file_category, category, category.name())
+                            .on_hover_text(category.extensions().join(" "));
+                    }
+                })
+                .response
+                .labelled_by(label.id)
+                .on_hover_text(tr("file-types-hint"));
+            if self.file_category != before {
+                self.apply_category_options();
+            }
+        });
+    }
+
+    fn save_category_options(&mut self) {
+        let mut obj = json::JsonValue::new_object();
+        for (category, options) in FileCategory::ALL.iter().zip(self.category_options.iter()) {
+            let mut entry = json::JsonValue::new_object();
+            entry["alignment"] = options.alignment.into();
+            entry["case_insensitive"] = options.case_insensitive.into();
+            obj[category.key()] = entry;
+        }
+        if let Err(err) = save_setting("file_type_options", obj) {
+            self.log_error(format!("Couldn't save the file type defaults: {}", err));
+        }
+    }
+
+    pub(crate) fn add_category_options_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_category_options;
+        let mut changed = false;
+        egui::Window::new(tr("file-type-defaults-title"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(tr("file-type-defaults-intro"));
+                egui::Grid::new("category_options")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong(tr("file-types"));
+                        ui.strong(tr("alignment-0-to-disable"));
+                        ui.strong(tr("case-insensitive"));
+                        ui.end_row();
+                        // "any" only filters nothing, it has no defaults
+                        for (category, options) in FileCategory::ALL
+                            .iter()
+                            .zip(self.category_options.iter_mut())
+                            .skip(1)
+                        {
+                            ui.label(category.name());
+                            changed |= ui
+                                .add(egui::DragValue::new(&mut options.alignment).range(0..=4096))
+                                .changed();
+                            changed |= ui.checkbox(&mut options.case_insensitive, "").changed();
+                            ui.end_row();
+                        }
+                    });
+            });
+        self.show_category_options = open;
+        if changed {
+            self.save_category_options();
+        }
+    }
+}
@@ -0,0 +1,907 @@
+use crate::accessibility::set_accessible_name;
+use crate::disassembly::{self, DisassemblyLine};
+use crate::i18n::{tr, tr_args};
+use crate::pattern_compare::PatternRegion;
+use crate::presets::parse_number;
+use crate::settings::save_setting;
+use crate::struct_decode::{self, DecodedNode};
+use crate::xrefs::{self, Xref, XrefSearch};
+use crate::{analysis, QuerApp};
+use eframe::egui;
+use std::fs;
+use std::fs::File;
+use std::io::Read;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+const FILE_VIEW_BYTES: usize = 256;
+const ENTROPY_BLOCKS: usize = 2048;
+const THUMBNAIL_SIZE: u32 = 256;
+const TEXT_PREVIEW_LINES: usize = 50;
+const SPLIT_VIEW_ROWS: usize = 64;
+const DEFAULT_CONTEXT: usize = 32;
+pub const MAX_CONTEXT: usize = 4096;
+
+#[derive(Clone)]
+pub struct PinnedPreview {
+    pub filepath: String,
+    pub offset: usize,
+    pub match_size: usize,
+    pub xrefs: Option<XrefSearch>, // references from code to the match, once asked for
+}
+
+// bytes shown before and after a match, as last chosen
+pub fn load_hexdump_context(settings: &json::JsonValue) -> (usize, usize) {
+    let side = |key: &str| {
+        settings["hexdump_context"][key]
+            .as_usize()
+            .map_or(DEFAULT_CONTEXT, |bytes| std::cmp::min(bytes, MAX_CONTEXT))
+    };
+    (side("before"), side("after"))
+}
+
+impl QuerApp {
+    pub(crate) fn save_hexdump_context(&mut self) {
+        let mut obj = json::JsonValue::new_object();
+        obj["before"] = self.hexdump_context.0.into();
+        obj["after"] = self.hexdump_context.1.into();
+        if let Err(err) = save_setting("hexdump_context", obj) {
+            self.log_error(format!("Couldn't save the preview context: {}", err));
+        }
+    }
+
+    // grows or shrinks the context on both sides of a match by one hexdump row
+    fn step_hexdump_context(&mut self, grow: bool) {
+        let step = std::cmp::max(self.hexdump_width, 1);
+        let side = |bytes: usize| match grow {
+            true => std::cmp::min(bytes + step, MAX_CONTEXT),
+            false => bytes.saturating_sub(step),
+        };
+        self.hexdump_context = (side(self.hexdump_context.0), side(self.hexdump_context.1));
+        self.save_hexdump_context();
+    }
+
+    pub(crate) fn select_finding(&mut self, path: &str, offset: usize, match_size: usize) {
+        if self.selected_file.as_deref() != Some(path) {
+            self.selected_file = Some(path.to_string());
+            self.file_view_size = fs::metadata(path).map(|m| m.len() as usize).unwrap_or(0);
+            self.file_view_matches.clear();
+            self.file_view_regions.clear();
+            self.file_view_src_len = 0;
+            self.file_view_text = looks_like_text(path);
+        }
+        self.file_view_offset = offset;
+        self.selected_match_size = match_size;
+        // the split view already shows the selection, don't pop up a window on top of it
+        if !self.split_view {
+            self.show_file_view = true;
+        }
+    }
+
+    pub(crate) fn pin_preview(&mut self, path: &str, offset: usize, match_size: usize) {
+        let already_pinned = self
+            .pinned_previews
+            .iter()
+            .any(|pinned| pinned.filepath == path && pinned.offset == offset);
+        if !already_pinned {
+            self.pinned_previews.push(PinnedPreview {
+                filepath: path.to_string(),
+                offset,
+                match_size,
+                xrefs: None,
+            });
+        }
+    }
+
+    pub(crate) fn add_pinned_preview_windows(&mut self, ctx: &egui::Context) {
+        let mut closed = Vec::new();
+        for (idx, pinned) in self.pinned_previews.clone().iter().enumerate() {
+            let start = pinned.offset.saturating_sub(self.hexdump_context.0);
+            let length = (pinned.offset - start)
+                + std::cmp::min(pinned.match_size, 4096)
+                + self.hexdump_context.1;
+            let contents = self.get_file_contents(&pinned.filepath, start, length);
+            let mut hex_dump_str = match &contents {
+                Some(contents) => self.bytes_to_hexdump(contents.as_slice(), start),
+                None => "Unable to read file".to_string(),
+            };
+            // the match alone, without the context around it
+            let decoded = contents.as_ref().and_then(|contents| {
+                let match_start = pinned.offset - start;
+                let match_end = match_start + std::cmp::min(pinned.match_size, 4096);
+                struct_decode::decode(contents.get(match_start..match_end)?)
+            });
+            let listing = self.disassembly(&pinned.filepath, pinned.offset, pinned.match_size);
+            let has_code = self.has_x86_code(&pinned.filepath, pinned.offset);
+            let mut find_xrefs = false;
+            let mut pin_xref = None;
+            let mut context_step = None;
+
+            let mut open = true;
+            egui::Window::new(tr_args(
+                "preview-at",
+                &[("offset", format!("0x{:x}", pinned.offset).into())],
+            ))
+            .id(egui::Id::new((
+                "pinned_preview",
+                &pinned.filepath,
+                pinned.offset,
+            )))
+            .open(&mut open)
+            .resizable(true)
+            .default_width(560.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.button(tr("copy")).clicked() {
+                        ctx.copy_text(hex_dump_str.clone());
+                    }
+                    let context = tr_args(
+                        "context-bytes",
+                        &[
+                            ("before", self.hexdump_context.0.into()),
+                            ("after", self.hexdump_context.1.into()),
+                        ],
+                    );
+                    if ui.small_button("-").on_hover_text(&context).clicked() {
+                        context_step = Some(false);
+                    }
+                    if ui.small_button("+").on_hover_text(&context).clicked() {
+                        context_step = Some(true);
+                    }
+                    ui.label(&pinned.filepath);
+                });
+                if !listing.is_empty() {
+                    egui::CollapsingHeader::new(tr("disassembly"))
+                        .id_source(("disassembly", &pinned.filepath, pinned.offset))
+                        .default_open(true)
+                        .show(ui, |ui| {
+                            egui::ScrollArea::vertical()
+                                .id_source("disassembly_lines")
+                                .max_height(240.0)
+                                .show(ui, |ui| show_disassembly(ui, &listing));
+                        });
+                }
+                if has_code {
+                    egui::CollapsingHeader::new(tr("references-from-code"))
+                        .id_source(("xrefs", &pinned.filepath, pinned.offset))
+                        .show(ui, |ui| match &pinned.xrefs {
+                            None => {
+                                find_xrefs = ui
+                                    .button(tr("find-references"))
+                                    .on_hover_text(tr("find-references-hint"))
+                                    .clicked();
+                            }
+                            Some(search) => {
+                                pin_xref = show_xrefs(ui, search);
+                            }
+                        });
+                }
+                egui::CollapsingHeader::new(tr("decoded-structure"))
+                    .id_source(("decoded_structure", &pinned.filepath, pinned.offset))
+                    .show(ui, |ui| match &decoded {
+                        Some((format, nodes)) => {
+                            ui.label(tr_args("decoded-as", &[("format", (*format).into())]));
+                            egui::ScrollArea::vertical()
+                                .id_source("decoded_nodes")
+                                .max_height(240.0)
+                                .show(ui, |ui| show_decoded_nodes(ui, nodes));
+                        }
+                        None => {
+                            ui.label(tr("no-decoded-structure"));
+                        }
+                    });
+                ui.separator();
+                egui::ScrollArea::both().show(ui, |ui| {
+                    ui.code_editor(&mut hex_dump_str);
+                });
+            });
+            if !open {
+                closed.push(idx);
+            }
+            if let Some(grow) = context_step {
+                self.step_hexdump_context(grow);
+            }
+            if find_xrefs {
+                self.pinned_previews[idx].xrefs = Some(spawn_xref_search(ctx, pinned));
+            }
+            if let Some(offset) = pin_xref {
+                self.pin_preview(&pinned.filepath, offset as usize, 1);
+            }
+        }
+
+        for idx in closed.into_iter().rev() {
+            self.pinned_previews.remove(idx);
+        }
+    }
+
+    // a listing around a match in the code of an x86 executable, empty for anything else
+    fn disassembly(
+        &mut self,
+        path: &str,
+        offset: usize,
+        match_size: usize,
+    ) -> Vec<DisassemblyLine> {
+        let Some(section) = self.code_section(path, offset) else {
+            return Vec::new();
+        };
+        let (start, length) = disassembly::window(&section, offset as u64, match_size);
+        let Some(code) = self.get_file_contents(&path.to_string(), start as usize, length) else {
+            return Vec::new();
+        };
+        disassembly::disassemble(&section, &code, start, offset as u64, match_size)
+    }
+
+    pub(crate) fn is_selected(&self, path: &str, offset: usize) -> bool {
+        (self.show_file_view || self.split_view)
+            && self.file_view_offset == offset
+            && self.selected_file.as_deref() == Some(path)
+    }
+
+    pub(crate) fn add_split_hex_view(&mut self, ui: &mut egui::Ui) {
+        let Some(path) = self.selected_file.clone() else {
+            ui.centered_and_justified(|ui| {
+                ui.label(tr("file-view-empty"));
+            });
+            return;
+        };
+
+        self.update_file_view_matches();
+        ui.horizontal(|ui| {
+            if ui.button(tr("open-file-view")).clicked() {
+                self.show_file_view = true;
+            }
+            ui.label(format!("{} @ 0x{:x}", path, self.file_view_offset));
+        });
+        self.add_offset_navigation(ui);
+        self.add_pattern_compare_selector(ui);
+        ui.separator();
+
+        let offset = self.file_view_offset;
+
+        let width = std::cmp::max(self.hexdump_width, 1);
+        let match_end = offset + self.selected_match_size.clamp(1, 4096);
+        let start = (offset / width).saturating_sub(SPLIT_VIEW_ROWS) * width;
+        let end = (match_end / width + SPLIT_VIEW_ROWS + 1) * width;
+        let Some(contents) = self.get_file_contents(&path, start, end - start) else {
+            return;
+        };
+
+        // only scroll when the selection changes so the user can scroll around freely
+        let selection = (path, offset);
+        let scroll_to_match = self.split_scrolled_to.as_ref() != Some(&selection);
+        self.split_scrolled_to = Some(selection);
+        let compared = self.compared_patterns();
+        let regions = self.pattern_regions_in(start..end);
+
+        egui::ScrollArea::both()
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                let mut scrolled = false;
+                for (row_idx, row) in contents.chunks(width).enumerate() {
+                    let row_offset = start + row_idx * width;
+                    let in_match = row_offset < match_end && offset < row_offset + width;
+                    let resp = if let Some(patterns) = &compared {
+                        let background = if in_match {
+                            ui.visuals().selection.bg_fill
+                        } else {
+                            egui::Color32::TRANSPARENT
+                        };
+                        let job = self.hexdump_compare_row(
+                            ui,
+                            (row, row_offset),
+                            background,
+                            &regions,
+                            patterns,
+                        );
+                        ui.add(egui::Label::new(job).extend())
+                    } else {
+                        let row_str = self.bytes_to_hexdump(row, row_offset);
+                        let mut rich_text = egui::RichText::new(row_str.trim_end()).monospace();
+                        if in_match {
+                            rich_text = rich_text.background_color(ui.visuals().selection.bg_fill);
+                        }
+                        ui.add(egui::Label::new(rich_text).extend())
+                    };
+                    if in_match && scroll_to_match && !scrolled {
+                        resp.scroll_to_me(Some(egui::Align::Center));
+                        scrolled = true;
+                    }
+                }
+            });
+    }
+
+    fn update_file_view_matches(&mut self) {
+        let Some(selected) = &self.selected_file else {
+            return;
+        };
+        if self.findings.len() < self.file_view_src_len {
+            self.file_view_matches.clear();
+            self.file_view_regions.clear();
+            self.file_view_src_len = 0;
+        }
+        if self.findings.len() == self.file_view_src_len {
+            return;
+        }
+
+        for finding in self.findings[self.file_view_src_len..].iter() {
+            if finding.filepath == *selected && !finding.decoded {
+                self.file_view_matches.push(finding.offset);
+                if let Some(pattern) = &finding.pattern {
+                    self.file_view_regions.push(PatternRegion {
+                        range: finding.offset..finding.offset + finding.match_size,
+                        pattern: Arc::clone(pattern),
+                    });
+                }
+            }
+        }
+        self.file_view_matches.sort_unstable();
+        self.file_view_src_len = self.findings.len();
+    }
+
+    pub(crate) fn add_file_view_window(&mut self, ctx: &egui::Context) {
+        if !self.show_file_view {
+            return;
+        }
+        let Some(path) = self.selected_file.clone() else {
+            return;
+        };
+        self.update_file_view_matches();
+        self.request_entropy_map(&path, ctx);
+        self.request_image_preview(&path, ctx);
+
+        let mut open = self.show_file_view;
+        egui::Window::new(tr("file-view"))
+            .open(&mut open)
+            .resizable(true)
+            .default_width(640.0)
+            .show(ctx, |ui| {
+                ui.label(&path);
+                ui.label(tr_args(
+                    "matches-in-bytes",
+                    &[
+                        ("matches", self.file_view_matches.len().into()),
+                        ("size", self.file_view_size.into()),
+                    ],
+                ));
+                self.add_match_minimap(ui);
+                self.add_entropy_heatmap(ui, &path);
+                self.add_image_preview(ui, &path);
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    ui.selectable_value(&mut self.file_view_text, false, tr("hex"));
+                    ui.selectable_value(&mut self.file_view_text, true, tr("text"));
+                });
+                if self.file_view_text {
+                    self.add_text_preview(ui, &path);
+                } else {
+                    self.add_offset_navigation(ui);
+                    self.add_pattern_compare_selector(ui);
+                    // keep rows aligned to the dump width so offsets are easy to read
+                    let width = std::cmp::max(self.hexdump_width, 1);
+                    let start = self.file_view_offset.saturating_sub(self.hexdump_context.0)
+                        / width
+                        * width;
+                    ui.label(format!("0x{:x} - 0x{:x}", start, start + FILE_VIEW_BYTES));
+                    let contents = self.get_file_contents(&path, start, FILE_VIEW_BYTES);
+                    match (contents, self.compared_patterns()) {
+                        (Some(contents), Some(patterns)) => {
+                            let regions = self.pattern_regions_in(start..start + contents.len());
+                            egui::ScrollArea::vertical().show(ui, |ui| {
+                                for (row_idx, row) in contents.chunks(width).enumerate() {
+                                    let job = self.hexdump_compare_row(
+                                        ui,
+                                        (row, start + row_idx * width),
+                                        egui::Color32::TRANSPARENT,
+                                        &regions,
+                                        &patterns,
+                                    );
+                                    ui.label(job);
+                                }
+                            });
+                        }
+                        (Some(contents), None) => {
+                            let mut hex_dump_str =
+                                self.bytes_to_hexdump(contents.as_slice(), start);
+                            egui::ScrollArea::vertical().show(ui, |ui| {
+                                ui.code_editor(&mut hex_dump_str);
+                            });
+                        }
+                        (None, _) => {}
+                    }
+                }
+            });
+        self.show_file_view = open;
+    }
+
+    // a strip representing the whole file, with a tick wherever a match starts
+    fn add_match_minimap(&mut self, ui: &mut egui::Ui) {
+        let size = std::cmp::max(self.file_view_size, 1);
+        let (rect, resp) =
+            ui.allocate_exact_size(egui::vec2(ui.available_width(), 28.0), egui::Sense::click());
+        set_accessible_name(&resp, &tr("match-minimap"));
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+
+        let offset_to_x =
+            |offset: usize| rect.left() + (offset as f32 / size as f32) * rect.width();
+
+        // bucket per pixel column so huge match counts stay cheap to draw
+        let num_buckets = std::cmp::max(rect.width() as usize, 1);
+        let mut buckets = vec![0_u32; num_buckets];
+        for offset in self.file_view_matches.iter() {
+            let bucket = (*offset as f32 / size as f32 * num_buckets as f32) as usize;
+            buckets[std::cmp::min(bucket, num_buckets - 1)] += 1;
+        }
+        let max_bucket = buckets.iter().copied().max().unwrap_or(0).max(1) as f32;
+        for (idx, count) in buckets.iter().enumerate() {
+            if *count == 0 {
+                continue;
+            }
+            // log scale so a single match is still visible next to a dense cluster
+            let height = 0.35 + 0.65 * ((*count as f32).ln_1p() / max_bucket.ln_1p());
+            let x = rect.left() + idx as f32 + 0.5;
+            painter.vline(
+                x,
+                egui::Rangef::new(rect.bottom() - rect.height() * height, rect.bottom()),
+                (1.0, egui::Color32::from_rgb(0x2a, 0x7e, 0x43)),
+            );
+        }
+        painter.vline(
+            offset_to_x(self.file_view_offset),
+            rect.y_range(),
+            (2.0, egui::Color32::from_rgb(0x8f, 0x00, 0x00)),
+        );
+
+        if let Some(pos) = resp.hover_pos() {
+            let hover_offset = ((pos.x - rect.left()) / rect.width() * size as f32) as usize;
+            resp.clone().on_hover_text(tr_args(
+                "minimap-hint",
+                &[("offset", format!("0x{:x}", hover_offset).into())],
+            ));
+        }
+
+        if resp.clicked() {
+            if let Some(pos) = resp.interact_pointer_pos() {
+                let target = ((pos.x - rect.left()) / rect.width() * size as f32) as usize;
+                self.file_view_offset = self.nearest_match(target).unwrap_or(target);
+                self.selected_match_size = 0;
+            }
+        }
+    }
+
+    fn request_entropy_map(&mut self, path: &str, ctx: &egui::Context) {
+        if self.entropy_requested.as_deref() == Some(path) {
+            return;
+        }
+        self.entropy_requested = Some(path.to_string());
+
+        let entropy_map = Arc::clone(&self.entropy_map);
+        let path = path.to_string();
+        let ctx = ctx.clone();
+        thread::spawn(move || {
+            if let Ok(map) = analysis::entropy_map(&path, ENTROPY_BLOCKS) {
+                if let Ok(mut entropy_map) = entropy_map.lock() {
+                    *entropy_map = Some(map);
+                }
+                ctx.request_repaint();
+            }
+        });
+    }
+
+    pub(crate) fn line_col(&mut self, path: &str, offset: usize) -> Option<(usize, usize)> {
+        let line_starts = self.line_index(path)?;
+        Some(analysis::line_col(&line_starts, offset))
+    }
+
+    // built the first time a file's line numbers are needed, then reused
+    fn line_index(&mut self, path: &str) -> Option<Arc<Vec<usize>>> {
+        if let Some(line_starts) = self.line_indexes.get(path) {
+            return Some(Arc::clone(line_starts));
+        }
+
+        let file = File::open(path).ok()?;
+        let data = unsafe { memmap2::Mmap::map(&file).ok()? };
+        let line_starts = Arc::new(analysis::line_starts(&data));
+        self.line_indexes
+            .insert(path.to_string(), Arc::clone(&line_starts));
+        Some(line_starts)
+    }
+
+    fn add_text_preview(&mut self, ui: &mut egui::Ui, path: &String) {
+        let Some(line_starts) = self.line_index(path) else {
+            return;
+        };
+        let (line, col) = analysis::line_col(&line_starts, self.file_view_offset);
+        ui.label(tr_args(
+            "line-column",
+            &[("line", line.into()), ("column", col.into())],
+        ));
+
+        let first = std::cmp::max(line.saturating_sub(TEXT_PREVIEW_LINES), 1);
+        let last = std::cmp::min(line + TEXT_PREVIEW_LINES, line_starts.len());
+        let start = line_starts[first - 1];
+        let end = line_starts
+            .get(last)
+            .copied()
+            .unwrap_or(self.file_view_size);
+        let Some(contents) = self.get_file_contents(path, start, end.saturating_sub(start)) else {
+            return;
+        };
+        let text = String::from_utf8_lossy(&contents);
+
+        // only scroll when the selection changes so the user can scroll around freely
+        let scroll_to_match = self.text_preview_scrolled_to != Some(self.file_view_offset);
+        self.text_preview_scrolled_to = Some(self.file_view_offset);
+
+        egui::ScrollArea::both()
+            .max_height(ui.available_height().max(200.0))
+            .show(ui, |ui| {
+                for (idx, text_line) in text.lines().enumerate() {
+                    let line_num = first + idx;
+                    let mut rich_text =
+                        egui::RichText::new(format!("{:>6}  {}", line_num, text_line)).monospace();
+                    if line_num == line {
+                        rich_text = rich_text.background_color(ui.visuals().selection.bg_fill);
+                    }
+                    let resp = ui.add(egui::Label::new(rich_text).extend());
+                    if line_num == line && scroll_to_match {
+                        resp.scroll_to_me(Some(egui::Align::Center));
+                    }
+                }
+            });
+    }
+
+    fn request_image_preview(&mut self, path: &str, ctx: &egui::Context) {
+        if self.image_preview_requested.as_deref() == Some(path) {
+            return;
+        }
+        self.image_preview_requested = Some(path.to_string());
+
+        let image_preview = Arc::clone(&self.image_preview);
+        let path = path.to_string();
+        let ctx = ctx.clone();
+        thread::spawn(move || {
+            if let Some(thumbnail) = load_thumbnail(&path) {
+                if let Ok(mut image_preview) = image_preview.lock() {
+                    *image_preview = Some((path, thumbnail));
+                }
+                ctx.request_repaint();
+            }
+        });
+    }
+
+    fn add_image_preview(&mut self, ui: &mut egui::Ui, path: &str) {
+        if self.image_texture.as_ref().map(|(p, _)| p.as_str()) != Some(path) {
+            let Ok(mut image_preview) = self.image_preview.lock() else {
+                return;
+            };
+            match image_preview.take() {
+                Some((preview_path, thumbnail)) if preview_path == path => {
+                    let texture =
+                        ui.ctx()
+                            .load_texture("image_preview", thumbnail, Default::default());
+                    drop(image_preview);
+                    self.image_texture = Some((path.to_string(), texture));
+                }
+                // not an image, or still decoding
+                _ => return,
+            }
+        }
+
+        if let Some((_, texture)) = &self.image_texture {
+            ui.collapsing("Image Preview", |ui| {
+                ui.image((texture.id(), texture.size_vec2()));
+            });
+        }
+    }
+
+    // block entropy from dark (low) to bright (high), matches drawn on top
+    fn add_entropy_heatmap(&mut self, ui: &mut egui::Ui, path: &str) {
+        let size = std::cmp::max(self.file_view_size, 1);
+        let (rect, resp) =
+            ui.allocate_exact_size(egui::vec2(ui.available_width(), 20.0), egui::Sense::click());
+        set_accessible_name(&resp, &tr("entropy-heatmap"));
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 0.0, ui.visuals().extreme_bg_color);
+
+        let mut hover_entropy = None;
+        if let Ok(entropy_map) = self.entropy_map.lock() {
+            match entropy_map.as_ref() {
+                Some(map) if map.filepath == path && !map.entropies.is_empty() => {
+                    let block_width = rect.width() / map.entropies.len() as f32;
+                    for (idx, entropy) in map.entropies.iter().enumerate() {
+                        let left = rect.left() + idx as f32 * block_width;
+                        let block_rect = egui::Rect::from_min_max(
+                            egui::pos2(left, rect.top()),
+                            egui::pos2(left + block_width.max(1.0), rect.bottom()),
+                        );
+                        painter.rect_filled(block_rect, 0.0, entropy_color(*entropy));
+                    }
+
+                    if let Some(pos) = resp.hover_pos() {
+                        let idx = ((pos.x - rect.left()) / block_width) as usize;
+                        if let Some(entropy) = map.entropies.get(idx) {
+                            hover_entropy = Some((idx * map.block_size, *entropy * 8.0));
+                        }
+                    }
+                }
+                _ => {
+                    painter.text(
+                        rect.center(),
+                        egui::Align2::CENTER_CENTER,
+                        "Computing entropy...",
+                        egui::FontId::proportional(11.0),
+                        ui.visuals().weak_text_color(),
+                    );
+                }
+            }
+        }
+
+        for offset in self.file_view_matches.iter() {
+            let x = rect.left() + (*offset as f32 / size as f32) * rect.width();
+            painter.vline(
+                x,
+                egui::Rangef::new(rect.top(), rect.top() + rect.height() * 0.4),
+                (1.0, egui::Color32::WHITE),
+            );
+        }
+
+        if let Some((block_offset, bits)) = hover_entropy {
+            resp.clone().on_hover_text(tr_args(
+                "entropy-block-hint",
+                &[
+                    ("offset", format!("0x{:x}", block_offset).into()),
+                    ("bits", format!("{:.2}", bits).into()),
+                ],
+            ));
+        }
+        if resp.clicked() {
+            if let Some(pos) = resp.interact_pointer_pos() {
+                let target = ((pos.x - rect.left()) / rect.width() * size as f32) as usize;
+                self.file_view_offset = self.nearest_match(target).unwrap_or(target);
+                self.selected_match_size = 0;
+            }
+        }
+    }
+
+    // go-to-offset box and previous/next match buttons for the selected file
+    fn add_offset_navigation(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            let label = ui.label(tr("go-to-offset"));
+            let resp = ui
+                .add(
+                    egui::TextEdit::singleline(&mut self.goto_offset_str)
+                        .hint_text("0x1000")
+                        .desired_width(100.0),
+                )
+                .labelled_by(label.id)
+                .on_hover_text(tr("go-to-offset-hint"));
+            let entered = resp.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            if ui.button(tr("go")).clicked() || entered {
+                match parse_number(&self.goto_offset_str) {
+                    Ok(target) => {
+                        let last = self.file_view_size.saturating_sub(1);
+                        self.file_view_offset = std::cmp::min(target as usize, last);
+                        self.selected_match_size = 0;
+                        self.goto_offset_error = None;
+                    }
+                    Err(err) => self.goto_offset_error = Some(err),
+                }
+            }
+            ui.separator();
+
+            let current = self.file_view_offset;
+            let next_idx = self
+                .file_view_matches
+                .partition_point(|off| *off <= current);
+            let prev_idx = self
+                .file_view_matches
+                .partition_point(|off| *off < current)
+                .checked_sub(1);
+            let prev = prev_idx.map(|idx| self.file_view_matches[idx]);
+            let next = self.file_view_matches.get(next_idx).copied();
+            if ui
+                .add_enabled(prev.is_some(), egui::Button::new("⏶"))
+                .on_hover_text(tr("previous-match"))
+                .clicked()
+            {
+                self.go_to_match(prev);
+            }
+            if ui
+                .add_enabled(next.is_some(), egui::Button::new("⏷"))
+                .on_hover_text(tr("next-match"))
+                .clicked()
+            {
+                self.go_to_match(next);
+            }
+            if let Ok(idx) = self.file_view_matches.binary_search(&current) {
+                ui.label(tr_args(
+                    "match-n-of-total",
+                    &[
+                        ("n", (idx + 1).into()),
+                        ("total", self.file_view_matches.len().into()),
+                    ],
+                ));
+            }
+        });
+        if let Some(err) = &self.goto_offset_error {
+            ui.colored_label(egui::Color32::from_rgb(0x8f, 0x0, 0x0), err);
+        }
+    }
+
+    fn go_to_match(&mut self, offset: Option<usize>) {
+        let Some(offset) = offset else {
+            return;
+        };
+        let match_size = self
+            .findings
+            .iter()
+            .find(|finding| {
+                finding.offset == offset
+                    && self.selected_file.as_deref() == Some(finding.filepath.as_str())
+            })
+            .map(|finding| finding.match_size)
+            .unwrap_or(0);
+        self.file_view_offset = offset;
+        self.selected_match_size = match_size;
+    }
+
+    fn nearest_match(&self, target: usize) -> Option<usize> {
+        let idx = self.file_view_matches.partition_point(|off| *off < target);
+        let after = self.file_view_matches.get(idx);
+        let before = idx
+            .checked_sub(1)
+            .and_then(|i| self.file_view_matches.get(i));
+        match (before, after) {
+            (Some(b), Some(a)) => Some(if target - b <= a - target { *b } else { *a }),
+            (Some(b), None) => Some(*b),
+            (None, Some(a)) => Some(*a),
+            (None, None) => None,
+        }
+    }
+}
+
+// same heuristic as most tools: no NUL bytes near the start of the file
+fn looks_like_text(path: &str) -> bool {
+    let mut head = Vec::with_capacity(8192);
+    match File::open(path) {
+        Ok(file) => match file.take(8192).read_to_end(&mut head) {
+            Ok(_) => !head.contains(&0),
+            Err(_) => false,
+        },
+        Err(_) => false,
+    }
+}
+
+fn load_thumbnail(path: &str) -> Option<egui::ColorImage> {
+    let file = File::open(path).ok()?;
+    let data = unsafe { memmap2::Mmap::map(&file).ok()? };
+    // cheap magic check first so non-images never get handed to the decoders
+    image::guess_format(&data).ok()?;
+
+    let thumbnail = image::load_from_memory(&data)
+        .ok()?
+        .thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE)
+        .to_rgba8();
+    let size = [thumbnail.width() as usize, thumbnail.height() as usize];
+    Some(egui::ColorImage::from_rgba_unmultiplied(
+        size,
+        thumbnail.as_raw(),
+    ))
+}
+
+fn entropy_color(entropy: f32) -> egui::Color32 {
+    // dark blue (structured/zeroed) -> green (text/code) -> red (compressed/encrypted)
+    let e = entropy.clamp(0.0, 1.0);
+    if e < 0.5 {
+        let t = e * 2.0;
+        egui::Color32::from_rgb(
+            0x10,
+            (0x20 as f32 + t * 0x90 as f32) as u8,
+            (0x60 as f32 * (1.0 - t)) as u8 + 0x20,
+        )
+    } else {
+        let t = (e - 0.5) * 2.0;
+        egui::Color32::from_rgb(
+            (0x10 as f32 + t * 0xd0 as f32) as u8,
+            (0xb0 as f32 * (1.0 - t)) as u8 + 0x10,
+            0x20,
+        )
+    }
+}
+
+fn show_decoded_nodes(ui: &mut egui::Ui, nodes: &[DecodedNode]) {
+    for (idx, node) in nodes.iter().enumerate() {
+        if node.children.is_empty() {
+            ui.monospace(&node.label);
+            continue;
+        }
+        ui.push_id(idx, |ui| {
+            egui::CollapsingHeader::new(egui::RichText::new(&node.label).monospace())
+                .default_open(true)
+                .show(ui, |ui| show_decoded_nodes(ui, &node.children));
+        });
+    }
+}
+
+fn show_disassembly(ui: &mut egui::Ui, lines: &[DisassemblyLine]) {
+    for line in lines {
+        let mut text = egui::RichText::new(format!(
+            "{:016x}  {:<30} {}",
+            line.address, line.bytes, line.text
+        ))
+        .monospace();
+        if line.in_match {
+            text = text.background_color(ui.visuals().selection.bg_fill);
+        }
+        ui.add(egui::Label::new(text).extend());
+    }
+}
+
+fn spawn_xref_search(ctx: &egui::Context, pinned: &PinnedPreview) -> XrefSearch {
+    let search: XrefSearch = Arc::new(Mutex::new(None));
+    let result = Arc::clone(&search);
+    let (path, offset, match_size) = (pinned.filepath.clone(), pinned.offset, pinned.match_size);
+    let ctx = ctx.clone();
+    thread::spawn(move || {
+        let xrefs = xrefs::find_xrefs(&path, offset as u64, match_size);
+        if let Ok(mut result) = result.lock() {
+            *result = Some(xrefs);
+        }
+        ctx.request_repaint();
+    });
+    search
+}
+
+// the references found so far, returns the file offset of one that was clicked
+fn show_xrefs(ui: &mut egui::Ui, search: &XrefSearch) -> Option<u64> {
+    let Ok(result) = search.lock() else {
+        return None;
+    };
+    let xrefs: &Vec<Xref> = match result.as_ref() {
+        None => {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label(tr("searching-references"));
+            });
+            return None;
+        }
+        Some(Err(err)) => {
+            ui.colored_label(egui::Color32::from_rgb(0x8f, 0, 0), err);
+            return None;
+        }
+        Some(Ok(xrefs)) => xrefs,
+    };
+    if xrefs.is_empty() {
+        ui.label(tr("no-references-found"));
+        return None;
+    }
+    let mut clicked = None;
+    ui.label(tr_args(
+        "references-found",
+        &[("count", xrefs.len().into())],
+    ));
+    egui::ScrollArea::vertical()
+        .id_source("xref_lines")
+        .max_height(240.0)
+        .show(ui, |ui| {
+            for xref in xrefs {
+                let text = format!(
+                    "0x{:08x}  {:016x}  {}",
+                    xref.offset, xref.address, xref.text
+                );
+                if ui
+                    .add(
+                        egui::Label::new(egui::RichText::new(text).monospace())
+                            .sense(egui::Sense::click()),
+                    )
+                    .on_hover_text(tr("preview-pin-hint"))
+                    .clicked()
+                {
+                    clicked = Some(xref.offset);
+                }
+            }
+        });
+    clicked
+}
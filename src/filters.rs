@@ -0,0 +1,525 @@
+use crate::accessibility::set_accessible_name;
+use crate::i18n::{tr, tr_args};
+use crate::presets::parse_number;
+use crate::settings::save_setting;
+use crate::{Finding, QuerApp};
+use eframe::egui;
+use regex::Regex;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+const MAX_CHIPS: usize = 8;
+// the "first 1 KiB" shortcut of the offset range filter
+const HEADER_REGION_SIZE: u64 = 0x400;
+
+// one term of the results filter expression, a finding has to match all of them
+#[derive(Clone)]
+enum FilterTerm {
+    Text(String),       // lowercase, found in the path, offset or match preview
+    Path(Regex),        // path:<glob>
+    NotPath(Regex),     // -path:<glob>
+    Offset(Range<u64>), // offset:<start>..<end>, either end can be left out
+    Content(Regex),     // re:<regex> on the match preview
+}
+
+#[derive(Clone, Default)]
+pub struct TextFilter {
+    terms: Vec<FilterTerm>,
+}
+
+// `*` matches any run of characters and `?` a single one, against the whole path
+fn glob_regex(glob: &str) -> Result<Regex, String> {
+    let mut pattern = String::from("^");
+    for c in glob.chars() {
+        match c {
+            '*' => pattern.push_str(".*"),
+            '?' => pattern.push('.'),
+            _ => pattern.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    pattern.push('$');
+    Regex::new(&pattern).map_err(|err| err.to_string())
+}
+
+fn offset_range(spec: &str) -> Result<Range<u64>, String> {
+    let (start, end) = spec
+        .split_once("..")
+        .ok_or_else(|| format!("'{}' isn't a range like 0x1000..0x2000", spec))?;
+    let start = match start.trim() {
+        "" => 0,
+        start => parse_number(start)?,
+    };
+    let end = match end.trim() {
+        "" => u64::MAX,
+        end => parse_number(end)?,
+    };
+    Ok(start..end)
+}
+
+// bounds of the offset range filter, a missing start is 0 and a missing end is unbounded
+fn parse_offset_bounds(from: &str, to: &str) -> Result<Option<Range<u64>>, String> {
+    if from.trim().is_empty() && to.trim().is_empty() {
+        return Ok(None);
+    }
+    offset_range(&format!("{}..{}", from, to)).map(Some)
+}
+
+pub fn parse_filter(expr: &str) -> Result<TextFilter, String> {
+    let terms = expr
+        .split_whitespace()
+        .map(|term| {
+            if let Some(glob) = term.strip_prefix("path:") {
+                glob_regex(glob).map(FilterTerm::Path)
+            } else if let Some(glob) = term.strip_prefix("-path:") {
+                glob_regex(glob).map(FilterTerm::NotPath)
+            } else if let Some(range) = term.strip_prefix("offset:") {
+                offset_range(range).map(FilterTerm::Offset)
+            } else if let Some(re) = term.strip_prefix("re:") {
+                Regex::new(re)
+                    .map(FilterTerm::Content)
+                    .map_err(|err| err.to_string())
+            } else {
+                Ok(FilterTerm::Text(term.to_lowercase()))
+            }
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(TextFilter { terms })
+}
+
+impl TextFilter {
+    fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    fn matches(&self, finding: &Finding) -> bool {
+        let preview = finding.match_content.to_string();
+        self.terms.iter().all(|term| match term {
+            FilterTerm::Text(text) => {
+                finding.filepath.to_lowercase().contains(text)
+                    || format!("0x{:x}", finding.offset).contains(text)
+                    || preview.to_lowercase().contains(text)
+            }
+            FilterTerm::Path(re) => re.is_match(&finding.filepath),
+            FilterTerm::NotPath(re) => !re.is_match(&finding.filepath),
+            FilterTerm::Offset(range) => range.contains(&(finding.offset as u64)),
+            FilterTerm::Content(re) => re.is_match(&preview),
+        })
+    }
+}
+
+// a filter expression kept under a name in the settings
+#[derive(Clone)]
+pub struct SavedFilter {
+    pub name: String,
+    pub filter: String,
+}
+
+pub fn load_saved_filters(settings: &json::JsonValue) -> Vec<SavedFilter> {
+    settings["saved_filters"]
+        .members()
+        .filter_map(|obj| {
+            Some(SavedFilter {
+                name: obj["name"].as_str()?.to_string(),
+                filter: obj["filter"].as_str()?.to_string(),
+            })
+        })
+        .collect()
+}
+
+#[derive(Clone, Default)]
+pub struct FilterChips {
+    pub ext_counts: HashMap<String, usize>,
+    pub dir_counts: HashMap<String, usize>,
+    pub src_len: usize,
+}
+
+fn finding_ext(finding: &Finding) -> String {
+    Path::new(&finding.filepath)
+        .extension()
+        .map(|ext| format!(".{}", ext.to_string_lossy().to_lowercase()))
+        .unwrap_or_default()
+}
+
+// a glob for everything below `dir`, whitespace becomes `?` since terms are split on it
+fn dir_glob(dir: &str) -> String {
+    let glob: String = dir
+        .chars()
+        .map(|c| if c.is_whitespace() { '?' } else { c })
+        .collect();
+    format!("{}{}*", glob, std::path::MAIN_SEPARATOR)
+}
+
+pub fn load_excluded_dirs(settings: &json::JsonValue) -> Vec<PathBuf> {
+    settings["excluded_dirs"]
+        .members()
+        .filter_map(|dir| dir.as_str().map(PathBuf::from))
+        .collect()
+}
+
+fn finding_dir(finding: &Finding) -> String {
+    Path::new(&finding.filepath)
+        .parent()
+        .map(|dir| dir.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+impl QuerApp {
+    // an invalid filter expression doesn't hide anything
+    fn active_text_filter(&self) -> Option<&TextFilter> {
+        self.text_filter
+            .as_ref()
+            .ok()
+            .filter(|filter| !filter.is_empty())
+    }
+
+    pub(crate) fn has_active_filters(&self) -> bool {
+        !self.ext_filters.is_empty()
+            || !self.dir_filters.is_empty()
+            || self.active_text_filter().is_some()
+            || matches!(self.offset_filter, Ok(Some(_)))
+    }
+
+    fn passes_filters(&self, finding: &Finding) -> bool {
+        (self.ext_filters.is_empty() || self.ext_filters.contains(&finding_ext(finding)))
+            && (self.dir_filters.is_empty() || self.dir_filters.contains(&finding_dir(finding)))
+            && self
+                .active_text_filter()
+                .map_or(true, |filter| filter.matches(finding))
+            && match &self.offset_filter {
+                Ok(Some(range)) => range.contains(&(finding.offset as u64)),
+                _ => true,
+            }
+    }
+
+    pub(crate) fn exclude_dir_from_results(&mut self, dir: &str) {
+        let term = format!("-path:{}", dir_glob(dir));
+        if !self
+            .filter_str
+            .split_whitespace()
+            .any(|other| other == term)
+        {
+            if !self.filter_str.trim().is_empty() {
+                self.filter_str.push(' ');
+            }
+            self.filter_str.push_str(&term);
+        }
+        self.sync_text_filter();
+    }
+
+    // also hides what was already found below it
+    pub(crate) fn exclude_dir_from_searches(&mut self, dir: &str) {
+        let dir_path = PathBuf::from(dir);
+        if !self.excluded_dirs.contains(&dir_path) {
+            self.excluded_dirs.push(dir_path);
+            self.save_excluded_dirs();
+        }
+        self.exclude_dir_from_results(dir);
+    }
+
+    fn save_excluded_dirs(&mut self) {
+        let dirs = json::JsonValue::Array(
+            self.excluded_dirs
+                .iter()
+                .map(|dir| dir.to_string_lossy().to_string().into())
+                .collect(),
+        );
+        if let Err(err) = save_setting("excluded_dirs", dirs) {
+            self.log_error(format!("Couldn't save the excluded directories: {}", err));
+        }
+    }
+
+    pub(crate) fn add_excluded_dirs(&mut self, ui: &mut egui::Ui) {
+        if self.excluded_dirs.is_empty() {
+            return;
+        }
+        let mut remove = None;
+        ui.collapsing(
+            tr_args(
+                "excluded-directories",
+                &[("count", self.excluded_dirs.len().into())],
+            ),
+            |ui| {
+                for (idx, dir) in self.excluded_dirs.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        if ui
+                            .small_button("🗑")
+                            .on_hover_text(tr("search-this-directory-again"))
+                            .clicked()
+                        {
+                            remove = Some(idx);
+                        }
+                        ui.label(
+                            egui::RichText::new(dir.to_string_lossy())
+                                .text_style(egui::TextStyle::Small),
+                        );
+                    });
+                }
+            },
+        );
+        if let Some(idx) = remove {
+            self.excluded_dirs.remove(idx);
+            self.save_excluded_dirs();
+        }
+    }
+
+    fn set_offset_filter(&mut self) {
+        self.offset_filter = parse_offset_bounds(&self.offset_filter_from, &self.offset_filter_to);
+        self.refilter();
+    }
+
+    pub(crate) fn add_offset_filter(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+        let label = ui.label(tr("offsets-from"));
+        changed |= ui
+            .add(
+                egui::TextEdit::singleline(&mut self.offset_filter_from)
+                    .hint_text("0")
+                    .desired_width(80.0),
+            )
+            .labelled_by(label.id)
+            .changed();
+        let label = ui.label(tr("offsets-to"));
+        changed |= ui
+            .add(
+                egui::TextEdit::singleline(&mut self.offset_filter_to)
+                    .hint_text(tr("offsets-to-hint"))
+                    .desired_width(80.0),
+            )
+            .labelled_by(label.id)
+            .on_hover_text(tr("offsets-to-exclusive"))
+            .changed();
+        if ui
+            .button(tr("first-kib"))
+            .on_hover_text(tr("first-kib-hint"))
+            .clicked()
+        {
+            self.offset_filter_from = "0".to_string();
+            self.offset_filter_to = format!("0x{:x}", HEADER_REGION_SIZE);
+            changed = true;
+        }
+        if changed {
+            self.set_offset_filter();
+        }
+        if let Err(err) = &self.offset_filter {
+            ui.colored_label(egui::Color32::from_rgb(0x8f, 0x0, 0x0), err);
+        }
+    }
+
+    // re-parses the filter expression after it was edited, restored or picked from the saved ones
+    pub(crate) fn sync_text_filter(&mut self) {
+        if self.filter_str == self.text_filter_src {
+            return;
+        }
+        self.text_filter_src = self.filter_str.clone();
+        self.text_filter = parse_filter(&self.filter_str);
+        self.refilter();
+    }
+
+    pub(crate) fn text_filter_error(&self) -> Option<&str> {
+        self.text_filter.as_ref().err().map(|err| err.as_str())
+    }
+
+    fn save_saved_filters(&mut self) {
+        let filters = json::JsonValue::Array(
+            self.saved_filters
+                .iter()
+                .map(|saved| {
+                    let mut obj = json::JsonValue::new_object();
+                    obj["name"] = saved.name.as_str().into();
+                    obj["filter"] = saved.filter.as_str().into();
+                    obj
+                })
+                .collect(),
+        );
+        if let Err(err) = save_setting("saved_filters", filters) {
+            self.log_error(format!("Couldn't save filters: {}", err));
+        }
+    }
+
+    pub(crate) fn add_saved_filters(&mut self, ui: &mut egui::Ui) {
+        let mut apply = None;
+        egui::ComboBox::from_id_source("saved_filters")
+            .selected_text(tr("saved-filters"))
+            .show_ui(ui, |ui| {
+                if self.saved_filters.is_empty() {
+                    ui.label(tr("no-saved-filters"));
+                }
+                for saved in self.saved_filters.iter() {
+                    if ui
+                        .selectable_label(self.filter_str == saved.filter, &saved.name)
+                        .on_hover_text(&saved.filter)
+                        .clicked()
+                    {
+                        apply = Some(saved.clone());
+                    }
+                }
+            })
+            .response
+            .on_hover_text(tr("saved-filters-hint"));
+        if let Some(saved) = apply {
+            self.filter_str = saved.filter;
+            self.saved_filter_name = saved.name;
+        }
+
+        let name_resp = ui.add(
+            egui::TextEdit::singleline(&mut self.saved_filter_name)
+                .hint_text(tr("filter-name"))
+                .desired_width(120.0),
+        );
+        set_accessible_name(&name_resp, &tr("filter-name"));
+        let name = self.saved_filter_name.trim().to_string();
+        let existing = self
+            .saved_filters
+            .iter()
+            .position(|saved| saved.name == name);
+        if ui
+            .add_enabled(
+                !name.is_empty() && !self.filter_str.trim().is_empty(),
+                egui::Button::new(tr("save-filter")),
+            )
+            .on_hover_text(tr("save-filter-hint"))
+            .clicked()
+        {
+            let saved = SavedFilter {
+                name,
+                filter: self.filter_str.trim().to_string(),
+            };
+            match existing {
+                Some(idx) => self.saved_filters[idx] = saved,
+                None => self.saved_filters.push(saved),
+            }
+            self.save_saved_filters();
+        }
+        if ui
+            .add_enabled(existing.is_some(), egui::Button::new(tr("delete-filter")))
+            .clicked()
+        {
+            if let Some(idx) = existing {
+                self.saved_filters.remove(idx);
+                self.save_saved_filters();
+            }
+        }
+    }
+
+    pub(crate) fn reset_filter_chips(&mut self) {
+        self.filter_chips = FilterChips::default();
+        self.filtered_rows.clear();
+        self.filtered_src_len = 0;
+    }
+
+    fn update_filter_chips(&mut self) {
+        if self.findings.len() < self.filter_chips.src_len {
+            self.reset_filter_chips();
+        }
+
+        for finding in self.findings[self.filter_chips.src_len..].iter() {
+            *self
+                .filter_chips
+                .ext_counts
+                .entry(finding_ext(finding))
+                .or_default() += 1;
+            *self
+                .filter_chips
+                .dir_counts
+                .entry(finding_dir(finding))
+                .or_default() += 1;
+        }
+        self.filter_chips.src_len = self.findings.len();
+    }
+
+    // indices into findings that pass the active filters, only kept up to date while filtering
+    pub(crate) fn update_filtered_rows(&mut self) {
+        if self.findings.len() < self.filtered_src_len {
+            self.filtered_rows.clear();
+            self.filtered_src_len = 0;
+        }
+
+        for idx in self.filtered_src_len..self.findings.len() {
+            if self.passes_filters(&self.findings[idx]) {
+                self.filtered_rows.push(idx);
+            }
+        }
+        self.filtered_src_len = self.findings.len();
+    }
+
+    // filtered rows are indices, so they have to be rebuilt when findings get reordered
+    pub(crate) fn invalidate_row_order(&mut self) {
+        self.filtered_rows.clear();
+        self.filtered_src_len = 0;
+        self.selected_row = None;
+    }
+
+    fn refilter(&mut self) {
+        self.filtered_rows.clear();
+        self.filtered_src_len = 0;
+        self.update_filtered_rows();
+    }
+
+    pub(crate) fn add_filter_chips(&mut self, ui: &mut egui::Ui) {
+        self.update_filter_chips();
+        if self.findings.is_empty() {
+            return;
+        }
+
+        let mut exts: Vec<_> = self.filter_chips.ext_counts.iter().collect();
+        exts.sort_by_key(|(ext, count)| (std::cmp::Reverse(**count), ext.to_string()));
+        let mut dirs: Vec<_> = self.filter_chips.dir_counts.iter().collect();
+        dirs.sort_by_key(|(dir, count)| (std::cmp::Reverse(**count), dir.to_string()));
+
+        let mut toggled_ext = None;
+        let mut toggled_dir = None;
+        let mut clear = false;
+        ui.horizontal_wrapped(|ui| {
+            for (ext, count) in exts.into_iter().take(MAX_CHIPS) {
+                let label = if ext.is_empty() { "(no ext)" } else { ext };
+                let chip = ui.selectable_label(
+                    self.ext_filters.contains(ext),
+                    format!("{} ({})", label, count),
+                );
+                if chip.clicked() {
+                    toggled_ext = Some(ext.clone());
+                }
+            }
+            ui.separator();
+            for (dir, count) in dirs.into_iter().take(MAX_CHIPS) {
+                let chip = ui
+                    .selectable_label(
+                        self.dir_filters.contains(dir),
+                        format!("{} ({})", dir, count),
+                    )
+                    .on_hover_text(tr("filter-direct-children-hint"));
+                if chip.clicked() {
+                    toggled_dir = Some(dir.clone());
+                }
+            }
+            if self.has_active_filters() {
+                ui.separator();
+                clear = ui.button(tr("clear-filters")).clicked();
+            }
+        });
+
+        let changed = toggled_ext.is_some() || toggled_dir.is_some() || clear;
+        if let Some(ext) = toggled_ext {
+            if !self.ext_filters.remove(&ext) {
+                self.ext_filters.insert(ext);
+            }
+        }
+        if let Some(dir) = toggled_dir {
+            if !self.dir_filters.remove(&dir) {
+                self.dir_filters.insert(dir);
+            }
+        }
+        if clear {
+            self.ext_filters.clear();
+            self.dir_filters.clear();
+            self.filter_str.clear();
+            self.offset_filter_from.clear();
+            self.offset_filter_to.clear();
+            self.offset_filter = Ok(None);
+        }
+        if changed {
+            self.refilter();
+        }
+    }
+}
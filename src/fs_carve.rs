@@ -0,0 +1,725 @@
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::Arc;
+
+// bounds the work spent on a damaged or gigantic filesystem
+const MAX_RECORDS: u64 = 4_000_000;
+const MAX_PATH_DEPTH: usize = 64;
+// runs checked backwards from a lookup, only deleted files overlap live ones
+const MAX_OVERLAP_SCAN: usize = 64;
+
+const MBR_SIGNATURE: &[u8] = b"\x55\xaa";
+const MBR_GPT_PROTECTIVE: u8 = 0xee;
+const GPT_SIGNATURE: &[u8] = b"EFI PART";
+const SECTOR: u64 = 512;
+
+const NTFS_OEM_ID: &[u8] = b"NTFS    ";
+const NTFS_ROOT_RECORD: u64 = 5;
+const NTFS_FILE_NAME: u32 = 0x30;
+const NTFS_DATA: u32 = 0x80;
+const NTFS_END: u32 = 0xffff_ffff;
+const NTFS_IN_USE: u16 = 0x01;
+const NTFS_DOS_NAMESPACE: u8 = 2;
+
+const EXT4_MAGIC: u16 = 0xef53;
+const EXT4_ROOT_INODE: u64 = 2;
+const EXT4_FEATURE_64BIT: u32 = 0x80;
+const EXT4_EXTENTS_FL: u32 = 0x80000;
+const EXT4_EXTENT_MAGIC: u16 = 0xf30a;
+const EXT4_DIRECTORY: u16 = 0x4000;
+
+fn le_u16(data: &[u8], pos: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(data.get(pos..pos + 2)?.try_into().ok()?))
+}
+
+fn le_u32(data: &[u8], pos: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(data.get(pos..pos + 4)?.try_into().ok()?))
+}
+
+fn le_u64(data: &[u8], pos: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(data.get(pos..pos + 8)?.try_into().ok()?))
+}
+
+fn slice(data: &[u8], start: u64, len: u64) -> Option<&[u8]> {
+    let start = usize::try_from(start).ok()?;
+    let end = start.checked_add(usize::try_from(len).ok()?)?;
+    data.get(start..end)
+}
+
+struct Run {
+    range: Range<u64>, // bytes of the image
+    owner: Arc<str>,
+    deleted: bool,
+}
+
+// the files (possibly deleted ones) that own the byte ranges of a raw volume or disk image
+pub struct FsMap {
+    runs: Vec<Run>,
+    unallocated: Arc<str>,
+}
+
+impl FsMap {
+    // None unless the data is an NTFS or ext4 volume, or a disk partitioned into some
+    pub fn build(data: &[u8]) -> Option<FsMap> {
+        let volume_runs = |start: u64| {
+            let volume = slice(data, start, (data.len() as u64).checked_sub(start)?)?;
+            ntfs_runs(volume, start).or_else(|| ext4_runs(volume, start))
+        };
+        // a volume's boot sector has the MBR signature too, so it's tried first
+        let mut runs = match volume_runs(0) {
+            Some(runs) => runs,
+            None => partition_starts(data)
+                .into_iter()
+                .filter_map(volume_runs)
+                .flatten()
+                .collect(),
+        };
+        if runs.is_empty() {
+            return None;
+        }
+        // live files first among runs starting at the same offset
+        runs.sort_by_key(|run| (run.range.start, run.deleted));
+        Some(FsMap {
+            runs,
+            unallocated: Arc::from("unallocated"),
+        })
+    }
+
+    // the file owning `offset`, preferring a live file over a deleted one sharing the cluster
+    pub fn owner(&self, offset: u64) -> Arc<str> {
+        let idx = self.runs.partition_point(|run| run.range.start <= offset);
+        let mut deleted_owner = None;
+        for run in self.runs[..idx].iter().rev().take(MAX_OVERLAP_SCAN) {
+            if run.range.contains(&offset) {
+                if !run.deleted {
+                    return Arc::clone(&run.owner);
+                }
+                deleted_owner.get_or_insert(&run.owner);
+            }
+        }
+        Arc::clone(deleted_owner.unwrap_or(&self.unallocated))
+    }
+}
+
+// offsets of the partitions of an MBR or GPT partitioned disk
+fn partition_starts(data: &[u8]) -> Vec<u64> {
+    let mut starts = Vec::new();
+    if data.get(510..512) != Some(MBR_SIGNATURE) {
+        return starts;
+    }
+    for entry in 0..4 {
+        let pos = 446 + entry * 16;
+        let (Some(kind), Some(lba)) = (data.get(pos + 4), le_u32(data, pos + 8)) else {
+            continue;
+        };
+        if *kind == MBR_GPT_PROTECTIVE {
+            starts.extend(gpt_starts(data));
+        } else if *kind != 0 && lba != 0 {
+            starts.push(lba as u64 * SECTOR);
+        }
+    }
+    starts
+}
+
+fn gpt_starts(data: &[u8]) -> Vec<u64> {
+    let header = SECTOR as usize;
+    if data.get(header..header + 8) != Some(GPT_SIGNATURE) {
+        return Vec::new();
+    }
+    let (Some(table_lba), Some(count), Some(entry_size)) = (
+        le_u64(data, header + 72),
+        le_u32(data, header + 80),
+        le_u32(data, header + 84),
+    ) else {
+        return Vec::new();
+    };
+    (0..count as u64)
+        .filter_map(|idx| {
+            let pos = usize::try_from(table_lba * SECTOR + idx * entry_size as u64).ok()?;
+            let first_lba = le_u64(data, pos + 32)?;
+            (first_lba != 0).then_some(first_lba * SECTOR)
+        })
+        .collect()
+}
+
+// joins names up to the root, `names` maps a record or inode to its name and parent
+fn full_path(
+    names: &HashMap<u64, (String, u64)>,
+    mut id: u64,
+    root: u64,
+    separator: char,
+) -> Option<String> {
+    names.get(&id)?;
+    let mut parts = Vec::new();
+    for _ in 0..MAX_PATH_DEPTH {
+        if id == root {
+            break;
+        }
+        let Some((name, parent)) = names.get(&id) else {
+            parts.push("?".to_string());
+            break;
+        };
+        parts.push(name.clone());
+        id = *parent;
+    }
+    if parts.is_empty() {
+        return None;
+    }
+    parts.reverse();
+    Some(format!(
+        "{}{}",
+        separator,
+        parts.join(&separator.to_string())
+    ))
+}
+
+// `unnamed` (e.g. "inode 12") when no name of the file could be found
+fn owner_label(fs: &str, path: Option<String>, unnamed: String, deleted: bool) -> Arc<str> {
+    let name = path.unwrap_or(unnamed);
+    match deleted {
+        true => Arc::from(format!("{}: {} (deleted)", fs, name)),
+        false => Arc::from(format!("{}: {}", fs, name)),
+    }
+}
+
+// (first cluster, cluster count) of each run, sparse runs left out
+fn ntfs_data_runs(runlist: &[u8]) -> Vec<(u64, u64)> {
+    ntfs_runlist(runlist)
+        .into_iter()
+        .filter_map(|(lcn, length)| Some((lcn?, length)))
+        .collect()
+}
+
+// (first cluster, cluster count) of each run in order, None for the first cluster of a
+// sparse run
+pub(crate) fn ntfs_runlist(runlist: &[u8]) -> Vec<(Option<u64>, u64)> {
+    let mut runs = Vec::new();
+    let mut pos = 0;
+    let mut lcn: i64 = 0;
+    while let Some(&header) = runlist.get(pos) {
+        if header == 0 {
+            break;
+        }
+        let len_size = (header & 0x0f) as usize;
+        let off_size = (header >> 4) as usize;
+        if len_size > 8 || off_size > 8 {
+            break;
+        }
+        let Some(fields) = runlist.get(pos + 1..pos + 1 + len_size + off_size) else {
+            break;
+        };
+        let mut length = 0_u64;
+        for (idx, byte) in fields[..len_size].iter().enumerate() {
+            length |= (*byte as u64) << (idx * 8);
+        }
+        if off_size > 0 {
+            let mut offset = 0_i64;
+            for (idx, byte) in fields[len_size..].iter().enumerate() {
+                offset |= (*byte as i64) << (idx * 8);
+            }
+            // sign-extend the relative offset
+            let shift = 64 - off_size * 8;
+            let Some(next) = lcn.checked_add((offset << shift) >> shift) else {
+                break;
+            };
+            lcn = next;
+            runs.push((Some(lcn as u64), length));
+        } else {
+            runs.push((None, length));
+        }
+        pos += 1 + len_size + off_size;
+    }
+    runs
+}
+
+// an MFT record with its update sequence fixups applied
+fn ntfs_record(data: &[u8], pos: u64, size: u64) -> Option<Vec<u8>> {
+    ntfs_fixups(slice(data, pos, size)?.to_vec(), b"FILE")
+}
+
+// puts back the last two bytes of every sector of an MFT record ("FILE") or index block
+// ("INDX"), which the update sequence array holds while they store its check value
+pub(crate) fn ntfs_fixups(mut record: Vec<u8>, magic: &[u8]) -> Option<Vec<u8>> {
+    if !record.starts_with(magic) {
+        return None;
+    }
+    let usa_offset = le_u16(&record, 4)? as usize;
+    let usa_count = le_u16(&record, 6)? as usize;
+    for idx in 1..usa_count {
+        let sector_end = idx * SECTOR as usize;
+        let fixup = record
+            .get(usa_offset + idx * 2..usa_offset + idx * 2 + 2)?
+            .to_vec();
+        record
+            .get_mut(sector_end - 2..sector_end)?
+            .copy_from_slice(&fixup);
+    }
+    Some(record)
+}
+
+struct NtfsFile {
+    name: Option<(String, u64)>,
+    runs: Vec<(u64, u64)>,
+}
+
+fn ntfs_parse_record(record: &[u8]) -> NtfsFile {
+    let mut file = NtfsFile {
+        name: None,
+        runs: Vec::new(),
+    };
+    let mut pos = le_u16(record, 20).unwrap_or(0) as usize;
+    while let (Some(kind), Some(len)) = (le_u32(record, pos), le_u32(record, pos + 4)) {
+        if kind == NTFS_END || len == 0 {
+            break;
+        }
+        let non_resident = record.get(pos + 8).copied().unwrap_or(0) != 0;
+        let attr_name_len = record.get(pos + 9).copied().unwrap_or(0);
+        match kind {
+            NTFS_FILE_NAME if !non_resident => {
+                let value = pos + le_u16(record, pos + 20).unwrap_or(0) as usize;
+                let parent = le_u64(record, value).unwrap_or(0) & 0xffff_ffff_ffff;
+                let name_len = record.get(value + 64).copied().unwrap_or(0) as usize;
+                let namespace = record.get(value + 65).copied().unwrap_or(0);
+                let units: Vec<u16> = record
+                    .get(value + 66..value + 66 + name_len * 2)
+                    .unwrap_or_default()
+                    .chunks_exact(2)
+                    .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+                    .collect();
+                // the short 8.3 name only when there's nothing better
+                if file.name.is_none() || namespace != NTFS_DOS_NAMESPACE {
+                    file.name = Some((String::from_utf16_lossy(&units), parent));
+                }
+            }
+            NTFS_DATA if non_resident && attr_name_len == 0 => {
+                let starting_vcn = le_u64(record, pos + 16).unwrap_or(1);
+                let runlist = pos + le_u16(record, pos + 32).unwrap_or(0) as usize;
+                if starting_vcn == 0 {
+                    if let Some(runlist) = record.get(runlist..pos + len as usize) {
+                        file.runs = ntfs_data_runs(runlist);
+                    }
+                }
+            }
+            _ => {}
+        }
+        pos += len as usize;
+    }
+    file
+}
+
+// (cluster size, first cluster of the MFT, MFT record size) from an NTFS boot sector
+pub(crate) fn ntfs_geometry(boot: &[u8]) -> Option<(u64, u64, u64)> {
+    if boot.get(3..11) != Some(NTFS_OEM_ID) {
+        return None;
+    }
+    let cluster = le_u16(boot, 11)? as u64 * *boot.get(13)? as u64;
+    let mft_lcn = le_u64(boot, 48)?;
+    let per_record = *boot.get(64)? as i8;
+    // negative counts are a power of two in bytes, real records are 1 KiB or 4 KiB
+    let record_size = if per_record > 0 {
+        (per_record as u64).checked_mul(cluster)?
+    } else {
+        let shift = -(per_record as i32);
+        if shift > 31 {
+            return None;
+        }
+        1_u64 << shift
+    };
+    if cluster == 0 || record_size == 0 {
+        return None;
+    }
+    Some((cluster, mft_lcn, record_size))
+}
+
+fn ntfs_runs(volume: &[u8], base: u64) -> Option<Vec<Run>> {
+    let (cluster, mft_lcn, record_size) = ntfs_geometry(volume)?;
+
+    // $MFT describes where the rest of the MFT is
+    let mft = ntfs_parse_record(&ntfs_record(
+        volume,
+        mft_lcn.checked_mul(cluster)?,
+        record_size,
+    )?);
+    let mut names = HashMap::new();
+    let mut files = Vec::new();
+    let mut number = 0_u64;
+    'extents: for (lcn, clusters) in mft.runs {
+        let (Some(start), Some(len)) = (lcn.checked_mul(cluster), clusters.checked_mul(cluster))
+        else {
+            continue;
+        };
+        for idx in 0..len / record_size {
+            if number >= MAX_RECORDS {
+                break 'extents;
+            }
+            let record = start
+                .checked_add(idx * record_size)
+                .and_then(|pos| ntfs_record(volume, pos, record_size));
+            if let Some(record) = record {
+                let in_use = le_u16(&record, 22).unwrap_or(0) & NTFS_IN_USE != 0;
+                // extension records only continue a base record elsewhere
+                if le_u64(&record, 32).unwrap_or(0) == 0 {
+                    let file = ntfs_parse_record(&record);
+                    if let Some(name) = file.name {
+                        names.insert(number, name);
+                    }
+                    if !file.runs.is_empty() {
+                        files.push((number, !in_use, file.runs));
+                    }
+                }
+            }
+            number += 1;
+        }
+    }
+
+    let mut runs = Vec::new();
+    for (number, deleted, file_runs) in files {
+        let owner = owner_label(
+            "NTFS",
+            full_path(&names, number, NTFS_ROOT_RECORD, '\\'),
+            format!("record {}", number),
+            deleted,
+        );
+        for (lcn, clusters) in file_runs {
+            let range = lcn
+                .checked_mul(cluster)
+                .and_then(|pos| base.checked_add(pos))
+                .and_then(|start| Some(start..start.checked_add(clusters.checked_mul(cluster)?)?));
+            let Some(range) = range else {
+                continue;
+            };
+            runs.push(Run {
+                range,
+                owner: Arc::clone(&owner),
+                deleted,
+            });
+        }
+    }
+    Some(runs)
+}
+
+// (first block, block count) of the extents below an extent tree node. `nodes_left` is shared
+// by all trees of the volume, each block is a node of one tree at most.
+fn ext4_extents(
+    volume: &[u8],
+    node: &[u8],
+    block_size: u64,
+    depth_left: u8,
+    nodes_left: &mut u64,
+) -> Vec<(u64, u64)> {
+    let mut extents = Vec::new();
+    if le_u16(node, 0) != Some(EXT4_EXTENT_MAGIC) || depth_left == 0 {
+        return extents;
+    }
+    let entries = le_u16(node, 2).unwrap_or(0) as usize;
+    let depth = le_u16(node, 6).unwrap_or(0);
+    for idx in 0..entries {
+        let entry = 12 + idx * 12;
+        if depth == 0 {
+            let (Some(len), Some(hi), Some(lo)) = (
+                le_u16(node, entry + 4),
+                le_u16(node, entry + 6),
+                le_u32(node, entry + 8),
+            ) else {
+                break;
+            };
+            // lengths above 32768 mark preallocated, unwritten extents
+            let len = if len > 32768 { len - 32768 } else { len };
+            extents.push((((hi as u64) << 32) | lo as u64, len as u64));
+        } else {
+            let (Some(lo), Some(hi)) = (le_u32(node, entry + 4), le_u16(node, entry + 8)) else {
+                break;
+            };
+            if *nodes_left == 0 {
+                break;
+            }
+            *nodes_left -= 1;
+            let leaf = ((hi as u64) << 32) | lo as u64;
+            let child = leaf
+                .checked_mul(block_size)
+                .and_then(|pos| slice(volume, pos, block_size));
+            if let Some(child) = child {
+                extents.extend(ext4_extents(
+                    volume,
+                    child,
+                    block_size,
+                    depth_left - 1,
+                    nodes_left,
+                ));
+            }
+        }
+    }
+    extents
+}
+
+fn ext4_runs(volume: &[u8], base: u64) -> Option<Vec<Run>> {
+    let sb = slice(volume, 1024, 1024)?;
+    if le_u16(sb, 0x38)? != EXT4_MAGIC {
+        return None;
+    }
+    let inodes_count = le_u32(sb, 0)? as u64;
+    // 64 KiB blocks are the largest ext4 knows
+    let log_block_size = le_u32(sb, 0x18)?;
+    if log_block_size > 6 {
+        return None;
+    }
+    let block_size = 1024_u64 << log_block_size;
+    let inodes_per_group = le_u32(sb, 0x28)? as u64;
+    let first_data_block = le_u32(sb, 0x14)? as u64;
+    let inode_size = match le_u32(sb, 0x4c)? {
+        0 => 128,
+        _ => le_u16(sb, 0x58)? as u64,
+    };
+    let is_64bit = le_u32(sb, 0x60)? & EXT4_FEATURE_64BIT != 0;
+    let desc_size = match is_64bit {
+        true => std::cmp::max(le_u16(sb, 0xfe)? as u64, 32),
+        false => 32,
+    };
+    if inodes_per_group == 0 || inode_size < 128 {
+        return None;
+    }
+    let groups = (inodes_count + inodes_per_group - 1) / inodes_per_group;
+    let gdt = first_data_block.checked_add(1)?.checked_mul(block_size)?;
+    let inode_tables: Vec<u64> = (0..groups)
+        .map_while(|group| {
+            let pos = gdt.checked_add(group.checked_mul(desc_size)?)?;
+            let desc = slice(volume, pos, desc_size)?;
+            let lo = le_u32(desc, 8)? as u64;
+            let hi = match is_64bit {
+                true => le_u32(desc, 0x28).unwrap_or(0) as u64,
+                false => 0,
+            };
+            Some((hi << 32) | lo)
+        })
+        .collect();
+
+    let mut names = HashMap::new();
+    let mut removed_names = HashMap::new();
+    let mut files = Vec::new();
+    let mut nodes_left = volume.len() as u64 / block_size;
+    for inode in 1..=std::cmp::min(inodes_count, MAX_RECORDS) {
+        let group = (inode - 1) / inodes_per_group;
+        let Some(table) = inode_tables.get(group as usize) else {
+            break;
+        };
+        let index = (inode - 1) % inodes_per_group * inode_size;
+        let pos = table
+            .checked_mul(block_size)
+            .and_then(|table| table.checked_add(index));
+        let Some(raw) = pos.and_then(|pos| slice(volume, pos, inode_size)) else {
+            continue;
+        };
+        let mode = le_u16(raw, 0).unwrap_or(0);
+        if mode == 0 {
+            continue;
+        }
+        let deleted = le_u32(raw, 0x14).unwrap_or(0) != 0 || le_u16(raw, 0x1a).unwrap_or(0) == 0;
+        let i_block = &raw[0x28..std::cmp::min(0x28 + 60, raw.len())];
+        let blocks = if le_u32(raw, 0x20).unwrap_or(0) & EXT4_EXTENTS_FL != 0 {
+            ext4_extents(volume, i_block, block_size, 5, &mut nodes_left)
+        } else {
+            // only the direct blocks of old-style block maps
+            (0..12)
+                .filter_map(|idx| le_u32(i_block, idx * 4))
+                .filter(|block| *block != 0)
+                .map(|block| (block as u64, 1))
+                .collect()
+        };
+        if mode & 0xf000 == EXT4_DIRECTORY && !deleted {
+            for (start, count) in blocks.iter() {
+                let dir = start
+                    .checked_mul(block_size)
+                    .zip(count.checked_mul(block_size))
+                    .and_then(|(pos, len)| slice(volume, pos, len));
+                let Some(dir) = dir else {
+                    continue;
+                };
+                ext4_dir_names(dir, inode, &mut names, &mut removed_names);
+            }
+        }
+        if !blocks.is_empty() {
+            files.push((inode, deleted, blocks));
+        }
+    }
+
+    // names of removed entries only where no live entry names the inode
+    for (inode, name) in removed_names {
+        names.entry(inode).or_insert(name);
+    }
+
+    let mut runs = Vec::new();
+    for (inode, deleted, blocks) in files {
+        let owner = owner_label(
+            "ext4",
+            full_path(&names, inode, EXT4_ROOT_INODE, '/'),
+            format!("inode {}", inode),
+            deleted,
+        );
+        for (block, count) in blocks {
+            let range = block
+                .checked_mul(block_size)
+                .and_then(|pos| base.checked_add(pos))
+                .and_then(|start| Some(start..start.checked_add(count.checked_mul(block_size)?)?));
+            let Some(range) = range else {
+                continue;
+            };
+            runs.push(Run {
+                range,
+                owner: Arc::clone(&owner),
+                deleted,
+            });
+        }
+    }
+    Some(runs)
+}
+
+// the size an entry with a name of `name_len` bytes needs, entries are 4-byte aligned
+fn ext4_dirent_size(name_len: usize) -> usize {
+    (8 + name_len + 3) / 4 * 4
+}
+
+// (inode, name, entry size) of a plausible directory entry at `pos`
+fn ext4_dirent_name(dir: &[u8], pos: usize, end: usize) -> Option<(u64, String, usize)> {
+    let inode = le_u32(dir, pos)?;
+    let name_len = *dir.get(pos + 6)? as usize;
+    if inode == 0 || name_len == 0 || pos + 8 + name_len > end {
+        return None;
+    }
+    let name = dir.get(pos + 8..pos + 8 + name_len)?;
+    if name == b"." || name == b".." || name.contains(&0) || name.contains(&b'/') {
+        return None;
+    }
+    Some((
+        inode as u64,
+        String::from_utf8_lossy(name).to_string(),
+        ext4_dirent_size(name_len),
+    ))
+}
+
+// removing an entry grows the one before it over it, so removed names are still in the
+// slack after each live entry until they get overwritten
+fn ext4_dir_names(
+    dir: &[u8],
+    dir_inode: u64,
+    names: &mut HashMap<u64, (String, u64)>,
+    removed_names: &mut HashMap<u64, (String, u64)>,
+) {
+    let mut pos = 0;
+    while let (Some(inode), Some(rec_len)) = (le_u32(dir, pos), le_u16(dir, pos + 4)) {
+        if rec_len < 8 {
+            break;
+        }
+        let end = std::cmp::min(pos + rec_len as usize, dir.len());
+        if let Some((inode, name, _size)) = ext4_dirent_name(dir, pos, end) {
+            names.entry(inode).or_insert((name, dir_inode));
+        }
+        let name_len = dir.get(pos + 6).copied().unwrap_or(0) as usize;
+        let mut slack = match inode {
+            0 => pos,
+            _ => pos + ext4_dirent_size(name_len),
+        };
+        while slack + 8 <= end {
+            match ext4_dirent_name(dir, slack, end) {
+                Some((inode, name, size)) => {
+                    removed_names.entry(inode).or_insert((name, dir_inode));
+                    slack += size;
+                }
+                None => slack += 4,
+            }
+        }
+        pos += rec_len as usize;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLOCK: usize = 1024;
+
+    fn put_u16(data: &mut [u8], pos: usize, value: u16) {
+        data[pos..pos + 2].copy_from_slice(&value.to_le_bytes());
+    }
+
+    fn put_u32(data: &mut [u8], pos: usize, value: u32) {
+        data[pos..pos + 4].copy_from_slice(&value.to_le_bytes());
+    }
+
+    fn ntfs_boot(per_record: i8) -> Vec<u8> {
+        let mut boot = vec![0; 512];
+        boot[3..11].copy_from_slice(NTFS_OEM_ID);
+        put_u16(&mut boot, 11, 512); // bytes per sector
+        boot[13] = 8; // sectors per cluster
+        boot[48..56].copy_from_slice(&4_u64.to_le_bytes()); // MFT cluster
+        boot[64] = per_record as u8;
+        boot
+    }
+
+    #[test]
+    fn ntfs_hostile_geometry_is_rejected() {
+        assert_eq!(ntfs_geometry(&ntfs_boot(-10)), Some((4096, 4, 1024)));
+        assert!(ntfs_geometry(&ntfs_boot(-40)).is_none());
+        assert!(ntfs_geometry(&ntfs_boot(-10)[..60]).is_none());
+        let mut boot = ntfs_boot(-10);
+        boot[13] = 0; // sectors per cluster
+        assert!(ntfs_geometry(&boot).is_none());
+    }
+
+    #[test]
+    fn ntfs_mft_past_the_end_is_skipped() {
+        let mut boot = ntfs_boot(-10);
+        boot[48..56].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(ntfs_runs(&boot, 0).is_none());
+    }
+
+    // 1 KiB blocks, one group of 16 inodes with its inode table in block 4
+    fn ext4_volume() -> Vec<u8> {
+        let mut volume = vec![0; 8 * BLOCK];
+        let sb = 1024;
+        put_u32(&mut volume, sb, 16); // inodes
+        put_u32(&mut volume, sb + 0x14, 1); // first data block
+        put_u32(&mut volume, sb + 0x28, 16); // inodes per group
+        put_u16(&mut volume, sb + 0x38, EXT4_MAGIC);
+        put_u32(&mut volume, 2 * BLOCK + 8, 4); // inode table
+        volume
+    }
+
+    #[test]
+    fn ext4_truncated_superblock_is_rejected() {
+        let volume = ext4_volume();
+        assert!(ext4_runs(&volume[..1500], 0).is_none());
+    }
+
+    #[test]
+    fn ext4_hostile_superblock_is_rejected() {
+        let mut volume = ext4_volume();
+        put_u32(&mut volume, 1024 + 0x18, 40); // log block size
+        assert!(ext4_runs(&volume, 0).is_none());
+
+        let mut volume = ext4_volume();
+        put_u32(&mut volume, 1024 + 0x28, 0); // inodes per group
+        assert!(ext4_runs(&volume, 0).is_none());
+    }
+
+    #[test]
+    fn ext4_extent_tree_pointing_at_itself_ends() {
+        let mut volume = ext4_volume();
+        let root = 4 * BLOCK + 128;
+        put_u16(&mut volume, root, EXT4_DIRECTORY | 0o755);
+        put_u16(&mut volume, root + 0x1a, 1); // links
+        put_u32(&mut volume, root + 0x20, EXT4_EXTENTS_FL);
+        // every index entry, at every level, points at block 6
+        let mut node = |pos: usize, entries: usize| {
+            put_u16(&mut volume, pos, EXT4_EXTENT_MAGIC);
+            put_u16(&mut volume, pos + 2, entries as u16);
+            put_u16(&mut volume, pos + 6, 1); // depth
+            for idx in 0..entries {
+                put_u32(&mut volume, pos + 12 + idx * 12 + 4, 6);
+            }
+        };
+        node(root + 0x28, 4);
+        node(6 * BLOCK, (BLOCK - 12) / 12);
+        let runs = ext4_runs(&volume, 0).unwrap();
+        assert!(runs.is_empty());
+    }
+}
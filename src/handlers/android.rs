@@ -0,0 +1,390 @@
+use super::Segment;
+use flate2::read::{DeflateDecoder, ZlibDecoder};
+use std::io::Read;
+
+// inflated data pulled out of one APK or backup, decoded resource strings included
+const MAX_EXTRACTED: usize = 512 * 1024 * 1024;
+// packages are nested one level deep in a resource table, more is a broken file
+const MAX_DEPTH: usize = 4;
+const RES_CHUNK_HEADER: usize = 8;
+
+const ZIP_LOCAL_MAGIC: &[u8] = b"PK\x03\x04";
+const ZIP_CENTRAL_MAGIC: u32 = 0x0201_4b50;
+const ZIP_END_MAGIC: u32 = 0x0605_4b50;
+const ZIP_STORED: u16 = 0;
+const ZIP_DEFLATED: u16 = 8;
+const APK_MANIFEST: &str = "AndroidManifest.xml";
+
+const BACKUP_MAGIC: &[u8] = b"ANDROID BACKUP\n";
+const TAR_BLOCK: usize = 512;
+
+// resource chunk types, see ResourceTypes.h in the Android framework
+const RES_STRING_POOL: u16 = 0x0001;
+const RES_TABLE: u16 = 0x0002;
+const RES_XML: u16 = 0x0003;
+const RES_TABLE_PACKAGE: u16 = 0x0200;
+const STRING_POOL_UTF8: u32 = 0x100;
+
+fn le_u16(data: &[u8], pos: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(
+        data.get(pos..pos.checked_add(2)?)?.try_into().ok()?,
+    ))
+}
+
+fn le_u32(data: &[u8], pos: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(
+        data.get(pos..pos.checked_add(4)?)?.try_into().ok()?,
+    ))
+}
+
+struct ZipEntry {
+    name: String,
+    method: u16,
+    compressed_size: usize,
+    size: usize,
+    header_offset: usize,
+}
+
+// entries listed in the central directory, zip64 archives aren't handled
+fn zip_entries(data: &[u8]) -> Vec<ZipEntry> {
+    // the end record is followed by a comment of at most 64 KiB
+    let search_start = data.len().saturating_sub(22 + 0xffff);
+    let Some(end) = (search_start..data.len().saturating_sub(21))
+        .rev()
+        .find(|pos| le_u32(data, *pos) == Some(ZIP_END_MAGIC))
+    else {
+        return Vec::new();
+    };
+    let count = le_u16(data, end + 10).unwrap_or(0) as usize;
+    let mut pos = le_u32(data, end + 16).unwrap_or(u32::MAX) as usize;
+
+    let mut entries = Vec::new();
+    for _ in 0..count {
+        if le_u32(data, pos) != Some(ZIP_CENTRAL_MAGIC) {
+            break;
+        }
+        let (Some(method), Some(compressed_size), Some(size)) = (
+            le_u16(data, pos + 10),
+            le_u32(data, pos + 20),
+            le_u32(data, pos + 24),
+        ) else {
+            break;
+        };
+        let name_len = le_u16(data, pos + 28).unwrap_or(0) as usize;
+        let extra_len = le_u16(data, pos + 30).unwrap_or(0) as usize;
+        let comment_len = le_u16(data, pos + 32).unwrap_or(0) as usize;
+        let header_offset = le_u32(data, pos + 42).unwrap_or(u32::MAX);
+        let Some(name) = data.get(pos + 46..pos + 46 + name_len) else {
+            break;
+        };
+        entries.push(ZipEntry {
+            name: String::from_utf8_lossy(name).into_owned(),
+            method,
+            compressed_size: compressed_size as usize,
+            size: size as usize,
+            header_offset: header_offset as usize,
+        });
+        pos += 46 + name_len + extra_len + comment_len;
+    }
+    entries
+}
+
+fn inflate(reader: impl Read, limit: usize) -> Vec<u8> {
+    let mut out = Vec::new();
+    // a truncated stream still yields whatever was inflated before the damage
+    let _ = reader.take(limit as u64).read_to_end(&mut out);
+    out
+}
+
+fn zip_entry_data(data: &[u8], entry: &ZipEntry, limit: usize) -> Option<Vec<u8>> {
+    let pos = entry.header_offset;
+    if data.get(pos..pos + 4)? != ZIP_LOCAL_MAGIC {
+        return None;
+    }
+    let name_len = le_u16(data, pos + 26)? as usize;
+    let extra_len = le_u16(data, pos + 28)? as usize;
+    let start = pos + 30 + name_len + extra_len;
+    let end = std::cmp::min(start.saturating_add(entry.compressed_size), data.len());
+    let raw = data.get(start..end)?;
+    match entry.method {
+        ZIP_STORED => Some(raw[..std::cmp::min(raw.len(), limit)].to_vec()),
+        ZIP_DEFLATED => Some(inflate(DeflateDecoder::new(raw), limit)),
+        _ => None,
+    }
+}
+
+// a zip carrying a manifest, so plain zips and office documents are left alone
+pub fn is_apk(data: &[u8]) -> bool {
+    data.starts_with(ZIP_LOCAL_MAGIC)
+        && zip_entries(data)
+            .iter()
+            .any(|entry| entry.name == APK_MANIFEST)
+}
+
+pub fn is_backup(data: &[u8]) -> bool {
+    data.starts_with(BACKUP_MAGIC)
+}
+
+// the length prefix of a UTF-8 pool string, one byte or two with the high bit set
+fn utf8_len(data: &[u8], pos: &mut usize) -> Option<usize> {
+    let first = *data.get(*pos)? as usize;
+    *pos += 1;
+    if first & 0x80 == 0 {
+        return Some(first);
+    }
+    let second = *data.get(*pos)? as usize;
+    *pos += 1;
+    Some(((first & 0x7f) << 8) | second)
+}
+
+fn pool_string(data: &[u8], pos: usize, utf8: bool) -> Option<String> {
+    if utf8 {
+        let mut pos = pos;
+        // the length in UTF-16 units comes first, then the one in bytes
+        utf8_len(data, &mut pos)?;
+        let len = utf8_len(data, &mut pos)?;
+        return Some(String::from_utf8_lossy(data.get(pos..pos + len)?).into_owned());
+    }
+    let mut len = le_u16(data, pos)? as usize;
+    let mut start = pos + 2;
+    if len & 0x8000 != 0 {
+        len = ((len & 0x7fff) << 16) | le_u16(data, start)? as usize;
+        start += 2;
+    }
+    let units: Vec<u16> = data
+        .get(start..start + len * 2)?
+        .chunks_exact(2)
+        .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+        .collect();
+    Some(String::from_utf16_lossy(&units))
+}
+
+// every offset of a pool may point at the same long string, so the decoded strings count
+// towards the extraction limit like inflated data does
+fn string_pool(
+    data: &[u8],
+    chunk: usize,
+    strings: &mut Vec<String>,
+    extracted: &mut usize,
+) -> Option<()> {
+    let header_size = le_u16(data, chunk + 2)? as usize;
+    let count = le_u32(data, chunk + 8)? as usize;
+    let utf8 = le_u32(data, chunk + 16)? & STRING_POOL_UTF8 != 0;
+    let strings_start = chunk + le_u32(data, chunk + 20)? as usize;
+    for idx in 0..count {
+        let offset = le_u32(data, chunk + header_size + idx * 4)? as usize;
+        if let Some(string) = pool_string(data, strings_start + offset, utf8) {
+            if *extracted + string.len() > MAX_EXTRACTED {
+                return None;
+            }
+            *extracted += string.len();
+            strings.push(string);
+        }
+    }
+    Some(())
+}
+
+// string pools of the chunks between `pos` and `end`, descending into resource packages
+// which hold the type and key names
+fn collect_strings(
+    data: &[u8],
+    mut pos: usize,
+    end: usize,
+    depth: usize,
+    strings: &mut Vec<String>,
+    extracted: &mut usize,
+) {
+    while pos + RES_CHUNK_HEADER <= end {
+        let (Some(chunk_type), Some(header_size), Some(size)) = (
+            le_u16(data, pos),
+            le_u16(data, pos + 2),
+            le_u32(data, pos + 4),
+        ) else {
+            break;
+        };
+        let (size, header_size) = (size as usize, header_size as usize);
+        if size < RES_CHUNK_HEADER || pos + size > end {
+            break;
+        }
+        match chunk_type {
+            RES_STRING_POOL => {
+                let _ = string_pool(data, pos, strings, extracted);
+            }
+            // a header shorter than the chunk header would start over at the same chunk
+            RES_TABLE_PACKAGE if depth < MAX_DEPTH && header_size >= RES_CHUNK_HEADER => {
+                collect_strings(
+                    data,
+                    pos + header_size,
+                    pos + size,
+                    depth + 1,
+                    strings,
+                    extracted,
+                )
+            }
+            _ => {}
+        }
+        pos += size;
+    }
+}
+
+// the strings of a compiled XML file or resources.arsc, one per line
+fn decode_resource(data: &[u8], extracted: &mut usize) -> Option<Vec<u8>> {
+    let chunk_type = le_u16(data, 0)?;
+    if chunk_type != RES_XML && chunk_type != RES_TABLE {
+        return None;
+    }
+    let header_size = le_u16(data, 2)? as usize;
+    let size = std::cmp::min(le_u32(data, 4)? as usize, data.len());
+    let mut strings = Vec::new();
+    collect_strings(data, header_size, size, 0, &mut strings, extracted);
+    Some(strings.join("\n").into_bytes())
+}
+
+fn extract_apk_into(data: &[u8], prefix: &str, extracted: &mut usize, segments: &mut Vec<Segment>) {
+    for entry in zip_entries(data) {
+        if entry.name.ends_with('/') || *extracted >= MAX_EXTRACTED {
+            continue;
+        }
+        let limit = std::cmp::min(entry.size, MAX_EXTRACTED - *extracted);
+        let Some(content) = zip_entry_data(data, &entry, limit) else {
+            continue;
+        };
+        *extracted += content.len();
+        let location = format!("{}{}", prefix, entry.name);
+        match decode_resource(&content, extracted) {
+            Some(strings) => segments.push(Segment {
+                location: format!("{} strings", location),
+                data: strings,
+            }),
+            None => segments.push(Segment {
+                location,
+                data: content,
+            }),
+        }
+    }
+}
+
+// every entry inflated, compiled XML and the resource table as their string pools
+pub fn extract_apk(data: &[u8]) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    extract_apk_into(data, "", &mut 0, &mut segments);
+    segments
+}
+
+fn tar_field(header: &[u8], range: std::ops::Range<usize>) -> &[u8] {
+    let field = &header[range];
+    let len = field.iter().position(|b| *b == 0).unwrap_or(field.len());
+    &field[..len]
+}
+
+fn tar_size(header: &[u8]) -> Option<usize> {
+    let digits = std::str::from_utf8(tar_field(header, 124..136)).ok()?;
+    usize::from_str_radix(digits.trim(), 8).ok()
+}
+
+// `adb backup` files: a short text header, then a tar of the app data that is usually
+// zlib compressed. encrypted backups would need the user's password and are skipped.
+pub fn extract_backup(data: &[u8]) -> Vec<Segment> {
+    let mut lines = data.splitn(5, |b| *b == b'\n');
+    let (Some(_magic), Some(_version), Some(compressed), Some(encryption), Some(body)) = (
+        lines.next(),
+        lines.next(),
+        lines.next(),
+        lines.next(),
+        lines.next(),
+    ) else {
+        return Vec::new();
+    };
+    if encryption != b"none" {
+        return Vec::new();
+    }
+    let tar = if compressed == b"1" {
+        inflate(ZlibDecoder::new(body), MAX_EXTRACTED)
+    } else {
+        body.to_vec()
+    };
+
+    let mut segments = Vec::new();
+    let mut extracted = 0;
+    let mut pos = 0;
+    while let Some(header) = tar.get(pos..pos + TAR_BLOCK) {
+        // the archive ends with zeroed blocks
+        if header[0] == 0 {
+            break;
+        }
+        let Some(size) = tar_size(header) else {
+            break;
+        };
+        let start = pos + TAR_BLOCK;
+        let end = std::cmp::min(start.saturating_add(size), tar.len());
+        // only regular files, long names are kept in the ustar prefix
+        let type_flag = header[156];
+        if (type_flag == b'0' || type_flag == 0) && start < end {
+            let name = String::from_utf8_lossy(tar_field(header, 0..100));
+            let prefix = String::from_utf8_lossy(tar_field(header, 345..500));
+            let name = if prefix.is_empty() {
+                name.into_owned()
+            } else {
+                format!("{}/{}", prefix, name)
+            };
+            let content = &tar[start..end];
+            if is_apk(content) {
+                extract_apk_into(
+                    content,
+                    &format!("{} ", name),
+                    &mut extracted,
+                    &mut segments,
+                );
+            } else {
+                segments.push(Segment {
+                    location: name,
+                    data: content.to_vec(),
+                });
+            }
+        }
+        pos = start + (size + TAR_BLOCK - 1) / TAR_BLOCK * TAR_BLOCK;
+    }
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(chunk_type: u16, header_size: u16, size: u32) -> Vec<u8> {
+        let mut chunk = Vec::new();
+        chunk.extend(chunk_type.to_le_bytes());
+        chunk.extend(header_size.to_le_bytes());
+        chunk.extend(size.to_le_bytes());
+        chunk
+    }
+
+    #[test]
+    fn package_without_header_is_skipped() {
+        let mut table = chunk(RES_TABLE, 8, 16);
+        table.extend(chunk(RES_TABLE_PACKAGE, 0, 8));
+        assert_eq!(decode_resource(&table, &mut 0), Some(Vec::new()));
+    }
+
+    #[test]
+    fn repeated_pool_strings_count_towards_the_limit() {
+        // a pool of 1000 offsets, all pointing at the same string
+        let mut pool = chunk(RES_STRING_POOL, 28, 0);
+        pool.extend(1000_u32.to_le_bytes()); // string count
+        pool.extend(0_u32.to_le_bytes()); // style count
+        pool.extend(STRING_POOL_UTF8.to_le_bytes());
+        pool.extend((28 + 4000_u32).to_le_bytes()); // strings start
+        pool.extend(0_u32.to_le_bytes()); // styles start
+        pool.extend(std::iter::repeat(0).take(4000));
+        pool.extend([5, 5]);
+        pool.extend(b"hello");
+        let size = pool.len() as u32;
+        pool[4..8].copy_from_slice(&size.to_le_bytes());
+
+        let mut extracted = MAX_EXTRACTED - 5 * 10;
+        let mut strings = Vec::new();
+        assert!(string_pool(&pool, 0, &mut strings, &mut extracted).is_none());
+        assert_eq!(strings.len(), 10);
+        assert_eq!(extracted, MAX_EXTRACTED);
+    }
+}
@@ -0,0 +1,401 @@
+use super::Segment;
+use std::fmt::Write;
+
+const FILE_MAGIC: &[u8] = b"ElfFile\0";
+const CHUNK_MAGIC: &[u8] = b"ElfChnk\0";
+const RECORD_MAGIC: &[u8] = b"**\0\0";
+const FILE_HEADER: usize = 4096;
+const CHUNK_SIZE: usize = 65536;
+const CHUNK_HEADER: usize = 512;
+const CHUNK_FREE_SPACE: usize = 48;
+const RECORD_HEADER: usize = 24;
+// templates can nest binary XML in their substitution values
+const MAX_DEPTH: usize = 16;
+
+// binary XML tokens, the 0x40 bit marks elements with attributes and more data following
+const TOKEN_EOF: u8 = 0x00;
+const TOKEN_OPEN_START: u8 = 0x01;
+const TOKEN_CLOSE_START: u8 = 0x02;
+const TOKEN_CLOSE_EMPTY: u8 = 0x03;
+const TOKEN_END_ELEMENT: u8 = 0x04;
+const TOKEN_VALUE: u8 = 0x05;
+const TOKEN_ATTRIBUTE: u8 = 0x06;
+const TOKEN_CDATA: u8 = 0x07;
+const TOKEN_CHAR_REF: u8 = 0x08;
+const TOKEN_ENTITY_REF: u8 = 0x09;
+const TOKEN_PI_TARGET: u8 = 0x0a;
+const TOKEN_PI_DATA: u8 = 0x0b;
+const TOKEN_TEMPLATE: u8 = 0x0c;
+const TOKEN_SUBSTITUTION: u8 = 0x0d;
+const TOKEN_OPTIONAL_SUBSTITUTION: u8 = 0x0e;
+const TOKEN_FRAGMENT_HEADER: u8 = 0x0f;
+const TOKEN_MORE_BIT: u8 = 0x40;
+
+// substitution value types, 0x80 marks arrays
+const VALUE_STRING: u8 = 0x01;
+const VALUE_ANSI_STRING: u8 = 0x02;
+const VALUE_INT8: u8 = 0x03;
+const VALUE_UINT8: u8 = 0x04;
+const VALUE_INT16: u8 = 0x05;
+const VALUE_UINT16: u8 = 0x06;
+const VALUE_INT32: u8 = 0x07;
+const VALUE_UINT32: u8 = 0x08;
+const VALUE_INT64: u8 = 0x09;
+const VALUE_UINT64: u8 = 0x0a;
+const VALUE_REAL32: u8 = 0x0b;
+const VALUE_REAL64: u8 = 0x0c;
+const VALUE_BOOL: u8 = 0x0d;
+const VALUE_GUID: u8 = 0x0f;
+const VALUE_SIZE_T: u8 = 0x10;
+const VALUE_FILETIME: u8 = 0x11;
+const VALUE_SYSTEMTIME: u8 = 0x12;
+const VALUE_SID: u8 = 0x13;
+const VALUE_HEX_INT32: u8 = 0x14;
+const VALUE_HEX_INT64: u8 = 0x15;
+const VALUE_BINXML: u8 = 0x21;
+const VALUE_STRING_ARRAY: u8 = 0x81;
+
+fn le_u16(data: &[u8], pos: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(data.get(pos..pos + 2)?.try_into().ok()?))
+}
+
+fn le_u32(data: &[u8], pos: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(data.get(pos..pos + 4)?.try_into().ok()?))
+}
+
+fn le_u64(data: &[u8], pos: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(data.get(pos..pos + 8)?.try_into().ok()?))
+}
+
+pub fn is_evtx(data: &[u8]) -> bool {
+    data.starts_with(FILE_MAGIC)
+}
+
+fn utf16(bytes: &[u8]) -> String {
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// 100ns ticks since 1601 as UTC
+fn filetime(ticks: u64) -> String {
+    let secs = (ticks / 10_000_000) as i64 - 11_644_473_600;
+    let nanos = (ticks % 10_000_000) as u32 * 100;
+    match chrono::DateTime::from_timestamp(secs, nanos) {
+        Some(time) => time.format("%Y-%m-%dT%H:%M:%S%.6fZ").to_string(),
+        None => format!("0x{:016x}", ticks),
+    }
+}
+
+fn guid(bytes: &[u8]) -> Option<String> {
+    Some(format!(
+        "{{{:08X}-{:04X}-{:04X}-{}-{}}}",
+        le_u32(bytes, 0)?,
+        le_u16(bytes, 4)?,
+        le_u16(bytes, 6)?,
+        hex(bytes.get(8..10)?),
+        hex(bytes.get(10..16)?)
+    ))
+}
+
+fn sid(bytes: &[u8]) -> Option<String> {
+    let revision = *bytes.first()?;
+    let count = *bytes.get(1)? as usize;
+    let authority = bytes
+        .get(2..8)?
+        .iter()
+        .fold(0_u64, |acc, byte| (acc << 8) | *byte as u64);
+    let mut text = format!("S-{}-{}", revision, authority);
+    for idx in 0..count {
+        let _ = write!(text, "-{}", le_u32(bytes, 8 + idx * 4)?);
+    }
+    Some(text)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02X}", b)).collect()
+}
+
+// a substitution value as text, None if it doesn't hold what its type says
+fn value_text(kind: u8, bytes: &[u8]) -> Option<String> {
+    let int = |size: usize| -> Option<u64> {
+        Some(match size {
+            1 => *bytes.first()? as u64,
+            2 => le_u16(bytes, 0)? as u64,
+            4 => le_u32(bytes, 0)? as u64,
+            _ => le_u64(bytes, 0)?,
+        })
+    };
+    Some(match kind {
+        VALUE_STRING => utf16(bytes).trim_end_matches('\0').to_string(),
+        VALUE_ANSI_STRING => String::from_utf8_lossy(bytes)
+            .trim_end_matches('\0')
+            .to_string(),
+        VALUE_INT8 => (*bytes.first()? as i8).to_string(),
+        VALUE_UINT8 => int(1)?.to_string(),
+        VALUE_INT16 => (int(2)? as i16).to_string(),
+        VALUE_UINT16 => int(2)?.to_string(),
+        VALUE_INT32 => (int(4)? as i32).to_string(),
+        VALUE_UINT32 => int(4)?.to_string(),
+        VALUE_INT64 => (int(8)? as i64).to_string(),
+        VALUE_UINT64 => int(8)?.to_string(),
+        VALUE_REAL32 => f32::from_bits(int(4)? as u32).to_string(),
+        VALUE_REAL64 => f64::from_bits(int(8)?).to_string(),
+        VALUE_BOOL => (int(4)? != 0).to_string(),
+        VALUE_GUID => guid(bytes)?,
+        VALUE_SIZE_T | VALUE_HEX_INT32 | VALUE_HEX_INT64 => {
+            format!("0x{:x}", int(bytes.len())?)
+        }
+        VALUE_FILETIME => filetime(int(8)?),
+        VALUE_SYSTEMTIME => format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+            int(2)?,
+            le_u16(bytes, 2)?,
+            le_u16(bytes, 6)?,
+            le_u16(bytes, 8)?,
+            le_u16(bytes, 10)?,
+            le_u16(bytes, 12)?,
+            le_u16(bytes, 14)?
+        ),
+        VALUE_SID => sid(bytes)?,
+        VALUE_STRING_ARRAY => utf16(bytes)
+            .split('\0')
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<&str>>()
+            .join(", "),
+        _ => hex(bytes),
+    })
+}
+
+// a substitution value of a template instance, where it lies in the chunk
+#[derive(Clone, Copy)]
+struct Substitution {
+    kind: u8,
+    start: usize,
+    size: usize,
+}
+
+// renders the binary XML of one chunk, names and templates are referenced by chunk offset
+struct Renderer<'a> {
+    chunk: &'a [u8],
+    xml: String,
+    open: Vec<String>,  // elements not yet closed
+    in_attribute: bool, // an attribute value is being written, its quote still open
+}
+
+impl Renderer<'_> {
+    // the name at `offset`, and where the data after a name reference at `pos` continues.
+    // a name is stored inline right where it is first used.
+    fn name(&self, offset: usize, pos: usize) -> Option<(String, usize)> {
+        let count = le_u16(self.chunk, offset + 6)? as usize;
+        let name = utf16(self.chunk.get(offset + 8..offset + 8 + count * 2)?);
+        let next = match offset == pos {
+            true => pos + 8 + count * 2 + 2,
+            false => pos,
+        };
+        Some((name, next))
+    }
+
+    fn close_attribute(&mut self) {
+        if self.in_attribute {
+            self.xml.push('"');
+            self.in_attribute = false;
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        self.xml.push_str(&escape(text));
+    }
+
+    fn substitution(&mut self, value: Substitution, depth: usize) -> Option<()> {
+        let bytes = self.chunk.get(value.start..value.start + value.size)?;
+        match value.kind {
+            0 => {}
+            VALUE_BINXML => {
+                return self.fragment(value.start, value.start + value.size, &[], depth + 1);
+            }
+            kind => {
+                let text = value_text(kind, bytes).unwrap_or_else(|| hex(bytes));
+                self.text(&text);
+            }
+        }
+        Some(())
+    }
+
+    // a template instance at `pos`: the template's fragment rendered with the values that
+    // follow it. returns where the data after the values continues.
+    fn template(&mut self, pos: usize, depth: usize) -> Option<usize> {
+        let definition = le_u32(self.chunk, pos + 6)? as usize;
+        let definition_size = le_u32(self.chunk, definition + 20)? as usize;
+        let mut pos = pos + 10;
+        // the definition is stored inline the first time a template is used
+        if definition == pos {
+            pos += 24 + definition_size;
+        }
+        let count = le_u32(self.chunk, pos)? as usize;
+        pos += 4;
+        let mut values = Vec::new();
+        let mut start = pos + count * 4;
+        for idx in 0..count {
+            let size = le_u16(self.chunk, pos + idx * 4)? as usize;
+            let kind = *self.chunk.get(pos + idx * 4 + 2)?;
+            values.push(Substitution { kind, start, size });
+            start += size;
+        }
+        let fragment = definition + 24;
+        self.fragment(fragment, fragment + definition_size, &values, depth + 1)?;
+        Some(start)
+    }
+
+    // the tokens from `pos` up to `end` or the end of the fragment
+    fn fragment(
+        &mut self,
+        mut pos: usize,
+        end: usize,
+        values: &[Substitution],
+        depth: usize,
+    ) -> Option<()> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+        while pos < end {
+            let token = *self.chunk.get(pos)?;
+            match token & !TOKEN_MORE_BIT {
+                TOKEN_EOF => return Some(()),
+                TOKEN_FRAGMENT_HEADER => pos += 4,
+                TOKEN_OPEN_START => {
+                    self.close_attribute();
+                    // dependency id and data size come before the name
+                    let (name, next) =
+                        self.name(le_u32(self.chunk, pos + 7)? as usize, pos + 11)?;
+                    let _ = write!(self.xml, "<{}", name);
+                    self.open.push(name);
+                    pos = next;
+                    if token & TOKEN_MORE_BIT != 0 {
+                        pos += 4; // size of the attribute list
+                    }
+                }
+                TOKEN_ATTRIBUTE => {
+                    self.close_attribute();
+                    let (name, next) = self.name(le_u32(self.chunk, pos + 1)? as usize, pos + 5)?;
+                    let _ = write!(self.xml, " {}=\"", name);
+                    self.in_attribute = true;
+                    pos = next;
+                }
+                TOKEN_CLOSE_START => {
+                    self.close_attribute();
+                    self.xml.push('>');
+                    pos += 1;
+                }
+                TOKEN_CLOSE_EMPTY => {
+                    self.close_attribute();
+                    self.xml.push_str("/>");
+                    self.open.pop();
+                    pos += 1;
+                }
+                TOKEN_END_ELEMENT => {
+                    self.close_attribute();
+                    if let Some(name) = self.open.pop() {
+                        let _ = write!(self.xml, "</{}>", name);
+                    }
+                    pos += 1;
+                }
+                TOKEN_VALUE | TOKEN_CDATA | TOKEN_PI_DATA => {
+                    // values carry a type byte before the string
+                    let at = if token & !TOKEN_MORE_BIT == TOKEN_VALUE {
+                        pos + 2
+                    } else {
+                        pos + 1
+                    };
+                    let count = le_u16(self.chunk, at)? as usize;
+                    let text = utf16(self.chunk.get(at + 2..at + 2 + count * 2)?);
+                    self.text(&text);
+                    if token == TOKEN_PI_DATA {
+                        self.xml.push_str("?>");
+                    }
+                    pos = at + 2 + count * 2;
+                }
+                TOKEN_CHAR_REF => {
+                    let _ = write!(self.xml, "&#{};", le_u16(self.chunk, pos + 1)?);
+                    pos += 3;
+                }
+                TOKEN_ENTITY_REF | TOKEN_PI_TARGET => {
+                    let (name, next) = self.name(le_u32(self.chunk, pos + 1)? as usize, pos + 5)?;
+                    match token == TOKEN_ENTITY_REF {
+                        true => write!(self.xml, "&{};", name),
+                        false => write!(self.xml, "<?{} ", name),
+                    }
+                    .ok()?;
+                    pos = next;
+                }
+                TOKEN_TEMPLATE => pos = self.template(pos, depth)?,
+                TOKEN_SUBSTITUTION | TOKEN_OPTIONAL_SUBSTITUTION => {
+                    let id = le_u16(self.chunk, pos + 1)? as usize;
+                    if let Some(value) = values.get(id) {
+                        self.substitution(*value, depth)?;
+                    }
+                    pos += 4;
+                }
+                _ => return None,
+            }
+        }
+        Some(())
+    }
+}
+
+fn render_record(chunk: &[u8], pos: usize, end: usize) -> String {
+    let mut renderer = Renderer {
+        chunk,
+        xml: String::new(),
+        open: Vec::new(),
+        in_attribute: false,
+    };
+    // whatever rendered before damage or an unknown token is still worth searching
+    let _ = renderer.fragment(pos, end, &[], 0);
+    renderer.xml
+}
+
+// every event record as its rendered XML, found by record id and time written
+pub fn extract(data: &[u8]) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    let mut chunk_start = FILE_HEADER;
+    while let Some(chunk) = data.get(chunk_start..chunk_start + CHUNK_SIZE) {
+        chunk_start += CHUNK_SIZE;
+        if !chunk.starts_with(CHUNK_MAGIC) {
+            continue;
+        }
+        let used = le_u32(chunk, CHUNK_FREE_SPACE).unwrap_or(0) as usize;
+        let used = std::cmp::min(used, CHUNK_SIZE);
+        let mut pos = CHUNK_HEADER;
+        while pos + RECORD_HEADER <= used && chunk[pos..].starts_with(RECORD_MAGIC) {
+            let (Some(size), Some(id), Some(written)) = (
+                le_u32(chunk, pos + 4),
+                le_u64(chunk, pos + 8),
+                le_u64(chunk, pos + 16),
+            ) else {
+                break;
+            };
+            let size = size as usize;
+            if size <= RECORD_HEADER || pos + size > used {
+                break;
+            }
+            // the record ends with a copy of its size
+            let xml = render_record(chunk, pos + RECORD_HEADER, pos + size - 4);
+            segments.push(Segment {
+                location: format!("record {} {}", id, filetime(written)),
+                data: xml.into_bytes(),
+            });
+            pos += size;
+        }
+    }
+    segments
+}
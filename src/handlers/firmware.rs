@@ -0,0 +1,702 @@
+use super::Segment;
+use flate2::read::{GzDecoder, ZlibDecoder};
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+
+// unpacked data pulled out of one image, and how deep containers inside files are followed
+const MAX_EXTRACTED: usize = 512 * 1024 * 1024;
+const MAX_DEPTH: usize = 4;
+
+// raw dumps and update files the containers are usually embedded in rather than at the start
+const FIRMWARE_EXTENSIONS: [&str; 8] = ["bin", "img", "fd", "rom", "fw", "trx", "chk", "uimage"];
+
+const SQUASHFS_MAGIC: &[u8] = b"hsqs";
+const SQUASHFS_ZLIB: u16 = 1;
+const SQUASHFS_METADATA_SIZE: usize = 8192;
+// the block sizes mksquashfs accepts, all powers of two
+const SQUASHFS_MIN_BLOCK_SIZE: usize = 4 * 1024;
+const SQUASHFS_MAX_BLOCK_SIZE: usize = 1024 * 1024;
+const SQUASHFS_METADATA_UNCOMPRESSED: u16 = 0x8000;
+const SQUASHFS_BLOCK_UNCOMPRESSED: u32 = 1 << 24;
+const SQUASHFS_NO_FRAGMENT: u32 = 0xffff_ffff;
+const SQUASHFS_DIR: u16 = 1;
+const SQUASHFS_FILE: u16 = 2;
+const SQUASHFS_EXT_DIR: u16 = 8;
+const SQUASHFS_EXT_FILE: u16 = 9;
+
+const CPIO_NEWC: &[u8] = b"070701";
+const CPIO_NEWC_CRC: &[u8] = b"070702";
+const CPIO_HEADER: usize = 110;
+const CPIO_TRAILER: &[u8] = b"TRAILER!!!";
+const CPIO_REGULAR: u32 = 0o100000;
+const CPIO_TYPE_MASK: u32 = 0o170000;
+const GZIP_MAGIC: &[u8] = b"\x1f\x8b\x08";
+
+const FV_SIGNATURE: &[u8] = b"_FVH";
+const FV_SIGNATURE_OFFSET: usize = 40;
+const FFS_LARGE_FILE: u8 = 0x01;
+const FFS_RAW: u8 = 0x01;
+const FFS_PAD: u8 = 0xf0;
+const SECTION_COMPRESSION: u8 = 0x01;
+const SECTION_GUID_DEFINED: u8 = 0x02;
+const SECTION_USER_INTERFACE: u8 = 0x15;
+const SECTION_FV_IMAGE: u8 = 0x17;
+const GUID_PROCESSING_REQUIRED: u16 = 0x01;
+
+#[derive(Clone, Copy)]
+enum Kind {
+    Squashfs,
+    Cpio,
+    GzipCpio,
+    FirmwareVolume,
+}
+
+fn le_u16(data: &[u8], pos: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(
+        data.get(pos..pos.checked_add(2)?)?.try_into().ok()?,
+    ))
+}
+
+fn le_u32(data: &[u8], pos: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(
+        data.get(pos..pos.checked_add(4)?)?.try_into().ok()?,
+    ))
+}
+
+fn le_u64(data: &[u8], pos: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(
+        data.get(pos..pos.checked_add(8)?)?.try_into().ok()?,
+    ))
+}
+
+fn le_u24(data: &[u8], pos: usize) -> Option<usize> {
+    let bytes = data.get(pos..pos.checked_add(3)?)?;
+    Some(bytes[0] as usize | (bytes[1] as usize) << 8 | (bytes[2] as usize) << 16)
+}
+
+fn align(pos: usize, to: usize) -> usize {
+    (pos + to - 1) / to * to
+}
+
+fn inflate(reader: impl Read, limit: usize) -> Vec<u8> {
+    let mut out = Vec::new();
+    let _ = reader.take(limit as u64).read_to_end(&mut out);
+    out
+}
+
+fn has_extension(path: &Path, extensions: &[&str]) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| extensions.iter().any(|e| e.eq_ignore_ascii_case(ext)))
+        .unwrap_or(false)
+}
+
+// a squashfs 4 superblock this reader can follow
+fn is_squashfs(data: &[u8]) -> bool {
+    data.starts_with(SQUASHFS_MAGIC)
+        && le_u16(data, 28) == Some(4)
+        && le_u16(data, 22).is_some_and(|log| (12..=20).contains(&log))
+        && le_u64(data, 40).is_some_and(|used| used <= data.len() as u64)
+}
+
+fn cpio_hex(data: &[u8], pos: usize, field: usize) -> Option<u32> {
+    let start = pos + CPIO_NEWC.len() + field * 8;
+    let digits = std::str::from_utf8(data.get(start..start + 8)?).ok()?;
+    u32::from_str_radix(digits, 16).ok()
+}
+
+fn is_cpio(data: &[u8]) -> bool {
+    (data.starts_with(CPIO_NEWC) || data.starts_with(CPIO_NEWC_CRC))
+        && cpio_hex(data, 0, 11).is_some()
+}
+
+// compressed initramfs images, only worth unpacking when a cpio archive comes out
+fn is_gzip_cpio(data: &[u8]) -> bool {
+    data.starts_with(GZIP_MAGIC) && is_cpio(&inflate(GzDecoder::new(data), CPIO_HEADER))
+}
+
+fn is_firmware_volume(data: &[u8]) -> bool {
+    data.get(FV_SIGNATURE_OFFSET..FV_SIGNATURE_OFFSET + 4) == Some(FV_SIGNATURE)
+        && le_u16(data, 48).is_some_and(|header| header >= 56)
+        && le_u64(data, 32).is_some_and(|len| len <= data.len() as u64)
+}
+
+fn kind_at_start(data: &[u8]) -> Option<Kind> {
+    if is_squashfs(data) {
+        Some(Kind::Squashfs)
+    } else if is_cpio(data) {
+        Some(Kind::Cpio)
+    } else if is_firmware_volume(data) {
+        Some(Kind::FirmwareVolume)
+    } else if is_gzip_cpio(data) {
+        Some(Kind::GzipCpio)
+    } else {
+        None
+    }
+}
+
+pub fn is_firmware(path: &Path, data: &[u8]) -> bool {
+    kind_at_start(data).is_some() || has_extension(path, &FIRMWARE_EXTENSIONS)
+}
+
+// containers found inside a larger dump, skipping over the ones already found
+fn embedded_containers(data: &[u8]) -> Vec<(usize, Kind)> {
+    let mut found = Vec::new();
+    let mut pos = 0;
+    while pos + 4 <= data.len() {
+        let rest = &data[pos..];
+        let start_and_kind = if rest.starts_with(FV_SIGNATURE) && pos >= FV_SIGNATURE_OFFSET {
+            let start = pos - FV_SIGNATURE_OFFSET;
+            Some((start, Kind::FirmwareVolume)).filter(|_| is_firmware_volume(&data[start..]))
+        } else {
+            kind_at_start(rest)
+                .filter(|kind| !matches!(kind, Kind::FirmwareVolume))
+                .map(|kind| (pos, kind))
+        };
+        let Some((start, kind)) = start_and_kind else {
+            pos += 1;
+            continue;
+        };
+        found.push((start, kind));
+        // compressed streams don't tell their length up front
+        let len = match kind {
+            Kind::Squashfs => le_u64(rest, 40).unwrap_or(0) as usize,
+            Kind::FirmwareVolume => le_u64(&data[start..], 32).unwrap_or(0) as usize,
+            Kind::Cpio => cpio_entries(rest).1,
+            Kind::GzipCpio => 0,
+        };
+        pos = std::cmp::max(start + len, pos + 1);
+    }
+    found
+}
+
+// regular files of a newc archive and where the archive ended
+fn cpio_entries(data: &[u8]) -> (Vec<(String, &[u8])>, usize) {
+    let mut files = Vec::new();
+    let mut pos = 0;
+    while is_cpio(&data[pos..]) {
+        let (Some(mode), Some(size), Some(name_size)) = (
+            cpio_hex(data, pos, 1),
+            cpio_hex(data, pos, 6),
+            cpio_hex(data, pos, 11),
+        ) else {
+            break;
+        };
+        let name_start = pos + CPIO_HEADER;
+        let Some(name) = data.get(name_start..name_start + (name_size as usize).saturating_sub(1))
+        else {
+            break;
+        };
+        let data_start = align(name_start + name_size as usize, 4);
+        let data_end = std::cmp::min(data_start.saturating_add(size as usize), data.len());
+        pos = align(data_start + size as usize, 4);
+        if name == CPIO_TRAILER {
+            break;
+        }
+        if mode & CPIO_TYPE_MASK == CPIO_REGULAR && data_start < data_end {
+            let name = String::from_utf8_lossy(name);
+            files.push((
+                format!("/{}", name.trim_start_matches("./").trim_start_matches('/')),
+                &data[data_start..data_end],
+            ));
+        }
+        if pos >= data.len() {
+            break;
+        }
+    }
+    (files, std::cmp::min(pos, data.len()))
+}
+
+struct Squashfs<'a> {
+    data: &'a [u8],
+    block_size: usize,
+    inodes: Vec<u8>,
+    inode_blocks: HashMap<u64, usize>,
+    dirs: Vec<u8>,
+    dir_blocks: HashMap<u64, usize>,
+    fragments: Vec<u8>,
+}
+
+// metadata blocks between `start` and `end` unpacked back to back, with where each block
+// starts in the output since inode and directory references use the packed positions
+fn squashfs_metadata(data: &[u8], start: usize, end: usize) -> (Vec<u8>, HashMap<u64, usize>) {
+    let mut out = Vec::new();
+    let mut blocks = HashMap::new();
+    let mut pos = start;
+    while out.len() < MAX_EXTRACTED
+        && pos
+            .checked_add(2)
+            .is_some_and(|header_end| header_end <= end)
+    {
+        let Some((block, len)) = squashfs_metadata_block(data, pos) else {
+            break;
+        };
+        blocks.insert((pos - start) as u64, out.len());
+        out.extend(block);
+        pos += len;
+    }
+    (out, blocks)
+}
+
+// one unpacked metadata block at `pos` and how many bytes it took up
+fn squashfs_metadata_block(data: &[u8], pos: usize) -> Option<(Vec<u8>, usize)> {
+    let header = le_u16(data, pos)?;
+    let len = (header & !SQUASHFS_METADATA_UNCOMPRESSED) as usize;
+    let block = data.get(pos.checked_add(2)?..pos.checked_add(2 + len)?)?;
+    let unpacked = if header & SQUASHFS_METADATA_UNCOMPRESSED != 0 {
+        block.to_vec()
+    } else {
+        inflate(ZlibDecoder::new(block), SQUASHFS_METADATA_SIZE)
+    };
+    Some((unpacked, 2 + len))
+}
+
+impl<'a> Squashfs<'a> {
+    // only zlib compressed images, the other compressors have no decoder here
+    fn open(data: &'a [u8]) -> Option<Squashfs<'a>> {
+        if le_u16(data, 20)? != SQUASHFS_ZLIB {
+            return None;
+        }
+        let block_size = le_u32(data, 12)? as usize;
+        if !block_size.is_power_of_two()
+            || !(SQUASHFS_MIN_BLOCK_SIZE..=SQUASHFS_MAX_BLOCK_SIZE).contains(&block_size)
+        {
+            return None;
+        }
+        let fragment_count = le_u32(data, 16)? as usize;
+        let inode_table = le_u64(data, 64)? as usize;
+        let dir_table = le_u64(data, 72)? as usize;
+        let fragment_table = le_u64(data, 80)?;
+        // the directory table runs up to whichever lookup table follows it
+        let dir_end = [48, 56, 80, 88]
+            .iter()
+            .filter_map(|pos| le_u64(data, *pos))
+            .filter(|table| *table as usize > dir_table && *table != u64::MAX)
+            .min()
+            .unwrap_or(data.len() as u64) as usize;
+        let (inodes, inode_blocks) = squashfs_metadata(data, inode_table, dir_table);
+        let (dirs, dir_blocks) = squashfs_metadata(data, dir_table, dir_end);
+
+        // fragment entries sit in metadata blocks listed by an index at the table position
+        let mut fragments = Vec::new();
+        if fragment_table != u64::MAX {
+            let fragment_table = usize::try_from(fragment_table).ok()?;
+            let index_entries = fragment_count
+                .checked_mul(16)?
+                .checked_add(SQUASHFS_METADATA_SIZE - 1)?
+                / SQUASHFS_METADATA_SIZE;
+            // every index entry could name the same block, so they share the limit
+            for idx in 0..index_entries {
+                if fragments.len() >= MAX_EXTRACTED {
+                    break;
+                }
+                let pos = fragment_table.checked_add(idx.checked_mul(8)?)?;
+                let block = le_u64(data, pos)? as usize;
+                fragments.extend(squashfs_metadata_block(data, block)?.0);
+            }
+        }
+        Some(Squashfs {
+            data,
+            block_size,
+            inodes,
+            inode_blocks,
+            dirs,
+            dir_blocks,
+            fragments,
+        })
+    }
+
+    fn inode(&self, reference: u64) -> Option<usize> {
+        let block = self.inode_blocks.get(&(reference >> 16))?;
+        Some(block + (reference & 0xffff) as usize)
+    }
+
+    fn data_block(&self, pos: usize, size: u32, limit: usize) -> Option<Vec<u8>> {
+        let len = (size & !SQUASHFS_BLOCK_UNCOMPRESSED) as usize;
+        let raw = self.data.get(pos..pos.checked_add(len)?)?;
+        if size & SQUASHFS_BLOCK_UNCOMPRESSED != 0 {
+            return Some(raw[..std::cmp::min(raw.len(), limit)].to_vec());
+        }
+        Some(inflate(ZlibDecoder::new(raw), limit))
+    }
+
+    fn file_content(&self, inode: usize, extended: bool, limit: usize) -> Option<Vec<u8>> {
+        let at = inode + 16;
+        let (start, size, fragment, frag_offset, sizes_at) = if extended {
+            (
+                le_u64(&self.inodes, at)? as usize,
+                le_u64(&self.inodes, at + 8)? as usize,
+                le_u32(&self.inodes, at + 28)?,
+                le_u32(&self.inodes, at + 32)? as usize,
+                at + 40,
+            )
+        } else {
+            (
+                le_u32(&self.inodes, at)? as usize,
+                le_u32(&self.inodes, at + 12)? as usize,
+                le_u32(&self.inodes, at + 4)?,
+                le_u32(&self.inodes, at + 8)? as usize,
+                at + 16,
+            )
+        };
+        let size = std::cmp::min(size, limit);
+        // the tail of the file lives in a shared fragment block unless there is none
+        let blocks = if fragment == SQUASHFS_NO_FRAGMENT {
+            (size + self.block_size - 1) / self.block_size
+        } else {
+            size / self.block_size
+        };
+
+        let mut content = Vec::with_capacity(size);
+        let mut pos = start;
+        for idx in 0..blocks {
+            let block_size = le_u32(&self.inodes, sizes_at + idx * 4)?;
+            if block_size == 0 {
+                // sparse block
+                content.resize(content.len() + self.block_size, 0);
+                continue;
+            }
+            content.extend(self.data_block(pos, block_size, self.block_size)?);
+            pos = pos.checked_add((block_size & !SQUASHFS_BLOCK_UNCOMPRESSED) as usize)?;
+        }
+        if fragment != SQUASHFS_NO_FRAGMENT {
+            let entry = fragment as usize * 16;
+            let frag_start = le_u64(&self.fragments, entry)? as usize;
+            let frag_size = le_u32(&self.fragments, entry + 8)?;
+            let block = self.data_block(frag_start, frag_size, self.block_size)?;
+            let tail = size - content.len().min(size);
+            content.extend_from_slice(block.get(frag_offset..frag_offset + tail)?);
+        }
+        content.truncate(size);
+        Some(content)
+    }
+
+    // regular files below the directory inode at `inode`, depth first
+    fn walk(&self, inode: usize, path: &str, depth: usize, files: &mut Vec<(String, usize, bool)>) {
+        if depth > 64 {
+            return;
+        }
+        let at = inode + 16;
+        let listing = if le_u16(&self.inodes, inode) == Some(SQUASHFS_EXT_DIR) {
+            le_u32(&self.inodes, at + 8)
+                .zip(le_u16(&self.inodes, at + 18))
+                .zip(le_u32(&self.inodes, at + 4))
+        } else {
+            le_u32(&self.inodes, at)
+                .zip(le_u16(&self.inodes, at + 10))
+                .zip(le_u16(&self.inodes, at + 8).map(u32::from))
+        };
+        let Some(((start_block, offset), size)) = listing else {
+            return;
+        };
+        let Some(block) = self.dir_blocks.get(&(start_block as u64)) else {
+            return;
+        };
+        let mut pos = block + offset as usize;
+        // the size counts the implicit "." and ".." entries
+        let end = pos + (size as usize).saturating_sub(3);
+        while pos + 12 <= end {
+            let (Some(count), Some(inode_block)) =
+                (le_u32(&self.dirs, pos), le_u32(&self.dirs, pos + 4))
+            else {
+                return;
+            };
+            pos += 12;
+            for _ in 0..=count {
+                let (Some(inode_offset), Some(name_size)) =
+                    (le_u16(&self.dirs, pos), le_u16(&self.dirs, pos + 6))
+                else {
+                    return;
+                };
+                let name_start = pos + 8;
+                let Some(name) = self
+                    .dirs
+                    .get(name_start..name_start + name_size as usize + 1)
+                else {
+                    return;
+                };
+                pos = name_start + name.len();
+                let child_path = format!("{}/{}", path, String::from_utf8_lossy(name));
+                let reference = (inode_block as u64) << 16 | inode_offset as u64;
+                let Some(child) = self.inode(reference) else {
+                    continue;
+                };
+                match le_u16(&self.inodes, child) {
+                    Some(SQUASHFS_DIR) | Some(SQUASHFS_EXT_DIR) => {
+                        self.walk(child, &child_path, depth + 1, files)
+                    }
+                    Some(SQUASHFS_FILE) => files.push((child_path, child, false)),
+                    Some(SQUASHFS_EXT_FILE) => files.push((child_path, child, true)),
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+fn guid(bytes: &[u8]) -> String {
+    format!(
+        "{:08X}-{:04X}-{:04X}-{}",
+        u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+        u16::from_le_bytes([bytes[4], bytes[5]]),
+        u16::from_le_bytes([bytes[6], bytes[7]]),
+        bytes[8..16]
+            .iter()
+            .enumerate()
+            .map(|(idx, b)| if idx == 2 {
+                format!("-{b:02X}")
+            } else {
+                format!("{b:02X}")
+            })
+            .collect::<String>()
+    )
+}
+
+fn section_name(section_type: u8) -> String {
+    match section_type {
+        0x10 => "pe32".to_string(),
+        0x11 => "pic".to_string(),
+        0x12 => "te".to_string(),
+        0x13 => "dxe-depex".to_string(),
+        0x14 => "version".to_string(),
+        0x18 => "freeform".to_string(),
+        0x19 => "raw".to_string(),
+        0x1b => "pei-depex".to_string(),
+        other => format!("section-{:02x}", other),
+    }
+}
+
+// leaf sections of an FFS file, and its name from the user interface section. compressed
+// sections (Tiano, LZMA) have no decoder here and are skipped.
+fn fv_sections<'d>(body: &'d [u8], name: &mut Option<String>, sections: &mut Vec<(u8, &'d [u8])>) {
+    let mut pos = 0;
+    while pos + 4 <= body.len() {
+        let (Some(mut size), section_type) = (le_u24(body, pos), body[pos + 3]) else {
+            break;
+        };
+        let mut header = 4;
+        if size == 0xff_ffff {
+            size = le_u32(body, pos + 4).unwrap_or(0) as usize;
+            header = 8;
+        }
+        if size < header || pos + size > body.len() {
+            break;
+        }
+        let content = &body[pos + header..pos + size];
+        match section_type {
+            SECTION_COMPRESSION => {
+                if content.get(4) == Some(&0) {
+                    fv_sections(&content[5..], name, sections);
+                }
+            }
+            SECTION_GUID_DEFINED => {
+                let data_offset = le_u16(content, 16).unwrap_or(0) as usize;
+                let attributes = le_u16(content, 18).unwrap_or(GUID_PROCESSING_REQUIRED);
+                if attributes & GUID_PROCESSING_REQUIRED == 0 && data_offset <= size {
+                    fv_sections(&body[pos + data_offset..pos + size], name, sections);
+                }
+            }
+            SECTION_USER_INTERFACE => {
+                let units: Vec<u16> = content
+                    .chunks_exact(2)
+                    .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+                    .take_while(|unit| *unit != 0)
+                    .collect();
+                *name = Some(String::from_utf16_lossy(&units));
+            }
+            _ => sections.push((section_type, content)),
+        }
+        pos = align(pos + size, 4);
+    }
+}
+
+struct Unpacker {
+    segments: Vec<Segment>,
+    extracted: usize,
+}
+
+impl Unpacker {
+    fn room(&self) -> usize {
+        MAX_EXTRACTED.saturating_sub(self.extracted)
+    }
+
+    // a file pulled out of a container, unpacked further when it is one itself
+    fn push(&mut self, location: String, data: &[u8], depth: usize) {
+        if depth < MAX_DEPTH {
+            if let Some(kind) = kind_at_start(data) {
+                self.unpack(kind, data, &format!("{} > ", location), depth + 1);
+                return;
+            }
+        }
+        let data = &data[..std::cmp::min(data.len(), self.room())];
+        self.extracted += data.len();
+        self.segments.push(Segment {
+            location,
+            data: data.to_vec(),
+        });
+    }
+
+    fn unpack(&mut self, kind: Kind, data: &[u8], prefix: &str, depth: usize) {
+        match kind {
+            Kind::Squashfs => self.unpack_squashfs(data, prefix, depth),
+            Kind::Cpio => self.unpack_cpio(data, prefix, depth),
+            Kind::GzipCpio => {
+                let cpio = inflate(GzDecoder::new(data), self.room());
+                self.unpack_cpio(&cpio, prefix, depth);
+            }
+            Kind::FirmwareVolume => self.unpack_fv(data, prefix, depth),
+        }
+    }
+
+    fn unpack_cpio(&mut self, data: &[u8], prefix: &str, depth: usize) {
+        let (files, end) = cpio_entries(data);
+        for (path, content) in files {
+            self.push(format!("{}cpio:{}", prefix, path), content, depth);
+        }
+        // initramfs images are often several archives back to back, padded with zeroes
+        let rest = &data[end..];
+        let next = rest.iter().position(|b| *b != 0).unwrap_or(rest.len());
+        match kind_at_start(&rest[next..]) {
+            Some(kind @ (Kind::Cpio | Kind::GzipCpio)) if depth < MAX_DEPTH => {
+                self.unpack(kind, &rest[next..], prefix, depth + 1)
+            }
+            _ => {}
+        }
+    }
+
+    fn unpack_squashfs(&mut self, data: &[u8], prefix: &str, depth: usize) {
+        let Some(fs) = Squashfs::open(data) else {
+            return;
+        };
+        let Some(root) = le_u64(data, 32).and_then(|root| fs.inode(root)) else {
+            return;
+        };
+        let mut files = Vec::new();
+        fs.walk(root, "", 0, &mut files);
+        for (path, inode, extended) in files {
+            if self.room() == 0 {
+                break;
+            }
+            if let Some(content) = fs.file_content(inode, extended, self.room()) {
+                self.push(format!("{}squashfs:{}", prefix, path), &content, depth);
+            }
+        }
+    }
+
+    fn unpack_fv(&mut self, data: &[u8], prefix: &str, depth: usize) {
+        let fv_len = std::cmp::min(le_u64(data, 32).unwrap_or(0) as usize, data.len());
+        let header_len = le_u16(data, 48).unwrap_or(0) as usize;
+        let ext_header = le_u16(data, 52).unwrap_or(0) as usize;
+        let mut pos = match ext_header {
+            0 => header_len,
+            ext => align(ext + le_u32(data, ext + 16).unwrap_or(0) as usize, 8),
+        };
+        while pos + 24 <= fv_len {
+            // the rest of the volume is free space
+            if data[pos..pos + 24].iter().all(|b| *b == 0xff) {
+                break;
+            }
+            let file_type = data[pos + 18];
+            let (size, header) = if data[pos + 19] & FFS_LARGE_FILE != 0 {
+                (le_u64(data, pos + 24).unwrap_or(0) as usize, 32)
+            } else {
+                (le_u24(data, pos + 20).unwrap_or(0), 24)
+            };
+            if size < header || pos + size > fv_len {
+                break;
+            }
+            let body = &data[pos + header..pos + size];
+            let mut name = None;
+            let mut sections = Vec::new();
+            match file_type {
+                FFS_PAD => {}
+                FFS_RAW => sections.push((0x19, body)),
+                _ => fv_sections(body, &mut name, &mut sections),
+            }
+            let name = name.unwrap_or_else(|| guid(&data[pos..pos + 16]));
+            for (section_type, content) in sections {
+                let location = format!("{}fv:{}/{}", prefix, name, section_name(section_type));
+                if section_type == SECTION_FV_IMAGE && depth < MAX_DEPTH {
+                    self.unpack_fv(content, &format!("{} > ", location), depth + 1);
+                } else {
+                    self.push(location, content, depth);
+                }
+            }
+            pos = align(pos + size, 8);
+        }
+    }
+}
+
+// the files of squashfs, cpio (plain or gzipped initramfs) and UEFI firmware volume images,
+// following containers inside them. a dump with the containers somewhere inside is kept whole
+// as well, since the parts between them (bootloaders, kernels) are searched raw.
+pub fn extract(data: &[u8]) -> Vec<Segment> {
+    let mut unpacker = Unpacker {
+        segments: Vec::new(),
+        extracted: 0,
+    };
+    if let Some(kind) = kind_at_start(data) {
+        unpacker.unpack(kind, data, "", 0);
+        return unpacker.segments;
+    }
+    let embedded = embedded_containers(data);
+    if embedded.is_empty() {
+        return Vec::new();
+    }
+    unpacker.segments.push(Segment {
+        location: "firmware image".to_string(),
+        data: data.to_vec(),
+    });
+    for (start, kind) in embedded {
+        unpacker.unpack(kind, &data[start..], &format!("0x{:x} ", start), 0);
+    }
+    unpacker.segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn squashfs_superblock(block_size: u32) -> Vec<u8> {
+        let mut sb = vec![0; 96];
+        sb[..4].copy_from_slice(SQUASHFS_MAGIC);
+        sb[12..16].copy_from_slice(&block_size.to_le_bytes());
+        sb[20..22].copy_from_slice(&SQUASHFS_ZLIB.to_le_bytes());
+        // no lookup tables
+        for pos in [48, 56, 80, 88] {
+            sb[pos..pos + 8].copy_from_slice(&u64::MAX.to_le_bytes());
+        }
+        sb
+    }
+
+    #[test]
+    fn squashfs_truncated_superblock_is_rejected() {
+        assert!(Squashfs::open(SQUASHFS_MAGIC).is_none());
+        assert!(Squashfs::open(&squashfs_superblock(128 * 1024)[..70]).is_none());
+    }
+
+    #[test]
+    fn squashfs_hostile_block_size_is_rejected() {
+        for block_size in [0, 3000, 1 << 31] {
+            assert!(Squashfs::open(&squashfs_superblock(block_size)).is_none());
+        }
+    }
+
+    #[test]
+    fn squashfs_tables_past_the_end_are_empty() {
+        let mut sb = squashfs_superblock(128 * 1024);
+        sb[64..72].copy_from_slice(&(u64::MAX - 1).to_le_bytes()); // inode table
+        sb[72..80].copy_from_slice(&u64::MAX.to_le_bytes()); // directory table
+        let fs = Squashfs::open(&sb).unwrap();
+        assert!(fs.inodes.is_empty() && fs.dirs.is_empty());
+    }
+
+    #[test]
+    fn squashfs_fragment_index_past_the_end_is_rejected() {
+        let mut sb = squashfs_superblock(128 * 1024);
+        sb[16..20].copy_from_slice(&u32::MAX.to_le_bytes()); // fragment count
+        sb[80..88].copy_from_slice(&(u64::MAX - 4).to_le_bytes()); // fragment table
+        assert!(Squashfs::open(&sb).is_none());
+    }
+}
@@ -0,0 +1,120 @@
+use super::Segment;
+use mailparse::{parse_mail, MailHeaderMap, ParsedMail};
+use std::path::Path;
+
+const EML_EXTENSIONS: [&str; 2] = ["eml", "msg"];
+const MBOX_EXTENSIONS: [&str; 3] = ["mbox", "mbx", "mbs"];
+
+pub fn is_mbox(path: &Path, data: &[u8]) -> bool {
+    has_extension(path, &MBOX_EXTENSIONS) || data.starts_with(b"From ")
+}
+
+pub fn is_eml(path: &Path, data: &[u8]) -> bool {
+    // .msg is also Outlook's OLE format, which won't parse as MIME
+    has_extension(path, &EML_EXTENSIONS) && !data.starts_with(&[0xd0, 0xcf, 0x11, 0xe0])
+}
+
+fn has_extension(path: &Path, extensions: &[&str]) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| extensions.iter().any(|e| e.eq_ignore_ascii_case(ext)))
+        .unwrap_or(false)
+}
+
+pub fn extract_mbox(data: &[u8]) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    for (msg_idx, message) in split_mbox(data).into_iter().enumerate() {
+        extract_message(message, &format!("message {}", msg_idx + 1), &mut segments);
+    }
+    segments
+}
+
+pub fn extract_eml(data: &[u8]) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    extract_message(data, "message 1", &mut segments);
+    segments
+}
+
+// messages in an mbox are separated by "From " lines at the start of a line
+fn split_mbox(data: &[u8]) -> Vec<&[u8]> {
+    let mut starts = Vec::new();
+    let mut line_start = 0;
+    while line_start < data.len() {
+        if data[line_start..].starts_with(b"From ") {
+            starts.push(line_start);
+        }
+        match data[line_start..].iter().position(|b| *b == b'\n') {
+            Some(pos) => line_start += pos + 1,
+            None => break,
+        }
+    }
+
+    let mut messages = Vec::with_capacity(starts.len());
+    for (idx, start) in starts.iter().enumerate() {
+        let end = starts.get(idx + 1).copied().unwrap_or(data.len());
+        // skip the "From " separator line itself
+        let msg = &data[*start..end];
+        let body_start = msg
+            .iter()
+            .position(|b| *b == b'\n')
+            .map(|p| p + 1)
+            .unwrap_or(msg.len());
+        messages.push(&msg[body_start..]);
+    }
+    messages
+}
+
+fn extract_message(raw: &[u8], msg_location: &str, segments: &mut Vec<Segment>) {
+    let mail = match parse_mail(raw) {
+        Ok(mail) => mail,
+        Err(_) => return,
+    };
+
+    let headers = mail
+        .headers
+        .iter()
+        .map(|h| format!("{}: {}", h.get_key(), h.get_value()))
+        .collect::<Vec<_>>()
+        .join("\n");
+    segments.push(Segment {
+        location: format!("{}, headers", msg_location),
+        data: headers.into_bytes(),
+    });
+
+    extract_parts(&mail, msg_location, "1", segments);
+}
+
+fn extract_parts(
+    part: &ParsedMail,
+    msg_location: &str,
+    part_id: &str,
+    segments: &mut Vec<Segment>,
+) {
+    if !part.subparts.is_empty() {
+        for (idx, subpart) in part.subparts.iter().enumerate() {
+            let sub_id = format!("{}.{}", part_id, idx + 1);
+            extract_parts(subpart, msg_location, &sub_id, segments);
+        }
+        return;
+    }
+
+    // leaf part, decode base64/quoted-printable bodies and attachments alike
+    if let Ok(body) = part.get_body_raw() {
+        let mut desc = part.ctype.mimetype.clone();
+        let filename = part
+            .get_content_disposition()
+            .params
+            .get("filename")
+            .cloned()
+            .or_else(|| part.ctype.params.get("name").cloned())
+            .or_else(|| part.headers.get_first_value("Content-Description"));
+        if let Some(filename) = filename {
+            desc.push_str(&format!(", {}", filename));
+        }
+
+        segments.push(Segment {
+            location: format!("{}, part {} ({})", msg_location, part_id, desc),
+            data: body,
+        });
+    }
+}
@@ -0,0 +1,200 @@
+use super::Segment;
+use goblin::pe::options::ParseOptions;
+use goblin::pe::utils::find_offset;
+use goblin::pe::PE;
+use std::io::Cursor;
+
+const RT_VERSION: u32 = 16;
+
+// every metadata field becomes its own segment, named after the field
+pub fn extract(data: &[u8]) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    extract_exif(data, &mut segments);
+    extract_pe_version_info(data, &mut segments);
+    extract_id3(data, &mut segments);
+    segments
+}
+
+fn extract_exif(data: &[u8], segments: &mut Vec<Segment>) {
+    let exif = match exif::Reader::new().read_from_container(&mut Cursor::new(data)) {
+        Ok(exif) => exif,
+        Err(_) => return,
+    };
+
+    for field in exif.fields() {
+        segments.push(Segment {
+            location: format!("EXIF {}", field.tag),
+            data: field
+                .display_value()
+                .with_unit(&exif)
+                .to_string()
+                .into_bytes(),
+        });
+    }
+}
+
+fn extract_id3(data: &[u8], segments: &mut Vec<Segment>) {
+    let tag = match id3::v1v2::read_from(Cursor::new(data)) {
+        Ok(tag) => tag,
+        Err(_) => return,
+    };
+
+    for frame in tag.frames() {
+        segments.push(Segment {
+            location: format!("ID3 {} ({})", frame.id(), frame.name()),
+            data: frame.content().to_string().into_bytes(),
+        });
+    }
+}
+
+fn extract_pe_version_info(data: &[u8], segments: &mut Vec<Segment>) {
+    if !data.starts_with(b"MZ") {
+        return;
+    }
+    if let Some(strings) = pe_version_strings(data) {
+        for (key, value) in strings {
+            segments.push(Segment {
+                location: format!("PE {}", key),
+                data: value.into_bytes(),
+            });
+        }
+    }
+}
+
+fn pe_version_strings(data: &[u8]) -> Option<Vec<(String, String)>> {
+    let pe = PE::parse(data).ok()?;
+    let optional_header = pe.header.optional_header?;
+    let resource_table = optional_header.data_directories.get_resource_table()?;
+    let file_alignment = optional_header.windows_fields.file_alignment;
+    let opts = ParseOptions::default();
+
+    let rsrc_base = find_offset(
+        resource_table.virtual_address as usize,
+        &pe.sections,
+        file_alignment,
+        &opts,
+    )?;
+
+    // resource tree is type -> name -> language -> data entry
+    let mut entry = find_resource_entry(data, rsrc_base, 0, Some(RT_VERSION))?;
+    for _level in 0..2 {
+        if entry & 0x8000_0000 == 0 {
+            break;
+        }
+        entry = find_resource_entry(data, rsrc_base, (entry & 0x7fff_ffff) as usize, None)?;
+    }
+    if entry & 0x8000_0000 != 0 {
+        return None;
+    }
+
+    let data_entry = rsrc_base + entry as usize;
+    let blob_rva = read_u32(data, data_entry)? as usize;
+    let blob_size = read_u32(data, data_entry + 4)? as usize;
+    let blob_offset = find_offset(blob_rva, &pe.sections, file_alignment, &opts)?;
+    let blob = data.get(blob_offset..blob_offset + blob_size)?;
+
+    let mut strings = Vec::new();
+    walk_version_blocks(blob, 0, blob.len(), 0, false, &mut strings);
+    Some(strings)
+}
+
+// returns the OffsetToData of the matching entry (or the first one if id is None)
+fn find_resource_entry(data: &[u8], rsrc_base: usize, dir: usize, id: Option<u32>) -> Option<u32> {
+    let dir_offset = rsrc_base + dir;
+    let num_named = read_u16(data, dir_offset + 12)? as usize;
+    let num_ids = read_u16(data, dir_offset + 14)? as usize;
+
+    for idx in 0..(num_named + num_ids) {
+        let entry_offset = dir_offset + 16 + idx * 8;
+        let name = read_u32(data, entry_offset)?;
+        let offset_to_data = read_u32(data, entry_offset + 4)?;
+        match id {
+            Some(id) if name == id => return Some(offset_to_data),
+            Some(_) => {}
+            None => return Some(offset_to_data),
+        }
+    }
+    None
+}
+
+// VS_VERSIONINFO -> StringFileInfo -> StringTable -> String, all sharing the same block layout
+fn walk_version_blocks(
+    blob: &[u8],
+    start: usize,
+    end: usize,
+    depth: u32,
+    in_string_info: bool,
+    strings: &mut Vec<(String, String)>,
+) {
+    let mut pos = start;
+    while pos + 6 <= end && depth < 4 {
+        let length = match read_u16(blob, pos) {
+            Some(0) | None => return,
+            Some(length) => length as usize,
+        };
+        let value_length = read_u16(blob, pos + 2).unwrap_or(0) as usize;
+        let is_text = read_u16(blob, pos + 4) == Some(1);
+        let block_end = std::cmp::min(pos + length, end);
+
+        let (key, key_end) = read_utf16z(blob, pos + 6, block_end);
+        let value_start = align4(key_end);
+        let value_bytes = if is_text {
+            value_length * 2
+        } else {
+            value_length
+        };
+
+        if in_string_info && depth == 3 {
+            let (value, _) = read_utf16z(
+                blob,
+                value_start,
+                std::cmp::min(value_start + value_bytes, block_end),
+            );
+            strings.push((key, value));
+        } else {
+            let children_start = align4(value_start + value_bytes);
+            let is_string_info = in_string_info || (depth == 1 && key == "StringFileInfo");
+            if depth == 0 || is_string_info {
+                walk_version_blocks(
+                    blob,
+                    children_start,
+                    block_end,
+                    depth + 1,
+                    is_string_info,
+                    strings,
+                );
+            }
+        }
+
+        pos = align4(pos + length);
+    }
+}
+
+fn align4(val: usize) -> usize {
+    (val + 3) & !3
+}
+
+// reads a NUL terminated UTF-16LE string, returning it and the offset just past the terminator
+fn read_utf16z(blob: &[u8], start: usize, end: usize) -> (String, usize) {
+    let mut units = Vec::new();
+    let mut pos = start;
+    while pos + 2 <= end {
+        let unit = u16::from_le_bytes([blob[pos], blob[pos + 1]]);
+        pos += 2;
+        if unit == 0 {
+            break;
+        }
+        units.push(unit);
+    }
+    (String::from_utf16_lossy(&units), pos)
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    data.get(offset..offset + 2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    data.get(offset..offset + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
@@ -0,0 +1,81 @@
+use std::path::Path;
+
+mod android;
+mod evtx;
+mod firmware;
+mod mail;
+pub mod metadata;
+mod pdf;
+mod registry;
+mod vdisk;
+
+// a chunk of decoded content pulled out of a container file, searched instead of the raw bytes
+pub struct Segment {
+    pub location: String,
+    pub data: Vec<u8>,
+}
+
+#[derive(Clone, Default)]
+pub struct HandlerOptions {
+    pub pdf_raw_streams: bool,
+}
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum ContainerEnum {
+    Pdf,
+    Mbox,
+    Eml,
+    Disk(vdisk::DiskFormat), // VHD, VMDK and qcow2 virtual disks
+    Apk,
+    AndroidBackup,
+    Firmware, // squashfs, cpio/initramfs and UEFI firmware volumes
+    RegistryHive,
+    EventLog, // EVTX
+}
+
+impl ContainerEnum {
+    pub fn detect(path: &Path, data: &[u8]) -> Option<ContainerEnum> {
+        if let Some(format) = vdisk::detect(data) {
+            return Some(ContainerEnum::Disk(format));
+        }
+        if android::is_backup(data) {
+            return Some(ContainerEnum::AndroidBackup);
+        }
+        if android::is_apk(data) {
+            return Some(ContainerEnum::Apk);
+        }
+        if firmware::is_firmware(path, data) {
+            return Some(ContainerEnum::Firmware);
+        }
+        if evtx::is_evtx(data) {
+            return Some(ContainerEnum::EventLog);
+        }
+        if registry::is_hive(data) {
+            return Some(ContainerEnum::RegistryHive);
+        }
+        if pdf::is_pdf(data) {
+            return Some(ContainerEnum::Pdf);
+        }
+        if mail::is_mbox(path, data) {
+            return Some(ContainerEnum::Mbox);
+        }
+        if mail::is_eml(path, data) {
+            return Some(ContainerEnum::Eml);
+        }
+        None
+    }
+
+    pub fn extract(&self, data: &[u8], options: &HandlerOptions) -> Vec<Segment> {
+        match self {
+            ContainerEnum::Pdf => pdf::extract(data, options.pdf_raw_streams),
+            ContainerEnum::Mbox => mail::extract_mbox(data),
+            ContainerEnum::Eml => mail::extract_eml(data),
+            ContainerEnum::Disk(format) => vdisk::extract(*format, data),
+            ContainerEnum::Apk => android::extract_apk(data),
+            ContainerEnum::AndroidBackup => android::extract_backup(data),
+            ContainerEnum::Firmware => firmware::extract(data),
+            ContainerEnum::RegistryHive => registry::extract(data),
+            ContainerEnum::EventLog => evtx::extract(data),
+        }
+    }
+}
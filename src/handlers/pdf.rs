@@ -0,0 +1,41 @@
+use super::Segment;
+use lopdf::{Document, Object};
+
+pub fn is_pdf(data: &[u8]) -> bool {
+    // the header is allowed to be preceded by junk within the first 1024 bytes
+    let head = &data[..std::cmp::min(data.len(), 1024)];
+    head.windows(5).any(|w| w == b"%PDF-")
+}
+
+pub fn extract(data: &[u8], raw_streams: bool) -> Vec<Segment> {
+    let doc = match Document::load_mem(data) {
+        Ok(doc) => doc,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut segments = Vec::new();
+    for page_num in doc.get_pages().keys() {
+        if let Ok(text) = doc.extract_text(&[*page_num]) {
+            segments.push(Segment {
+                location: format!("page {}", page_num),
+                data: text.into_bytes(),
+            });
+        }
+    }
+
+    if raw_streams {
+        for ((obj_num, generation), obj) in doc.objects.iter() {
+            if let Object::Stream(stream) = obj {
+                let content = stream
+                    .decompressed_content()
+                    .unwrap_or_else(|_| stream.content.clone());
+                segments.push(Segment {
+                    location: format!("stream {} {} R", obj_num, generation),
+                    data: content,
+                });
+            }
+        }
+    }
+
+    segments
+}
@@ -0,0 +1,273 @@
+use super::Segment;
+use std::collections::HashSet;
+
+const REGF_MAGIC: &[u8] = b"regf";
+const HBIN_MAGIC: &[u8] = b"hbin";
+// cell offsets count from the first hive bin, right after the base block
+const BASE_BLOCK: usize = 4096;
+const ROOT_CELL_OFFSET: usize = 0x24;
+const MAX_DEPTH: usize = 512;
+
+const KEY_COMP_NAME: u16 = 0x20;
+const VALUE_COMP_NAME: u16 = 0x01;
+const DATA_INLINE: u32 = 0x8000_0000;
+
+const REG_SZ: u32 = 1;
+const REG_EXPAND_SZ: u32 = 2;
+const REG_BINARY: u32 = 3;
+const REG_DWORD: u32 = 4;
+const REG_DWORD_BIG_ENDIAN: u32 = 5;
+const REG_LINK: u32 = 6;
+const REG_MULTI_SZ: u32 = 7;
+const REG_QWORD: u32 = 11;
+
+fn le_u16(data: &[u8], pos: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(data.get(pos..pos + 2)?.try_into().ok()?))
+}
+
+fn le_u32(data: &[u8], pos: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(data.get(pos..pos + 4)?.try_into().ok()?))
+}
+
+pub fn is_hive(data: &[u8]) -> bool {
+    data.starts_with(REGF_MAGIC) && data.get(BASE_BLOCK..BASE_BLOCK + 4) == Some(HBIN_MAGIC)
+}
+
+// the contents of a cell, its size comes first and is negative while it's in use
+fn cell(data: &[u8], offset: u32) -> Option<&[u8]> {
+    let pos = BASE_BLOCK.checked_add(offset as usize)?;
+    let size = le_u32(data, pos)? as i32;
+    let size = size.unsigned_abs() as usize;
+    data.get(pos + 4..pos.checked_add(size)?)
+}
+
+fn utf16(bytes: &[u8]) -> String {
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+// names are stored as Latin-1 when flagged as compressed, as UTF-16 otherwise
+fn name(bytes: &[u8], compressed: bool) -> String {
+    match compressed {
+        true => bytes.iter().map(|b| *b as char).collect(),
+        false => utf16(bytes),
+    }
+}
+
+fn type_name(kind: u32) -> String {
+    let name = match kind {
+        0 => "REG_NONE",
+        REG_SZ => "REG_SZ",
+        REG_EXPAND_SZ => "REG_EXPAND_SZ",
+        REG_BINARY => "REG_BINARY",
+        REG_DWORD => "REG_DWORD",
+        REG_DWORD_BIG_ENDIAN => "REG_DWORD_BIG_ENDIAN",
+        REG_LINK => "REG_LINK",
+        REG_MULTI_SZ => "REG_MULTI_SZ",
+        REG_QWORD => "REG_QWORD",
+        kind => return format!("type 0x{:x}", kind),
+    };
+    name.to_string()
+}
+
+// the raw bytes of a value, big ones are split over cells listed by a "db" record
+fn value_data(data: &[u8], vk: &[u8]) -> Option<Vec<u8>> {
+    let size = le_u32(vk, 4)?;
+    if size & DATA_INLINE != 0 {
+        let size = std::cmp::min((size & !DATA_INLINE) as usize, 4);
+        return Some(vk.get(8..8 + size)?.to_vec());
+    }
+    // a value's segments are cells of the hive, it can't hold more than the hive does
+    let size = std::cmp::min(size as usize, data.len());
+    let content = cell(data, le_u32(vk, 8)?)?;
+    if content.starts_with(b"db") && size > content.len() {
+        let count = le_u16(content, 2)? as usize;
+        let list = cell(data, le_u32(content, 4)?)?;
+        let mut bytes = Vec::with_capacity(size);
+        for idx in 0..count {
+            let segment = cell(data, le_u32(list, idx * 4)?)?;
+            // cells are padded to 8 bytes, only the value's own bytes are kept
+            let take = std::cmp::min(segment.len(), size - bytes.len());
+            bytes.extend_from_slice(&segment[..take]);
+        }
+        return Some(bytes);
+    }
+    Some(content.get(..size)?.to_vec())
+}
+
+// strings and numbers as text, binary data and anything else as the stored bytes
+fn render(kind: u32, bytes: &[u8]) -> Vec<u8> {
+    match kind {
+        REG_SZ | REG_EXPAND_SZ | REG_LINK => {
+            utf16(bytes).trim_end_matches('\0').to_string().into_bytes()
+        }
+        REG_MULTI_SZ => utf16(bytes)
+            .split('\0')
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<&str>>()
+            .join("\n")
+            .into_bytes(),
+        REG_DWORD | REG_DWORD_BIG_ENDIAN if bytes.len() == 4 => {
+            let array = [bytes[0], bytes[1], bytes[2], bytes[3]];
+            let value = match kind {
+                REG_DWORD => u32::from_le_bytes(array),
+                _ => u32::from_be_bytes(array),
+            };
+            format!("0x{:08x} ({})", value, value).into_bytes()
+        }
+        REG_QWORD if bytes.len() == 8 => {
+            let value = u64::from_le_bytes(bytes.try_into().unwrap_or_default());
+            format!("0x{:016x} ({})", value, value).into_bytes()
+        }
+        _ => bytes.to_vec(),
+    }
+}
+
+// offsets of the subkeys' nk cells, index roots ("ri") list further lists
+fn subkeys(data: &[u8], list: u32, depth: usize, offsets: &mut Vec<u32>) {
+    let Some(list) = cell(data, list) else {
+        return;
+    };
+    let count = le_u16(list, 2).unwrap_or(0) as usize;
+    let (stride, nested) = match list.get(..2) {
+        Some(b"lf") | Some(b"lh") => (8, false),
+        Some(b"li") => (4, false),
+        Some(b"ri") => (4, true),
+        _ => return,
+    };
+    for idx in 0..count {
+        let Some(offset) = le_u32(list, 4 + idx * stride) else {
+            break;
+        };
+        if !nested {
+            offsets.push(offset);
+        } else if depth < MAX_DEPTH {
+            subkeys(data, offset, depth + 1, offsets);
+        }
+    }
+}
+
+struct HiveWalker<'a> {
+    data: &'a [u8],
+    visited: HashSet<u32>,
+    segments: Vec<Segment>,
+}
+
+impl HiveWalker<'_> {
+    fn key(&mut self, offset: u32, parent: &str, depth: usize) {
+        if depth > MAX_DEPTH || !self.visited.insert(offset) {
+            return;
+        }
+        let data = self.data;
+        let Some(nk) = cell(data, offset).filter(|nk| nk.starts_with(b"nk")) else {
+            return;
+        };
+        let flags = le_u16(nk, 2).unwrap_or(0);
+        let name_len = le_u16(nk, 0x48).unwrap_or(0) as usize;
+        let key_name = name(
+            nk.get(0x4c..0x4c + name_len).unwrap_or_default(),
+            flags & KEY_COMP_NAME != 0,
+        );
+        let path = match parent.is_empty() {
+            true => key_name.clone(),
+            false => format!("{}\\{}", parent, key_name),
+        };
+        self.segments.push(Segment {
+            location: path.clone(),
+            data: key_name.into_bytes(),
+        });
+
+        let value_count = le_u32(nk, 0x24).unwrap_or(0) as usize;
+        if let Some(values) = le_u32(nk, 0x28).and_then(|list| cell(data, list)) {
+            for idx in 0..std::cmp::min(value_count, values.len() / 4) {
+                let Some(vk) = le_u32(values, idx * 4)
+                    .and_then(|offset| cell(data, offset))
+                    .filter(|vk| vk.starts_with(b"vk"))
+                else {
+                    continue;
+                };
+                let name_len = le_u16(vk, 2).unwrap_or(0) as usize;
+                let kind = le_u32(vk, 0x0c).unwrap_or(0);
+                let value_name = name(
+                    vk.get(0x14..0x14 + name_len).unwrap_or_default(),
+                    le_u16(vk, 0x10).unwrap_or(0) & VALUE_COMP_NAME != 0,
+                );
+                let mut content = value_name.clone().into_bytes();
+                content.push(b'\n');
+                content.extend(render(kind, &value_data(data, vk).unwrap_or_default()));
+                let value_name = match value_name.is_empty() {
+                    true => "(Default)".to_string(),
+                    false => value_name,
+                };
+                self.segments.push(Segment {
+                    location: format!("{} [{}] {}", path, value_name, type_name(kind)),
+                    data: content,
+                });
+            }
+        }
+
+        let subkey_count = le_u32(nk, 0x14).unwrap_or(0);
+        if subkey_count == 0 {
+            return;
+        }
+        let mut offsets = Vec::new();
+        if let Some(list) = le_u32(nk, 0x1c) {
+            subkeys(data, list, 0, &mut offsets);
+        }
+        for offset in offsets {
+            self.key(offset, &path, depth + 1);
+        }
+    }
+}
+
+// every key as its name and every value as its name and data, found at the full key path.
+// value names and data share a segment, the name on the first line.
+pub fn extract(data: &[u8]) -> Vec<Segment> {
+    let Some(root) = le_u32(data, ROOT_CELL_OFFSET) else {
+        return Vec::new();
+    };
+    let mut walker = HiveWalker {
+        data,
+        visited: HashSet::new(),
+        segments: Vec::new(),
+    };
+    walker.key(root, "", 0);
+    walker.segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push_cell(hive: &mut Vec<u8>, body: &[u8]) -> u32 {
+        let offset = (hive.len() - BASE_BLOCK) as u32;
+        hive.extend((-(body.len() as i32 + 4)).to_le_bytes());
+        hive.extend(body);
+        offset
+    }
+
+    #[test]
+    fn huge_value_size_is_bounded_by_the_hive() {
+        let mut hive = vec![0; BASE_BLOCK];
+        let segment = push_cell(&mut hive, &[0x41; 1000]);
+        let list: Vec<u8> = std::iter::repeat(segment.to_le_bytes())
+            .take(8)
+            .flatten()
+            .collect();
+        let list = push_cell(&mut hive, &list);
+        let mut db = b"db".to_vec();
+        db.extend(8_u16.to_le_bytes());
+        db.extend(list.to_le_bytes());
+        let db = push_cell(&mut hive, &db);
+
+        let mut vk = b"vk\0\0".to_vec();
+        vk.extend(0x7fff_ffff_u32.to_le_bytes());
+        vk.extend(db.to_le_bytes());
+        let bytes = value_data(&hive, &vk).unwrap();
+        assert_eq!(bytes.len(), hive.len());
+        assert!(bytes.iter().all(|byte| *byte == 0x41));
+    }
+}
@@ -0,0 +1,313 @@
+use super::Segment;
+
+// data reassembled out of one image, the rest of a huge disk is left to the raw scan.
+// it is a copy of bytes the raw scan sees anyway, so only enough to join blocks back up
+const MAX_EXTRACTED: usize = 64 * 1024 * 1024;
+const SECTOR: u64 = 512;
+
+const VHD_COOKIE: &[u8] = b"conectix";
+const VHD_DYNAMIC: u32 = 3;
+const VHD_DIFFERENCING: u32 = 4;
+const VHD_UNUSED_BLOCK: u32 = 0xffff_ffff;
+const VMDK_MAGIC: &[u8] = b"KDMV";
+const VMDK_GD_AT_END: u64 = u64::MAX;
+// real images use 128 sectors per grain and 512 entries per table, anything far past it is junk
+const VMDK_MAX_GRAIN_SIZE: u64 = 1 << 16;
+const VMDK_MAX_GTES_PER_GT: u64 = 1 << 16;
+const QCOW2_MAGIC: &[u8] = b"QFI\xfb";
+const QCOW2_OFFSET_MASK: u64 = 0x00ff_ffff_ffff_fe00;
+const QCOW2_COMPRESSED: u64 = 1 << 62;
+const QCOW2_ZERO: u64 = 1;
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum DiskFormat {
+    Vhd,
+    Vmdk,
+    Qcow2,
+}
+
+// where a run of virtual disk bytes is stored in the image file
+struct Extent {
+    disk_offset: u64,
+    file_offset: u64,
+    len: u64,
+}
+
+fn be_u32(data: &[u8], pos: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(
+        data.get(pos..pos.checked_add(4)?)?.try_into().ok()?,
+    ))
+}
+
+fn be_u64(data: &[u8], pos: usize) -> Option<u64> {
+    Some(u64::from_be_bytes(
+        data.get(pos..pos.checked_add(8)?)?.try_into().ok()?,
+    ))
+}
+
+fn le_u32(data: &[u8], pos: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(
+        data.get(pos..pos.checked_add(4)?)?.try_into().ok()?,
+    ))
+}
+
+fn le_u64(data: &[u8], pos: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(
+        data.get(pos..pos.checked_add(8)?)?.try_into().ok()?,
+    ))
+}
+
+// fixed VHDs and flat VMDK extents are plain disk bytes already, the raw scan covers them
+pub fn detect(data: &[u8]) -> Option<DiskFormat> {
+    if data.starts_with(VMDK_MAGIC) {
+        return Some(DiskFormat::Vmdk);
+    }
+    if data.starts_with(QCOW2_MAGIC) {
+        return Some(DiskFormat::Qcow2);
+    }
+    // dynamic disks keep a copy of the footer at the start
+    if data.starts_with(VHD_COOKIE) {
+        let disk_type = be_u32(data, 60)?;
+        if disk_type == VHD_DYNAMIC || disk_type == VHD_DIFFERENCING {
+            return Some(DiskFormat::Vhd);
+        }
+    }
+    None
+}
+
+fn vhd_extents(data: &[u8]) -> Option<Vec<Extent>> {
+    let header = usize::try_from(be_u64(data, 16)?).ok()?;
+    if data.get(header..header.checked_add(8)?)? != b"cxsparse" {
+        return None;
+    }
+    let table = usize::try_from(be_u64(data, header.checked_add(16)?)?).ok()?;
+    let entries = be_u32(data, header.checked_add(28)?)? as usize;
+    let block_size = be_u32(data, header.checked_add(32)?)? as u64;
+    if block_size == 0 {
+        return None;
+    }
+    // each block starts with a bitmap of its sectors, padded to a whole sector
+    let bitmap_size = ((block_size / SECTOR / 8) + SECTOR - 1) / SECTOR * SECTOR;
+
+    let mut extents = Vec::new();
+    for idx in 0..entries {
+        let Some(sector) = table.checked_add(idx * 4).and_then(|pos| be_u32(data, pos)) else {
+            break;
+        };
+        if sector == VHD_UNUSED_BLOCK {
+            continue;
+        }
+        extents.push(Extent {
+            disk_offset: (idx as u64).checked_mul(block_size)?,
+            file_offset: sector as u64 * SECTOR + bitmap_size,
+            len: block_size,
+        });
+    }
+    Some(extents)
+}
+
+fn vmdk_extents(data: &[u8]) -> Option<Vec<Extent>> {
+    let capacity = le_u64(data, 12)?;
+    let grain_size = le_u64(data, 20)?;
+    let gtes_per_gt = le_u32(data, 44)? as u64;
+    let gd_offset = le_u64(data, 56)?;
+    let compression = u16::from_le_bytes(data.get(77..79)?.try_into().ok()?);
+    // stream-optimized images are compressed grain by grain, not supported yet
+    if gd_offset == VMDK_GD_AT_END
+        || compression != 0
+        || !(1..=VMDK_MAX_GRAIN_SIZE).contains(&grain_size)
+        || !(1..=VMDK_MAX_GTES_PER_GT).contains(&gtes_per_gt)
+    {
+        return None;
+    }
+    let grain_len = grain_size * SECTOR;
+    let gt_coverage = grain_size * gtes_per_gt;
+    let gd_entries = capacity.checked_add(gt_coverage - 1)? / gt_coverage;
+    let gd_pos = usize::try_from(gd_offset.checked_mul(SECTOR)?).ok()?;
+
+    let mut extents = Vec::new();
+    for gd_idx in 0..gd_entries {
+        let Some(gt_sector) = usize::try_from(gd_idx)
+            .ok()
+            .and_then(|gd_idx| gd_pos.checked_add(gd_idx.checked_mul(4)?))
+            .and_then(|pos| le_u32(data, pos))
+        else {
+            break;
+        };
+        if gt_sector == 0 {
+            continue;
+        }
+        let gt_pos = usize::try_from(gt_sector as u64 * SECTOR).ok()?;
+        for gt_idx in 0..gtes_per_gt {
+            let Some(grain_sector) = le_u32(data, gt_pos + gt_idx as usize * 4) else {
+                break;
+            };
+            // 0 is unallocated, 1 a grain of zeroes
+            if grain_sector <= 1 {
+                continue;
+            }
+            let grain = gd_idx.checked_mul(gtes_per_gt)?.checked_add(gt_idx)?;
+            extents.push(Extent {
+                disk_offset: grain.checked_mul(grain_len)?,
+                file_offset: grain_sector as u64 * SECTOR,
+                len: grain_len,
+            });
+        }
+    }
+    Some(extents)
+}
+
+fn qcow2_extents(data: &[u8]) -> Option<Vec<Extent>> {
+    let cluster_bits = be_u32(data, 20)?;
+    let crypt_method = be_u32(data, 32)?;
+    if crypt_method != 0 || !(9..=21).contains(&cluster_bits) {
+        return None;
+    }
+    let cluster_size = 1_u64 << cluster_bits;
+    let l2_entries = cluster_size / 8;
+    let l1_size = be_u32(data, 36)? as usize;
+    let l1_pos = usize::try_from(be_u64(data, 40)?).ok()?;
+
+    let mut extents = Vec::new();
+    for l1_idx in 0..l1_size {
+        let Some(l1_entry) = l1_pos
+            .checked_add(l1_idx * 8)
+            .and_then(|pos| be_u64(data, pos))
+        else {
+            break;
+        };
+        let l2_pos = (l1_entry & QCOW2_OFFSET_MASK) as usize;
+        if l2_pos == 0 {
+            continue;
+        }
+        for l2_idx in 0..l2_entries {
+            let Some(l2_entry) = be_u64(data, l2_pos + l2_idx as usize * 8) else {
+                break;
+            };
+            // compressed clusters would need inflating, zero clusters hold nothing
+            if l2_entry & QCOW2_COMPRESSED != 0 || l2_entry & QCOW2_ZERO != 0 {
+                continue;
+            }
+            let file_offset = l2_entry & QCOW2_OFFSET_MASK;
+            if file_offset == 0 {
+                continue;
+            }
+            let cluster = (l1_idx as u64)
+                .checked_mul(l2_entries)?
+                .checked_add(l2_idx)?;
+            extents.push(Extent {
+                disk_offset: cluster.checked_mul(cluster_size)?,
+                file_offset,
+                len: cluster_size,
+            });
+        }
+    }
+    Some(extents)
+}
+
+fn format_name(format: DiskFormat) -> &'static str {
+    match format {
+        DiskFormat::Vhd => "vhd",
+        DiskFormat::Vmdk => "vmdk",
+        DiskFormat::Qcow2 => "qcow2",
+    }
+}
+
+// the allocated parts of the virtual disk, one segment per run of consecutive disk offsets
+// so matches across block boundaries are found and the location gives the offset in the disk
+pub fn extract(format: DiskFormat, data: &[u8]) -> Vec<Segment> {
+    let extents = match format {
+        DiskFormat::Vhd => vhd_extents(data),
+        DiskFormat::Vmdk => vmdk_extents(data),
+        DiskFormat::Qcow2 => qcow2_extents(data),
+    };
+    let Some(mut extents) = extents else {
+        return Vec::new();
+    };
+    extents.sort_by_key(|extent| extent.disk_offset);
+
+    let mut segments: Vec<Segment> = Vec::new();
+    let mut run_start = 0;
+    let mut run_end = None;
+    let mut extracted = 0;
+    for extent in extents {
+        let start = extent.file_offset as usize;
+        let end = std::cmp::min(start.saturating_add(extent.len as usize), data.len());
+        if start >= end || extracted + (end - start) > MAX_EXTRACTED {
+            continue;
+        }
+        extracted += end - start;
+        match segments.last_mut() {
+            Some(segment) if run_end == Some(extent.disk_offset) => {
+                segment.data.extend_from_slice(&data[start..end]);
+            }
+            _ => {
+                run_start = extent.disk_offset;
+                segments.push(Segment {
+                    location: format!("{} disk 0x{:x}", format_name(format), run_start),
+                    data: data[start..end].to_vec(),
+                });
+            }
+        }
+        run_end = run_start.checked_add(segments.last().map_or(0, |s| s.data.len() as u64));
+    }
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vmdk_header(grain_size: u64, gtes_per_gt: u32, gd_offset: u64) -> Vec<u8> {
+        let mut header = vec![0; 512];
+        header[..4].copy_from_slice(VMDK_MAGIC);
+        header[12..20].copy_from_slice(&(1_u64 << 40).to_le_bytes()); // capacity
+        header[20..28].copy_from_slice(&grain_size.to_le_bytes());
+        header[44..48].copy_from_slice(&gtes_per_gt.to_le_bytes());
+        header[56..64].copy_from_slice(&gd_offset.to_le_bytes());
+        header
+    }
+
+    #[test]
+    fn truncated_headers_yield_nothing() {
+        for (format, magic) in [
+            (DiskFormat::Vhd, VHD_COOKIE),
+            (DiskFormat::Vmdk, VMDK_MAGIC),
+            (DiskFormat::Qcow2, QCOW2_MAGIC),
+        ] {
+            assert!(extract(format, magic).is_empty());
+        }
+    }
+
+    #[test]
+    fn vmdk_huge_grain_size_is_rejected() {
+        let header = vmdk_header(1 << 63, 2, 1);
+        assert!(extract(DiskFormat::Vmdk, &header).is_empty());
+    }
+
+    #[test]
+    fn vmdk_grain_directory_past_the_end_is_skipped() {
+        let header = vmdk_header(128, 512, u64::MAX / SECTOR);
+        assert!(extract(DiskFormat::Vmdk, &header).is_empty());
+    }
+
+    #[test]
+    fn qcow2_l1_table_past_the_end_is_skipped() {
+        let mut header = vec![0; 512];
+        header[..4].copy_from_slice(QCOW2_MAGIC);
+        header[20..24].copy_from_slice(&16_u32.to_be_bytes()); // cluster bits
+        header[36..40].copy_from_slice(&u32::MAX.to_be_bytes()); // l1 size
+        header[40..48].copy_from_slice(&u64::MAX.to_be_bytes()); // l1 offset
+        assert!(extract(DiskFormat::Qcow2, &header).is_empty());
+    }
+
+    #[test]
+    fn vhd_header_past_the_end_is_skipped() {
+        let mut footer = vec![0; 512];
+        footer[..8].copy_from_slice(VHD_COOKIE);
+        footer[16..24].copy_from_slice(&(u64::MAX - 4).to_be_bytes()); // header offset
+        footer[60..64].copy_from_slice(&VHD_DYNAMIC.to_be_bytes());
+        assert!(detect(&footer) == Some(DiskFormat::Vhd));
+        assert!(extract(DiskFormat::Vhd, &footer).is_empty());
+    }
+}
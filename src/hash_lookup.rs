@@ -0,0 +1,91 @@
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum LookupService {
+    VirusTotal,
+    MalwareBazaar,
+}
+
+impl LookupService {
+    pub fn name(&self) -> &'static str {
+        match self {
+            LookupService::VirusTotal => "VirusTotal",
+            LookupService::MalwareBazaar => "MalwareBazaar",
+        }
+    }
+
+    // page a human can look at, used when no API key is configured
+    pub fn browse_url(&self, sha256: &str) -> String {
+        match self {
+            LookupService::VirusTotal => format!("https://www.virustotal.com/gui/file/{sha256}"),
+            LookupService::MalwareBazaar => {
+                format!("https://bazaar.abuse.ch/browse.php?search=sha256%3A{sha256}")
+            }
+        }
+    }
+
+    pub fn query(&self, api_key: &str, sha256: &str) -> Result<String, String> {
+        match self {
+            LookupService::VirusTotal => query_virustotal(api_key, sha256),
+            LookupService::MalwareBazaar => query_malwarebazaar(api_key, sha256),
+        }
+    }
+}
+
+pub fn sha256_file(path: &Path) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher)?;
+
+    Ok(to_hex(&hasher.finalize()))
+}
+
+pub fn sha256_bytes(data: &[u8]) -> String {
+    to_hex(&Sha256::digest(data))
+}
+
+fn to_hex(digest: &[u8]) -> String {
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn query_virustotal(api_key: &str, sha256: &str) -> Result<String, String> {
+    let url = format!("https://www.virustotal.com/api/v3/files/{sha256}");
+    let body = match ureq::get(&url).set("x-apikey", api_key).call() {
+        Ok(resp) => resp.into_string().map_err(|e| e.to_string())?,
+        Err(ureq::Error::Status(404, _)) => return Ok("VT: not found".to_string()),
+        Err(err) => return Err(err.to_string()),
+    };
+
+    let parsed = json::parse(&body).map_err(|e| e.to_string())?;
+    let stats = &parsed["data"]["attributes"]["last_analysis_stats"];
+    if stats.is_null() {
+        return Err("VT: unexpected response".to_string());
+    }
+
+    let malicious = stats["malicious"].as_u32().unwrap_or(0);
+    let total: u32 = stats.entries().filter_map(|(_k, v)| v.as_u32()).sum();
+    Ok(format!("VT: {}/{} malicious", malicious, total))
+}
+
+fn query_malwarebazaar(api_key: &str, sha256: &str) -> Result<String, String> {
+    let body = ureq::post("https://mb-api.abuse.ch/api/v1/")
+        .set("Auth-Key", api_key)
+        .send_form(&[("query", "get_info"), ("hash", sha256)])
+        .map_err(|e| e.to_string())?
+        .into_string()
+        .map_err(|e| e.to_string())?;
+
+    let parsed = json::parse(&body).map_err(|e| e.to_string())?;
+    match parsed["query_status"].as_str() {
+        Some("ok") => {
+            let signature = parsed["data"][0]["signature"].as_str().unwrap_or("unknown");
+            Ok(format!("MB: known malware ({})", signature))
+        }
+        Some("hash_not_found") => Ok("MB: not found".to_string()),
+        Some(status) => Err(format!("MB: {}", status)),
+        None => Err("MB: unexpected response".to_string()),
+    }
+}
@@ -0,0 +1,272 @@
+use crate::export::{content_mode_name, hidden_files_name, scan_mode_name};
+use crate::i18n::tr;
+use crate::{
+    ContentEnum, FileWalkOptions, FilterTypeEnum, LinkBehaviorEnum, QuerApp, ScanModeEnum,
+};
+use eframe::egui;
+use std::path::PathBuf;
+
+const CONTENT_TYPES: [ContentEnum; 3] = [ContentEnum::Hex, ContentEnum::Text, ContentEnum::Auto];
+const SCAN_MODES: [ScanModeEnum; 7] = [
+    ScanModeEnum::Content,
+    ScanModeEnum::Metadata,
+    ScanModeEnum::Duplicates,
+    ScanModeEnum::CountOnly,
+    ScanModeEnum::Diff,
+    ScanModeEnum::Padding,
+    ScanModeEnum::Records,
+];
+const HIDDEN_FILES: [FilterTypeEnum; 3] = [
+    FilterTypeEnum::AllFiles,
+    FilterTypeEnum::NoHidden,
+    FilterTypeEnum::NoHiddenOrSystem,
+];
+
+// everything needed to run a past search again as it was
+#[derive(Clone)]
+pub struct SearchHistoryEntry {
+    pub regex_str: String,
+    pub content_type: ContentEnum,
+    pub scan_mode: ScanModeEnum,
+    pub root_folder_path: PathBuf,
+    pub file_walk_options: FileWalkOptions,
+    pub filter_str: String,
+    pub alignment: i32,
+    pub alignment_base: usize,
+    pub max_hits: u32,
+    pub pinned: bool, // kept at the top and never pushed out
+}
+
+impl SearchHistoryEntry {
+    fn same_search(&self, other: &Self) -> bool {
+        self.regex_str == other.regex_str
+            && self.content_type == other.content_type
+            && self.scan_mode == other.scan_mode
+            && self.root_folder_path == other.root_folder_path
+            && self.file_walk_options == other.file_walk_options
+            && self.filter_str == other.filter_str
+            && self.alignment == other.alignment
+            && self.alignment_base == other.alignment_base
+            && self.max_hits == other.max_hits
+    }
+
+    // for searches kept between runs, the modes by the names exports use
+    pub fn to_json(&self) -> json::JsonValue {
+        let mut obj = json::JsonValue::new_object();
+        obj["pattern"] = self.regex_str.as_str().into();
+        obj["content_mode"] = content_mode_name(&self.content_type).into();
+        obj["scan_mode"] = scan_mode_name(&self.scan_mode).into();
+        obj["root"] = self.root_folder_path.to_string_lossy().to_string().into();
+        obj["filter"] = self.filter_str.as_str().into();
+        obj["hidden_files"] = hidden_files_name(&self.file_walk_options.hidden_files).into();
+        obj["skipped_paths"] = self
+            .file_walk_options
+            .skip_paths
+            .iter()
+            .map(|path| path.to_string_lossy().to_string())
+            .collect::<Vec<String>>()
+            .into();
+        obj["alignment"] = self.alignment.into();
+        obj["alignment_base"] = self.alignment_base.into();
+        obj["max_hits"] = self.max_hits.into();
+        obj
+    }
+
+    pub fn from_json(obj: &json::JsonValue) -> Option<Self> {
+        let named = |names: &[&'static str], key: &str| {
+            names
+                .iter()
+                .position(|name| obj[key].as_str() == Some(name))
+        };
+        let content_type = CONTENT_TYPES
+            .get(named(
+                &CONTENT_TYPES.map(|c| content_mode_name(&c)),
+                "content_mode",
+            )?)?
+            .clone();
+        let scan_mode = SCAN_MODES
+            .get(named(&SCAN_MODES.map(|m| scan_mode_name(&m)), "scan_mode")?)?
+            .clone();
+        let hidden_files = HIDDEN_FILES
+            .get(named(
+                &HIDDEN_FILES.map(|h| hidden_files_name(&h)),
+                "hidden_files",
+            )?)?
+            .clone();
+        Some(Self {
+            regex_str: obj["pattern"].as_str()?.to_string(),
+            content_type,
+            scan_mode,
+            root_folder_path: PathBuf::from(obj["root"].as_str()?),
+            file_walk_options: FileWalkOptions {
+                hidden_files,
+                _links: LinkBehaviorEnum::NoFollow,
+                skip_paths: obj["skipped_paths"]
+                    .members()
+                    .filter_map(|path| path.as_str().map(PathBuf::from))
+                    .collect(),
+            },
+            filter_str: obj["filter"].as_str().unwrap_or_default().to_string(),
+            alignment: obj["alignment"].as_i32().unwrap_or(0),
+            alignment_base: obj["alignment_base"].as_usize().unwrap_or(0),
+            max_hits: obj["max_hits"].as_u32()?,
+            pinned: false,
+        })
+    }
+
+    fn details(&self) -> String {
+        let mut details = format!(
+            "In {}\nMax hits: {}",
+            self.root_folder_path.display(),
+            self.max_hits
+        );
+        if self.alignment > 0 {
+            details.push_str(&format!(
+                "\nAligned to {} from 0x{:x}",
+                self.alignment, self.alignment_base
+            ));
+        }
+        if !self.filter_str.is_empty() {
+            details.push_str(&format!("\nFilter: {}", self.filter_str));
+        }
+        details
+    }
+}
+
+impl QuerApp {
+    pub(crate) fn current_search_entry(&self) -> SearchHistoryEntry {
+        SearchHistoryEntry {
+            regex_str: self.regex_str.clone(),
+            content_type: self.content_type.clone(),
+            scan_mode: self.scan_mode.clone(),
+            root_folder_path: self.root_folder_path.clone(),
+            file_walk_options: self.file_walk_options.clone(),
+            filter_str: self.filter_str.clone(),
+            alignment: self.alignment,
+            alignment_base: self.alignment_base,
+            max_hits: self.max_hits,
+            pinned: false,
+        }
+    }
+
+    // repeating a search moves it to the top instead of adding it twice
+    pub(crate) fn record_search(&mut self) {
+        let mut entry = self.current_search_entry();
+        if let Some(idx) = self
+            .previous_searches
+            .iter()
+            .position(|prev| prev.same_search(&entry))
+        {
+            entry.pinned = self.previous_searches[idx].pinned;
+            self.previous_searches.remove(idx);
+        }
+        self.previous_searches.push_front(entry);
+        self.sort_and_trim_history();
+    }
+
+    fn sort_and_trim_history(&mut self) {
+        // stable, so both groups stay newest first
+        self.previous_searches
+            .make_contiguous()
+            .sort_by_key(|entry| !entry.pinned);
+        let mut unpinned = 0;
+        self.previous_searches.retain(|entry| {
+            unpinned += usize::from(!entry.pinned);
+            entry.pinned || unpinned <= self.history_len
+        });
+    }
+
+    pub(crate) fn restore_search(&mut self, entry: SearchHistoryEntry) {
+        self.regex_str = entry.regex_str;
+        self.content_type = entry.content_type;
+        self.scan_mode = entry.scan_mode;
+        self.root_folder_path = entry.root_folder_path;
+        self.file_walk_options = entry.file_walk_options;
+        self.filter_str = entry.filter_str;
+        self.alignment = entry.alignment;
+        self.alignment_base = entry.alignment_base;
+        self.max_hits = entry.max_hits;
+        self.update_regex();
+    }
+
+    pub(crate) fn add_history_menu(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            let label = ui.label(tr("remember"));
+            if ui
+                .add(egui::DragValue::new(&mut self.history_len).range(1..=100))
+                .labelled_by(label.id)
+                .changed()
+            {
+                self.sort_and_trim_history();
+            }
+            ui.label(tr("searches"));
+        })
+        .response
+        .on_hover_text(tr("history-pinned-limit-hint"));
+        ui.separator();
+        if self.previous_searches.is_empty() {
+            ui.label(tr("no-previous-searches-yet"));
+            return;
+        }
+
+        let mut picked = None;
+        let mut toggle_pin = None;
+        for (idx, entry) in self.previous_searches.iter().enumerate() {
+            ui.horizontal(|ui| {
+                let pin = if entry.pinned { "Unpin" } else { "Pin" };
+                if ui
+                    .small_button(pin)
+                    .on_hover_text(tr("history-pin-hint"))
+                    .clicked()
+                {
+                    toggle_pin = Some(idx);
+                }
+                if ui
+                    .small_button(tr("re-run"))
+                    .on_hover_text(tr("history-rerun-hint"))
+                    .clicked()
+                {
+                    picked = Some((entry.clone(), true));
+                    ui.close_menu();
+                }
+                if ui
+                    .button(&entry.regex_str)
+                    .on_hover_text(entry.details())
+                    .clicked()
+                {
+                    picked = Some((entry.clone(), false));
+                }
+            });
+        }
+        ui.separator();
+        if ui
+            .button(tr("clear"))
+            .on_hover_text(tr("history-clear-hint"))
+            .clicked()
+        {
+            self.previous_searches.retain(|entry| entry.pinned);
+            ui.close_menu();
+        }
+
+        if let Some(idx) = toggle_pin {
+            self.previous_searches[idx].pinned = !self.previous_searches[idx].pinned;
+            self.sort_and_trim_history();
+        }
+
+        if let Some((entry, run)) = picked {
+            self.restore_search(entry);
+            if !run {
+                return;
+            }
+            if !self.is_search_finished() {
+                self.log_error(
+                    "Wait for the current search to finish before re-running".to_string(),
+                );
+            } else if let (true, Err(err)) = (self.scan_mode.needs_pattern(), &self.regex_result) {
+                self.log_error(format!("Can't re-run, the pattern is invalid: {}", err));
+            } else {
+                self.search();
+            }
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource, FluentValue};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
+use unic_langid::LanguageIdentifier;
+
+// UI strings live in locales/<code>/quer.ftl, anything missing from a translation falls back to English
+#[derive(Clone, Copy, PartialEq)]
+pub enum Language {
+    English,
+    German,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::German];
+
+    // in the language itself, so it can be found without reading the current one
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::German => "Deutsch",
+        }
+    }
+
+    // from the usual locale variables, English when unset or not translated
+    pub fn from_env() -> Self {
+        let locale = ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .unwrap_or_default();
+        Language::ALL
+            .into_iter()
+            .find(|lang| locale.starts_with(lang.code()))
+            .unwrap_or(Language::English)
+    }
+
+    fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::German => "de",
+        }
+    }
+
+    fn source(&self) -> &'static str {
+        match self {
+            Language::English => include_str!("../locales/en/quer.ftl"),
+            Language::German => include_str!("../locales/de/quer.ftl"),
+        }
+    }
+
+    fn index(&self) -> usize {
+        Language::ALL
+            .iter()
+            .position(|lang| lang == self)
+            .unwrap_or(0)
+    }
+}
+
+static LANGUAGE: AtomicUsize = AtomicUsize::new(0);
+static BUNDLES: OnceLock<Vec<FluentBundle<FluentResource>>> = OnceLock::new();
+
+fn bundles() -> &'static [FluentBundle<FluentResource>] {
+    BUNDLES.get_or_init(|| {
+        Language::ALL
+            .iter()
+            .map(|lang| {
+                let langid: LanguageIdentifier = lang.code().parse().unwrap_or_default();
+                let mut bundle = FluentBundle::new_concurrent(vec![langid]);
+                // no bidi isolation marks around arguments, egui would draw them
+                bundle.set_use_isolating(false);
+                // a broken entry only loses that entry, the rest of the file still loads
+                let resource = match FluentResource::try_new(lang.source().to_string()) {
+                    Ok(resource) => resource,
+                    Err((resource, _errors)) => resource,
+                };
+                let _ = bundle.add_resource(resource);
+                bundle
+            })
+            .collect()
+    })
+}
+
+pub fn language() -> Language {
+    Language::ALL[LANGUAGE.load(Ordering::Relaxed)]
+}
+
+pub fn set_language(lang: Language) {
+    LANGUAGE.store(lang.index(), Ordering::Relaxed);
+}
+
+fn format(id: &str, args: Option<&FluentArgs>) -> String {
+    let bundles = bundles();
+    let current = &bundles[language().index()];
+    let english = &bundles[Language::English.index()];
+    for bundle in [current, english] {
+        if let Some(pattern) = bundle.get_message(id).and_then(|msg| msg.value()) {
+            let mut errors = Vec::new();
+            return bundle
+                .format_pattern(pattern, args, &mut errors)
+                .to_string();
+        }
+    }
+    // untranslated ids show up as themselves rather than as blank UI
+    id.to_string()
+}
+
+pub fn tr(id: &str) -> String {
+    format(id, None)
+}
+
+// for messages with placeables, e.g. tr_args("found-results", &[("count", count.into())])
+pub fn tr_args(id: &str, args: &[(&str, FluentValue)]) -> String {
+    let mut fluent_args = FluentArgs::new();
+    for (name, value) in args {
+        fluent_args.set(*name, value.clone());
+    }
+    format(id, Some(&fluent_args))
+}
@@ -0,0 +1,202 @@
+use crate::accessibility::set_accessible_name;
+use crate::i18n::tr;
+use crate::QuerApp;
+use eframe::egui::{self, TextStyle};
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+pub enum LogLevel {
+    Error,
+    Info,
+    Verbose, // e.g. per-file timings, only recorded while selected
+}
+
+impl LogLevel {
+    fn tag(&self) -> &'static str {
+        match self {
+            LogLevel::Error => "ERROR",
+            LogLevel::Info => "INFO",
+            LogLevel::Verbose => "VERBOSE",
+        }
+    }
+
+    const ALL: [LogLevel; 3] = [LogLevel::Error, LogLevel::Info, LogLevel::Verbose];
+
+    fn name(&self) -> String {
+        match self {
+            LogLevel::Error => tr("log-errors-only"),
+            LogLevel::Info => tr("log-normal"),
+            LogLevel::Verbose => tr("log-verbose"),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct LogLine {
+    pub level: LogLevel,
+    pub text: String, // with the timestamp
+}
+
+impl QuerApp {
+    pub(crate) fn log(&mut self, s: String) {
+        self.log_at(LogLevel::Info, s);
+    }
+
+    pub(crate) fn log_error(&mut self, s: String) {
+        self.log_at(LogLevel::Error, s);
+    }
+
+    fn log_at(&mut self, level: LogLevel, s: String) {
+        let date = chrono::Local::now();
+        self.log_lines.push(LogLine {
+            level,
+            text: format!("{} {}", date.format("[%Y-%m-%d][%H:%M:%S]"), s),
+        });
+    }
+
+    // the whole log with severities, for attaching to a report
+    fn log_text(&self) -> String {
+        self.log_lines
+            .iter()
+            .map(|line| format!("{:<7} {}\n", line.level.tag(), line.text))
+            .collect()
+    }
+
+    fn save_log(&mut self, path: &Path) {
+        match fs::write(path, self.log_text()) {
+            Ok(_) => self.log(format!("Saved the log to {}", path.display())),
+            Err(err) => self.log_error(format!(
+                "Couldn't save the log to {}: {}",
+                path.display(),
+                err
+            )),
+        }
+    }
+
+    fn add_log_save_dialog(&mut self, ctx: &egui::Context) {
+        let Some(dialog) = &mut self.log_save_dialog else {
+            return;
+        };
+        let (selected, done) =
+            crate::update_dialog_viewport(ctx, dialog, "log_save_dialog", "Save Log To");
+        if done || selected.is_some() {
+            self.log_save_dialog = None;
+        }
+        if let Some(path) = selected {
+            self.save_log(&path);
+        }
+    }
+
+    pub(crate) fn log_file_timing(&mut self, path: &Path, elapsed: Duration) {
+        if self.log_verbosity == LogLevel::Verbose {
+            self.log_at(
+                LogLevel::Verbose,
+                format!(
+                    "Scanned {} in {:.1} ms",
+                    path.display(),
+                    elapsed.as_secs_f64() * 1000.0
+                ),
+            );
+        }
+    }
+
+    // indices of the lines passing the verbosity and filter, rebuilt only when either
+    // or the log itself changed
+    fn update_log_view(&mut self) {
+        let key = (
+            self.log_lines.len(),
+            self.log_verbosity as usize,
+            self.log_filter.clone(),
+        );
+        if self.log_view_key == key {
+            return;
+        }
+        let filter = self.log_filter.to_lowercase();
+        self.log_view = self
+            .log_lines
+            .iter()
+            .enumerate()
+            .filter(|(_idx, line)| line.level <= self.log_verbosity)
+            .filter(|(_idx, line)| filter.is_empty() || line.text.to_lowercase().contains(&filter))
+            .map(|(idx, _line)| idx)
+            .collect();
+        self.log_view_key = key;
+    }
+
+    pub(crate) fn add_log_pane(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            let label = ui.label(tr("log-verbosity"));
+            egui::ComboBox::from_id_source("log_verbosity")
+                .selected_text(self.log_verbosity.name())
+                .show_ui(ui, |ui| {
+                    for level in LogLevel::ALL {
+                        ui.selectable_value(&mut self.log_verbosity, level, level.name());
+                    }
+                })
+                .response
+                .labelled_by(label.id)
+                .on_hover_text(tr("log-verbosity-hint"));
+            let resp = ui.add(
+                egui::TextEdit::singleline(&mut self.log_filter)
+                    .hint_text(tr("filter-log"))
+                    .desired_width(200.0),
+            );
+            set_accessible_name(&resp, &tr("filter-log"));
+            if ui
+                .button(tr("copy-all"))
+                .on_hover_text(tr("copy-all-hint"))
+                .clicked()
+            {
+                ui.ctx().copy_text(self.log_text());
+            }
+            if ui.button(tr("save-log")).clicked() {
+                let mut dialog = egui_file_dialog::FileDialog::new()
+                    .initial_directory(self.export_file_path.clone())
+                    .default_file_name("quer.log")
+                    .as_modal(false)
+                    .title_bar(false)
+                    .movable(false)
+                    .resizable(false)
+                    .min_size([800., 600.]);
+                dialog.save_file();
+                self.log_save_dialog = Some(dialog);
+            }
+        });
+        self.add_log_save_dialog(ui.ctx());
+        self.update_log_view();
+
+        let row_height = ui.text_style_height(&TextStyle::Body);
+        let error_color = egui::Color32::from_rgb(0x8f, 0x0, 0x0);
+        egui::Frame::none()
+            .show(ui, |ui| {
+                egui::ScrollArea::vertical().auto_shrink(false).show_rows(
+                    ui,
+                    row_height,
+                    self.log_view.len(),
+                    |ui, row_range| {
+                        for row in row_range {
+                            let line = &self.log_lines[self.log_view[row]];
+                            let text = egui::RichText::new(&line.text);
+                            let text = match line.level {
+                                LogLevel::Error => text.color(error_color),
+                                LogLevel::Info => text,
+                                LogLevel::Verbose => text.weak(),
+                            };
+                            ui.label(text);
+                        }
+                    },
+                )
+            })
+            .response
+            .context_menu(|ui| {
+                if ui.button(tr("clear-logs")).clicked() {
+                    self.log_lines.clear();
+                    // a refilled log could otherwise match the old key
+                    self.log_view_key.0 = usize::MAX;
+                    ui.close_menu();
+                }
+            });
+    }
+}
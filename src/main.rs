@@ -1,5 +1,6 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")] // hide console window on Windows in release
 
+use base64::Engine;
 use concurrent_queue::ConcurrentQueue;
 use eframe::egui;
 use egui::{FontFamily, FontId, IconData, RichText, TextStyle}; // FontFamily, FontId,
@@ -7,29 +8,108 @@ use egui_extras::{Column, TableBuilder};
 use egui_file_dialog::{DialogState, FileDialog};
 use memmap2::Mmap;
 use regex::bytes::Regex as BytesRegex;
+use regex::bytes::RegexBuilder as BytesRegexBuilder;
 use regex::Regex as Utf8Regex;
 use std::io::{Read, Seek};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::mpsc;
 use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::{Duration, Instant};
 
-use json;
-
-use work_queue::{LocalQueue, Queue};
-
-use std::collections::VecDeque;
-use std::fs;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::ffi::OsString;
 use std::fs::File;
 use std::fs::OpenOptions;
 use std::io::SeekFrom;
+use std::ops::Range;
 use std::path::Path;
 use std::path::PathBuf;
+use std::process::Command;
 use std::result::Result;
 use std::string::String;
 
 use walkdir::{DirEntry, FilterEntry, WalkDir};
 
-struct Task(Box<dyn FnOnce(&mut LocalQueue<Task>) + Send>);
+mod accessibility;
+mod address_map;
+mod analysis;
+mod anchors;
+mod byte_stats;
+mod clipboard_monitor;
+mod code_literals;
+mod converter;
+mod custom_column;
+mod diff;
+mod dir_tree;
+mod disassembly;
+mod elevated;
+mod export;
+mod file_read;
+mod file_types;
+mod file_view;
+mod filters;
+mod fs_carve;
+mod handlers;
+mod hash_lookup;
+mod history;
+mod i18n;
+mod log_pane;
+mod manifest;
+mod merge;
+mod pattern_compare;
+mod pattern_set;
+mod pattern_tester;
+mod presets;
+mod progress;
+mod raw_volume;
+mod regex_help;
+mod regex_highlight;
+mod results_store;
+mod row_colors;
+mod scheduler;
+mod settings;
+mod shadow_copies;
+mod shell_integration;
+mod similar_regions;
+mod similarity;
+mod single_instance;
+mod struct_decode;
+mod tray;
+mod webhook;
+mod worker_pool;
+mod xrefs;
+use accessibility::{set_accessible_name, ROWS_PER_KEY_PAGE};
+use analysis::EntropyMap;
+use dir_tree::DirTree;
+use export::{ExportFormat, ExportJob, DEFAULT_QUICK_EXPORT_TEMPLATE, QUICK_EXPORT_PLACEHOLDERS};
+use file_read::{FileData, ReadRetry};
+use file_types::{CategoryOptions, FileCategory};
+use file_view::PinnedPreview;
+use filters::{FilterChips, SavedFilter, TextFilter};
+use handlers::{ContainerEnum, HandlerOptions};
+use hash_lookup::LookupService;
+use history::SearchHistoryEntry;
+use i18n::{tr, tr_args, Language};
+use log_pane::{LogLevel, LogLine};
+use pattern_set::SearchPattern;
+use presets::{PendingTemplate, Preset};
+use row_colors::{paint_row_tint, RowColorRule};
+use similarity::FileCluster;
+use tray::ScanProgress;
+use webhook::{WebhookConfig, WebhookSink};
+use worker_pool::{panic_message, WorkerPool, WorkerScheduling};
+
+// threads the worker pool starts with until changed in the settings
+const DEFAULT_WORKER_COUNT: usize = 10;
+// files read ahead of the matcher, bounds the memory held by the I/O stage
+const PREFETCH_DEPTH: usize = 16;
+const PREFETCH_PAGE_SIZE: usize = 4096;
+// how much of a mapped file is read ahead, the rest is paged in as the matcher gets there
+const PREFETCH_WINDOW: usize = 4 * 1024 * 1024;
+// match preview read out by screen readers for a results row
+const MAX_ROW_NAME_LENGTH: usize = 64;
 
 fn expanding_content(ui: &mut egui::Ui) {
     let width = ui.available_width().clamp(20.0, 200.0);
@@ -92,9 +172,33 @@ fn load_icon() -> IconData {
     }
 }
 
+// the results table can't lay out many more rows than this before f32 row positions
+// lose precision, so bigger result sets are shown a page at a time
+const RESULTS_PAGE_ROWS: usize = 500_000;
+
+// rows a scheduled scan found that its previous run didn't
+const NEW_FINDING_TINT: egui::Color32 = egui::Color32::from_rgb(0x20, 0x90, 0xd0);
+
 fn main() -> Result<(), eframe::Error> {
     env_logger::init(); // Log to stderr (if you run with `RUST_LOG=debug`).
 
+    // file names from the shell's context menu don't have to be UTF-8
+    let args: Vec<OsString> = std::env::args_os().collect();
+    if args.get(1).is_some_and(|arg| arg == elevated::HELPER_ARG) {
+        let code = match args.get(2) {
+            Some(request) => elevated::run_helper(Path::new(request)),
+            None => 2,
+        };
+        std::process::exit(code);
+    }
+    // a running instance takes the request over, its window coming to the front
+    if single_instance::single_instance_enabled()
+        && single_instance::forward_to_running_instance(&args[1..])
+    {
+        return Ok(());
+    }
+    let launch = shell_integration::parse_args(&args[1..]);
+
     let viewport_bldr = egui::ViewportBuilder::default().with_icon(load_icon());
     let options = eframe::NativeOptions {
         viewport: viewport_bldr,
@@ -104,7 +208,22 @@ fn main() -> Result<(), eframe::Error> {
     eframe::run_native(
         "quer - Stuff Finder",
         options,
-        Box::new(|cc| Ok(Box::new(QuerApp::new(cc)))),
+        Box::new(move |cc| {
+            let mut app = QuerApp::new(cc);
+            if app.single_instance {
+                app.set_single_instance(&cc.egui_ctx, true);
+            }
+            match launch {
+                Some(Ok(request)) => app.apply_launch_request(request),
+                Some(Err(err)) => app.log_error(format!(
+                    "Couldn't open {}: {}",
+                    args[1].to_string_lossy(),
+                    err
+                )),
+                None => {}
+            }
+            Ok(Box::new(app))
+        }),
     )
 }
 
@@ -112,18 +231,79 @@ fn main() -> Result<(), eframe::Error> {
 enum ContentEnum {
     Hex,
     Text,
+    Auto, // text for text files, hex for binaries
 }
 
 #[derive(Clone)]
 enum RegexEnum {
     Hex(BytesRegex),
     Text(BytesRegex),
+    Auto(BytesRegex, Option<BytesRegex>), // text, and hex if the pattern is valid hex
+}
+
+#[derive(PartialEq, Clone)]
+enum ScanModeEnum {
+    Content,
+    Metadata,
+    Duplicates,
+    CountOnly,
+    Diff,
+    Padding,
+    Records,
+}
+
+impl ScanModeEnum {
+    fn needs_pattern(&self) -> bool {
+        !matches!(
+            self,
+            ScanModeEnum::Duplicates | ScanModeEnum::Diff | ScanModeEnum::Padding
+        )
+    }
+}
+
+// fixed-size records as used by record mode, all in bytes
+#[derive(Clone)]
+struct RecordLayout {
+    start: usize, // offset of the first record, e.g. to skip a file header
+    length: usize,
+    field_offset: usize,
+    field_length: usize, // 0 for the rest of the record
+}
+
+impl Default for RecordLayout {
+    fn default() -> Self {
+        Self {
+            start: 0,
+            length: 512,
+            field_offset: 0,
+            field_length: 0,
+        }
+    }
+}
+
+impl RecordLayout {
+    // the span of each record's field inside `data_len` bytes
+    fn fields(&self, data_len: usize) -> impl Iterator<Item = Range<usize>> + '_ {
+        let length = std::cmp::max(self.length, 1);
+        (self.start..data_len)
+            .step_by(length)
+            .map(move |record| {
+                let field_start = record + std::cmp::min(self.field_offset, length);
+                let field_end = match self.field_length {
+                    0 => record + length,
+                    field_length => std::cmp::min(field_start + field_length, record + length),
+                };
+                field_start..std::cmp::min(field_end, data_len)
+            })
+            .filter(|field| field.start < field.end)
+    }
 }
 
 #[derive(PartialEq, Clone)]
 enum FilterTypeEnum {
     AllFiles,
     NoHidden,
+    NoHiddenOrSystem, // system files only exist on Windows, elsewhere this is NoHidden
 }
 
 #[derive(PartialEq, Clone)]
@@ -138,10 +318,34 @@ enum RegexErr {
     EmptyRegex,
 }
 
-#[derive(Clone)]
+#[derive(PartialEq, Clone)]
 struct FileWalkOptions {
     hidden_files: FilterTypeEnum,
     _links: LinkBehaviorEnum,
+    skip_paths: Vec<PathBuf>, // left out of the walk entirely, with everything below them
+}
+
+// system and pseudo-filesystem paths that are only noise (or hang) when sweeping a whole drive
+#[cfg(windows)]
+const DEFAULT_SKIP_LIST: &str =
+    "C:\\Windows\\WinSxS\nC:\\pagefile.sys\nC:\\hiberfil.sys\nC:\\swapfile.sys";
+#[cfg(target_os = "macos")]
+const DEFAULT_SKIP_LIST: &str = "/System/Volumes\n/dev\n/private/var/vm";
+#[cfg(not(any(windows, target_os = "macos")))]
+const DEFAULT_SKIP_LIST: &str = "/proc\n/sys\n/run\n/dev";
+
+enum FileStatus {
+    Scanned(PathBuf),
+    Capped(PathBuf), // scanned until max_hits was reached, there may be more matches
+    Skipped(PathBuf),
+    Failed(PathBuf, String), // the matcher panicked on it
+}
+
+// how a file's scan ended
+enum ScanOutcome {
+    Scanned,
+    Capped,
+    Skipped,
 }
 
 struct FileCount {
@@ -149,11 +353,120 @@ struct FileCount {
     num_dirs: i32,
 }
 
+// binary matches keep their raw bytes, hex is only formatted where a match is shown
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum MatchContent {
+    Text(String),
+    Bytes(Box<[u8]>),
+}
+
+impl MatchContent {
+    // what was matched, for matches that can't be read back from the file
+    fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            MatchContent::Text(text) => text.as_bytes().to_vec(),
+            MatchContent::Bytes(bytes) => bytes.to_vec(),
+        }
+    }
+
+    // the start of the displayed form, without formatting all of a huge match
+    fn preview(&self, max_length: usize) -> String {
+        match self {
+            MatchContent::Text(text) => text.chars().take(max_length).collect(),
+            MatchContent::Bytes(bytes) => {
+                let shown = std::cmp::min(bytes.len(), max_length / 3 + 1);
+                let mut preview = MatchContent::Bytes(bytes[..shown].into()).to_string();
+                preview.truncate(max_length);
+                preview
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for MatchContent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MatchContent::Text(text) => f.write_str(text),
+            MatchContent::Bytes(bytes) => {
+                for (idx, byte) in bytes.iter().enumerate() {
+                    if idx > 0 {
+                        f.write_str(" ")?;
+                    }
+                    write!(f, "{:02x}", byte)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl From<String> for MatchContent {
+    fn from(text: String) -> Self {
+        MatchContent::Text(text)
+    }
+}
+
+#[derive(Clone)]
 struct Finding {
     filepath: String,
     offset: usize,
     match_size: usize,
-    match_content: String,
+    match_content: MatchContent,
+    location: Option<String>, // where inside a decoded container the match was, if anywhere
+    decoded: bool,            // offset is into the decoded content at `location`, not the file
+    hit_count: usize,         // > 1 for count-only summaries and merged regions
+    encoding: Option<&'static str>, // guessed for text matches only
+    virtual_address: Option<u64>, // for matches in executables
+    pattern: Option<Arc<str>>, // which pattern of a pattern file matched
+    aliases: Option<Arc<[String]>>, // other hard links or bind mounts of the same file
+    owner: Option<Arc<str>>,  // file of a raw volume, or module of a memory dump, it lies in
+    custom: Option<Arc<str>>, // value of the user defined column
+}
+
+impl Finding {
+    fn to_json(&self) -> json::JsonValue {
+        let mut obj = json::JsonValue::new_object();
+        obj["filepath"] = self.filepath.as_str().into();
+        obj["offset"] = self.offset.into();
+        obj["match_size"] = self.match_size.into();
+        obj["match_content"] = self.match_content.to_string().into();
+        if let Some(location) = &self.location {
+            obj["location"] = location.as_str().into();
+        }
+        if let Some(encoding) = self.encoding {
+            obj["encoding"] = encoding.into();
+        }
+        if let Some(address) = self.virtual_address {
+            obj["virtual_address"] = format!("0x{:x}", address).into();
+        }
+        if let Some(pattern) = &self.pattern {
+            obj["pattern"] = pattern.as_ref().into();
+        }
+        if let Some(aliases) = &self.aliases {
+            obj["aliases"] = aliases.to_vec().into();
+        }
+        if let Some(owner) = &self.owner {
+            obj["owner"] = owner.as_ref().into();
+        }
+        if let Some(custom) = &self.custom {
+            obj["custom"] = custom.as_ref().into();
+        }
+        obj
+    }
+}
+
+// a keyboard move through the results table
+enum RowStep {
+    Up(usize),
+    Down(usize),
+    First,
+    Last,
+}
+
+// all findings sharing the same match content, for the unique matches view
+struct UniqueMatch {
+    match_content: MatchContent,
+    locations: Vec<(String, usize)>,
 }
 
 struct QuerApp {
@@ -161,7 +474,16 @@ struct QuerApp {
     filter_str: String,
     root_folder_path: PathBuf,
     export_file_path: PathBuf,
-    imhex_file_path: String,
+    export_format: ExportFormat,
+    imhex_binary_path: String,
+    vt_api_key: String,
+    mb_api_key: String,
+    cyberchef_recipe: String,
+    webhook_enabled: bool,
+    webhook_url: String,
+    webhook_headers: String,
+    webhook_sink: Option<WebhookSink>,
+    verdicts: Arc<Mutex<HashMap<String, String>>>,
     search_dir_dialog: Option<FileDialog>,
     export_file_dialog: Option<FileDialog>,
     content_type: ContentEnum,
@@ -173,21 +495,208 @@ struct QuerApp {
     max_hits: u32,
     file_contents: String,
     alignment: i32,
-    worker_threads: Vec<Option<thread::JoinHandle<()>>>,
+    worker_pool: Arc<WorkerPool>,
+    worker_count: usize,
+    search_running: bool,
     findings: Vec<Finding>,
     rx_handles: Vec<mpsc::Receiver<Finding>>,
-    filecount_handles: Vec<mpsc::Receiver<i32>>,
+    filecount_handles: Vec<mpsc::Receiver<(FileStatus, Duration)>>,
     file_queue: Arc<ConcurrentQueue<DirEntry>>,
-    work_queue: Option<Queue<Task>>,
     clear_results_before_search: bool,
-    previous_searches: VecDeque<(String, ContentEnum)>,
-    log_lines: Vec<String>,
+    previous_searches: VecDeque<SearchHistoryEntry>,
+    log_lines: Vec<LogLine>,
+    show_clusters_window: bool,
+    similarity_threshold: u32,
+    clusters: Arc<Mutex<Option<Vec<FileCluster>>>>,
+    decode_containers: bool,
+    handler_options: HandlerOptions,
+    has_locations: bool,
+    scan_mode: ScanModeEnum,
+    active_scan_mode: ScanModeEnum,
+    search_start_index: usize,
+    scan_summary: String,
+    unique_matches_only: bool,
+    unique_matches: Vec<UniqueMatch>,
+    unique_index: HashMap<MatchContent, usize>,
+    unique_src_len: usize,
+    expanded_unique: HashSet<usize>,
+    first_match_only: bool,
+    show_file_view: bool,
+    selected_file: Option<String>,
+    file_view_offset: usize,
+    file_view_size: usize,
+    file_view_matches: Vec<usize>,
+    file_view_src_len: usize,
+    entropy_map: Arc<Mutex<Option<EntropyMap>>>,
+    entropy_requested: Option<String>,
+    show_dir_tree_window: bool,
+    dir_tree: DirTree,
+    image_preview_requested: Option<String>,
+    image_preview: Arc<Mutex<Option<(String, egui::ColorImage)>>>,
+    image_texture: Option<(String, egui::TextureHandle)>,
+    line_indexes: HashMap<String, Arc<Vec<usize>>>,
+    show_line_cols: bool,
+    file_view_text: bool,
+    text_preview_scrolled_to: Option<usize>,
+    hexdump_width: usize,
+    hexdump_grouping: usize,
+    hexdump_context: (usize, usize), // bytes before and after a match in previews
+    pinned_previews: Vec<PinnedPreview>,
+    filter_chips: FilterChips,
+    ext_filters: HashSet<String>,
+    dir_filters: HashSet<String>,
+    filtered_rows: Vec<usize>,
+    filtered_src_len: usize,
+    regex_error_span: Option<std::ops::Range<usize>>,
+    show_pattern_tester: bool,
+    tester_sample: String,
+    tester_hex: bool,
+    split_view: bool,
+    selected_match_size: usize,
+    selected_row: Option<usize>,
+    scroll_table_to: Option<usize>,
+    split_scrolled_to: Option<(String, usize)>,
+    results_detached: bool,
+    scan_progress: Arc<ScanProgress>,
+    tray_hidden: Arc<AtomicBool>,
+    tray_on_close: bool,
+    presets: Vec<Preset>,
+    pending_template: Option<PendingTemplate>,
+    min_match_len: usize,
+    max_match_len: usize,
+    similar_region: Option<similar_regions::SimilarRegion>,
+    compare_path_str: String,
+    compare_dialog: Option<FileDialog>,
+    show_byte_stats_window: bool,
+    byte_stats: Arc<Mutex<Option<Vec<analysis::ByteStats>>>>,
+    padding_min_run: usize,
+    padding_max_period: usize,
+    has_encodings: bool,
+    address_maps: HashMap<String, Option<Arc<address_map::AddressMap>>>,
+    has_addresses: bool,
+    show_converter: bool,
+    converter: converter::Converter,
+    show_regex_help: bool,
+    results_page: usize,
+    pattern_set: Option<pattern_set::PatternSet>,
+    pattern_file_dialog: Option<FileDialog>,
+    has_patterns: bool,
+    show_pattern_stats: bool,
+    pattern_stats: Vec<pattern_set::PatternStat>,
+    pattern_stats_src_len: usize,
+    pattern_stats_sort: (pattern_set::PatternStatsSort, bool),
+    alignment_base: usize,
+    record_layout: RecordLayout,
+    anchors_str: String,
+    anchors: Result<Vec<anchors::Anchor>, String>,
+    custom_column_str: String,
+    custom_column: Result<Option<custom_column::CustomColumn>, String>,
+    has_custom: bool,
+    history_len: usize,
+    prefetched_files: Arc<AtomicUsize>,
+    use_io_uring: bool,
+    read_retries: u32,
+    read_retry_backoff_ms: u64,
+    read_retry: ReadRetry, // of the current search, with its retry and failure counts
+    low_priority_workers: bool,
+    worker_cores_str: String,
+    worker_cores: Result<Vec<usize>, String>,
+    skip_system_dirs: bool,
+    skip_list_str: String,
+    file_aliases: HashMap<String, Arc<[String]>>,
+    capped_files: HashSet<String>,
+    files_capped: usize,
+    scanning_files: Arc<Mutex<HashSet<PathBuf>>>,
+    files_scanned: usize,
+    files_skipped: usize,
+    export_job: Option<ExportJob>,
+    quick_export_template: String,
+    quick_export_after_search: bool,
+    write_export_metadata: bool,
+    defang_exports: bool,
+    last_search: Option<SearchHistoryEntry>,
+    search_started_at: Option<chrono::DateTime<chrono::Local>>,
+    search_finished_at: Option<chrono::DateTime<chrono::Local>>,
+    high_contrast: bool,
+    normal_visuals: Option<egui::Visuals>,
+    row_color_rules: Vec<RowColorRule>,
+    show_row_colors: bool,
+    text_filter: Result<TextFilter, String>,
+    text_filter_src: String,
+    saved_filters: Vec<SavedFilter>,
+    saved_filter_name: String,
+    offset_filter_from: String,
+    offset_filter_to: String,
+    offset_filter: Result<Option<Range<u64>>, String>,
+    merge_adjacent: bool,
+    merge_gap: usize,
+    extract_before: usize,
+    extract_after: usize,
+    goto_offset_str: String,
+    goto_offset_error: Option<String>,
+    log_verbosity: LogLevel,
+    log_filter: String,
+    log_view: Vec<usize>,
+    log_view_key: (usize, usize, String),
+    log_save_dialog: Option<FileDialog>,
+    file_category: FileCategory,
+    category_options: Vec<CategoryOptions>,
+    show_category_options: bool,
+    case_insensitive: bool,
+    file_view_regions: Vec<pattern_compare::PatternRegion>,
+    compare_patterns_enabled: bool,
+    compare_patterns: [Option<Arc<str>>; 2],
+    excluded_dirs: Vec<PathBuf>,
+    skipped_files: Vec<PathBuf>,
+    elevated_outcome: Arc<Mutex<Option<Result<elevated::ElevatedOutcome, String>>>>,
+    elevated_running: bool,
+    attribute_fs: bool,
+    scan_shadow_copies: bool,
+    shadow_copies: Vec<shadow_copies::ShadowCopy>,
+    shadow_copy_listing: shadow_copies::ShadowCopyListing,
+    shadow_copies_listing: bool,
+    shadow_copy_error: Option<String>,
+    read_locked_files: bool,
+    watch_clipboard: bool,
+    clipboard_monitor: Option<Arc<clipboard_monitor::ClipboardMonitor>>,
+    clipboard_lookup: Option<clipboard_monitor::ClipboardLookup>,
+    single_instance: bool,
+    instance_server: Option<Arc<single_instance::InstanceServer>>,
+    confirm_launch_search: bool,
+    pending_launches: VecDeque<shell_integration::LaunchRequest>,
+    scheduled_scans: Vec<scheduler::ScheduledScan>,
+    scheduled_run: Option<(usize, chrono::DateTime<chrono::Local>)>,
+    show_scheduled_scans: bool,
+    new_findings: HashSet<(String, usize)>,
+    alert_sink: Option<WebhookSink>,
+    persist_results: bool,
+    restorable: Option<results_store::StoredResults>,
+    restore_outcome: results_store::RestoreOutcome,
+    results_store_error: Arc<Mutex<Option<String>>>,
+    scanned_files: Vec<PathBuf>,
+    manifest_dialog: Option<FileDialog>,
+    manifest_check: Option<manifest::ManifestCheckJob>,
 }
 
+#[derive(Clone)]
 struct SearchOptions {
+    min_match_len: usize,
+    max_match_len: usize, // 0 for no limit
     alignment: i32,
-    regex_result: Result<RegexEnum, String>,
+    alignment_base: usize,
+    patterns: Vec<SearchPattern>,
     max_hits: u32,
+    decode_containers: bool,
+    handler_options: HandlerOptions,
+    scan_mode: ScanModeEnum,
+    first_match_only: bool,
+    attribute_fs: bool, // name the files of raw volumes and disk images that matches lie in
+    search_root: PathBuf,
+    compare_root: PathBuf,
+    padding_min_run: usize,
+    padding_max_period: usize,
+    record_layout: RecordLayout,
+    custom_column: Option<Arc<custom_column::CustomColumn>>,
 }
 
 impl Clone for QuerApp {
@@ -197,46 +706,246 @@ impl Clone for QuerApp {
             filter_str: self.filter_str.clone(),
             root_folder_path: self.root_folder_path.clone(),
             export_file_path: self.export_file_path.clone(),
-            imhex_file_path: self.imhex_file_path.clone(),
+            export_format: self.export_format.clone(),
+            imhex_binary_path: self.imhex_binary_path.clone(),
+            vt_api_key: self.vt_api_key.clone(),
+            mb_api_key: self.mb_api_key.clone(),
+            cyberchef_recipe: self.cyberchef_recipe.clone(),
+            webhook_enabled: self.webhook_enabled,
+            webhook_url: self.webhook_url.clone(),
+            webhook_headers: self.webhook_headers.clone(),
+            webhook_sink: None,
+            verdicts: self.verdicts.clone(),
             search_dir_dialog: None,  // this is why we're clonin'
             export_file_dialog: None, // this is why we're clonin'
             content_type: self.content_type.clone(),
             regex_result: self.regex_result.clone(),
             file_walk_options: self.file_walk_options.clone(),
-            progress: self.progress.clone(),
-            max_files: self.max_files.clone(),
+            progress: self.progress,
+            max_files: self.max_files,
             current_files_mtx: self.current_files_mtx.clone(),
-            max_hits: self.max_hits.clone(),
+            max_hits: self.max_hits,
             file_contents: self.file_contents.clone(),
-            alignment: self.alignment.clone(),
-            worker_threads: Vec::new(), // worker threads don't need these vecs
+            alignment: self.alignment,
+            worker_pool: Arc::clone(&self.worker_pool),
+            worker_count: self.worker_count,
+            search_running: false,
             findings: Vec::new(),
             rx_handles: Vec::new(),
             filecount_handles: Vec::new(),
             file_queue: Arc::new(ConcurrentQueue::unbounded()),
-            work_queue: None,
             clear_results_before_search: true,
             previous_searches: VecDeque::new(),
             log_lines: Vec::new(),
+            show_clusters_window: false,
+            similarity_threshold: self.similarity_threshold,
+            clusters: Arc::new(Mutex::new(None)),
+            decode_containers: self.decode_containers,
+            handler_options: self.handler_options.clone(),
+            has_locations: false,
+            scan_mode: self.scan_mode.clone(),
+            active_scan_mode: self.active_scan_mode.clone(),
+            search_start_index: 0,
+            scan_summary: String::new(),
+            unique_matches_only: self.unique_matches_only,
+            unique_matches: Vec::new(),
+            unique_index: HashMap::new(),
+            unique_src_len: 0,
+            expanded_unique: HashSet::new(),
+            first_match_only: self.first_match_only,
+            show_file_view: false,
+            selected_file: None,
+            file_view_offset: 0,
+            file_view_size: 0,
+            file_view_matches: Vec::new(),
+            file_view_src_len: 0,
+            entropy_map: Arc::new(Mutex::new(None)),
+            entropy_requested: None,
+            show_dir_tree_window: self.show_dir_tree_window,
+            dir_tree: self.dir_tree.clone(),
+            image_preview_requested: self.image_preview_requested.clone(),
+            image_preview: self.image_preview.clone(),
+            image_texture: self.image_texture.clone(),
+            line_indexes: self.line_indexes.clone(),
+            show_line_cols: self.show_line_cols,
+            file_view_text: self.file_view_text,
+            text_preview_scrolled_to: self.text_preview_scrolled_to,
+            hexdump_width: self.hexdump_width,
+            hexdump_grouping: self.hexdump_grouping,
+            hexdump_context: self.hexdump_context,
+            pinned_previews: self.pinned_previews.clone(),
+            filter_chips: self.filter_chips.clone(),
+            ext_filters: self.ext_filters.clone(),
+            dir_filters: self.dir_filters.clone(),
+            filtered_rows: self.filtered_rows.clone(),
+            filtered_src_len: self.filtered_src_len,
+            regex_error_span: self.regex_error_span.clone(),
+            show_pattern_tester: self.show_pattern_tester,
+            tester_sample: self.tester_sample.clone(),
+            tester_hex: self.tester_hex,
+            split_view: self.split_view,
+            selected_match_size: self.selected_match_size,
+            selected_row: self.selected_row,
+            scroll_table_to: self.scroll_table_to,
+            split_scrolled_to: self.split_scrolled_to.clone(),
+            results_detached: self.results_detached,
+            scan_progress: self.scan_progress.clone(),
+            tray_hidden: self.tray_hidden.clone(),
+            tray_on_close: self.tray_on_close,
+            presets: self.presets.clone(),
+            pending_template: self.pending_template.clone(),
+            min_match_len: self.min_match_len,
+            max_match_len: self.max_match_len,
+            similar_region: self.similar_region.clone(),
+            compare_path_str: self.compare_path_str.clone(),
+            compare_dialog: None,
+            show_byte_stats_window: self.show_byte_stats_window,
+            byte_stats: Arc::new(Mutex::new(None)),
+            padding_min_run: self.padding_min_run,
+            padding_max_period: self.padding_max_period,
+            has_encodings: false,
+            address_maps: self.address_maps.clone(),
+            has_addresses: false,
+            show_converter: self.show_converter,
+            converter: self.converter.clone(),
+            show_regex_help: self.show_regex_help,
+            results_page: self.results_page,
+            pattern_set: self.pattern_set.clone(),
+            pattern_file_dialog: None,
+            has_patterns: false,
+            show_pattern_stats: self.show_pattern_stats,
+            pattern_stats: self.pattern_stats.clone(),
+            pattern_stats_src_len: self.pattern_stats_src_len,
+            pattern_stats_sort: self.pattern_stats_sort,
+            alignment_base: self.alignment_base,
+            record_layout: self.record_layout.clone(),
+            anchors_str: self.anchors_str.clone(),
+            anchors: self.anchors.clone(),
+            custom_column_str: self.custom_column_str.clone(),
+            custom_column: self.custom_column.clone(),
+            has_custom: self.has_custom,
+            history_len: self.history_len,
+            prefetched_files: self.prefetched_files.clone(),
+            use_io_uring: self.use_io_uring,
+            read_retries: self.read_retries,
+            read_retry_backoff_ms: self.read_retry_backoff_ms,
+            read_retry: self.read_retry.clone(),
+            low_priority_workers: self.low_priority_workers,
+            worker_cores_str: self.worker_cores_str.clone(),
+            worker_cores: self.worker_cores.clone(),
+            skip_system_dirs: self.skip_system_dirs,
+            skip_list_str: self.skip_list_str.clone(),
+            file_aliases: self.file_aliases.clone(),
+            capped_files: self.capped_files.clone(),
+            files_capped: self.files_capped,
+            scanning_files: self.scanning_files.clone(),
+            files_scanned: self.files_scanned,
+            files_skipped: self.files_skipped,
+            export_job: None,
+            quick_export_template: self.quick_export_template.clone(),
+            quick_export_after_search: self.quick_export_after_search,
+            write_export_metadata: self.write_export_metadata,
+            defang_exports: self.defang_exports,
+            last_search: self.last_search.clone(),
+            search_started_at: self.search_started_at,
+            search_finished_at: self.search_finished_at,
+            high_contrast: self.high_contrast,
+            normal_visuals: self.normal_visuals.clone(),
+            row_color_rules: self.row_color_rules.clone(),
+            show_row_colors: self.show_row_colors,
+            text_filter: self.text_filter.clone(),
+            text_filter_src: self.text_filter_src.clone(),
+            saved_filters: self.saved_filters.clone(),
+            saved_filter_name: self.saved_filter_name.clone(),
+            offset_filter_from: self.offset_filter_from.clone(),
+            offset_filter_to: self.offset_filter_to.clone(),
+            offset_filter: self.offset_filter.clone(),
+            merge_adjacent: self.merge_adjacent,
+            merge_gap: self.merge_gap,
+            extract_before: self.extract_before,
+            extract_after: self.extract_after,
+            goto_offset_str: self.goto_offset_str.clone(),
+            goto_offset_error: self.goto_offset_error.clone(),
+            log_verbosity: self.log_verbosity,
+            log_filter: self.log_filter.clone(),
+            log_view: self.log_view.clone(),
+            log_view_key: self.log_view_key.clone(),
+            log_save_dialog: None,
+            file_category: self.file_category,
+            category_options: self.category_options.clone(),
+            show_category_options: self.show_category_options,
+            case_insensitive: self.case_insensitive,
+            file_view_regions: self.file_view_regions.clone(),
+            compare_patterns_enabled: self.compare_patterns_enabled,
+            compare_patterns: self.compare_patterns.clone(),
+            excluded_dirs: self.excluded_dirs.clone(),
+            skipped_files: self.skipped_files.clone(),
+            elevated_outcome: Arc::new(Mutex::new(None)),
+            elevated_running: false,
+            attribute_fs: self.attribute_fs,
+            scan_shadow_copies: self.scan_shadow_copies,
+            shadow_copies: self.shadow_copies.clone(),
+            shadow_copy_listing: Arc::clone(&self.shadow_copy_listing),
+            shadow_copies_listing: self.shadow_copies_listing,
+            shadow_copy_error: self.shadow_copy_error.clone(),
+            read_locked_files: self.read_locked_files,
+            watch_clipboard: self.watch_clipboard,
+            clipboard_monitor: self.clipboard_monitor.clone(),
+            clipboard_lookup: None,
+            single_instance: self.single_instance,
+            instance_server: self.instance_server.clone(),
+            confirm_launch_search: false,
+            pending_launches: self.pending_launches.clone(),
+            scheduled_scans: self.scheduled_scans.clone(),
+            scheduled_run: None,
+            show_scheduled_scans: self.show_scheduled_scans,
+            new_findings: self.new_findings.clone(),
+            alert_sink: None,
+            persist_results: self.persist_results,
+            restorable: None,
+            restore_outcome: Arc::clone(&self.restore_outcome),
+            results_store_error: Arc::clone(&self.results_store_error),
+            scanned_files: self.scanned_files.clone(),
+            manifest_dialog: None,
+            manifest_check: None,
         }
     }
 }
 
 impl eframe::App for QuerApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        if ctx.input(|i| i.viewport().close_requested())
+            && self.tray_on_close
+            && !self.is_search_finished()
+        {
+            // keep long scans alive rather than losing them to a stray click
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            self.hide_to_tray(ctx);
+        }
+
         self.update_main_search_ui(ctx);
     }
 }
 
 impl QuerApp {
     fn new(cc: &eframe::CreationContext<'_>) -> Self {
-        configure_text_styles(&cc);
+        configure_text_styles(cc);
+        i18n::set_language(Language::from_env());
         Self {
             regex_str: "".to_owned(),
             filter_str: "".to_owned(),
             root_folder_path: PathBuf::from("/"),
             export_file_path: PathBuf::from("/"),
-            imhex_file_path: "".to_owned(),
+            export_format: ExportFormat::Csv,
+            imhex_binary_path: "imhex".to_owned(),
+            vt_api_key: "".to_owned(),
+            mb_api_key: "".to_owned(),
+            cyberchef_recipe: "From_Hex('Auto')".to_owned(),
+            webhook_enabled: false,
+            webhook_url: "".to_owned(),
+            webhook_headers: "".to_owned(),
+            webhook_sink: None,
+            verdicts: Arc::new(Mutex::new(HashMap::new())),
             search_dir_dialog: Option::None,
             export_file_dialog: Option::None,
             content_type: ContentEnum::Hex,
@@ -244,6 +953,7 @@ impl QuerApp {
             file_walk_options: FileWalkOptions {
                 hidden_files: FilterTypeEnum::NoHidden,
                 _links: LinkBehaviorEnum::NoFollow,
+                skip_paths: Vec::new(),
             },
             progress: 0.0,
             max_files: 0,
@@ -251,26 +961,204 @@ impl QuerApp {
             max_hits: 1024 * 1024,
             file_contents: String::from(""),
             alignment: 0,
-            worker_threads: Vec::new(),
+            worker_pool: Arc::new(WorkerPool::new(
+                DEFAULT_WORKER_COUNT,
+                WorkerScheduling::default(),
+            )),
+            worker_count: DEFAULT_WORKER_COUNT,
+            search_running: false,
             findings: Vec::new(),
             rx_handles: Vec::new(),
             filecount_handles: Vec::new(),
             file_queue: Arc::new(ConcurrentQueue::unbounded()),
-            work_queue: None,
             clear_results_before_search: true,
             previous_searches: VecDeque::new(),
             log_lines: Vec::new(),
+            show_clusters_window: false,
+            similarity_threshold: 50,
+            clusters: Arc::new(Mutex::new(None)),
+            decode_containers: false,
+            handler_options: HandlerOptions::default(),
+            has_locations: false,
+            scan_mode: ScanModeEnum::Content,
+            active_scan_mode: ScanModeEnum::Content,
+            search_start_index: 0,
+            scan_summary: String::new(),
+            unique_matches_only: false,
+            unique_matches: Vec::new(),
+            unique_index: HashMap::new(),
+            unique_src_len: 0,
+            expanded_unique: HashSet::new(),
+            first_match_only: false,
+            show_file_view: false,
+            selected_file: None,
+            file_view_offset: 0,
+            file_view_size: 0,
+            file_view_matches: Vec::new(),
+            file_view_src_len: 0,
+            entropy_map: Arc::new(Mutex::new(None)),
+            entropy_requested: None,
+            show_dir_tree_window: false,
+            dir_tree: DirTree::new(Path::new("/")),
+            image_preview_requested: None,
+            image_preview: Arc::new(Mutex::new(None)),
+            image_texture: None,
+            line_indexes: HashMap::new(),
+            show_line_cols: false,
+            file_view_text: false,
+            text_preview_scrolled_to: None,
+            hexdump_width: 16,
+            hexdump_grouping: 8,
+            hexdump_context: file_view::load_hexdump_context(&settings::load_settings()),
+            pinned_previews: Vec::new(),
+            filter_chips: FilterChips::default(),
+            ext_filters: HashSet::new(),
+            dir_filters: HashSet::new(),
+            filtered_rows: Vec::new(),
+            filtered_src_len: 0,
+            regex_error_span: None,
+            show_pattern_tester: false,
+            tester_sample: String::new(),
+            tester_hex: false,
+            split_view: true,
+            selected_match_size: 0,
+            selected_row: None,
+            scroll_table_to: None,
+            split_scrolled_to: None,
+            results_detached: false,
+            scan_progress: Arc::new(ScanProgress::default()),
+            tray_hidden: Arc::new(AtomicBool::new(false)),
+            tray_on_close: true,
+            presets: presets::builtin_presets(),
+            pending_template: None,
+            min_match_len: 0,
+            max_match_len: 0,
+            similar_region: None,
+            compare_path_str: String::new(),
+            compare_dialog: None,
+            show_byte_stats_window: false,
+            byte_stats: Arc::new(Mutex::new(None)),
+            padding_min_run: 64,
+            padding_max_period: 4,
+            has_encodings: false,
+            address_maps: HashMap::new(),
+            has_addresses: false,
+            show_converter: false,
+            converter: converter::Converter::default(),
+            show_regex_help: false,
+            results_page: 0,
+            pattern_set: None,
+            pattern_file_dialog: None,
+            has_patterns: false,
+            show_pattern_stats: false,
+            pattern_stats: Vec::new(),
+            pattern_stats_src_len: 0,
+            pattern_stats_sort: (pattern_set::PatternStatsSort::Hits, true),
+            alignment_base: 0,
+            record_layout: RecordLayout::default(),
+            anchors_str: String::new(),
+            anchors: Ok(Vec::new()),
+            custom_column_str: String::new(),
+            custom_column: Ok(None),
+            has_custom: false,
+            history_len: 10,
+            prefetched_files: Arc::new(AtomicUsize::new(0)),
+            use_io_uring: false,
+            read_retries: 2,
+            read_retry_backoff_ms: 200,
+            read_retry: ReadRetry::default(),
+            low_priority_workers: false,
+            worker_cores_str: String::new(),
+            worker_cores: Ok(Vec::new()),
+            skip_system_dirs: true,
+            skip_list_str: DEFAULT_SKIP_LIST.to_string(),
+            file_aliases: HashMap::new(),
+            capped_files: HashSet::new(),
+            files_capped: 0,
+            scanning_files: Arc::new(Mutex::new(HashSet::new())),
+            files_scanned: 0,
+            files_skipped: 0,
+            export_job: None,
+            quick_export_template: DEFAULT_QUICK_EXPORT_TEMPLATE.to_string(),
+            quick_export_after_search: false,
+            write_export_metadata: true,
+            defang_exports: false,
+            last_search: None,
+            search_started_at: None,
+            search_finished_at: None,
+            high_contrast: false,
+            normal_visuals: None,
+            row_color_rules: row_colors::load_row_color_rules(&settings::load_settings()),
+            show_row_colors: false,
+            text_filter: Ok(TextFilter::default()),
+            text_filter_src: String::new(),
+            saved_filters: filters::load_saved_filters(&settings::load_settings()),
+            saved_filter_name: String::new(),
+            offset_filter_from: String::new(),
+            offset_filter_to: String::new(),
+            offset_filter: Ok(None),
+            merge_adjacent: false,
+            merge_gap: 0,
+            extract_before: 0,
+            extract_after: 0,
+            goto_offset_str: String::new(),
+            goto_offset_error: None,
+            log_verbosity: LogLevel::Info,
+            log_filter: String::new(),
+            log_view: Vec::new(),
+            log_view_key: (usize::MAX, 0, String::new()),
+            log_save_dialog: None,
+            file_category: FileCategory::Any,
+            category_options: file_types::load_category_options(&settings::load_settings()),
+            show_category_options: false,
+            case_insensitive: false,
+            file_view_regions: Vec::new(),
+            compare_patterns_enabled: true,
+            compare_patterns: [None, None],
+            excluded_dirs: filters::load_excluded_dirs(&settings::load_settings()),
+            skipped_files: Vec::new(),
+            elevated_outcome: Arc::new(Mutex::new(None)),
+            elevated_running: false,
+            attribute_fs: false,
+            scan_shadow_copies: false,
+            shadow_copies: Vec::new(),
+            shadow_copy_listing: Arc::new(Mutex::new(None)),
+            shadow_copies_listing: false,
+            shadow_copy_error: None,
+            read_locked_files: false,
+            watch_clipboard: false,
+            clipboard_monitor: None,
+            clipboard_lookup: None,
+            single_instance: single_instance::single_instance_enabled(),
+            instance_server: None,
+            confirm_launch_search: false,
+            pending_launches: VecDeque::new(),
+            scheduled_scans: scheduler::load_scheduled_scans(&settings::load_settings()),
+            scheduled_run: None,
+            show_scheduled_scans: false,
+            new_findings: HashSet::new(),
+            alert_sink: None,
+            persist_results: results_store::persist_results_enabled(),
+            restorable: results_store::persist_results_enabled()
+                .then(results_store::load_stored_results)
+                .flatten(),
+            restore_outcome: Arc::new(Mutex::new(None)),
+            results_store_error: Arc::new(Mutex::new(None)),
+            scanned_files: Vec::new(),
+            manifest_dialog: None,
+            manifest_check: None,
         }
     }
 
     fn add_export_file_dialog(&mut self, ctx: &egui::Context) {
         let mut should_close_dialog = false;
+        let mut selected = None;
         if let Some(dialog) = &mut self.export_file_dialog {
-            let viewport_id = egui::ViewportId::from_hash_of(format!("file_dialog"));
+            let viewport_id = egui::ViewportId::from_hash_of("file_dialog");
             let viewport_builder = egui::ViewportBuilder::default()
                 .with_inner_size((800.0 + 10., 600.0 + 50.))
                 .with_resizable(false)
-                .with_title(format!("Export File To"))
+                .with_title("Export File To")
                 .with_decorations(true);
 
             let viewport_cb = |ctx: &egui::Context, _| {
@@ -289,14 +1177,7 @@ impl QuerApp {
             };
 
             ctx.show_viewport_immediate(viewport_id, viewport_builder, viewport_cb);
-            if let Some(file) = dialog.take_selected() {
-                self.export_file_path = file.to_path_buf();
-                Self::export_findings_to_imhexbm(
-                    &self.findings,
-                    &self.export_file_path,
-                    &self.imhex_file_path,
-                );
-            }
+            selected = dialog.take_selected();
 
             match dialog.state() {
                 DialogState::Open => {}
@@ -314,12 +1195,20 @@ impl QuerApp {
                 self.export_file_dialog = None;
             }
         }
+        if let Some(file) = selected {
+            self.export_file_path = file.to_path_buf();
+            self.start_export(
+                self.export_format.clone(),
+                self.export_file_path.clone(),
+                ctx,
+            );
+        }
     }
 
     fn add_folder_dialog(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
         ui.horizontal(|ui| {
             let path_label =
-                ui.label(RichText::new("Folder to search: ").text_style(TextStyle::Small));
+                ui.label(RichText::new(tr("folder-to-search")).text_style(TextStyle::Small));
             let mut path_string = String::from(
                 self.root_folder_path
                     .to_str()
@@ -327,13 +1216,18 @@ impl QuerApp {
             );
 
             let path_edit = egui::TextEdit::singleline(&mut path_string).font(TextStyle::Small);
+            let open_btns_width = if self.scan_mode == ScanModeEnum::Diff {
+                110.0
+            } else {
+                60.0
+            };
             let path_resp = ui.add_sized(
-                [f32::max(ui.available_width() - 60.0, 24.0), 24.0],
+                [f32::max(ui.available_width() - open_btns_width, 24.0), 24.0],
                 path_edit,
             );
             path_resp.labelled_by(path_label.id);
 
-            if ui.button(RichText::new("Open")).clicked() {
+            if ui.button(RichText::new(tr("open"))).clicked() {
                 let mut dialog = FileDialog::new()
                     .initial_directory(self.root_folder_path.clone())
                     .as_modal(false)
@@ -345,54 +1239,27 @@ impl QuerApp {
                 self.search_dir_dialog = Some(dialog);
             }
 
-            let mut should_close_dialog = false;
-            if let Some(dialog) = &mut self.search_dir_dialog {
-                // dialog.update(ctx);
-                // if let Some(file) = dialog.take_selected() {
-                //     self.root_folder_path = file.to_path_buf();
-                //     self.search_dir_dialog = None;
-                // }
-                let viewport_id = egui::ViewportId::from_hash_of(format!("folder_dialog"));
-                let viewport_builder = egui::ViewportBuilder::default()
-                    .with_inner_size((800.0 + 10., 600.0 + 50.))
-                    .with_resizable(false)
-                    .with_title(format!("Open Folder to Search"))
-                    .with_decorations(true);
-
-                let viewport_cb = |ctx: &egui::Context, _| {
-                    egui::CentralPanel::default().show(ctx, |ui| {
-                        ui.with_layout(
-                            egui::Layout::left_to_right(egui::Align::Center)
-                                .with_main_justify(true),
-                            |_ui| {
-                                dialog.update(ctx);
-                            },
-                        );
-                    });
-
-                    if ctx.input(|i| i.viewport().close_requested()) {
-                        should_close_dialog = true;
-                    }
-                };
-
-                ctx.show_viewport_immediate(viewport_id, viewport_builder, viewport_cb);
-                if let Some(file) = dialog.take_selected() {
-                    self.root_folder_path = file.to_path_buf();
-                }
+            if self.scan_mode == ScanModeEnum::Diff
+                && ui.button(RichText::new(tr("file"))).clicked()
+            {
+                let mut dialog = FileDialog::new()
+                    .initial_directory(self.root_folder_path.clone())
+                    .as_modal(false)
+                    .title_bar(false)
+                    .movable(false)
+                    .resizable(false)
+                    .min_size([800., 600.]);
+                dialog.select_file();
+                self.search_dir_dialog = Some(dialog);
+            }
 
-                match dialog.state() {
-                    DialogState::Open => {}
-                    DialogState::Closed => {
-                        self.search_dir_dialog = None;
-                    }
-                    DialogState::Selected(_) => {} // TODO use this in a nicer fashion than rebuilding gui element
-                    DialogState::SelectedMultiple(_) => {}
-                    DialogState::Cancelled => {
-                        self.search_dir_dialog = None;
-                    }
+            if let Some(dialog) = &mut self.search_dir_dialog {
+                let (selected, done) =
+                    update_dialog_viewport(ctx, dialog, "folder_dialog", "Open Folder to Search");
+                if let Some(file) = selected {
+                    self.root_folder_path = file;
                 }
-
-                if should_close_dialog {
+                if done {
                     self.search_dir_dialog = None;
                 }
             }
@@ -401,63 +1268,60 @@ impl QuerApp {
 
     fn add_mode_selector(&mut self, ui: &mut egui::Ui) {
         ui.horizontal(|ui| {
-            ui.label(RichText::new("Mode:").text_style(TextStyle::Small));
-            ui.selectable_value(&mut self.content_type, ContentEnum::Hex, "Hex")
-                .on_hover_text("Use this mode for data in base 16 pairs. E.g. 'DE AD BE . 00 00'. '.' matches one byte.");
-            ui.selectable_value(&mut self.content_type, ContentEnum::Text, "Text")
-                .on_hover_text("Use this mode for textual data. E.g. 'Mary had a \\w+ lamb.'");
+            ui.label(RichText::new(tr("mode")).text_style(TextStyle::Small));
+            ui.selectable_value(&mut self.content_type, ContentEnum::Hex, tr("hex"))
+                .on_hover_text(tr("mode-hex-hint"));
+            ui.selectable_value(&mut self.content_type, ContentEnum::Text, tr("text"))
+                .on_hover_text(tr("mode-text-hint"));
+            ui.selectable_value(&mut self.content_type, ContentEnum::Auto, tr("auto"))
+                .on_hover_text(tr("mode-auto-hint"));
+        });
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(tr("scan")).text_style(TextStyle::Small));
+            ui.selectable_value(&mut self.scan_mode, ScanModeEnum::Content, tr("content"))
+                .on_hover_text(tr("scan-content-hint"));
+            ui.selectable_value(&mut self.scan_mode, ScanModeEnum::Metadata, tr("metadata"))
+                .on_hover_text(tr("scan-metadata-hint"));
+            ui.selectable_value(
+                &mut self.scan_mode,
+                ScanModeEnum::Duplicates,
+                tr("duplicates"),
+            )
+            .on_hover_text(tr("scan-duplicates-hint"));
+            ui.selectable_value(
+                &mut self.scan_mode,
+                ScanModeEnum::CountOnly,
+                tr("count-only"),
+            )
+            .on_hover_text(tr("scan-count-only-hint"));
+            ui.selectable_value(&mut self.scan_mode, ScanModeEnum::Diff, tr("diff"))
+                .on_hover_text(tr("scan-diff-hint"));
+            ui.selectable_value(&mut self.scan_mode, ScanModeEnum::Records, tr("records"))
+                .on_hover_text(tr("scan-records-hint"));
+            ui.selectable_value(&mut self.scan_mode, ScanModeEnum::Padding, tr("padding"))
+                .on_hover_text(tr("scan-padding-hint"));
         });
 
-        // update regex
-        match self.content_type {
-            ContentEnum::Hex => {
-                let mut unicode_off_re_str = String::from("(?-u)"); // append unicode disable so even non-utf8 stuff matches
-                match convert_simplified_hex_regex(&self.regex_str) {
-                    Ok(r) => {
-                        unicode_off_re_str.push_str(&r);
-
-                        match BytesRegex::new(&unicode_off_re_str) {
-                            Ok(unicode_off_re) => {
-                                self.regex_result = Ok(RegexEnum::Hex(unicode_off_re));
-                            }
-                            Err(re_error) => {
-                                self.regex_result =
-                                    Err(format!("Error compiling regex: {}", re_error).to_string());
-                            }
-                        }
-                    }
-                    Err(err) => match err {
-                        RegexErr::InvalidChar => {
-                            self.regex_result = Err("Invalid char inside hex regex.".to_string())
-                        }
-                        RegexErr::EmptyRegex => {
-                            self.regex_result =
-                                Err("Empty regex, please add one to search".to_string())
-                        }
-                    },
-                }
-            }
-            ContentEnum::Text => {
-                if self.regex_str.len() == 0 as usize {
-                    self.regex_result = Err("Empty regex, please add one to search".to_string())
-                } else {
-                    let re = BytesRegex::new(&self.regex_str);
+        self.update_regex();
+    }
 
-                    match re {
-                        Ok(good_re) => {
-                            self.regex_result = Ok(RegexEnum::Text(good_re));
-                        }
-                        Err(re_error) => {
-                            self.regex_result =
-                                Err(format!("Error compiling regex: {}", re_error).to_string());
-                        }
-                    }
-                }
-            }
+    fn update_regex(&mut self) {
+        self.regex_error_span = None;
+        self.regex_result =
+            compile_pattern(&self.regex_str, &self.content_type, self.case_insensitive);
+        if self.regex_result.is_err()
+            && self.content_type != ContentEnum::Hex
+            && !self.regex_str.is_empty()
+        {
+            self.regex_error_span = regex_highlight::error_span(&self.regex_str);
         }
     }
 
     fn add_regex_error_line(&mut self, ui: &mut egui::Ui) {
+        if self.pattern_set.is_some() {
+            self.add_pattern_set_line(ui);
+            return;
+        }
         // add error output if there's something up'
         match &self.regex_result {
             Ok(_good_re) => {} // no need to worry bout this
@@ -471,7 +1335,8 @@ impl QuerApp {
                         .desired_width(0.0)
                         .desired_rows(1);
                     err_msg_te = err_msg_te.text_color(egui::Color32::from_rgb(0x8f, 0x0, 0x0));
-                    ui.add_sized([ui.available_width(), 6.0], err_msg_te);
+                    let resp = ui.add_sized([ui.available_width(), 6.0], err_msg_te);
+                    set_accessible_name(&resp, &tr("pattern-error"));
                 });
             }
         }
@@ -481,39 +1346,225 @@ impl QuerApp {
         ui.collapsing("Advanced Search", |ui| {
             ui.checkbox(
                 &mut self.clear_results_before_search,
-                "Clear Results on New Search",
+                tr("clear-results-on-new-search"),
             );
+            ui.checkbox(&mut self.decode_containers, tr("search-decoded-content"))
+                .on_hover_text(tr("search-decoded-content-hint"));
+            self.add_file_category_selector(ui);
+            if ui
+                .checkbox(
+                    &mut self.case_insensitive,
+                    RichText::new(tr("case-insensitive")).text_style(TextStyle::Small),
+                )
+                .on_hover_text(tr("case-insensitive-hint"))
+                .changed()
+            {
+                self.update_regex();
+            }
+            ui.checkbox(&mut self.attribute_fs, tr("attribute-matches-to-files"))
+                .on_hover_text(tr("attribute-matches-to-files-hint"));
+            self.add_shadow_copy_options(ui);
+            if self.decode_containers {
+                ui.indent("decode_options", |ui| {
+                    ui.checkbox(
+                        &mut self.handler_options.pdf_raw_streams,
+                        tr("include-raw-pdf-streams"),
+                    );
+                });
+            }
             ui.horizontal(|ui| {
                 ui.selectable_value(
                     &mut self.file_walk_options.hidden_files,
                     FilterTypeEnum::AllFiles,
-                    "All Files",
+                    tr("all-files"),
                 );
                 ui.selectable_value(
                     &mut self.file_walk_options.hidden_files,
                     FilterTypeEnum::NoHidden,
-                    "No Hidden Files",
+                    tr("no-hidden-files"),
                 );
+                if cfg!(windows) {
+                    ui.selectable_value(
+                        &mut self.file_walk_options.hidden_files,
+                        FilterTypeEnum::NoHiddenOrSystem,
+                        tr("no-hidden-or-system-files"),
+                    );
+                }
             });
 
+            ui.checkbox(&mut self.skip_system_dirs, tr("skip-system-dirs"))
+                .on_hover_text(tr("skip-system-dirs-hint"));
+            if self.skip_system_dirs {
+                ui.indent("skip_list", |ui| {
+                    ui.collapsing(tr("skip-list"), |ui| {
+                        let resp = ui
+                            .add(
+                                egui::TextEdit::multiline(&mut self.skip_list_str)
+                                    .font(TextStyle::Small)
+                                    .desired_rows(4),
+                            )
+                            .on_hover_text(tr("skip-list-hint"));
+                        set_accessible_name(&resp, &tr("skip-list"));
+                        if ui.small_button(tr("restore-defaults")).clicked() {
+                            self.skip_list_str = DEFAULT_SKIP_LIST.to_string();
+                        }
+                    });
+                });
+            }
+            self.add_excluded_dirs(ui);
+
+            ui.checkbox(&mut self.first_match_only, tr("first-match-only"))
+                .on_hover_text(tr("first-match-only-hint"));
+            ui.add_enabled_ui(!self.first_match_only, |ui| {
+                ui.horizontal(|ui| {
+                    let max_hits_label = ui
+                        .label(RichText::new(tr("max-hits-per-file")).text_style(TextStyle::Small));
+                    ui.add(
+                        egui::widgets::Slider::new(&mut self.max_hits, 1_u32..=2_u32.pow(20))
+                            .logarithmic(true),
+                    )
+                    .labelled_by(max_hits_label.id);
+                });
+            });
             ui.horizontal(|ui| {
-                let _max_hits_label =
-                    ui.label(RichText::new("Max Hits (per File): ").text_style(TextStyle::Small));
-                ui.add(
-                    egui::widgets::Slider::new(&mut self.max_hits, 1_u32..=2_u32.pow(20))
-                        .logarithmic(true),
+                let alignment_label = ui.label(
+                    RichText::new(tr("alignment-0-to-disable")).text_style(TextStyle::Small),
                 );
-            });
-            if self.content_type == ContentEnum::Hex {
+                ui.add(egui::widgets::DragValue::new(&mut self.alignment))
+                    .labelled_by(alignment_label.id);
+                if self.alignment < 0 {
+                    self.alignment = 0;
+                }
+                let base_label =
+                    ui.label(RichText::new(tr("from-offset")).text_style(TextStyle::Small));
+                ui.add_enabled(
+                    self.alignment > 0,
+                    egui::widgets::DragValue::new(&mut self.alignment_base)
+                        .hexadecimal(1, false, true),
+                )
+                .labelled_by(base_label.id);
+            })
+            .response
+            .on_hover_text(tr("alignment-hint"));
+            ui.horizontal(|ui| {
+                let label =
+                    ui.label(RichText::new(tr("match-length-min")).text_style(TextStyle::Small));
+                let min_changed = ui
+                    .add(egui::widgets::DragValue::new(&mut self.min_match_len))
+                    .labelled_by(label.id)
+                    .changed();
+                let label =
+                    ui.label(RichText::new(tr("max-0-for-no-limit")).text_style(TextStyle::Small));
+                let max_changed = ui
+                    .add(egui::widgets::DragValue::new(&mut self.max_match_len))
+                    .labelled_by(label.id)
+                    .changed();
+                // an inverted range would filter out every match, the other bound follows along
+                if self.max_match_len > 0 && self.min_match_len > self.max_match_len {
+                    if min_changed {
+                        self.max_match_len = self.min_match_len;
+                    } else if max_changed {
+                        self.min_match_len = self.max_match_len;
+                    } else {
+                        std::mem::swap(&mut self.min_match_len, &mut self.max_match_len);
+                    }
+                }
+            })
+            .response
+            .on_hover_text(tr("match-length-hint"));
+            ui.horizontal(|ui| {
+                ui.checkbox(
+                    &mut self.merge_adjacent,
+                    RichText::new(tr("merge-adjacent-matches")).text_style(TextStyle::Small),
+                );
+                let label = ui.label(RichText::new(tr("merge-gap")).text_style(TextStyle::Small));
+                ui.add_enabled(
+                    self.merge_adjacent,
+                    egui::widgets::DragValue::new(&mut self.merge_gap),
+                )
+                .labelled_by(label.id);
+            })
+            .response
+            .on_hover_text(tr("merge-adjacent-matches-hint"));
+            ui.horizontal(|ui| {
+                let anchors_label =
+                    ui.label(RichText::new(tr("anchors")).text_style(TextStyle::Small));
+                let anchors_edit = egui::TextEdit::singleline(&mut self.anchors_str)
+                    .font(TextStyle::Small)
+                    .hint_text(tr("anchors-hint"));
+                if ui.add(anchors_edit).labelled_by(anchors_label.id).changed() {
+                    self.anchors = anchors::parse_anchors(&self.anchors_str);
+                }
+                if let Err(err) = &self.anchors {
+                    ui.colored_label(egui::Color32::from_rgb(0x8f, 0x0, 0x0), err);
+                }
+            })
+            .response
+            .on_hover_text(tr("anchors-columns-hint"));
+            ui.horizontal(|ui| {
+                let column_label =
+                    ui.label(RichText::new(tr("custom-column")).text_style(TextStyle::Small));
+                let column_edit = egui::TextEdit::singleline(&mut self.custom_column_str)
+                    .font(TextStyle::Small)
+                    .hint_text(tr("custom-column-placeholder"));
+                if ui.add(column_edit).labelled_by(column_label.id).changed() {
+                    self.custom_column =
+                        custom_column::parse_custom_column(&self.custom_column_str);
+                }
+                if let Err(err) = &self.custom_column {
+                    ui.colored_label(egui::Color32::from_rgb(0x8f, 0x0, 0x0), err);
+                }
+            })
+            .response
+            .on_hover_text(tr("custom-column-hint"));
+            if self.scan_mode == ScanModeEnum::Records {
+                let layout = &mut self.record_layout;
+                ui.horizontal(|ui| {
+                    let label = ui
+                        .label(RichText::new(tr("records-first-at")).text_style(TextStyle::Small));
+                    ui.add(
+                        egui::widgets::DragValue::new(&mut layout.start)
+                            .hexadecimal(1, false, true),
+                    )
+                    .labelled_by(label.id);
+                    let label = ui.label(RichText::new(tr("length")).text_style(TextStyle::Small));
+                    ui.add(egui::widgets::DragValue::new(&mut layout.length).range(1..=usize::MAX))
+                        .labelled_by(label.id);
+                })
+                .response
+                .on_hover_text(tr("records-layout-hint"));
                 ui.horizontal(|ui| {
-                    let _max_hits_label = ui.label(
-                        RichText::new("Alignment (0 to disable): ").text_style(TextStyle::Small),
+                    let label =
+                        ui.label(RichText::new(tr("field-offset")).text_style(TextStyle::Small));
+                    ui.add(egui::widgets::DragValue::new(&mut layout.field_offset))
+                        .labelled_by(label.id);
+                    let label = ui.label(
+                        RichText::new(tr("length-0-for-rest-of-record"))
+                            .text_style(TextStyle::Small),
                     );
-                    ui.add(egui::widgets::DragValue::new(&mut self.alignment));
-                    if self.alignment < 0 {
-                        self.alignment = 0;
-                    }
-                });
+                    ui.add(egui::widgets::DragValue::new(&mut layout.field_length))
+                        .labelled_by(label.id);
+                })
+                .response
+                .on_hover_text(tr("records-field-hint"));
+            }
+            if self.scan_mode == ScanModeEnum::Padding {
+                ui.horizontal(|ui| {
+                    let label =
+                        ui.label(RichText::new(tr("padding-min-run")).text_style(TextStyle::Small));
+                    ui.add(
+                        egui::widgets::DragValue::new(&mut self.padding_min_run).range(2..=1 << 30),
+                    )
+                    .labelled_by(label.id);
+                    let label =
+                        ui.label(RichText::new(tr("max-period")).text_style(TextStyle::Small));
+                    ui.add(
+                        egui::widgets::DragValue::new(&mut self.padding_max_period).range(1..=16),
+                    )
+                    .labelled_by(label.id);
+                })
+                .response
+                .on_hover_text(tr("padding-run-hint"));
             }
         });
     }
@@ -530,7 +1581,11 @@ impl QuerApp {
             Ok(mut file) => {
                 match file.seek(SeekFrom::Start(offset as u64)) {
                     Ok(_) => match file.read(&mut preview_buff) {
-                        Ok(_size) => return Some(preview_buff),
+                        Ok(size) => {
+                            // don't show bytes past the end of the file
+                            preview_buff.truncate(size);
+                            return Some(preview_buff);
+                        }
                         Err(_e) => {}
                     },
                     Err(_e) => {} // do nothing
@@ -542,109 +1597,215 @@ impl QuerApp {
         None
     }
 
+    // for actions on a match, the file may have gone away or shrunk since the scan. matches
+    // in decoded content bring their bytes along, the file holds them encoded.
+    fn read_match(
+        &mut self,
+        path: &String,
+        offset: usize,
+        match_length: usize,
+        decoded: Option<&[u8]>,
+    ) -> Option<Vec<u8>> {
+        if let Some(bytes) = decoded {
+            return Some(bytes.to_vec());
+        }
+        let contents = self.get_file_contents(path, offset, match_length);
+        if contents.is_none() {
+            self.log_error(format!("Unable to read {} at 0x{:x}", path, offset));
+        }
+        contents
+    }
+
     fn respond_to_match_cell(
         &mut self,
         resp: &egui::Response,
         path: &String,
         offset: usize,
         match_length: usize,
-        cell_val: &String,
+        decoded: Option<&[u8]>,
         ctx: &egui::Context,
     ) {
         resp.context_menu(|ui| {
-            if ui.button("Copy as bytes").clicked() {
-                let contents = self.get_file_contents(path, offset, match_length).unwrap();
-                ctx.copy_text(String::from_utf8_lossy(contents.as_slice()).to_string());
+            if ui.button(tr("copy-as-bytes")).clicked() {
+                if let Some(contents) = self.read_match(path, offset, match_length, decoded) {
+                    ctx.copy_text(String::from_utf8_lossy(contents.as_slice()).to_string());
+                }
+                ui.close_menu();
+            }
+            if ui.button(tr("copy-as-hex-bytes")).clicked() {
+                if let Some(contents) = self.read_match(path, offset, match_length, decoded) {
+                    ctx.copy_text(self.bytes_to_hex(contents.as_slice(), match_length));
+                }
+                ui.close_menu();
+            }
+            if ui
+                .button(tr("copy-as-escaped-regex"))
+                .on_hover_text(tr("copy-as-escaped-regex-hint"))
+                .clicked()
+            {
+                if let Some(contents) = self.read_match(path, offset, match_length, decoded) {
+                    ctx.copy_text(code_literals::escaped_regex(&contents));
+                }
+                ui.close_menu();
+            }
+            ui.menu_button(tr("copy-as-code"), |ui| {
+                let literal: Option<fn(&[u8], usize) -> String> =
+                    if ui.button(tr("copy-as-c-array")).clicked() {
+                        Some(code_literals::c_array)
+                    } else if ui.button(tr("copy-as-rust-slice")).clicked() {
+                        Some(|bytes, _offset| code_literals::rust_slice(bytes))
+                    } else if ui.button(tr("copy-as-python-bytes")).clicked() {
+                        Some(|bytes, _offset| code_literals::python_bytes(bytes))
+                    } else {
+                        None
+                    };
+                if let Some(literal) = literal {
+                    if let Some(contents) = self.read_match(path, offset, match_length, decoded) {
+                        ctx.copy_text(literal(&contents, offset));
+                    }
+                    ui.close_menu();
+                }
+            });
+            if ui
+                .add_enabled(
+                    decoded.is_none(),
+                    egui::Button::new(tr("find-similar-regions-menu")),
+                )
+                .on_disabled_hover_text(tr("offset-inside-decoded-content"))
+                .clicked()
+            {
+                self.open_similar_regions(path, offset, match_length);
                 ui.close_menu();
             }
-            if ui.button("Copy as hex bytes").clicked() {
-                let contents = self.get_file_contents(path, offset, match_length).unwrap();
-                let hex_bytes_str = &mut self.bytes_to_hex(contents.as_slice(), match_length);
-                ctx.copy_text(hex_bytes_str.to_string());
+            if ui.button(tr("open-match-in-cyberchef")).clicked() {
+                if let Some(contents) = self.read_match(path, offset, match_length, decoded) {
+                    let hex_bytes_str = self.bytes_to_hex(contents.as_slice(), match_length);
+                    let url = cyberchef_url(&self.cyberchef_recipe, &hex_bytes_str);
+                    ctx.open_url(egui::OpenUrl::new_tab(url));
+                }
                 ui.close_menu();
             }
-            if ui.button(format!("Sort ascending")).clicked() {
-                self.findings
-                    .sort_by(|a, b| a.match_content.cmp(&b.match_content));
+            if ui.button(tr("sort-ascending")).clicked() {
+                // hit_count first so count-only summaries sort numerically
+                self.findings.sort_by(|a, b| {
+                    (a.hit_count, &a.match_content).cmp(&(b.hit_count, &b.match_content))
+                });
+                self.invalidate_row_order();
                 ui.close_menu();
             }
-            if ui.button(format!("Sort descending")).clicked() {
-                self.findings
-                    .sort_by(|a, b| b.match_content.cmp(&a.match_content));
+            if ui.button(tr("sort-descending")).clicked() {
+                self.findings.sort_by(|a, b| {
+                    (b.hit_count, &b.match_content).cmp(&(a.hit_count, &a.match_content))
+                });
+                self.invalidate_row_order();
                 ui.close_menu();
             }
-            if ui.button(format!("Cancel")).clicked() {
+            if ui.button(tr("cancel")).clicked() {
                 ui.close_menu();
             }
         });
-        resp.clone().on_hover_text(cell_val);
     }
 
     fn respond_to_filepath_cell(
         &mut self,
         resp: &egui::Response,
         path_value: &String,
+        aliases: Option<Arc<[String]>>,
         ctx: &egui::Context,
     ) {
         let path = Path::new(path_value);
         let parent = path.parent().unwrap().to_str().unwrap();
         let filename = path.file_name().unwrap().to_str().unwrap();
         resp.context_menu(|ui| {
-            if ui.button(format!("Copy full path")).clicked() {
+            if ui.button(tr("copy-full-path")).clicked() {
                 ctx.copy_text(path_value.to_string());
                 ui.close_menu();
             }
-            if ui.button(format!("Copy filename")).clicked() {
+            if ui.button(tr("copy-filename")).clicked() {
                 ctx.copy_text(filename.to_string());
                 ui.close_menu();
             }
-            if ui.button(format!("Copy enclosing dir")).clicked() {
+            if ui.button(tr("copy-enclosing-dir")).clicked() {
                 ctx.copy_text(parent.to_string());
                 ui.close_menu();
             }
             ui.separator();
-            if ui.button(format!("Sort ascending")).clicked() {
-                self.findings.sort_by(|a, b| a.filepath.cmp(&b.filepath));
+            if ui
+                .button(tr("exclude-dir-from-results"))
+                .on_hover_text(tr_args("exclude-dir-hint", &[("dir", parent.into())]))
+                .clicked()
+            {
+                self.exclude_dir_from_results(parent);
                 ui.close_menu();
             }
-            if ui.button(format!("Sort descending")).clicked() {
-                self.findings.sort_by(|a, b| b.filepath.cmp(&a.filepath));
+            if ui
+                .button(tr("exclude-dir-from-searches"))
+                .on_hover_text(tr_args("exclude-dir-hint", &[("dir", parent.into())]))
+                .clicked()
+            {
+                self.exclude_dir_from_searches(parent);
                 ui.close_menu();
             }
-            ui.separator();
             if ui
-                .button(format!("Export File results to .imhexbm..."))
+                .button(tr("copy-as-file-reference"))
+                .on_hover_text(tr("copy-as-file-reference-hint"))
                 .clicked()
             {
+                ctx.copy_text(file_uri(path_value));
+                ui.close_menu();
+            }
+            if let Some(aliases) = &aliases {
+                if ui.button(tr("copy-other-paths-to-this-file")).clicked() {
+                    ctx.copy_text(aliases.join("\n"));
+                    ui.close_menu();
+                }
+            }
+            ui.separator();
+            if ui.button(tr("sort-ascending")).clicked() {
+                self.findings.sort_by(|a, b| a.filepath.cmp(&b.filepath));
+                self.invalidate_row_order();
+                ui.close_menu();
+            }
+            if ui.button(tr("sort-descending")).clicked() {
+                self.findings.sort_by(|a, b| b.filepath.cmp(&a.filepath));
+                self.invalidate_row_order();
+                ui.close_menu();
+            }
+            ui.separator();
+            if ui.button(tr("export-imhex-bookmarks")).clicked() {
                 ui.close_menu();
 
-                self.log(format!("Exporting {} to imhexbm", path_value));
-
-                let mut dialog = FileDialog::new()
-                    .initial_directory(self.export_file_path.clone())
-                    .as_modal(false)
-                    .title_bar(false)
-                    .movable(false)
-                    .resizable(false)
-                    .min_size([800., 600.]); //.show_files_filter(filter);
-                dialog.save_file();
-                self.export_file_dialog = Some(dialog);
-                self.imhex_file_path = path_value.clone();
+                self.open_export_file_dialog(ExportFormat::ImHexBookmarks(path_value.clone()));
+            }
+            if ui.button(tr("byte-statistics-menu")).clicked() {
+                self.request_byte_stats(vec![path_value.clone()], ctx);
+                ui.close_menu();
             }
+            ui.menu_button(tr("hash-lookup"), |ui| {
+                for service in [LookupService::VirusTotal, LookupService::MalwareBazaar] {
+                    if ui.button(service.name()).clicked() {
+                        self.lookup_file_hash(service, path_value, ctx);
+                        ui.close_menu();
+                    }
+                }
+            });
 
             ui.separator();
-            if ui.button("Cancel").clicked() {
+            if ui.button(tr("cancel")).clicked() {
                 ui.close_menu();
             }
         });
 
-        resp.clone().on_hover_text(format!("{path_value}"));
+        resp.clone().on_hover_text(path_value.to_string());
     }
 
     fn respond_to_offset_cell(
         &mut self,
         resp: &egui::Response,
+        path: &String,
         offset: usize,
+        match_length: usize,
+        decoded: bool,
         ctx: &egui::Context,
     ) {
         let hex_value_w_0x = format!("0x{offset:x}");
@@ -654,59 +1815,106 @@ impl QuerApp {
 
         resp.context_menu(|ui| {
             if ui
-                .button(format!("Copy as hex: {hex_value_w_0x}"))
+                .button(tr_args(
+                    "copy-as-hex",
+                    &[("value", hex_value_w_0x.as_str().into())],
+                ))
                 .clicked()
             {
                 ctx.copy_text(hex_value_w_0x.to_string());
                 ui.close_menu();
             }
-            if ui.button(format!("Copy as hex: {hex_value}")).clicked() {
+            if ui
+                .button(tr_args(
+                    "copy-as-hex",
+                    &[("value", hex_value.as_str().into())],
+                ))
+                .clicked()
+            {
                 ctx.copy_text(hex_value.to_string());
                 ui.close_menu();
             }
-            if ui.button(format!("Copy as decimal: {dec_value}")).clicked() {
+            if ui
+                .button(tr_args(
+                    "copy-as-decimal",
+                    &[("value", dec_value.as_str().into())],
+                ))
+                .clicked()
+            {
                 ctx.copy_text(dec_value.to_string());
                 ui.close_menu();
             }
-            ui.separator();
-            if ui.button(format!("Sort ascending")).clicked() {
-                self.findings.sort_by(|a, b| a.offset.cmp(&b.offset));
-                ui.close_menu();
-            }
-            if ui.button(format!("Sort descending")).clicked() {
-                self.findings.sort_by(|a, b| b.offset.cmp(&a.offset));
+            if ui
+                .add_enabled(!decoded, egui::Button::new(tr("copy-path-and-offset")))
+                .on_hover_text(tr("copy-path-and-offset-hint"))
+                .on_disabled_hover_text(tr("offset-inside-decoded-content"))
+                .clicked()
+            {
+                ctx.copy_text(format!("{}:{}", path, hex_value_w_0x));
                 ui.close_menu();
             }
-            if ui.button("Cancel").clicked() {
-                ui.close_menu();
+            ui.separator();
+            if ui
+                .add_enabled(!decoded, egui::Button::new(tr("open-in-imhex-at-offset")))
+                .on_disabled_hover_text(tr("offset-inside-decoded-content"))
+                .clicked()
+            {
+                self.open_in_imhex(path, offset, match_length);
+                ui.close_menu();
+            }
+            ui.separator();
+            if ui.button(tr("sort-ascending")).clicked() {
+                self.findings.sort_by_key(|a| a.offset);
+                self.invalidate_row_order();
+                ui.close_menu();
+            }
+            if ui.button(tr("sort-descending")).clicked() {
+                self.findings.sort_by_key(|f| std::cmp::Reverse(f.offset));
+                self.invalidate_row_order();
+                ui.close_menu();
+            }
+            if ui.button(tr("cancel")).clicked() {
+                ui.close_menu();
             }
         });
 
         resp.clone().on_hover_text(hover_text);
     }
 
-    fn bytes_to_hexdump(&self, array: &[u8]) -> String {
-        let div_16 = array.len() as f32 / 16_f32;
-        let mut hexdump = String::with_capacity(div_16.ceil() as usize * 65);
-        let mut ascii_dump = String::with_capacity(16);
-        for (pos, byte) in array.iter().enumerate().take(array.len()) {
-            hexdump.push_str(&format!("{byte:02X} "));
-
-            if byte.is_ascii() && !byte.is_ascii_whitespace() && !byte.eq(&0) {
-                ascii_dump.push(*byte as char)
-            } else {
-                ascii_dump.push('.');
+    // `base_offset` is the file offset of array[0], used for the offset column
+    fn bytes_to_hexdump(&self, array: &[u8], base_offset: usize) -> String {
+        let width = std::cmp::max(self.hexdump_width, 1);
+        let mut hexdump =
+            String::with_capacity((array.len() + width - 1) / width * (width * 4 + 16));
+        for (row_idx, row) in array.chunks(width).enumerate() {
+            hexdump.push_str(&format!("{:08X}  ", base_offset + row_idx * width));
+
+            for pos in 0..width {
+                match row.get(pos) {
+                    Some(byte) => hexdump.push_str(&format!("{byte:02X} ")),
+                    // pad the last (partial) row so the ascii column lines up
+                    None => hexdump.push_str("   "),
+                }
+                if self.hexdump_grouping > 0
+                    && pos % self.hexdump_grouping == self.hexdump_grouping - 1
+                    && pos != width - 1
+                {
+                    hexdump.push(' ');
+                }
             }
 
-            if pos % 8 == 7 {
-                hexdump.push_str("    ");
-                hexdump.push_str(&ascii_dump);
-                ascii_dump.clear();
-                hexdump.push('\n');
+            hexdump.push_str("   ");
+            for byte in row.iter() {
+                if byte.is_ascii() && !byte.is_ascii_whitespace() && !byte.eq(&0) {
+                    hexdump.push(*byte as char)
+                } else {
+                    hexdump.push('.');
+                }
             }
+            hexdump.push('\n');
         }
 
-        return hexdump;
+        hexdump
     }
 
     fn bytes_to_hex(&self, array: &[u8], size: usize) -> String {
@@ -715,15 +1923,7 @@ impl QuerApp {
             hexdump.push_str(&format!("{byte:02X}"));
         }
 
-        return hexdump;
-    }
-
-    fn cap_string_length(&self, input: &str, max_length: usize) -> String {
-        if max_length == 0 {
-            String::new() // Return an empty string if max_length is 0
-        } else {
-            input.chars().take(max_length).collect()
-        }
+        hexdump
     }
 
     fn response_to_hex_preview(
@@ -731,48 +1931,387 @@ impl QuerApp {
         resp: egui::Response,
         path: &String,
         offset: usize,
-        _match_length: usize,
+        match_length: usize,
+        decoded: bool,
         ctx: &egui::Context,
     ) {
+        if decoded {
+            resp.on_hover_text(tr("offset-inside-decoded-content"));
+            return;
+        }
+        // context on both sides of the match, huge matches are cut short
+        let start = offset.saturating_sub(self.hexdump_context.0);
+        let length = (offset - start) + std::cmp::min(match_length, 4096) + self.hexdump_context.1;
+
         resp.context_menu(|ui| {
-            if ui.button("Copy as hexdump").clicked() {
-                let offset = std::cmp::max::<i64>(0 as i64, offset as i64 - 32) as usize;
-                let contents = self.get_file_contents(path, offset, 64).unwrap();
-                let hex_dump_str = &mut self.bytes_to_hexdump(contents.as_slice());
-                ctx.copy_text(hex_dump_str.to_string());
+            if ui.button(tr("copy-as-hexdump")).clicked() {
+                if let Some(contents) = self.get_file_contents(path, start, length) {
+                    ctx.copy_text(self.bytes_to_hexdump(contents.as_slice(), start));
+                }
                 ui.close_menu();
             }
-            if ui.button("Cancel").clicked() {
+            if ui.button(tr("cancel")).clicked() {
                 ui.close_menu();
             }
         });
 
-        resp.on_hover_ui(|ui| {
-            let offset = std::cmp::max::<i64>(0 as i64, offset as i64 - 32) as usize;
-            let contents = self.get_file_contents(path, offset, 64).unwrap();
-
-            let hex_dump_str = &mut self.bytes_to_hexdump(contents.as_slice());
-            ui.code_editor(hex_dump_str);
-        });
+        if resp.clicked() {
+            self.pin_preview(path, offset, match_length);
+        }
+        resp.on_hover_text(tr("preview-pin-hint"));
     }
 
-    fn add_listing_and_content_view(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+    fn receive_results(&mut self) {
+        // offsets of summaries and decoded content don't point into the file itself
+        let has_file_offsets = matches!(
+            self.active_scan_mode,
+            ScanModeEnum::Content
+                | ScanModeEnum::Diff
+                | ScanModeEnum::Padding
+                | ScanModeEnum::Records
+        );
+        let mut items = Vec::new();
         for rx in self.rx_handles.iter() {
-            for item in rx.try_iter() {
-                self.findings.push(item);
+            items.extend(rx.try_iter());
+        }
+        for mut item in items {
+            if has_file_offsets && !item.decoded {
+                item.virtual_address = self.virtual_address(&item.filepath, item.offset);
+                self.has_addresses |= item.virtual_address.is_some();
+                if let (None, Some(address)) = (&item.owner, item.virtual_address) {
+                    item.owner = self.address_module(&item.filepath, address);
+                }
             }
+            if let Some(sink) = &self.webhook_sink {
+                sink.send(item.to_json());
+            }
+            item.aliases = self.file_aliases.get(&item.filepath).cloned();
+            self.has_locations |= item.location.is_some() || item.owner.is_some();
+            self.has_encodings |= item.encoding.is_some();
+            self.has_patterns |= item.pattern.is_some();
+            self.has_custom |= item.custom.is_some();
+            // decoded offsets would open the file at some unrelated spot
+            let first_offset = if item.decoded { 0 } else { item.offset };
+            self.dir_tree
+                .add_match(Path::new(&item.filepath), item.hit_count, first_offset);
+            self.findings.push(item);
+        }
+
+        let mut errors = self.worker_pool.take_errors();
+        if let Some(sink) = &self.webhook_sink {
+            errors.extend(sink.take_errors());
+        }
+        for err in errors {
+            self.log_error(err);
         }
 
-        for rx in self.filecount_handles.iter() {
-            for val in rx.try_iter() {
-                let mut num = self.current_files_mtx.lock().unwrap();
-                *num += val;
+        let statuses: Vec<(FileStatus, Duration)> = self
+            .filecount_handles
+            .iter()
+            .flat_map(|rx| rx.try_iter())
+            .collect();
+        for (status, elapsed) in statuses {
+            match status {
+                FileStatus::Scanned(path) => {
+                    self.log_file_timing(&path, elapsed);
+                    self.dir_tree.add_scanned(&path);
+                    self.scanned_files.push(path);
+                    self.files_scanned += 1;
+                }
+                FileStatus::Capped(path) => {
+                    self.log_file_timing(&path, elapsed);
+                    self.dir_tree.add_scanned(&path);
+                    self.capped_files.insert(path.to_string_lossy().to_string());
+                    self.scanned_files.push(path);
+                    self.files_capped += 1;
+                    self.files_scanned += 1;
+                }
+                FileStatus::Skipped(path) => {
+                    self.dir_tree.add_skipped(&path);
+                    self.skipped_files.push(path);
+                    self.files_skipped += 1;
+                }
+                FileStatus::Failed(path, err) => {
+                    self.log_error(format!("Scanning {} failed: {}", path.display(), err));
+                    self.dir_tree.add_skipped(&path);
+                    self.skipped_files.push(path);
+                    self.files_skipped += 1;
+                }
             }
+            let mut num = self.current_files_mtx.lock().unwrap();
+            *num += 1;
         }
+    }
 
-        ui.separator();
+    // for when findings were rewritten in place rather than appended to
+    fn invalidate_finding_caches(&mut self) {
+        self.unique_matches.clear();
+        self.unique_index.clear();
+        self.expanded_unique.clear();
+        self.unique_src_len = 0;
+        self.file_view_matches.clear();
+        self.file_view_regions.clear();
+        self.file_view_src_len = 0;
+        self.reset_filter_chips();
+    }
+
+    // shows which page of a big result set the table is on, returns the rows of that page
+    fn add_page_controls(&mut self, ui: &mut egui::Ui, num_rows: usize) -> std::ops::Range<usize> {
+        let num_pages = std::cmp::max((num_rows + RESULTS_PAGE_ROWS - 1) / RESULTS_PAGE_ROWS, 1);
+        self.results_page = std::cmp::min(self.results_page, num_pages - 1);
+        let start = self.results_page * RESULTS_PAGE_ROWS;
+        let end = std::cmp::min(start + RESULTS_PAGE_ROWS, num_rows);
+
+        if num_pages > 1 {
+            ui.horizontal(|ui| {
+                let first_page = self.results_page == 0;
+                let last_page = self.results_page + 1 == num_pages;
+                if ui
+                    .add_enabled(!first_page, egui::Button::new("|<"))
+                    .clicked()
+                {
+                    self.results_page = 0;
+                }
+                if ui
+                    .add_enabled(!first_page, egui::Button::new("<"))
+                    .clicked()
+                {
+                    self.results_page -= 1;
+                }
+                ui.label(tr_args(
+                    "results-page",
+                    &[
+                        ("start", (start + 1).into()),
+                        ("end", end.into()),
+                        ("total", num_rows.into()),
+                        ("page", (self.results_page + 1).into()),
+                        ("pages", num_pages.into()),
+                    ],
+                ));
+                if ui.add_enabled(!last_page, egui::Button::new(">")).clicked() {
+                    self.results_page += 1;
+                }
+                if ui
+                    .add_enabled(!last_page, egui::Button::new(">|"))
+                    .clicked()
+                {
+                    self.results_page = num_pages - 1;
+                }
+            });
+        }
+        start..end
+    }
+
+    // arrow keys, PageUp/PageDown and Home/End step through the (filtered) results
+    // while nothing else has keyboard focus
+    fn handle_row_stepping(&mut self, ctx: &egui::Context, num_rows: usize, filtering: bool) {
+        if num_rows == 0 || ctx.memory(|mem| mem.focused().is_some()) {
+            return;
+        }
+        let step = ctx.input(|i| {
+            if i.key_pressed(egui::Key::ArrowDown) {
+                Some(RowStep::Down(1))
+            } else if i.key_pressed(egui::Key::ArrowUp) {
+                Some(RowStep::Up(1))
+            } else if i.key_pressed(egui::Key::PageDown) {
+                Some(RowStep::Down(ROWS_PER_KEY_PAGE))
+            } else if i.key_pressed(egui::Key::PageUp) {
+                Some(RowStep::Up(ROWS_PER_KEY_PAGE))
+            } else if i.key_pressed(egui::Key::Home) {
+                Some(RowStep::First)
+            } else if i.key_pressed(egui::Key::End) {
+                Some(RowStep::Last)
+            } else {
+                None
+            }
+        });
+        let next_row = match (self.selected_row, step) {
+            (_, None) => return,
+            (_, Some(RowStep::First)) | (None, Some(_)) => 0,
+            (_, Some(RowStep::Last)) => num_rows - 1,
+            (Some(row), Some(RowStep::Down(rows))) => std::cmp::min(row + rows, num_rows - 1),
+            (Some(row), Some(RowStep::Up(rows))) => row.saturating_sub(rows),
+        };
+        let next_row = std::cmp::min(next_row, num_rows - 1);
+
+        let finding_idx = if filtering {
+            self.filtered_rows[next_row]
+        } else {
+            next_row
+        };
+        let finding = &self.findings[finding_idx];
+        let (path, offset, match_size, decoded) = (
+            finding.filepath.clone(),
+            finding.offset,
+            finding.match_size,
+            finding.decoded,
+        );
+        self.selected_row = Some(next_row);
+        self.scroll_table_to = Some(next_row);
+        self.results_page = next_row / RESULTS_PAGE_ROWS;
+        if !decoded {
+            self.select_finding(&path, offset, match_size);
+        }
+    }
+
+    fn update_unique_matches(&mut self) {
+        if self.findings.len() < self.unique_src_len {
+            // findings were cleared or regrouped, start over
+            self.unique_matches.clear();
+            self.unique_index.clear();
+            self.expanded_unique.clear();
+            self.unique_src_len = 0;
+        }
+
+        for finding in self.findings[self.unique_src_len..].iter() {
+            let idx = *self
+                .unique_index
+                .entry(finding.match_content.clone())
+                .or_insert_with(|| {
+                    self.unique_matches.push(UniqueMatch {
+                        match_content: finding.match_content.clone(),
+                        locations: Vec::new(),
+                    });
+                    self.unique_matches.len() - 1
+                });
+            self.unique_matches[idx]
+                .locations
+                .push((finding.filepath.clone(), finding.offset));
+        }
+        self.unique_src_len = self.findings.len();
+    }
+
+    fn add_unique_matches_view(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        const MAX_SHOWN_LOCATIONS: usize = 50;
+        self.update_unique_matches();
+
+        let row_height = 22.0;
+        let heights: Vec<f32> = self
+            .unique_matches
+            .iter()
+            .enumerate()
+            .map(|(idx, unique)| {
+                if self.expanded_unique.contains(&idx) {
+                    let shown = std::cmp::min(unique.locations.len(), MAX_SHOWN_LOCATIONS + 1);
+                    row_height * (1 + shown) as f32
+                } else {
+                    row_height
+                }
+            })
+            .collect();
 
         TableBuilder::new(ui)
+            .striped(true)
+            .max_scroll_height(f32::INFINITY)
+            .resizable(true)
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Min))
+            .column(Column::remainder().at_least(72.))
+            .column(Column::auto().at_least(64.))
+            .column(Column::remainder().at_least(72.))
+            .header(20.0, |mut header| {
+                header.col(|ui| {
+                    ui.heading(tr("match"))
+                        .on_hover_text(tr("column-match-hint"));
+                    ui.separator();
+                });
+                header.col(|ui| {
+                    ui.heading(tr("count"))
+                        .on_hover_text(tr("column-count-hint"));
+                    ui.separator();
+                });
+                header.col(|ui| {
+                    ui.heading(tr("locations"))
+                        .on_hover_text(tr("column-locations-hint"));
+                    ui.separator();
+                });
+            })
+            .body(|body| {
+                body.heterogeneous_rows(heights.into_iter(), |mut row| {
+                    let row_index = row.index();
+                    let unique = &self.unique_matches[row_index];
+
+                    let match_content = unique.match_content.preview(1000);
+                    let (_rect, resp) = row.col(|ui| {
+                        let label = egui::Label::new(&match_content)
+                            .truncate()
+                            .selectable(false);
+                        ui.add(label);
+                    });
+                    resp.on_hover_text(&match_content).context_menu(|ui| {
+                        if ui.button(tr("copy-match")).clicked() {
+                            ctx.copy_text(unique.match_content.to_string());
+                            ui.close_menu();
+                        }
+                    });
+
+                    row.col(|ui| {
+                        ui.label(format!("{}", unique.locations.len()));
+                    });
+
+                    row.col(|ui| {
+                        ui.vertical(|ui| {
+                            let expanded = self.expanded_unique.contains(&row_index);
+                            let toggle_text = format!(
+                                "{} {} locations",
+                                if expanded { "⏷" } else { "⏵" },
+                                unique.locations.len()
+                            );
+                            if ui.small_button(toggle_text).clicked() {
+                                if expanded {
+                                    self.expanded_unique.remove(&row_index);
+                                } else {
+                                    self.expanded_unique.insert(row_index);
+                                }
+                            }
+                            if expanded {
+                                for (path, offset) in
+                                    unique.locations.iter().take(MAX_SHOWN_LOCATIONS)
+                                {
+                                    let label = egui::Label::new(format!("{path} @ 0x{offset:x}"))
+                                        .truncate();
+                                    ui.add_sized([ui.available_width(), row_height - 4.0], label);
+                                }
+                                if unique.locations.len() > MAX_SHOWN_LOCATIONS {
+                                    ui.label(tr_args(
+                                        "n-more",
+                                        &[(
+                                            "more",
+                                            (unique.locations.len() - MAX_SHOWN_LOCATIONS).into(),
+                                        )],
+                                    ));
+                                }
+                            }
+                        });
+                    });
+                });
+            });
+    }
+
+    fn add_listing_and_content_view(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        self.receive_results();
+
+        ui.separator();
+
+        if self.unique_matches_only {
+            self.add_unique_matches_view(ui, ctx);
+            return;
+        }
+
+        let show_verdicts = !self.vt_api_key.is_empty() || !self.mb_api_key.is_empty();
+
+        let filtering = self.has_active_filters();
+        if filtering {
+            self.update_filtered_rows();
+        }
+        let num_rows = if filtering {
+            self.filtered_rows.len()
+        } else {
+            self.findings.len()
+        };
+        self.handle_row_stepping(ctx, num_rows, filtering);
+        let page_rows = self.add_page_controls(ui, num_rows);
+
+        let mut table = TableBuilder::new(ui)
             .striped(true)
             .max_scroll_height(f32::INFINITY)
             .sense(egui::Sense {
@@ -784,46 +2323,182 @@ impl QuerApp {
             .column(Column::remainder().at_least(72.))
             .column(Column::remainder().at_least(64.))
             .column(Column::remainder().at_least(64.))
-            .column(Column::remainder())
+            .column(Column::remainder());
+        if self.has_locations {
+            table = table.column(Column::remainder().at_least(64.));
+        }
+        if show_verdicts {
+            table = table.column(Column::remainder().at_least(64.));
+        }
+        if self.show_line_cols {
+            table = table.column(Column::remainder().at_least(48.));
+        }
+        if self.has_encodings {
+            table = table.column(Column::remainder().at_least(56.));
+        }
+        if self.has_addresses {
+            table = table.column(Column::remainder().at_least(72.));
+        }
+        if self.has_patterns {
+            table = table.column(Column::remainder().at_least(72.));
+        }
+        if self.has_custom {
+            table = table.column(Column::remainder().at_least(64.));
+        }
+        let anchors = self.anchors.clone().unwrap_or_default();
+        for _anchor in anchors.iter() {
+            table = table.column(Column::remainder().at_least(64.));
+        }
+        if let Some(row) = self.scroll_table_to.take() {
+            if page_rows.contains(&row) {
+                table = table.scroll_to_row(row - page_rows.start, Some(egui::Align::Center));
+            }
+        }
+
+        table
             .header(20.0, |mut header| {
                 header.col(|ui| {
                     ui.horizontal(|ui| {
-                        ui.heading("File Path").on_hover_text(
-                            "File path to the file that a given match was found in.",
-                        );
+                        ui.heading(tr("file-path"))
+                            .on_hover_text(tr("column-file-path-hint"));
                     });
                     ui.separator();
                 });
                 header.col(|ui| {
-                    let resp = ui.heading("Offset");
-                    resp.on_hover_text("Offset into the file that the match starts at.");
+                    let resp = ui.heading(tr("offset"));
+                    resp.on_hover_text(tr("column-offset-hint"));
                     ui.separator();
                 });
                 header.col(|ui| {
-                    ui.heading("Match")
-                        .on_hover_text("Contents of the resulting match");
+                    ui.heading(tr("match"))
+                        .on_hover_text(tr("column-match-hint"));
                     ui.separator();
                 });
                 header.col(|ui| {
-                    ui.heading("Preview").on_hover_text("Visualize column");
+                    ui.heading(tr("preview"))
+                        .on_hover_text(tr("visualize-column"));
                     ui.separator();
                 });
+                if self.has_locations {
+                    header.col(|ui| {
+                        ui.heading(tr("location"))
+                            .on_hover_text(tr("column-location-hint"));
+                        ui.separator();
+                    });
+                }
+                if show_verdicts {
+                    header.col(|ui| {
+                        ui.heading(tr("verdict"))
+                            .on_hover_text(tr("column-verdict-hint"));
+                        ui.separator();
+                    });
+                }
+                if self.show_line_cols {
+                    header.col(|ui| {
+                        ui.heading(tr("line-col"))
+                            .on_hover_text(tr("column-line-col-hint"));
+                        ui.separator();
+                    });
+                }
+                if self.has_encodings {
+                    header.col(|ui| {
+                        ui.heading(tr("encoding"))
+                            .on_hover_text(tr("column-encoding-hint"));
+                        ui.separator();
+                    });
+                }
+                if self.has_addresses {
+                    header.col(|ui| {
+                        ui.heading(tr("va")).on_hover_text(tr("column-va-hint"));
+                        ui.separator();
+                    });
+                }
+                if self.has_patterns {
+                    header.col(|ui| {
+                        ui.heading(tr("pattern"))
+                            .on_hover_text(tr("column-pattern-hint"));
+                        ui.separator();
+                    });
+                }
+                if self.has_custom {
+                    let name = match &self.custom_column {
+                        Ok(Some(column)) => column.name.clone(),
+                        _ => tr("custom"),
+                    };
+                    header.col(|ui| {
+                        ui.heading(name)
+                            .on_hover_text(tr("custom-column-header-hint"));
+                        ui.separator();
+                    });
+                }
+                for anchor in anchors.iter() {
+                    header.col(|ui| {
+                        ui.heading(&anchor.name).on_hover_text(tr_args(
+                            "anchor-column-hint",
+                            &[
+                                ("name", anchor.name.as_str().into()),
+                                ("offset", format!("0x{:x}", anchor.offset).into()),
+                            ],
+                        ));
+                        ui.separator();
+                    });
+                }
             })
             .body(|body| {
                 let row_height = 22.0;
-                let num_rows = std::cmp::min(self.findings.len(), 10_000_000);
-                body.rows(row_height, num_rows, |mut row| {
-                    let row_index = row.index();
+                body.rows(row_height, page_rows.len(), |mut row| {
+                    let result_row = page_rows.start + row.index();
+                    let row_index = if filtering {
+                        self.filtered_rows[result_row]
+                    } else {
+                        result_row
+                    };
 
                     let path = &self.findings[row_index].filepath.clone();
+                    let aliases = self.findings[row_index].aliases.clone();
+                    row.set_selected(self.is_selected(path, self.findings[row_index].offset));
+                    let capped = self.capped_files.contains(path);
+                    let tint = if self.new_findings.is_empty() {
+                        self.row_tint(&self.findings[row_index])
+                    } else {
+                        let key = (path.clone(), self.findings[row_index].offset);
+                        match self.new_findings.contains(&key) {
+                            true => Some(NEW_FINDING_TINT),
+                            false => self.row_tint(&self.findings[row_index]),
+                        }
+                    };
                     let (_rect, resp) = row.col(|ui| {
-                        let label = egui::Label::new(format!("{path}"))
-                            .truncate()
-                            .selectable(false);
-                        ui.add(label);
+                        if let Some(color) = tint {
+                            paint_row_tint(ui, color);
+                        }
+                        let mut text = match &aliases {
+                            Some(aliases) => tr_args(
+                                "path-with-links",
+                                &[
+                                    ("path", path.as_str().into()),
+                                    ("links", aliases.len().into()),
+                                ],
+                            ),
+                            None => path.to_string(),
+                        };
+                        if capped {
+                            text.insert_str(0, "⚠ ");
+                        }
+                        let label = egui::Label::new(text).truncate().selectable(false);
+                        let mut label_resp = ui.add(label);
+                        if capped {
+                            label_resp = label_resp.on_hover_text(tr("capped-file-hint"));
+                        }
+                        if let Some(aliases) = &aliases {
+                            label_resp.on_hover_text(format!(
+                                "{}\n{}",
+                                tr("also-reachable-as"),
+                                aliases.join("\n")
+                            ));
+                        }
                         expanding_content(ui);
                     });
-                    self.respond_to_filepath_cell(&resp, &path, ctx);
+                    self.respond_to_filepath_cell(&resp, path, aliases, ctx);
 
                     let offset = self.findings[row_index].offset;
                     let (_rect, resp) = row.col(|ui| {
@@ -832,33 +2507,164 @@ impl QuerApp {
                             .selectable(false);
                         ui.add(label);
                     });
-                    self.respond_to_offset_cell(&resp, offset, ctx);
+                    let match_size = self.findings[row_index].match_size;
+                    let decoded = self.findings[row_index].decoded;
+                    self.respond_to_offset_cell(&resp, path, offset, match_size, decoded, ctx);
 
-                    let match_content =
-                        self.cap_string_length(&self.findings[row_index].match_content, 1000);
+                    let match_content = self.findings[row_index].match_content.preview(1000);
                     let (_rect, resp) = row.col(|ui| {
-                        let label = egui::Label::new(format!("{match_content}"))
+                        let label = egui::Label::new(match_content.to_string())
                             .truncate()
                             .selectable(false);
                         ui.add(label);
                     });
 
-                    let match_size = self.findings[row_index].match_size;
+                    let decoded_bytes =
+                        decoded.then(|| self.findings[row_index].match_content.to_bytes());
                     self.respond_to_match_cell(
                         &resp,
-                        &path,
+                        path,
                         offset,
                         match_size,
-                        &format!("{match_content}"),
+                        decoded_bytes.as_deref(),
                         ctx,
                     );
+                    resp.on_hover_text(match_content);
 
                     let (_rect, resp) = row.col(|ui| {
-                        let label = egui::Label::new(format!("🔍")).truncate().selectable(false);
+                        let label = egui::Label::new("🔍").truncate().selectable(false);
                         ui.add(label);
                     });
 
-                    self.response_to_hex_preview(resp, &path, offset, match_size, ctx);
+                    self.response_to_hex_preview(resp, path, offset, match_size, decoded, ctx);
+
+                    if self.has_locations {
+                        // matches in raw volumes show the file they lie in instead
+                        let finding = &self.findings[row_index];
+                        let location = match (&finding.location, &finding.owner) {
+                            (Some(location), _) => location.clone(),
+                            (None, Some(owner)) => owner.to_string(),
+                            (None, None) => String::new(),
+                        };
+                        row.col(|ui| {
+                            let label = egui::Label::new(location).truncate().selectable(false);
+                            ui.add(label);
+                        });
+                    }
+
+                    if show_verdicts {
+                        let verdict = match self.verdicts.lock() {
+                            Ok(verdicts) => verdicts.get(path).cloned().unwrap_or_default(),
+                            Err(_) => String::new(),
+                        };
+                        row.col(|ui| {
+                            let label = egui::Label::new(verdict).truncate().selectable(false);
+                            ui.add(label);
+                        });
+                    }
+
+                    if self.show_line_cols {
+                        // offsets of decoded content don't point into the file itself
+                        let line_col = match self.findings[row_index].decoded {
+                            true => None,
+                            false => self.line_col(path, offset),
+                        };
+                        let line_col_str = line_col
+                            .map(|(line, col)| format!("{line}:{col}"))
+                            .unwrap_or_default();
+                        row.col(|ui| {
+                            let label = egui::Label::new(line_col_str).truncate().selectable(false);
+                            ui.add(label);
+                        });
+                    }
+
+                    if self.has_encodings {
+                        let encoding = self.findings[row_index].encoding.unwrap_or_default();
+                        row.col(|ui| {
+                            let label = egui::Label::new(encoding).truncate().selectable(false);
+                            ui.add(label);
+                        });
+                    }
+
+                    if self.has_addresses {
+                        let address = self.findings[row_index]
+                            .virtual_address
+                            .map(|address| format!("0x{address:x}"))
+                            .unwrap_or_default();
+                        let (_rect, resp) = row.col(|ui| {
+                            let label = egui::Label::new(&address).truncate().selectable(false);
+                            ui.add(label);
+                        });
+                        resp.context_menu(|ui| {
+                            if ui.button(tr("copy-va")).clicked() {
+                                ctx.copy_text(address.clone());
+                                ui.close_menu();
+                            }
+                        });
+                    }
+
+                    if self.has_patterns {
+                        let pattern = self.findings[row_index].pattern.clone();
+                        row.col(|ui| {
+                            let label = egui::Label::new(pattern.as_deref().unwrap_or_default())
+                                .truncate()
+                                .selectable(false);
+                            ui.add(label);
+                        });
+                    }
+
+                    if self.has_custom {
+                        let custom = self.findings[row_index].custom.clone();
+                        row.col(|ui| {
+                            let label = egui::Label::new(custom.as_deref().unwrap_or_default())
+                                .truncate()
+                                .selectable(false);
+                            ui.add(label);
+                        });
+                    }
+
+                    for anchor in anchors.iter() {
+                        let relative = anchor.relative(offset);
+                        let (_rect, resp) = row.col(|ui| {
+                            let label = egui::Label::new(&relative).truncate().selectable(false);
+                            ui.add(label);
+                        });
+                        resp.context_menu(|ui| {
+                            if ui.button(tr("copy-relative-offset")).clicked() {
+                                ctx.copy_text(relative.clone());
+                                ui.close_menu();
+                            }
+                        });
+                    }
+
+                    let row_resp = row.response();
+                    row_resp.widget_info(|| {
+                        egui::WidgetInfo::selected(
+                            egui::WidgetType::SelectableLabel,
+                            true,
+                            self.is_selected(path, offset),
+                            tr_args(
+                                "result-row",
+                                &[
+                                    ("path", path.as_str().into()),
+                                    ("offset", format!("0x{:x}", offset).into()),
+                                    (
+                                        "match",
+                                        self.findings[row_index]
+                                            .match_content
+                                            .preview(MAX_ROW_NAME_LENGTH)
+                                            .into(),
+                                    ),
+                                ],
+                            ),
+                        )
+                    });
+                    if row_resp.clicked() {
+                        self.selected_row = Some(result_row);
+                        if !decoded {
+                            self.select_finding(path, offset, match_size);
+                        }
+                    }
 
                     // ^^ this is the click handler
                 })
@@ -869,42 +2675,61 @@ impl QuerApp {
         ui.horizontal(|ui| {
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                 ui.menu_button("v", |ui| {
-                    if self.previous_searches.len() > 0 {
-                        for (prev_search, content_type) in self.previous_searches.iter() {
-                            if ui.button(prev_search).clicked() {
-                                self.regex_str = prev_search.clone();
-                                self.content_type = content_type.clone();
-                            }
-                        }
-                        ui.separator();
-                        if ui.button("Clear").clicked() {
-                            self.previous_searches.clear();
-                            ui.close_menu();
-                        }
-                    } else {
-                        ui.label("No previous searches yet");
-                    }
+                    self.add_history_menu(ui);
+                })
+                .response
+                .on_hover_text(tr("past-searches"));
+                ui.menu_button(tr("presets"), |ui| {
+                    self.add_presets_menu(ui);
                 })
                 .response
-                .on_hover_text("Past searches");
+                .on_hover_text(tr("presets-hint"));
+                ui.toggle_value(&mut self.show_regex_help, "?")
+                    .on_hover_text(tr("pattern-syntax-reference"));
+                if ui
+                    .button("0x")
+                    .on_hover_text(tr("converter-hint"))
+                    .clicked()
+                {
+                    self.show_converter = !self.show_converter;
+                }
+                let error_span = self.regex_error_span.clone();
+                let mut layouter = |ui: &egui::Ui, pattern: &str, _wrap_width: f32| {
+                    let font_id = TextStyle::Small.resolve(ui.style());
+                    let job = regex_highlight::layout_job(
+                        pattern,
+                        error_span.as_ref(),
+                        font_id,
+                        ui.visuals().dark_mode,
+                    );
+                    ui.fonts(|fonts| fonts.layout_job(job))
+                };
                 let regex_edit = egui::TextEdit::singleline(&mut self.regex_str)
-                    .hint_text("Enter regex here")
-                    .font(TextStyle::Small);
-
-                ui.add_sized(ui.available_size(), regex_edit).on_hover_text(
-                    "Examples: abc.ef, ^hello world$, aa{3}h. See mode tooltips for more info.",
-                );
+                    .hint_text(tr("enter-regex-here"))
+                    .font(TextStyle::Small)
+                    .layouter(&mut layouter);
+
+                let resp = ui
+                    .add_sized(ui.available_size(), regex_edit)
+                    .on_hover_text(tr("search-pattern-hint"));
+                set_accessible_name(&resp, &tr("search-pattern"));
             });
         });
     }
 
     fn add_find_and_clear_btns(&mut self, ui: &mut egui::Ui, _ctx: &egui::Context) {
         ui.horizontal(|ui| {
-            let mut btn = egui::Button::new(RichText::new("Search").text_style(TextStyle::Heading));
+            let mut btn =
+                egui::Button::new(RichText::new(tr("search")).text_style(TextStyle::Heading));
             let enable_btn;
             let is_find_btn;
-            match &self.regex_result {
-                Ok(_good_re) => match self.current_files_mtx.lock() {
+            let pattern_result = if self.scan_mode.needs_pattern() && self.pattern_set.is_none() {
+                self.regex_result.as_ref().map(|_good_re| ())
+            } else {
+                Ok(())
+            };
+            match pattern_result {
+                Ok(()) => match self.current_files_mtx.lock() {
                     Ok(curr_files) => {
                         if curr_files.eq(&self.max_files) || self.max_files == 0 {
                             btn = btn.fill(egui::Color32::from_rgb(0x2a, 0x7e, 0x43));
@@ -912,7 +2737,7 @@ impl QuerApp {
                             is_find_btn = true;
                         } else {
                             btn = egui::Button::new(
-                                RichText::new("Stop").text_style(TextStyle::Heading),
+                                RichText::new(tr("stop")).text_style(TextStyle::Heading),
                             );
                             btn = btn.fill(egui::Color32::from_rgb(0x8f, 0x00, 0x00));
                             enable_btn = true;
@@ -939,25 +2764,36 @@ impl QuerApp {
                     self.search();
                 } else {
                     self.progress = 0.0;
-
-                    // empty the queue
-                    while !self.file_queue.is_empty() {
-                        self.file_queue.pop().unwrap();
-                    }
-
-                    self.rx_handles.clear(); // drop the rx handles so the threads wont write
-
-                    self.max_files = 0;
+                    self.stop_search();
                 }
             }
 
-            if self.findings.len() > 0 {
+            if !self.findings.is_empty() {
+                let refine_btn = egui::Button::new(
+                    RichText::new(tr("search-within-results")).text_style(TextStyle::Heading),
+                );
+                if ui
+                    .add_enabled(enable_btn && is_find_btn, refine_btn)
+                    .on_hover_text(tr("search-within-results-hint"))
+                    .clicked()
+                {
+                    self.progress = 0.0;
+                    self.search_within_results();
+                }
+
                 let btn = egui::Button::new(
-                    RichText::new("Clear Results").text_style(TextStyle::Heading),
+                    RichText::new(tr("clear-results")).text_style(TextStyle::Heading),
                 );
                 // let btn = btn.fill(egui::Color32::from_rgb(0xf, 0x3f, 0x3f));
                 if ui.add_enabled(self.is_search_finished(), btn).clicked() {
                     self.findings.clear();
+                    self.invalidate_finding_caches();
+                    self.has_locations = false;
+                    self.has_encodings = false;
+                    self.has_addresses = false;
+                    self.has_patterns = false;
+                    self.has_custom = false;
+                    self.capped_files.clear();
                     self.rx_handles.clear();
                 }
             }
@@ -966,12 +2802,16 @@ impl QuerApp {
 
     fn add_search_desc(&mut self, ui: &mut egui::Ui, _ctx: &egui::Context) {
         let _regex_label = ui.label(
-            RichText::new("Searching for: ".to_owned() + &self.regex_str)
-                .text_style(TextStyle::Small),
+            RichText::new(tr("searching-for") + &self.regex_str).text_style(TextStyle::Small),
         );
-        if self.findings.len() > 0 {
-            let _findings_label =
-                ui.label(format!("Found {} results.", self.findings.len()).to_owned());
+        if !self.findings.is_empty() {
+            let _findings_label = ui.label(tr_args(
+                "found-results",
+                &[("count", self.findings.len().into())],
+            ));
+        }
+        if !self.scan_summary.is_empty() {
+            ui.label(&self.scan_summary);
         }
         ui.horizontal(|ui| {
             if let Ok(count) = self.current_files_mtx.lock() {
@@ -981,8 +2821,12 @@ impl QuerApp {
             }
             if !self.is_search_finished() {
                 ui.spinner();
-            } else if self.worker_threads.len() > 0 {
-                self.cleanup_threads();
+            } else if self.search_running {
+                self.search_running = false;
+                self.finish_search();
+                if self.quick_export_after_search {
+                    self.quick_export(ui.ctx());
+                }
             }
         });
     }
@@ -990,189 +2834,818 @@ impl QuerApp {
     fn add_filter_line(&mut self, ui: &mut egui::Ui, _ctx: &egui::Context) {
         ui.horizontal(|ui| {
             let filter_edit = egui::TextEdit::singleline(&mut self.filter_str)
-                .hint_text(RichText::new("Filter...").text_style(TextStyle::Small));
+                .hint_text(RichText::new(tr("filter")).text_style(TextStyle::Small));
             let filter_resp = ui
                 .add_sized([ui.available_width(), 12.0], filter_edit)
                 .highlight();
-            filter_resp.on_hover_text("Filter results by string value, offset or preview text");
+            set_accessible_name(&filter_resp, &tr("filter-results"));
+            filter_resp.on_hover_text(tr("filter-results-hint"));
+        });
+        self.sync_text_filter();
+        if let Some(err) = self.text_filter_error() {
+            ui.colored_label(egui::Color32::from_rgb(0x8f, 0x0, 0x0), err);
+        }
+        ui.horizontal(|ui| {
+            self.add_offset_filter(ui);
+            ui.separator();
+            self.add_saved_filters(ui);
+        });
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.unique_matches_only, tr("unique-matches-only"))
+                .on_hover_text(tr("unique-matches-only-hint"));
+            ui.checkbox(&mut self.split_view, tr("split-view"))
+                .on_hover_text(tr("split-view-hint"));
+            if !self.results_detached && ui.button(tr("pop-out")).clicked() {
+                self.results_detached = true;
+            }
         });
+        self.add_filter_chips(ui);
     }
 
     fn update_main_search_ui(&mut self, ctx: &egui::Context) {
         // Top, search + options
         egui::TopBottomPanel::top("search_options").show(ctx, |ui| {
             egui::menu::bar(ui, |ui| {
-                ui.menu_button("File", |ui| {
-                    ui.menu_button("Export", |ui| {
-                        if ui.button("Export to CSV...").clicked() {
-                            self.log("*clack* (TODO)".to_string());
+                ui.menu_button(tr("file"), |ui| {
+                    let hide_label = if tray::TRAY_SUPPORTED {
+                        "Hide to tray"
+                    } else {
+                        "Minimize"
+                    };
+                    if ui
+                        .button(hide_label)
+                        .on_hover_text(tr("hide-to-tray-hint"))
+                        .clicked()
+                    {
+                        self.hide_to_tray(ctx);
+                        ui.close_menu();
+                    }
+                    if ui
+                        .button(tr("load-patterns-from-file"))
+                        .on_hover_text(tr("load-patterns-hint"))
+                        .clicked()
+                    {
+                        self.open_pattern_file_dialog();
+                        ui.close_menu();
+                    }
+                    if ui
+                        .button(tr("paste-pattern-list"))
+                        .on_hover_text(tr("paste-pattern-list-hint"))
+                        .clicked()
+                    {
+                        self.paste_pattern_list();
+                        ui.close_menu();
+                    }
+                    ui.menu_button(tr("export"), |ui| {
+                        if ui
+                            .add_enabled(
+                                self.export_job.is_none(),
+                                egui::Button::new(tr("export-to-csv")),
+                            )
+                            .clicked()
+                        {
+                            self.open_export_file_dialog(ExportFormat::Csv);
+                            ui.close_menu();
+                        }
+                        if ui
+                            .add_enabled(
+                                self.export_job.is_none(),
+                                egui::Button::new(tr("quick-export")),
+                            )
+                            .on_hover_text(tr_args(
+                                "quick-export-hint",
+                                &[("path", self.quick_export_template.as_str().into())],
+                            ))
+                            .clicked()
+                        {
+                            self.quick_export(ctx);
+                            ui.close_menu();
+                        }
+                        ui.separator();
+                        ui.horizontal(|ui| {
+                            let label = ui.label(tr("extract-context-before"));
+                            ui.add(egui::DragValue::new(&mut self.extract_before))
+                                .labelled_by(label.id);
+                            let label = ui.label(tr("extract-context-after"));
+                            ui.add(egui::DragValue::new(&mut self.extract_after))
+                                .labelled_by(label.id);
+                        });
+                        if ui
+                            .add_enabled(
+                                self.export_job.is_none(),
+                                egui::Button::new(tr("extract-matched-regions")),
+                            )
+                            .on_hover_text(tr("extract-matched-regions-hint"))
+                            .clicked()
+                        {
+                            self.open_export_file_dialog(ExportFormat::Regions(
+                                self.extract_before,
+                                self.extract_after,
+                            ));
                             ui.close_menu();
                         }
-                    });
-                });
-                ui.menu_button("About", |ui| {
-                    ui.vertical(|ui| {
-                        ui.label("quer - A data finding utility");
                         ui.separator();
-                        ui.hyperlink_to("Source Code", "https://github.com/TJ9867/quer");
+                        if ui
+                            .add_enabled(
+                                self.export_job.is_none() && !self.scanned_files.is_empty(),
+                                egui::Button::new(tr("export-manifest")),
+                            )
+                            .on_hover_text(tr("export-manifest-hint"))
+                            .clicked()
+                        {
+                            self.open_export_file_dialog(ExportFormat::Manifest);
+                            ui.close_menu();
+                        }
                     });
                 });
-            });
-            self.add_export_file_dialog(ctx);
+                ui.menu_button(tr("analysis"), |ui| {
+                    if ui.button(tr("cluster-files-menu")).clicked() {
+                        self.show_clusters_window = true;
+                        self.cluster_matched_files(ctx);
+                        ui.close_menu();
+                    }
+                    if ui.button(tr("byte-statistics-matched-menu")).clicked() {
+                        self.byte_stats_of_matched_files(ctx);
+                        ui.close_menu();
+                    }
+                    if ui
+                        .add_enabled(
+                            self.has_patterns,
+                            egui::Button::new(tr("pattern-statistics-menu")),
+                        )
+                        .on_hover_text(tr("pattern-statistics-hint"))
+                        .clicked()
+                    {
+                        self.show_pattern_stats = true;
+                        ui.close_menu();
+                    }
+                    if ui
+                        .add_enabled(
+                            self.manifest_check.is_none(),
+                            egui::Button::new(tr("check-against-manifest")),
+                        )
+                        .on_hover_text(tr("check-against-manifest-hint"))
+                        .clicked()
+                    {
+                        self.open_manifest_dialog();
+                        ui.close_menu();
+                    }
+                    if ui.button(tr("directory-tree-menu")).clicked() {
+                        self.show_dir_tree_window = true;
+                        ui.close_menu();
+                    }
+                    ui.separator();
+                    if ui.button(tr("pattern-tester-menu")).clicked() {
+                        self.show_pattern_tester = true;
+                        ui.close_menu();
+                    }
+                    if ui.button(tr("scheduled-scans-menu")).clicked() {
+                        self.show_scheduled_scans = true;
+                        ui.close_menu();
+                    }
+                });
+                ui.menu_button(tr("settings"), |ui| {
+                    ui.horizontal(|ui| {
+                        let label = ui.label(tr("language-label"));
+                        let mut language = i18n::language();
+                        egui::ComboBox::from_id_source("language")
+                            .selected_text(language.name())
+                            .show_ui(ui, |ui| {
+                                for lang in Language::ALL {
+                                    ui.selectable_value(&mut language, lang, lang.name());
+                                }
+                            })
+                            .response
+                            .labelled_by(label.id);
+                        if language != i18n::language() {
+                            i18n::set_language(language);
+                        }
+                    });
+                    if ui
+                        .checkbox(&mut self.high_contrast, tr("high-contrast"))
+                        .on_hover_text(tr("high-contrast-hint"))
+                        .changed()
+                    {
+                        self.apply_contrast(ctx);
+                    }
+                    if ui
+                        .button(tr("row-colors"))
+                        .on_hover_text(tr("row-colors-hint"))
+                        .clicked()
+                    {
+                        self.show_row_colors = true;
+                        ui.close_menu();
+                    }
+                    if ui
+                        .button(tr("file-type-defaults"))
+                        .on_hover_text(tr("file-type-defaults-hint"))
+                        .clicked()
+                    {
+                        self.show_category_options = true;
+                        ui.close_menu();
+                    }
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        let label = ui.label(tr("imhex-binary"));
+                        ui.text_edit_singleline(&mut self.imhex_binary_path)
+                            .labelled_by(label.id)
+                            .on_hover_text(tr("imhex-binary-hint"));
+                    });
+                    ui.horizontal(|ui| {
+                        let label = ui.label(tr("virustotal-api-key"));
+                        ui.add(egui::TextEdit::singleline(&mut self.vt_api_key).password(true))
+                            .labelled_by(label.id)
+                            .on_hover_text(tr("virustotal-api-key-hint"));
+                    });
+                    ui.horizontal(|ui| {
+                        let label = ui.label(tr("malwarebazaar-api-key"));
+                        ui.add(egui::TextEdit::singleline(&mut self.mb_api_key).password(true))
+                            .labelled_by(label.id)
+                            .on_hover_text(tr("virustotal-api-key-hint"));
+                    });
+                    ui.horizontal(|ui| {
+                        let label = ui.label(tr("quick-export-path"));
+                        ui.text_edit_singleline(&mut self.quick_export_template)
+                            .labelled_by(label.id)
+                            .on_hover_text(QUICK_EXPORT_PLACEHOLDERS);
+                        if ui.button(tr("reset")).clicked() {
+                            self.quick_export_template = DEFAULT_QUICK_EXPORT_TEMPLATE.to_string();
+                        }
+                    });
+                    ui.checkbox(
+                        &mut self.quick_export_after_search,
+                        tr("quick-export-on-finish"),
+                    );
+                    ui.checkbox(&mut self.write_export_metadata, tr("export-meta-json"))
+                        .on_hover_text(tr("export-meta-json-hint"));
+                    ui.checkbox(&mut self.defang_exports, tr("defang-exports"))
+                        .on_hover_text(tr("defang-exports-hint"));
+                    ui.horizontal(|ui| {
+                        let label = ui.label(tr("cyberchef-recipe"));
+                        ui.text_edit_singleline(&mut self.cyberchef_recipe)
+                            .labelled_by(label.id)
+                            .on_hover_text(tr("cyberchef-recipe-hint"));
+                    });
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        ui.label(tr("hexdump-width"));
+                        for width in [8, 16, 32] {
+                            ui.selectable_value(&mut self.hexdump_width, width, width.to_string());
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        let label = ui.label(tr("hexdump-grouping"));
+                        ui.add(
+                            egui::DragValue::new(&mut self.hexdump_grouping)
+                                .range(0..=self.hexdump_width),
+                        )
+                        .labelled_by(label.id)
+                        .on_hover_text(tr("hexdump-grouping-hint"));
+                    });
+                    ui.horizontal(|ui| {
+                        let label = ui.label(tr("hexdump-context-bytes"));
+                        let before = ui
+                            .add(
+                                egui::DragValue::new(&mut self.hexdump_context.0)
+                                    .range(0..=file_view::MAX_CONTEXT)
+                                    .prefix(tr("context-before")),
+                            )
+                            .labelled_by(label.id)
+                            .on_hover_text(tr("hexdump-context-hint"));
+                        let after = ui
+                            .add(
+                                egui::DragValue::new(&mut self.hexdump_context.1)
+                                    .range(0..=file_view::MAX_CONTEXT)
+                                    .prefix(tr("context-after")),
+                            )
+                            .labelled_by(label.id)
+                            .on_hover_text(tr("hexdump-context-hint"));
+                        // saved once a drag or edit is done, not on every step
+                        if before.drag_stopped()
+                            || before.lost_focus()
+                            || after.drag_stopped()
+                            || after.lost_focus()
+                        {
+                            self.save_hexdump_context();
+                        }
+                    });
+                    ui.separator();
+                    ui.checkbox(&mut self.tray_on_close, tr("hide-while-scanning"));
+                    if ui
+                        .checkbox(&mut self.watch_clipboard, tr("watch-clipboard"))
+                        .on_hover_text(tr("watch-clipboard-hint"))
+                        .changed()
+                    {
+                        self.set_clipboard_monitor(ctx, self.watch_clipboard);
+                    }
+                    if ui
+                        .checkbox(&mut self.single_instance, tr("single-instance"))
+                        .on_hover_text(tr("single-instance-hint"))
+                        .changed()
+                    {
+                        self.set_single_instance(ctx, self.single_instance);
+                        self.save_single_instance();
+                    }
+                    if ui
+                        .checkbox(&mut self.persist_results, tr("persist-results"))
+                        .on_hover_text(tr("persist-results-hint"))
+                        .changed()
+                    {
+                        self.save_persist_results();
+                    }
+                    self.add_shell_integration_options(ui);
+                    ui.horizontal(|ui| {
+                        let label = ui.label(tr("worker-threads"));
+                        // at least one thread reading and one matching
+                        ui.add(egui::DragValue::new(&mut self.worker_count).range(2..=256))
+                            .labelled_by(label.id)
+                            .on_hover_text(tr("worker-threads-hint"));
+                    });
+                    ui.add_enabled(
+                        worker_pool::SCHEDULING_SUPPORTED,
+                        egui::Checkbox::new(
+                            &mut self.low_priority_workers,
+                            tr("low-priority-workers"),
+                        ),
+                    )
+                    .on_hover_text(tr("low-priority-workers-hint"))
+                    .on_disabled_hover_text(tr("low-priority-workers-unsupported"));
+                    ui.add_enabled_ui(worker_pool::SCHEDULING_SUPPORTED, |ui| {
+                        ui.horizontal(|ui| {
+                            let label = ui.label(tr("pin-workers-to-cores"));
+                            let cores_edit = egui::TextEdit::singleline(&mut self.worker_cores_str)
+                                .hint_text(tr("worker-cores-hint"));
+                            if ui.add(cores_edit).labelled_by(label.id).changed() {
+                                self.worker_cores =
+                                    worker_pool::parse_core_list(&self.worker_cores_str);
+                            }
+                        })
+                        .response
+                        .on_hover_text(tr("pin-workers-to-cores-hint"));
+                        if let Err(err) = &self.worker_cores {
+                            ui.colored_label(egui::Color32::from_rgb(0x8f, 0x0, 0x0), err);
+                        }
+                    });
+                    // io_uring only, there's no overlapped I/O reader for Windows
+                    if cfg!(target_os = "linux") {
+                        ui.add_enabled(
+                            file_read::IO_URING_SUPPORTED,
+                            egui::Checkbox::new(
+                                &mut self.use_io_uring,
+                                tr("batch-small-file-reads"),
+                            ),
+                        )
+                        .on_hover_text(tr("batch-small-file-reads-hint"))
+                        .on_disabled_hover_text(tr("batch-small-file-reads-unsupported"));
+                    }
+                    ui.horizontal(|ui| {
+                        let label = ui.label(tr("retry-failed-reads"));
+                        ui.add(egui::DragValue::new(&mut self.read_retries).range(0..=10))
+                            .labelled_by(label.id);
+                        let label = ui.label(tr("retry-backoff-ms"));
+                        ui.add(
+                            egui::DragValue::new(&mut self.read_retry_backoff_ms)
+                                .range(0..=60_000)
+                                .suffix(" ms"),
+                        )
+                        .labelled_by(label.id);
+                    })
+                    .response
+                    .on_hover_text(tr("retry-failed-reads-hint"));
+                    ui.add_enabled(
+                        raw_volume::RAW_VOLUME_SUPPORTED,
+                        egui::Checkbox::new(
+                            &mut self.read_locked_files,
+                            tr("read-locked-files-raw"),
+                        ),
+                    )
+                    .on_hover_text(tr("read-locked-files-raw-hint"))
+                    .on_disabled_hover_text(tr("read-locked-files-raw-unsupported"));
+                    ui.separator();
+                    ui.checkbox(&mut self.webhook_enabled, tr("post-findings-to-webhook"));
+                    ui.add_enabled_ui(self.webhook_enabled, |ui| {
+                        ui.horizontal(|ui| {
+                            let label = ui.label(tr("webhook-url"));
+                            ui.text_edit_singleline(&mut self.webhook_url)
+                                .labelled_by(label.id);
+                        });
+                        let label = ui.label(tr("webhook-headers"));
+                        ui.text_edit_multiline(&mut self.webhook_headers)
+                            .labelled_by(label.id)
+                            .on_hover_text(tr("webhook-headers-hint"));
+                    });
+                });
+                ui.menu_button(tr("help"), |ui| {
+                    ui.checkbox(&mut self.show_regex_help, tr("pattern-reference"));
+                });
+                ui.menu_button(tr("about"), |ui| {
+                    ui.vertical(|ui| {
+                        ui.label(tr("about-title"));
+                        ui.separator();
+                        ui.hyperlink_to(tr("source-code"), "https://github.com/TJ9867/quer");
+                    });
+                });
+            });
+            self.add_export_file_dialog(ctx);
+            self.add_export_progress_window(ctx);
+            self.add_manifest_check_window(ctx);
+            self.add_pattern_file_dialog(ctx);
+            self.add_pattern_stats_window(ctx);
+            self.add_clusters_window(ctx);
+            self.add_byte_stats_window(ctx);
+            self.add_dir_tree_window(ctx);
+            self.add_file_view_window(ctx);
+            self.add_pinned_preview_windows(ctx);
+            self.poll_clipboard_monitor();
+            self.poll_forwarded_launches(ctx);
+            self.add_launch_search_confirmation(ctx);
+            self.poll_scheduled_scans(ctx);
+            self.add_scheduled_scans_window(ctx);
+            self.add_clipboard_toast(ctx);
+            self.add_pattern_tester_window(ctx);
+            self.add_template_window(ctx);
+            self.add_similar_regions_window(ctx);
+            self.add_converter_window(ctx);
+            self.add_row_colors_window(ctx);
+            self.add_category_options_window(ctx);
 
             self.add_regex_line(ui, ctx);
             self.add_regex_error_line(ui);
             self.add_folder_dialog(ui, ctx);
             self.add_mode_selector(ui);
+            if self.scan_mode == ScanModeEnum::Diff {
+                self.add_compare_path(ui, ctx);
+            }
             self.add_advanced_view_options(ui);
             ui.end_row();
         });
 
         // Bottom, progress etc
         egui::TopBottomPanel::bottom("search_progress").show(ctx, |ui| {
-            let progress = egui::widgets::ProgressBar::new(self.progress);
-            ui.add(progress);
+            self.add_progress_breakdown(ui);
+            self.add_elevated_retry(ui);
             ui.separator();
-
-            let text_style = TextStyle::Body;
-            let row_height = ui.text_style_height(&text_style);
-
-            egui::Frame::none()
-                .show(ui, |ui| {
-                    egui::ScrollArea::vertical().auto_shrink(false).show_rows(
-                        ui,
-                        row_height,
-                        self.log_lines.len(),
-                        |ui, row_range| {
-                            for row in row_range {
-                                ui.label(&self.log_lines[row]);
-                            }
-                        },
-                    )
-                })
-                .response
-                .context_menu(|ui| {
-                    if ui.button("Clear Logs").clicked() {
-                        self.log_lines.clear();
-                        ui.close_menu();
-                    }
-                });
+            self.add_log_pane(ui);
         });
 
+        self.add_regex_help_panel(ctx);
+
         egui::CentralPanel::default().show(ctx, |ui| {
             self.add_find_and_clear_btns(ui, ctx);
 
             self.add_search_desc(ui, ctx);
+            self.add_restore_banner(ui, ctx);
 
-            self.add_filter_line(ui, ctx);
-
-            self.add_listing_and_content_view(ui, ctx);
+            if self.results_detached {
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label(tr("results-popped-out"));
+                    if ui.button(tr("reattach")).clicked() {
+                        self.results_detached = false;
+                    }
+                });
+            } else {
+                self.add_results_view(ui, ctx);
+            }
         });
+
+        if self.results_detached {
+            self.add_detached_results_viewport(ctx);
+        }
+    }
+
+    fn add_results_view(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        self.add_filter_line(ui, ctx);
+
+        if self.split_view {
+            egui::TopBottomPanel::bottom("split_hex_view")
+                .resizable(true)
+                .default_height(240.0)
+                .show_inside(ui, |ui| {
+                    self.add_split_hex_view(ui);
+                });
+        }
+
+        self.add_listing_and_content_view(ui, ctx);
+    }
+
+    // results in their own OS window, e.g. to put them on a second monitor
+    fn add_detached_results_viewport(&mut self, ctx: &egui::Context) {
+        let viewport_id = egui::ViewportId::from_hash_of("results_viewport");
+        let viewport_builder = egui::ViewportBuilder::default()
+            .with_inner_size((1000.0, 700.0))
+            .with_resizable(true)
+            .with_title("quer - Results")
+            .with_decorations(true);
+
+        let mut should_reattach = false;
+        let viewport_cb = |ctx: &egui::Context, _| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                self.add_results_view(ui, ctx);
+            });
+
+            if ctx.input(|i| i.viewport().close_requested()) {
+                should_reattach = true;
+            }
+        };
+
+        ctx.show_viewport_immediate(viewport_id, viewport_builder, viewport_cb);
+        if should_reattach {
+            self.results_detached = false;
+        }
+    }
+
+    // the loaded pattern file wins over the pattern in the search box
+    fn search_patterns(&self) -> Vec<SearchPattern> {
+        match &self.pattern_set {
+            Some(pattern_set) => pattern_set.patterns.clone(),
+            None => self
+                .regex_result
+                .iter()
+                .map(|regex| SearchPattern {
+                    name: None,
+                    regex: regex.clone(),
+                })
+                .collect(),
+        }
     }
 
     fn get_search_options(&self) -> SearchOptions {
         SearchOptions {
+            min_match_len: self.min_match_len,
+            max_match_len: self.max_match_len,
             alignment: self.alignment,
-            regex_result: self.regex_result.clone(),
+            alignment_base: self.alignment_base,
+            patterns: match self.scan_mode {
+                ScanModeEnum::Records => self
+                    .search_patterns()
+                    .iter()
+                    .filter_map(|pattern| pattern.anchored())
+                    .collect(),
+                _ => self.search_patterns(),
+            },
             max_hits: self.max_hits,
+            decode_containers: self.decode_containers,
+            handler_options: self.handler_options.clone(),
+            scan_mode: self.scan_mode.clone(),
+            first_match_only: self.first_match_only,
+            attribute_fs: self.attribute_fs,
+            search_root: self.root_folder_path.clone(),
+            compare_root: PathBuf::from(&self.compare_path_str),
+            padding_min_run: self.padding_min_run,
+            padding_max_period: self.padding_max_period,
+            record_layout: self.record_layout.clone(),
+            custom_column: self.custom_column.clone().ok().flatten().map(Arc::new),
         }
     }
 
-    fn enqueue_files<P: core::ops::FnMut(&DirEntry) -> bool>(
+    fn enqueue_files(
         &mut self,
-        file_iter: FilterEntry<walkdir::IntoIter, P>,
+        file_iter: impl Iterator<Item = walkdir::Result<DirEntry>>,
     ) -> FileCount {
-        let mut file_count = 0;
         let mut dir_count = 0;
+        let mut file_entries: Vec<DirEntry> = Vec::new();
+        // hard links and bind mounts reach one file through several paths, it's scanned once
+        let mut identities: HashMap<(u64, u64), usize> = HashMap::new();
+        let mut aliases: HashMap<String, Vec<String>> = HashMap::new();
+        let mut num_aliases = 0;
 
         for entry in file_iter {
-            if entry.is_ok() {
-                if let Some(ent) = entry.as_ref().ok() {
+            match entry.as_ref() {
+                Ok(ent) => {
                     if ent.file_type().is_file() {
-                        file_count += 1;
-                        self.file_queue.push(ent.clone()).unwrap();
+                        if !self.file_category.includes(ent.path()) {
+                            continue;
+                        }
+                        if let Some(identity) = file_identity(ent) {
+                            if let Some(&first) = identities.get(&identity) {
+                                aliases
+                                    .entry(file_entries[first].path().to_string_lossy().to_string())
+                                    .or_default()
+                                    .push(ent.path().to_string_lossy().to_string());
+                                num_aliases += 1;
+                                continue;
+                            }
+                            identities.insert(identity, file_entries.len());
+                        }
+                        file_entries.push(ent.clone());
                     } else if ent.file_type().is_dir() {
                         dir_count += 1;
                     }
                 }
+                Err(err) => {
+                    if let Some(path) = err.path() {
+                        self.dir_tree.add_skipped(path);
+                    }
+                }
             }
         }
 
-        FileCount {
-            num_files: file_count,
-            num_dirs: dir_count,
+        if num_aliases > 0 {
+            self.log(format!(
+                "Skipped {} hard-linked or bind-mounted paths to files already queued",
+                num_aliases
+            ));
         }
+        self.file_aliases = aliases
+            .into_iter()
+            .map(|(path, alias_paths)| (path, Arc::from(alias_paths)))
+            .collect();
+        self.enqueue_entries(file_entries, dir_count)
     }
 
-    fn export_findings_to_imhexbm(
-        findings: &Vec<Finding>,
-        output_path: &PathBuf,
-        filepath: &String,
-    ) {
-        let mut bookmarks_vec: Vec<json::JsonValue> = Vec::new();
-        let mut json_data = json::JsonValue::new_object();
+    // excluded directories always, the skip list only when sweeping a whole drive since
+    // a search below it was asked for on purpose
+    fn skip_paths(&self) -> Vec<PathBuf> {
+        let mut paths = self.excluded_dirs.clone();
+        if self.skip_system_dirs && self.root_folder_path.parent().is_none() {
+            paths.extend(
+                self.skip_list_str
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .map(PathBuf::from),
+            );
+        }
+        paths
+    }
 
-        let mut id: u32 = 1;
-        for finding in findings.iter() {
-            if *filepath == finding.filepath {
-                let mut bookmark_obj = json::JsonValue::new_object();
-                bookmark_obj["color"] = 1341756994.into();
-                bookmark_obj["comment"] = "\n".into();
-                bookmark_obj["id"] = id.into();
-                bookmark_obj["locked"] = true.into();
-                bookmark_obj["name"] =
-                    format!("{} @ 0x{:x}", finding.match_content, finding.offset).into();
+    // the distinct files of the current (filtered) findings, in order of first appearance
+    fn result_file_entries(&self) -> Vec<DirEntry> {
+        let mut seen = HashSet::new();
+        let indices: Box<dyn Iterator<Item = usize>> = if self.has_active_filters() {
+            Box::new(self.filtered_rows.iter().copied())
+        } else {
+            Box::new(0..self.findings.len())
+        };
 
-                let mut region_obj = json::JsonValue::new_object();
-                region_obj["address"] = finding.offset.into();
-                region_obj["size"] = finding.match_size.into();
-                bookmark_obj["region"] = region_obj;
+        indices
+            .map(|idx| &self.findings[idx].filepath)
+            .filter(|path| seen.insert(path.as_str()))
+            .filter_map(|path| WalkDir::new(path).max_depth(0).into_iter().next())
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .collect()
+    }
 
-                bookmarks_vec.push(bookmark_obj);
+    fn enqueue_entries(&mut self, mut file_entries: Vec<DirEntry>, dir_count: i32) -> FileCount {
+        let mut file_count = 0;
+        if self.scan_mode == ScanModeEnum::Duplicates {
+            let num_entries = file_entries.len();
+            file_entries = retain_same_size_files(file_entries);
+            self.dir_tree.node.skipped += num_entries - file_entries.len();
+        }
 
-                id += 1;
-            }
+        for ent in file_entries {
+            file_count += 1;
+            self.file_queue.push(ent).unwrap();
         }
-        json_data["bookmarks"] = bookmarks_vec.into();
 
-        match fs::write(output_path, json::stringify_pretty(json_data, 4)) {
-            Ok(_ok) => {}
-            Err(_err) => {}
+        FileCount {
+            num_files: file_count,
+            num_dirs: dir_count,
         }
     }
 
+    fn open_export_file_dialog(&mut self, format: ExportFormat) {
+        let mut dialog = FileDialog::new()
+            .initial_directory(self.export_file_path.clone())
+            .as_modal(false)
+            .title_bar(false)
+            .movable(false)
+            .resizable(false)
+            .min_size([800., 600.]); //.show_files_filter(filter);
+        if format.is_directory() {
+            dialog.select_directory();
+        } else {
+            dialog.save_file();
+        }
+        self.export_file_dialog = Some(dialog);
+        self.export_format = format;
+    }
+
     fn search(&mut self) {
-        if self.clear_results_before_search {
+        self.start_search(None);
+    }
+
+    fn search_within_results(&mut self) {
+        self.update_filtered_rows();
+        let entries = self.result_file_entries();
+        self.start_search(Some(entries));
+    }
+
+    // `result_files` restricts the search to those files instead of walking the search root
+    fn start_search(&mut self, result_files: Option<Vec<DirEntry>>) {
+        if self.scan_mode == ScanModeEnum::Diff && self.compare_path_str.is_empty() {
+            self.log_error("Pick a folder or file to compare with first".to_string());
+            return;
+        }
+        // refining replaces the results it was derived from
+        if self.clear_results_before_search || result_files.is_some() {
             self.findings.clear();
+            self.invalidate_finding_caches();
+            self.has_locations = false;
+            self.has_encodings = false;
+            self.has_addresses = false;
+            self.has_patterns = false;
+            self.has_custom = false;
+            self.capped_files.clear();
             self.rx_handles.clear();
         }
-
-        if self.previous_searches.len() == 10 {
-            // TODO make configurable
-            self.previous_searches.pop_back();
+        // files may have changed since they were indexed
+        self.line_indexes.clear();
+        self.address_maps.clear();
+        self.show_line_cols =
+            self.content_type == ContentEnum::Text && self.scan_mode == ScanModeEnum::Content;
+        if self.clear_results_before_search || self.dir_tree.root != self.root_folder_path {
+            self.dir_tree = DirTree::new(&self.root_folder_path);
         }
 
-        self.previous_searches
-            .push_front((self.regex_str.clone(), self.content_type.clone()));
+        self.record_search();
+        self.last_search = Some(self.current_search_entry());
+        self.search_started_at = Some(chrono::Local::now());
+        self.search_finished_at = None;
 
+        // a stopped search leaves its queue closed
+        if self.file_queue.is_closed() {
+            self.file_queue = Arc::new(ConcurrentQueue::unbounded());
+        }
         self.max_files = 0;
         self.current_files_mtx = Arc::new(Mutex::new(0));
+        self.active_scan_mode = self.scan_mode.clone();
+        self.search_start_index = self.findings.len();
+        self.scan_summary.clear();
+        self.files_capped = 0;
+        self.files_scanned = 0;
+        self.scanned_files.clear();
+        self.files_skipped = 0;
+        self.skipped_files.clear();
+        self.scanning_files = Arc::new(Mutex::new(HashSet::new()));
+        self.new_findings.clear();
+
+        // replacing the sink lets the previous one flush and exit
+        self.webhook_sink = if self.webhook_enabled && !self.webhook_url.is_empty() {
+            Some(WebhookSink::spawn(WebhookConfig {
+                url: self.webhook_url.clone(),
+                headers: WebhookConfig::parse_headers(&self.webhook_headers),
+            }))
+        } else {
+            None
+        };
 
-        let filtered_iter = create_filter_iter(
-            WalkDir::new(&self.root_folder_path),
-            self.file_walk_options.clone(),
-        );
-
-        self.log(
-            format!(
-                "Searching for {} in {}",
-                self.regex_str,
-                self.root_folder_path.to_str().unwrap()
-            )
-            .to_string(),
-        );
+        let searched_for = match &self.pattern_set {
+            Some(pattern_set) => format!("{} patterns", pattern_set.patterns.len()),
+            None => self.regex_str.clone(),
+        };
+        let count_struct = match result_files {
+            Some(entries) => {
+                self.log(format!(
+                    "Searching for {} in {} result files",
+                    searched_for,
+                    entries.len()
+                ));
+                self.enqueue_entries(entries, 0)
+            }
+            None => {
+                let mut walk_options = self.file_walk_options.clone();
+                walk_options.skip_paths = self.skip_paths();
+                if !walk_options.skip_paths.is_empty() {
+                    self.log(format!(
+                        "Skipping {} system or excluded paths, see Advanced Search",
+                        walk_options.skip_paths.len()
+                    ));
+                }
+                // snapshots are walked like the search folder, at the same path inside them
+                let shadow_roots = self.shadow_copy_roots(&walk_options.skip_paths);
+                if !shadow_roots.is_empty() {
+                    self.log(format!(
+                        "Also searching {} shadow copies",
+                        shadow_roots.len()
+                    ));
+                }
+                let shadow_iters = shadow_roots.into_iter().flat_map(|(root, skip_paths)| {
+                    let options = FileWalkOptions {
+                        skip_paths,
+                        ..walk_options.clone()
+                    };
+                    create_filter_iter(WalkDir::new(root), options)
+                });
+                let filtered_iter =
+                    create_filter_iter(WalkDir::new(&self.root_folder_path), walk_options.clone())
+                        .chain(shadow_iters);
+
+                self.log(
+                    format!(
+                        "Searching for {} in {}",
+                        searched_for,
+                        self.root_folder_path.to_str().unwrap()
+                    )
+                    .to_string(),
+                );
 
-        let count_struct = self.enqueue_files(filtered_iter);
+                self.enqueue_files(filtered_iter)
+            }
+        };
 
         self.max_files = /*count_struct.num_dirs +*/ count_struct.num_files;
         self.log(format!(
@@ -1183,6 +3656,15 @@ impl QuerApp {
         if self.max_files < 1 {
             return;
         }
+        self.scan_progress = Arc::new(ScanProgress::new(count_struct.num_files as usize));
+        self.read_retry = ReadRetry {
+            locked_files: (self.read_locked_files && raw_volume::RAW_VOLUME_SUPPORTED)
+                .then(Default::default),
+            ..ReadRetry::new(
+                self.read_retries,
+                Duration::from_millis(self.read_retry_backoff_ms),
+            )
+        };
 
         let (result_tx, result_rx) = mpsc::channel();
         let arc_result_tx = Arc::new(result_tx);
@@ -1195,185 +3677,993 @@ impl QuerApp {
         self.filecount_handles.push(filecount_rx);
 
         let search_opts = Arc::new(self.get_search_options());
-        let search_threads = 10;
-        let queue: Queue<Task> = Queue::new(search_threads, 4096);
+        // only idle pools get here, so swapping one out never strands a search
+        let scheduling = WorkerScheduling {
+            low_priority: self.low_priority_workers,
+            cores: self.worker_cores.clone().unwrap_or_default(),
+        };
+        if self.worker_pool.size() != self.worker_count
+            || *self.worker_pool.scheduling() != scheduling
+        {
+            self.worker_pool = Arc::new(WorkerPool::new(self.worker_count, scheduling));
+        }
 
-        for _i in 0..count_struct.num_files {
+        // a quarter of the workers read files ahead, the rest match whatever has been read
+        // so slow disks and slow patterns overlap instead of taking turns
+        let pool_size = self.worker_pool.size();
+        let io_workers = std::cmp::max(pool_size / 4, 1);
+        let (prefetch_tx, prefetch_rx) =
+            mpsc::sync_channel::<(DirEntry, Option<FileData>)>(PREFETCH_DEPTH);
+        let prefetch_rx = Arc::new(Mutex::new(prefetch_rx));
+        self.prefetched_files = Arc::new(AtomicUsize::new(0));
+
+        // I/O workers drain the shared queue until it is empty or closed by Stop
+        for _i in 0..io_workers {
+            let file_entry_q = Arc::clone(&self.file_queue);
+            let prefetch_tx = prefetch_tx.clone();
+            let prefetched = Arc::clone(&self.prefetched_files);
+            let scan_mode = search_opts.scan_mode.clone();
+            let use_io_uring = self.use_io_uring;
+            let read_retry = self.read_retry.clone();
+            self.worker_pool.submit(move || {
+                #[cfg(all(feature = "io-uring", target_os = "linux"))]
+                if use_io_uring {
+                    if let Some(mut reader) = file_read::UringReader::new() {
+                        file_read::prefetch_batched(
+                            &mut reader,
+                            &file_entry_q,
+                            &prefetch_tx,
+                            &prefetched,
+                            &scan_mode,
+                            &read_retry,
+                        );
+                        return;
+                    }
+                }
+                #[cfg(not(all(feature = "io-uring", target_os = "linux")))]
+                let _ = use_io_uring;
+
+                while let Ok(filt_ent) = file_entry_q.pop() {
+                    let file_data = prefetch_file(&filt_ent, &scan_mode, &read_retry);
+                    prefetched.fetch_add(1, Ordering::Relaxed);
+                    if prefetch_tx.send((filt_ent, file_data)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        // the matchers stop once every I/O worker is done and the channel is empty
+        drop(prefetch_tx);
+
+        for _i in io_workers..pool_size {
             let search_opts_ref = Arc::clone(&search_opts);
             let file_entry_q = Arc::clone(&self.file_queue);
+            let prefetch_rx = Arc::clone(&prefetch_rx);
+            let prefetched = Arc::clone(&self.prefetched_files);
+            let scanning_files = Arc::clone(&self.scanning_files);
             let result_tx = Arc::clone(&arc_result_tx);
             let filecount_tx = Arc::clone(&arc_filecount_tx);
-            queue.push(Task(Box::new(move |_local| {
-                if let Ok(filt_ent) = file_entry_q.pop() {
-                    search_file(&filt_ent, &result_tx, search_opts_ref);
+            let scan_progress = Arc::clone(&self.scan_progress);
+            self.worker_pool.submit(move || loop {
+                let next = match prefetch_rx.lock() {
+                    Ok(prefetch_rx) => prefetch_rx.recv(),
+                    Err(_) => break,
+                };
+                let Ok((filt_ent, file_data)) = next else {
+                    break;
+                };
+                prefetched.fetch_sub(1, Ordering::Relaxed);
+                // files read before Stop was pressed are dropped, not matched
+                if file_entry_q.is_closed() {
+                    continue;
+                }
+
+                let path = filt_ent.path().to_path_buf();
+                if let Ok(mut scanning) = scanning_files.lock() {
+                    scanning.insert(path.clone());
+                }
+                let scan_start = Instant::now();
+                // one broken file must not take the matcher down with the rest of the queue
+                let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+                    search_file(
+                        &filt_ent,
+                        file_data.as_deref(),
+                        &result_tx,
+                        Arc::clone(&search_opts_ref),
+                    )
+                }));
+                if let Ok(mut scanning) = scanning_files.lock() {
+                    scanning.remove(&path);
                 }
+                let status = match outcome {
+                    Ok(ScanOutcome::Scanned) => FileStatus::Scanned(path),
+                    Ok(ScanOutcome::Capped) => FileStatus::Capped(path),
+                    Ok(ScanOutcome::Skipped) => FileStatus::Skipped(path),
+                    Err(err) => FileStatus::Failed(path, panic_message(&err)),
+                };
 
-                match filecount_tx.send(1) {
+                scan_progress.files_done.fetch_add(1, Ordering::Relaxed);
+                match filecount_tx.send((status, scan_start.elapsed())) {
                     Ok(_) => {}
                     Err(_err) => {
                         //println!("Error sending result {:?}", err);
                     }
                 }
-            })));
+            });
         }
+        self.search_running = true;
+    }
 
-        let thread_handles: Vec<_> = queue
-            .local_queues()
-            .map(|mut local_queue| {
-                std::thread::spawn(move || {
-                    while let Some(task) = local_queue.pop() {
-                        task.0(&mut local_queue);
-                    }
-                })
-            })
-            .collect();
+    fn finish_search(&mut self) {
+        // make sure nothing is left sitting in the channels before post-processing
+        self.receive_results();
+        self.search_finished_at = Some(chrono::Local::now());
+        if self.pattern_set.is_some() {
+            self.pattern_stats.clear();
+            self.show_pattern_stats = true;
+        }
 
-        for handle in thread_handles {
-            self.worker_threads.push(Some(handle));
+        match self.active_scan_mode {
+            ScanModeEnum::Duplicates => self.group_duplicate_findings(),
+            ScanModeEnum::CountOnly => {
+                let start = std::cmp::min(self.search_start_index, self.findings.len());
+                let summaries = &self.findings[start..];
+                let total: usize = summaries.iter().map(|f| f.hit_count).sum();
+                self.scan_summary = format!("{} matches in {} files.", total, summaries.len());
+            }
+            ScanModeEnum::Diff => {
+                let start = std::cmp::min(self.search_start_index, self.findings.len());
+                let diffs = &self.findings[start..];
+                let files: HashSet<&str> = diffs.iter().map(|f| f.filepath.as_str()).collect();
+                let changed: usize = diffs.iter().map(|f| f.match_size).sum();
+                self.scan_summary = format!(
+                    "{} differing regions ({}) in {} files.",
+                    diffs.len(),
+                    format_size(changed),
+                    files.len()
+                );
+            }
+            ScanModeEnum::Padding => {
+                let start = std::cmp::min(self.search_start_index, self.findings.len());
+                let runs = &self.findings[start..];
+                let padded: usize = runs.iter().map(|f| f.match_size).sum();
+                self.scan_summary = format!(
+                    "{} padding runs, {} in total.",
+                    runs.len(),
+                    format_size(padded)
+                );
+            }
+            ScanModeEnum::Content if self.merge_adjacent => self.merge_adjacent_findings(),
+            ScanModeEnum::Content | ScanModeEnum::Metadata | ScanModeEnum::Records => {}
         }
 
-        self.work_queue = Some(queue);
+        if self.files_capped > 0 {
+            let note = format!(
+                "⚠ {} files stopped at {} hits, their counts are a lower bound.",
+                self.files_capped, self.max_hits
+            );
+            self.log(note.clone());
+            if !self.scan_summary.is_empty() {
+                self.scan_summary.push(' ');
+            }
+            self.scan_summary.push_str(&note);
+        }
+        self.store_results();
+    }
+
+    fn group_duplicate_findings(&mut self) {
+        let start = std::cmp::min(self.search_start_index, self.findings.len());
+        let mut by_hash: HashMap<MatchContent, Vec<Finding>> = HashMap::new();
+        for finding in self.findings.drain(start..) {
+            by_hash
+                .entry(finding.match_content.clone())
+                .or_default()
+                .push(finding);
+        }
+
+        let mut groups: Vec<Vec<Finding>> = by_hash
+            .into_values()
+            .filter(|group| group.len() > 1)
+            .collect();
+        // biggest space wasters first
+        groups.sort_by_key(|group| std::cmp::Reverse(group[0].match_size * (group.len() - 1)));
+
+        let num_groups = groups.len();
+        let mut wasted = 0;
+        for (idx, group) in groups.into_iter().enumerate() {
+            let copies = group.len();
+            wasted += group[0].match_size * (copies - 1);
+            for mut finding in group {
+                finding.location = Some(format!("group {} ({} copies)", idx + 1, copies));
+                self.findings.push(finding);
+            }
+        }
+        self.has_locations |= num_groups > 0;
+        self.invalidate_finding_caches();
+
+        self.scan_summary = format!(
+            "{} duplicate groups, {} wasted.",
+            num_groups,
+            format_size(wasted)
+        );
+        self.log(format!("Duplicate scan finished: {}", self.scan_summary));
+    }
+
+    // closing the queue makes the workers find it empty, so they only finish the files
+    // they are already scanning and those results still come in
+    fn stop_search(&mut self) {
+        self.file_queue.close();
+        let mut skipped = 0;
+        while self.file_queue.pop().is_ok() {
+            skipped += 1;
+        }
+        self.max_files = 0;
+        self.log(format!("Search stopped, skipped {} queued files", skipped));
     }
 
     fn is_search_finished(&self) -> bool {
-        for thread in self.worker_threads.iter() {
-            if let Some(thread_ref) = thread.as_ref() {
-                if !thread_ref.is_finished() {
-                    return false;
+        self.worker_pool.is_idle()
+    }
+
+    fn open_in_imhex(&mut self, path: &String, offset: usize, match_length: usize) {
+        // ImHex opens the file, then --select highlights (and scrolls to) the match region
+        let end = offset.saturating_add(match_length.saturating_sub(1));
+        let spawn_res = Command::new(&self.imhex_binary_path)
+            .arg("--open")
+            .arg(path)
+            .arg("--select")
+            .arg(format!("0x{offset:x}"))
+            .arg(format!("0x{end:x}"))
+            .spawn();
+
+        match spawn_res {
+            Ok(_child) => self.log(format!("Opened {} in ImHex at 0x{:x}", path, offset)),
+            Err(err) => self.log_error(format!(
+                "Error launching ImHex ({}): {}",
+                self.imhex_binary_path, err
+            )),
+        }
+    }
+
+    fn lookup_file_hash(&mut self, service: LookupService, path: &String, ctx: &egui::Context) {
+        let api_key = match service {
+            LookupService::VirusTotal => self.vt_api_key.clone(),
+            LookupService::MalwareBazaar => self.mb_api_key.clone(),
+        };
+        self.log(format!("Looking up {} on {}", path, service.name()));
+
+        let path = path.clone();
+        let verdicts = Arc::clone(&self.verdicts);
+        let ctx = ctx.clone();
+        // hashing and querying can take a while, keep it off the UI thread
+        thread::spawn(move || {
+            let verdict = match hash_lookup::sha256_file(Path::new(&path)) {
+                Ok(sha256) if api_key.is_empty() => {
+                    ctx.open_url(egui::OpenUrl::new_tab(service.browse_url(&sha256)));
+                    return;
                 }
+                Ok(sha256) => match service.query(&api_key, &sha256) {
+                    Ok(verdict) => verdict,
+                    Err(err) => format!("Lookup failed: {}", err),
+                },
+                Err(err) => format!("Hashing failed: {}", err),
+            };
+
+            if let Ok(mut verdicts) = verdicts.lock() {
+                verdicts.insert(path, verdict);
             }
+            ctx.request_repaint();
+        });
+    }
+
+    fn cluster_matched_files(&mut self, ctx: &egui::Context) {
+        let mut filepaths: Vec<String> = self.findings.iter().map(|f| f.filepath.clone()).collect();
+        filepaths.sort();
+        filepaths.dedup();
+        self.log(format!(
+            "Clustering {} matched files by ssdeep",
+            filepaths.len()
+        ));
+
+        if let Ok(mut clusters) = self.clusters.lock() {
+            *clusters = None;
         }
-        true
+        let clusters = Arc::clone(&self.clusters);
+        let threshold = self.similarity_threshold;
+        let ctx = ctx.clone();
+        thread::spawn(move || {
+            let result = similarity::cluster_files(&filepaths, threshold);
+            if let Ok(mut clusters) = clusters.lock() {
+                *clusters = Some(result);
+            }
+            ctx.request_repaint();
+        });
     }
 
-    fn cleanup_threads(&mut self) {
-        for mut thread in self.worker_threads.drain(..) {
-            if let Some(thread_taken) = thread.take() {
-                match thread_taken.join() {
-                    Ok(_) => {}
-                    Err(err) => {
-                        println!("Error joining on thread {:#?}", err)
+    fn add_clusters_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_clusters_window;
+        let mut recompute = false;
+        egui::Window::new(tr("similarity-clusters"))
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr("min-similarity-score"));
+                    ui.add(egui::widgets::Slider::new(
+                        &mut self.similarity_threshold,
+                        1..=100,
+                    ));
+                    recompute = ui.button(tr("recompute")).clicked();
+                });
+                ui.separator();
+
+                let clusters = self.clusters.lock();
+                match clusters.as_deref() {
+                    Ok(Some(clusters)) => {
+                        egui::ScrollArea::vertical().show(ui, |ui| {
+                            for (idx, cluster) in clusters.iter().enumerate() {
+                                egui::CollapsingHeader::new(tr_args(
+                                    "cluster-files",
+                                    &[
+                                        ("cluster", (idx + 1).into()),
+                                        ("files", cluster.members.len().into()),
+                                    ],
+                                ))
+                                .default_open(cluster.members.len() > 1)
+                                .show(ui, |ui| {
+                                    for member in cluster.members.iter() {
+                                        ui.label(&member.filepath).on_hover_text(&member.ssdeep);
+                                    }
+                                });
+                            }
+                        });
                     }
+                    Ok(None) => {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label(tr("hashing-files"));
+                        });
+                    }
+                    Err(_) => {}
                 }
-            }
+            });
+
+        self.show_clusters_window = open;
+        if recompute {
+            self.cluster_matched_files(ctx);
         }
     }
 
-    fn log(&mut self, s: String) {
-        let date = chrono::Local::now();
-        self.log_lines
-            .push(format!("{} {}", date.format("[%Y-%m-%d][%H:%M:%S]"), s));
+    fn hide_to_tray(&mut self, ctx: &egui::Context) {
+        tray::hide_to_tray(
+            ctx,
+            Arc::clone(&self.scan_progress),
+            Arc::clone(&self.tray_hidden),
+        );
     }
 }
 
-fn search_file(entry: &DirEntry, tx: &mpsc::Sender<Finding>, search_opts: Arc<SearchOptions>) {
-    let f_res = OpenOptions::new().read(true).open(entry.path());
-
-    if let Ok(f) = f_res {
-        let file_data = unsafe {
+// the I/O stage: maps a file and touches the pages of its start so the matcher finds those
+// in memory, big files aren't read in whole before the matcher even starts on them
+fn prefetch_file(
+    entry: &DirEntry,
+    scan_mode: &ScanModeEnum,
+    retry: &ReadRetry,
+) -> Option<FileData> {
+    if *scan_mode == ScanModeEnum::Diff {
+        return None; // diffing maps both sides itself
+    }
+    let mut locked = false;
+    let file_data = retry.run(|| {
+        let f = OpenOptions::new()
+            .read(true)
+            .open(entry.path())
+            .map_err(|err| {
+                locked = raw_volume::is_locked(&err);
+                err
+            })?;
+        unsafe {
             // this is marked as unsafe because the contents of the backing file can change
             // outside of the compiler's expectation (and thus contents of refs may change etc)
             Mmap::map(&f)
-        };
+        }
+    });
+    let Some(file_data) = file_data else {
+        let reader = retry.locked_files.as_ref().filter(|_| locked)?;
+        let bytes = reader.read(entry.path())?;
+        // it no longer counts as unreadable
+        retry.failed.fetch_sub(1, Ordering::Relaxed);
+        return Some(FileData::Read(bytes));
+    };
 
-        if !file_data.is_ok() {
-            return;
+    #[cfg(unix)]
+    let _ = file_data.advise(memmap2::Advice::Sequential);
+    let mut touched = 0_u8;
+    let window = std::cmp::min(file_data.len(), PREFETCH_WINDOW);
+    for byte in file_data[..window].iter().step_by(PREFETCH_PAGE_SIZE) {
+        touched = touched.wrapping_add(*byte);
+    }
+    std::hint::black_box(touched);
+    Some(FileData::Mapped(file_data))
+}
+
+// the matching stage, `file_data` is None if the file couldn't be read
+fn search_file(
+    entry: &DirEntry,
+    file_data: Option<&[u8]>,
+    tx: &mpsc::Sender<Finding>,
+    search_opts: Arc<SearchOptions>,
+) -> ScanOutcome {
+    if search_opts.scan_mode == ScanModeEnum::Diff {
+        return diff::diff_file(entry, tx, &search_opts);
+    }
+    let Some(data) = file_data else {
+        return ScanOutcome::Skipped;
+    };
+
+    // Auto patterns are settled once per file, from a sniff of its first block
+    let search_opts = if search_opts
+        .patterns
+        .iter()
+        .any(|pattern| matches!(pattern.regex, RegexEnum::Auto(..)))
+    {
+        let text_file = analysis::looks_like_text(data);
+        Arc::new(SearchOptions {
+            patterns: search_opts
+                .patterns
+                .iter()
+                .map(|pattern| pattern.resolve_auto(text_file))
+                .collect(),
+            ..(*search_opts).clone()
+        })
+    } else {
+        search_opts
+    };
+
+    let mut curr_hits = 0;
+
+    if search_opts.scan_mode == ScanModeEnum::Duplicates {
+        let _ = tx.send(Finding {
+            filepath: String::from(entry.path().to_str().unwrap()),
+            offset: 0,
+            match_size: data.len(),
+            match_content: hash_lookup::sha256_bytes(data).into(),
+            location: None,
+            decoded: false,
+            hit_count: 1,
+            encoding: None,
+            virtual_address: None,
+            pattern: None,
+            aliases: None,
+            owner: None,
+            custom: None,
+        });
+        return ScanOutcome::Scanned;
+    }
+
+    if search_opts.scan_mode == ScanModeEnum::CountOnly {
+        for pattern in search_opts.patterns.iter() {
+            let count = count_matches(&search_opts, &pattern.regex, data);
+            if count > 0 {
+                let _ = tx.send(Finding {
+                    filepath: String::from(entry.path().to_str().unwrap()),
+                    offset: 0,
+                    match_size: 0,
+                    match_content: format!("{} matches", count).into(),
+                    location: None,
+                    decoded: false,
+                    hit_count: count,
+                    encoding: None,
+                    virtual_address: None,
+                    pattern: pattern.name.clone(),
+                    aliases: None,
+                    owner: None,
+                    custom: None,
+                });
+            }
         }
+        return ScanOutcome::Scanned;
+    }
 
-        let mut curr_hits = 0;
+    if search_opts.scan_mode == ScanModeEnum::Records {
+        return search_records(&search_opts, data, entry, tx);
+    }
 
-        match search_opts.regex_result.clone() {
-            Ok(re_enum) => match &re_enum {
-                RegexEnum::Hex(hex_re) => {
-                    for m in hex_re.find_iter(&file_data.unwrap()[..]) {
-                        process_binary_match(&search_opts, m, &entry, &tx);
-                        curr_hits += 1;
-                        if curr_hits >= search_opts.max_hits {
-                            return;
-                        }
+    if search_opts.scan_mode == ScanModeEnum::Padding {
+        let runs = analysis::repeated_runs(
+            data,
+            search_opts.padding_min_run,
+            search_opts.padding_max_period,
+        );
+        let num_runs = runs.len() as u32;
+        for (range, period) in runs.into_iter().take(search_opts.max_hits as usize) {
+            let fill: Vec<String> = data[range.start..range.start + period]
+                .iter()
+                .map(|byte| format!("{byte:02X}"))
+                .collect();
+            let _ = tx.send(Finding {
+                filepath: String::from(entry.path().to_str().unwrap()),
+                offset: range.start,
+                match_size: range.len(),
+                match_content: format!("{} x {}", range.len() / period, fill.join(" ")).into(),
+                location: None,
+                decoded: false,
+                hit_count: 1,
+                encoding: None,
+                virtual_address: None,
+                pattern: None,
+                aliases: None,
+                owner: None,
+                custom: None,
+            });
+        }
+        return hits_outcome(&search_opts, num_runs);
+    }
+
+    if search_opts.scan_mode == ScanModeEnum::Metadata {
+        for segment in handlers::metadata::extract(data).iter() {
+            let location = Some(segment.location.as_str());
+            if !search_data(
+                &search_opts,
+                &segment.data,
+                location,
+                entry,
+                tx,
+                &mut curr_hits,
+            ) {
+                break;
+            }
+        }
+        return hits_outcome(&search_opts, curr_hits);
+    }
+
+    if search_opts.decode_containers {
+        if let Some(container) = ContainerEnum::detect(entry.path(), data) {
+            let segments = container.extract(data, &search_opts.handler_options);
+            if !segments.is_empty() {
+                for segment in segments.iter() {
+                    let location = Some(segment.location.as_str());
+                    if !search_data(
+                        &search_opts,
+                        &segment.data,
+                        location,
+                        entry,
+                        tx,
+                        &mut curr_hits,
+                    ) {
+                        break;
                     }
                 }
-                RegexEnum::Text(txt_re) => {
-                    for m in txt_re.find_iter(&file_data.unwrap()[..]) {
-                        process_text_match(&search_opts, m, &entry, &tx);
+                return hits_outcome(&search_opts, curr_hits);
+            }
+        }
+    }
 
-                        curr_hits += 1;
-                        if curr_hits >= search_opts.max_hits {
-                            return;
-                        }
-                    }
+    if search_opts.attribute_fs {
+        if let Some(fs_map) = fs_carve::FsMap::build(data) {
+            let (fs_tx, fs_rx) = mpsc::channel();
+            search_data(&search_opts, data, None, entry, &fs_tx, &mut curr_hits);
+            drop(fs_tx);
+            for mut finding in fs_rx.iter() {
+                finding.owner = Some(fs_map.owner(finding.offset as u64));
+                if tx.send(finding).is_err() {
+                    break;
                 }
-            },
+            }
+            return hits_outcome(&search_opts, curr_hits);
+        }
+    }
+
+    search_data(&search_opts, data, None, entry, tx, &mut curr_hits);
+    hits_outcome(&search_opts, curr_hits)
+}
+
+fn hits_outcome(search_opts: &SearchOptions, hits: u32) -> ScanOutcome {
+    if hits >= search_opts.max_hits {
+        ScanOutcome::Capped
+    } else {
+        ScanOutcome::Scanned
+    }
+}
+
+// returns false once max_hits has been reached
+fn search_data(
+    search_opts: &SearchOptions,
+    data: &[u8],
+    location: Option<&str>,
+    entry: &DirEntry,
+    tx: &mpsc::Sender<Finding>,
+    curr_hits: &mut u32,
+) -> bool {
+    if search_opts.patterns.is_empty() {
+        return false; // don't continue if there's a problem with regex
+    }
+    for pattern in search_opts.patterns.iter() {
+        if !search_pattern(search_opts, pattern, data, location, entry, tx, curr_hits) {
+            return false;
+        }
+    }
+    true
+}
+
+// returns false once max_hits has been reached
+fn search_pattern(
+    search_opts: &SearchOptions,
+    pattern: &SearchPattern,
+    data: &[u8],
+    location: Option<&str>,
+    entry: &DirEntry,
+    tx: &mpsc::Sender<Finding>,
+    curr_hits: &mut u32,
+) -> bool {
+    if search_opts.first_match_only {
+        let found = send_first_match(search_opts, pattern, data, location, entry, tx);
+        // with several patterns, each one gets its first match reported
+        return !found || search_opts.patterns.len() > 1;
+    }
+
+    match &pattern.regex {
+        RegexEnum::Hex(hex_re) => {
+            for m in hex_re.find_iter(data) {
+                if !process_binary_match(search_opts, pattern, data, m.range(), location, entry, tx)
+                {
+                    continue;
+                }
+                *curr_hits += 1;
+                if *curr_hits >= search_opts.max_hits {
+                    return false;
+                }
+            }
+        }
+        RegexEnum::Text(txt_re) | RegexEnum::Auto(txt_re, _) => {
+            for m in txt_re.find_iter(data) {
+                if !process_text_match(search_opts, pattern, data, m.range(), location, entry, tx) {
+                    continue;
+                }
+
+                *curr_hits += 1;
+                if *curr_hits >= search_opts.max_hits {
+                    return false;
+                }
+            }
+        }
+    }
+
+    true
+}
+
+// returns true if a match was found (and sent)
+fn send_first_match(
+    search_opts: &SearchOptions,
+    pattern: &SearchPattern,
+    data: &[u8],
+    location: Option<&str>,
+    entry: &DirEntry,
+    tx: &mpsc::Sender<Finding>,
+) -> bool {
+    match &pattern.regex {
+        RegexEnum::Hex(hex_re) => {
+            match hex_re.find_iter(data).find(|m| {
+                is_aligned(search_opts, m.start()) && is_wanted_length(search_opts, m.len())
+            }) {
+                Some(m) => {
+                    process_binary_match(
+                        search_opts,
+                        pattern,
+                        data,
+                        m.range(),
+                        location,
+                        entry,
+                        tx,
+                    );
+                    true
+                }
+                None => false,
+            }
+        }
+        RegexEnum::Text(txt_re) | RegexEnum::Auto(txt_re, _) => match txt_re
+            .find_iter(data)
+            .find(|m| is_aligned(search_opts, m.start()) && is_wanted_length(search_opts, m.len()))
+        {
+            Some(m) => {
+                process_text_match(search_opts, pattern, data, m.range(), location, entry, tx);
+                true
+            }
+            None => false,
+        },
+    }
+}
 
-            Err(_err_msg) => {
-                return; // don't continue if there's a problem with regex
+// patterns are anchored in record mode, so a match has to start right at the field
+fn search_records(
+    search_opts: &SearchOptions,
+    data: &[u8],
+    entry: &DirEntry,
+    tx: &mpsc::Sender<Finding>,
+) -> ScanOutcome {
+    let mut curr_hits = 0;
+    for field in search_opts.record_layout.fields(data.len()) {
+        for pattern in search_opts.patterns.iter() {
+            let reported = match &pattern.regex {
+                RegexEnum::Hex(hex_re) => hex_re.find(&data[field.clone()]).is_some_and(|m| {
+                    let m = field.start + m.start()..field.start + m.end();
+                    process_binary_match(search_opts, pattern, data, m, None, entry, tx)
+                }),
+                RegexEnum::Text(txt_re) | RegexEnum::Auto(txt_re, _) => {
+                    txt_re.find(&data[field.clone()]).is_some_and(|m| {
+                        let m = field.start + m.start()..field.start + m.end();
+                        process_text_match(search_opts, pattern, data, m, None, entry, tx)
+                    })
+                }
+            };
+            if reported {
+                curr_hits += 1;
+                if search_opts.first_match_only || curr_hits >= search_opts.max_hits {
+                    return hits_outcome(search_opts, curr_hits);
+                }
             }
         }
     }
+    ScanOutcome::Scanned
 }
 
+// no content capture and no per-match messages, just a tally
+fn count_matches(search_opts: &SearchOptions, regex: &RegexEnum, data: &[u8]) -> usize {
+    match regex {
+        RegexEnum::Hex(hex_re) => hex_re
+            .find_iter(data)
+            .filter(|m| {
+                is_aligned(search_opts, m.start()) && is_wanted_length(search_opts, m.len())
+            })
+            .count(),
+        RegexEnum::Text(txt_re) | RegexEnum::Auto(txt_re, _) => txt_re
+            .find_iter(data)
+            .filter(|m| {
+                is_aligned(search_opts, m.start()) && is_wanted_length(search_opts, m.len())
+            })
+            .count(),
+    }
+}
+
+fn is_aligned(search_opts: &SearchOptions, offset: usize) -> bool {
+    search_opts.alignment == 0
+        || (offset >= search_opts.alignment_base
+            && (offset - search_opts.alignment_base) % search_opts.alignment as usize == 0)
+}
+
+fn is_wanted_length(search_opts: &SearchOptions, len: usize) -> bool {
+    len >= search_opts.min_match_len
+        && (search_opts.max_match_len == 0 || len <= search_opts.max_match_len)
+}
+
+// the user defined column for a match, from the pattern's own regex so its groups are there
+fn custom_value(
+    search_opts: &SearchOptions,
+    pattern: &SearchPattern,
+    data: &[u8],
+    m: &Range<usize>,
+) -> Option<Arc<str>> {
+    let column = search_opts.custom_column.as_ref()?;
+    let regex = match &pattern.regex {
+        RegexEnum::Hex(regex) | RegexEnum::Text(regex) | RegexEnum::Auto(regex, _) => regex,
+    };
+    column.value(regex, data, m.clone())
+}
+
+// returns whether the match passed the filters and was reported
 fn process_binary_match(
     search_opts: &SearchOptions,
-    m: regex::bytes::Match,
+    pattern: &SearchPattern,
+    data: &[u8],
+    m: Range<usize>,
+    location: Option<&str>,
     entry: &DirEntry,
     tx: &mpsc::Sender<Finding>,
-) {
-    if search_opts.alignment != 0 && (m.start() % search_opts.alignment as usize) != 0 {
-        return;
+) -> bool {
+    if !is_aligned(search_opts, m.start) || !is_wanted_length(search_opts, m.len()) {
+        return false;
     }
+    let custom = custom_value(search_opts, pattern, data, &m);
     match tx.send(Finding {
         filepath: String::from(entry.path().to_str().unwrap()),
-        offset: m.start(),
+        offset: m.start,
         match_size: m.len(),
-        match_content: m
-            .as_bytes()
-            .iter()
-            .map(|b| format!("{:02x}", b))
-            .collect::<Vec<_>>()
-            .join(" "),
+        match_content: MatchContent::Bytes(data[m].into()),
+        location: location.map(String::from),
+        decoded: location.is_some(),
+        hit_count: 1,
+        encoding: None,
+        virtual_address: None,
+        pattern: pattern.name.clone(),
+        aliases: None,
+        owner: None,
+        custom,
     }) {
         Ok(_) => {}
         Err(_) => { /* TODO do something about an error */ }
     }
+    true
 }
 
+// returns whether the match passed the filters and was reported
 fn process_text_match(
-    _search_opts: &SearchOptions,
-    m: regex::bytes::Match,
+    search_opts: &SearchOptions,
+    pattern: &SearchPattern,
+    data: &[u8],
+    m: Range<usize>,
+    location: Option<&str>,
     entry: &DirEntry,
     tx: &mpsc::Sender<Finding>,
-) {
+) -> bool {
+    if !is_aligned(search_opts, m.start) || !is_wanted_length(search_opts, m.len()) {
+        return false;
+    }
+    let custom = custom_value(search_opts, pattern, data, &m);
     match tx.send(Finding {
         filepath: String::from(entry.path().to_str().unwrap()),
-        offset: m.start(),
+        offset: m.start,
         match_size: m.len(),
-        match_content: String::from_utf8_lossy(m.as_bytes()).to_string(),
+        match_content: String::from_utf8_lossy(&data[m.clone()]).to_string().into(),
+        location: location.map(String::from),
+        decoded: location.is_some(),
+        hit_count: 1,
+        encoding: Some(analysis::detect_encoding(data, m)),
+        virtual_address: None,
+        pattern: pattern.name.clone(),
+        aliases: None,
+        owner: None,
+        custom,
     }) {
         Ok(_) => {}
         Err(_) => { /* TODO do something about an error */ }
     }
+    true
+}
+
+// only files sharing their size with another file can possibly be duplicates
+fn retain_same_size_files(entries: Vec<DirEntry>) -> Vec<DirEntry> {
+    let sizes: Vec<u64> = entries
+        .iter()
+        .map(|e| e.metadata().map(|m| m.len()).unwrap_or(0))
+        .collect();
+    let mut size_counts: HashMap<u64, usize> = HashMap::new();
+    for size in sizes.iter() {
+        *size_counts.entry(*size).or_default() += 1;
+    }
+
+    entries
+        .into_iter()
+        .zip(sizes)
+        .filter(|(_e, size)| *size > 0 && size_counts[size] > 1)
+        .map(|(e, _size)| e)
+        .collect()
+}
+
+// file:// reference to a local path, as file managers and editors accept on paste
+fn file_uri(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let mut uri = String::from(if path.starts_with('/') {
+        "file://"
+    } else {
+        "file:///"
+    });
+    // segment by segment, a drive letter keeps its colon
+    let segments: Vec<String> = path
+        .split('/')
+        .map(|segment| match segment.strip_suffix(':') {
+            Some(drive) if drive.len() == 1 && drive.chars().all(|c| c.is_ascii_alphabetic()) => {
+                segment.to_string()
+            }
+            _ => percent_encode(segment),
+        })
+        .collect();
+    uri.push_str(&segments.join("/"));
+    uri
+}
+
+fn format_size(bytes: usize) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+// (device, inode), the same for every path that leads to a file
+#[cfg(unix)]
+fn file_identity(entry: &DirEntry) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    entry
+        .metadata()
+        .ok()
+        .map(|metadata| (metadata.dev(), metadata.ino()))
+}
+
+// std doesn't expose file ids on other platforms, so nothing is deduplicated there
+#[cfg(not(unix))]
+fn file_identity(_entry: &DirEntry) -> Option<(u64, u64)> {
+    None
 }
 
 fn create_filter_iter(
     wlkdir: WalkDir,
     options: FileWalkOptions,
 ) -> FilterEntry<walkdir::IntoIter, impl core::ops::FnMut(&DirEntry) -> bool> {
-    return wlkdir
-        .into_iter()
-        .filter_entry(move |e| match options.hidden_files {
-            FilterTypeEnum::NoHidden => {
-                return !is_hidden(e);
+    wlkdir.into_iter().filter_entry(move |e| {
+        let wanted = match options.hidden_files {
+            FilterTypeEnum::NoHidden => !is_hidden(e),
+            FilterTypeEnum::NoHiddenOrSystem => !is_hidden(e) && !is_system(e),
+            FilterTypeEnum::AllFiles => true,
+        };
+        wanted && !options.skip_paths.iter().any(|skip| e.path() == skip)
+    })
+}
+
+// `case_insensitive` only affects text patterns
+fn compile_pattern(
+    pattern: &str,
+    content_type: &ContentEnum,
+    case_insensitive: bool,
+) -> Result<RegexEnum, String> {
+    match content_type {
+        ContentEnum::Hex => {
+            let mut unicode_off_re_str = String::from("(?-u)"); // append unicode disable so even non-utf8 stuff matches
+            match convert_simplified_hex_regex(pattern) {
+                Ok(r) => {
+                    unicode_off_re_str.push_str(&r);
+
+                    match BytesRegex::new(&unicode_off_re_str) {
+                        Ok(unicode_off_re) => Ok(RegexEnum::Hex(unicode_off_re)),
+                        Err(re_error) => Err(format!("Error compiling regex: {}", re_error)),
+                    }
+                }
+                Err(err) => match err {
+                    RegexErr::InvalidChar => Err("Invalid char inside hex regex.".to_string()),
+                    RegexErr::EmptyRegex => {
+                        Err("Empty regex, please add one to search".to_string())
+                    }
+                },
             }
-            FilterTypeEnum::AllFiles => {
-                return true;
+        }
+        ContentEnum::Text => {
+            if pattern.is_empty() {
+                Err("Empty regex, please add one to search".to_string())
+            } else {
+                match BytesRegexBuilder::new(pattern)
+                    .case_insensitive(case_insensitive)
+                    .build()
+                {
+                    Ok(good_re) => Ok(RegexEnum::Text(good_re)),
+                    Err(re_error) => Err(format!("Error compiling regex: {}", re_error)),
+                }
             }
-        });
+        }
+        ContentEnum::Auto => {
+            let text_re = match compile_pattern(pattern, &ContentEnum::Text, case_insensitive)? {
+                RegexEnum::Text(text_re) => text_re,
+                regex => return Ok(regex),
+            };
+            // binaries fall back to the text interpretation when the pattern isn't hex
+            let hex_re = match compile_pattern(pattern, &ContentEnum::Hex, false) {
+                Ok(RegexEnum::Hex(hex_re)) => Some(hex_re),
+                _ => None,
+            };
+            Ok(RegexEnum::Auto(text_re, hex_re))
+        }
+    }
 }
 
-fn convert_simplified_hex_regex(regex_str: &String) -> Result<String, RegexErr> {
+fn convert_simplified_hex_regex(regex_str: &str) -> Result<String, RegexErr> {
     let no_spaces = regex_str.replace(" ", "");
     let invalid_char_re = Utf8Regex::new("[^a-fA-F0-9.?\\[\\]\\{\\}\\(\\)\\|,-]").unwrap();
-    if let Some(_) = invalid_char_re.find(&no_spaces) {
+    if invalid_char_re.find(&no_spaces).is_some() {
         // found an invalid character
         return Err(RegexErr::InvalidChar);
     }
@@ -1386,11 +4676,102 @@ fn convert_simplified_hex_regex(regex_str: &String) -> Result<String, RegexErr>
     Ok(add_x_escapes.to_string())
 }
 
+// shows a file dialog in its own viewport, returns the picked path and whether the dialog is done
+fn update_dialog_viewport(
+    ctx: &egui::Context,
+    dialog: &mut FileDialog,
+    id: &str,
+    title: &str,
+) -> (Option<PathBuf>, bool) {
+    let mut should_close_dialog = false;
+    let viewport_id = egui::ViewportId::from_hash_of(id);
+    let viewport_builder = egui::ViewportBuilder::default()
+        .with_inner_size((800.0 + 10., 600.0 + 50.))
+        .with_resizable(false)
+        .with_title(title)
+        .with_decorations(true);
+
+    let viewport_cb = |ctx: &egui::Context, _| {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.with_layout(
+                egui::Layout::left_to_right(egui::Align::Center).with_main_justify(true),
+                |_ui| {
+                    dialog.update(ctx);
+                },
+            );
+        });
+
+        if ctx.input(|i| i.viewport().close_requested()) {
+            should_close_dialog = true;
+        }
+    };
+
+    ctx.show_viewport_immediate(viewport_id, viewport_builder, viewport_cb);
+    let selected = dialog.take_selected();
+
+    let done = match dialog.state() {
+        DialogState::Open => false,
+        DialogState::Closed => true,
+        DialogState::Selected(_) => false, // TODO use this in a nicer fashion than rebuilding gui element
+        DialogState::SelectedMultiple(_) => false,
+        DialogState::Cancelled => true,
+    };
+
+    (selected, done || should_close_dialog)
+}
+
+fn cyberchef_url(recipe: &str, input: &str) -> String {
+    // CyberChef expects the input as unpadded base64 in the URL fragment
+    let b64_input = base64::engine::general_purpose::STANDARD_NO_PAD.encode(input);
+    format!(
+        "https://gchq.github.io/CyberChef/#recipe={}&input={}",
+        percent_encode(recipe),
+        percent_encode(&b64_input)
+    )
+}
+
+fn percent_encode(input: &str) -> String {
+    let mut encoded = String::with_capacity(input.len() * 3);
+    for byte in input.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
 // identify unix hidden files
+// dot files everywhere, plus the hidden attribute on Windows
 fn is_hidden(entry: &DirEntry) -> bool {
-    entry
+    let dot_file = entry
         .file_name()
         .to_str()
         .map(|s| s.starts_with("."))
+        .unwrap_or(false);
+    dot_file || has_attribute(entry, FILE_ATTRIBUTE_HIDDEN)
+}
+
+fn is_system(entry: &DirEntry) -> bool {
+    has_attribute(entry, FILE_ATTRIBUTE_SYSTEM)
+}
+
+const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+const FILE_ATTRIBUTE_SYSTEM: u32 = 0x4;
+
+// the walk already read the attributes on Windows, so this doesn't touch the disk again
+#[cfg(windows)]
+fn has_attribute(entry: &DirEntry, attribute: u32) -> bool {
+    use std::os::windows::fs::MetadataExt;
+    entry
+        .metadata()
+        .map(|metadata| metadata.file_attributes() & attribute != 0)
         .unwrap_or(false)
 }
+
+#[cfg(not(windows))]
+fn has_attribute(_entry: &DirEntry, _attribute: u32) -> bool {
+    false
+}
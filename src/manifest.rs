@@ -0,0 +1,302 @@
+use crate::hash_lookup::sha256_file;
+use crate::i18n::{tr, tr_args};
+use crate::QuerApp;
+use eframe::egui::{self, Color32};
+use egui_file_dialog::FileDialog;
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+// files listed per group in the check window before the rest is only counted
+const MAX_LISTED: usize = 200;
+
+fn modified(metadata: &fs::Metadata) -> Option<String> {
+    let modified = metadata.modified().ok()?;
+    Some(chrono::DateTime::<chrono::Local>::from(modified).to_rfc3339())
+}
+
+// one line of the manifest, files that can't be read anymore are listed with the error
+fn file_entry(path: &Path) -> json::JsonValue {
+    let mut obj = json::JsonValue::new_object();
+    obj["path"] = path.to_string_lossy().to_string().into();
+    let hashed = fs::metadata(path).and_then(|metadata| Ok((metadata, sha256_file(path)?)));
+    match hashed {
+        Ok((metadata, sha256)) => {
+            obj["size"] = metadata.len().into();
+            if let Some(modified) = modified(&metadata) {
+                obj["mtime"] = modified.into();
+            }
+            obj["sha256"] = sha256.into();
+        }
+        Err(err) => obj["error"] = err.to_string().into(),
+    }
+    obj
+}
+
+// JSON Lines, one object with path, size, mtime and sha256 per scanned file. the files are
+// hashed as they are when the manifest is written, not as they were during the scan.
+pub(crate) fn write_manifest(
+    files: &[PathBuf],
+    path: &Path,
+    written: &AtomicUsize,
+    cancel: &AtomicBool,
+) -> Result<usize, String> {
+    let mut writer = BufWriter::new(File::create(path).map_err(|err| err.to_string())?);
+    for (idx, file) in files.iter().enumerate() {
+        // hashing takes long enough to check after every file
+        if cancel.load(Ordering::Relaxed) {
+            return Err("cancelled".to_string());
+        }
+        written.store(idx, Ordering::Relaxed);
+        writeln!(writer, "{}", file_entry(file).dump()).map_err(|err| err.to_string())?;
+    }
+    writer.flush().map_err(|err| err.to_string())?;
+    written.store(files.len(), Ordering::Relaxed);
+    Ok(files.len())
+}
+
+// how the files on disk differ from a manifest
+#[derive(Default)]
+pub struct ManifestCheck {
+    unchanged: usize,
+    changed: Vec<String>, // different size or content
+    touched: Vec<String>, // same content, but modified since
+    missing: Vec<String>,
+    added: Vec<String>, // scanned by the last search but not in the manifest
+}
+
+impl ManifestCheck {
+    fn is_identical(&self) -> bool {
+        self.changed.is_empty()
+            && self.touched.is_empty()
+            && self.missing.is_empty()
+            && self.added.is_empty()
+    }
+}
+
+pub struct ManifestCheckJob {
+    path: PathBuf,
+    total: Arc<AtomicUsize>,
+    checked: Arc<AtomicUsize>,
+    cancel: Arc<AtomicBool>,
+    result: Arc<Mutex<Option<Result<ManifestCheck, String>>>>,
+    outcome: Option<ManifestCheck>, // once the check is done
+}
+
+fn check_manifest(
+    path: &Path,
+    scanned: &[PathBuf],
+    total: &AtomicUsize,
+    checked: &AtomicUsize,
+    cancel: &AtomicBool,
+) -> Result<ManifestCheck, String> {
+    let reader = BufReader::new(File::open(path).map_err(|err| err.to_string())?);
+    let mut entries = Vec::new();
+    for (idx, line) in reader.lines().enumerate() {
+        let line = line.map_err(|err| err.to_string())?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry = json::parse(&line)
+            .ok()
+            .filter(|entry| entry["path"].is_string())
+            .ok_or_else(|| format!("line {} isn't a manifest entry", idx + 1))?;
+        entries.push(entry);
+    }
+    total.store(entries.len(), Ordering::Relaxed);
+
+    let mut check = ManifestCheck::default();
+    let mut listed = HashSet::new();
+    for (idx, entry) in entries.iter().enumerate() {
+        if cancel.load(Ordering::Relaxed) {
+            return Err("cancelled".to_string());
+        }
+        checked.store(idx, Ordering::Relaxed);
+        let file = entry["path"].as_str().unwrap_or_default().to_string();
+        listed.insert(PathBuf::from(&file));
+        let Ok(metadata) = fs::metadata(&file) else {
+            check.missing.push(file);
+            continue;
+        };
+        let same_content = entry["size"].as_u64() == Some(metadata.len())
+            && entry["sha256"].as_str().is_some_and(|sha256| {
+                sha256_file(Path::new(&file)).is_ok_and(|current| current == sha256)
+            });
+        if !same_content {
+            check.changed.push(file);
+        } else if entry["mtime"].as_str() != modified(&metadata).as_deref() {
+            check.touched.push(file);
+        } else {
+            check.unchanged += 1;
+        }
+    }
+    checked.store(entries.len(), Ordering::Relaxed);
+    // a file scanned twice, e.g. by searching within results, is listed once
+    let mut seen = HashSet::new();
+    for file in scanned {
+        if !listed.contains(file) && seen.insert(file) {
+            check.added.push(file.to_string_lossy().to_string());
+        }
+    }
+    Ok(check)
+}
+
+impl QuerApp {
+    pub(crate) fn open_manifest_dialog(&mut self) {
+        let mut dialog = FileDialog::new()
+            .initial_directory(self.export_file_path.clone())
+            .as_modal(false)
+            .title_bar(false)
+            .movable(false)
+            .resizable(false)
+            .min_size([800., 600.]);
+        dialog.select_file();
+        self.manifest_dialog = Some(dialog);
+    }
+
+    fn start_manifest_check(&mut self, path: PathBuf, ctx: &egui::Context) {
+        self.log(format!("Checking the files listed in {}", path.display()));
+        let job = ManifestCheckJob {
+            path: path.clone(),
+            total: Arc::new(AtomicUsize::new(0)),
+            checked: Arc::new(AtomicUsize::new(0)),
+            cancel: Arc::new(AtomicBool::new(false)),
+            result: Arc::new(Mutex::new(None)),
+            outcome: None,
+        };
+        let scanned = self.scanned_files.clone();
+        let (total, checked) = (Arc::clone(&job.total), Arc::clone(&job.checked));
+        let (cancel, result) = (Arc::clone(&job.cancel), Arc::clone(&job.result));
+        let ctx = ctx.clone();
+        thread::spawn(move || {
+            let outcome = check_manifest(&path, &scanned, &total, &checked, &cancel);
+            if let Ok(mut result) = result.lock() {
+                *result = Some(outcome);
+            }
+            ctx.request_repaint();
+        });
+        self.manifest_check = Some(job);
+    }
+
+    pub(crate) fn add_manifest_check_window(&mut self, ctx: &egui::Context) {
+        if let Some(dialog) = &mut self.manifest_dialog {
+            let (selected, done) =
+                crate::update_dialog_viewport(ctx, dialog, "manifest_dialog", "Open Manifest");
+            if done || selected.is_some() {
+                self.manifest_dialog = None;
+            }
+            if let Some(path) = selected {
+                self.start_manifest_check(path, ctx);
+            }
+        }
+
+        let Some(job) = &self.manifest_check else {
+            return;
+        };
+        let finished = job.result.lock().ok().and_then(|mut result| result.take());
+        if let Some(outcome) = finished {
+            let path = job.path.display().to_string();
+            match &outcome {
+                Ok(check) if check.is_identical() => self.log(format!(
+                    "All {} files of {} are unchanged",
+                    check.unchanged, path
+                )),
+                Ok(check) => self.log(format!(
+                    "{} differs: {} changed, {} modified without changes, {} missing, {} new",
+                    path,
+                    check.changed.len(),
+                    check.touched.len(),
+                    check.missing.len(),
+                    check.added.len()
+                )),
+                Err(err) => self.log_error(format!("Couldn't check {}: {}", path, err)),
+            }
+            match (outcome, &mut self.manifest_check) {
+                (Ok(check), Some(job)) => job.outcome = Some(check),
+                _ => {
+                    self.manifest_check = None;
+                    return;
+                }
+            }
+        }
+        let Some(job) = &self.manifest_check else {
+            return;
+        };
+
+        let mut open = true;
+        let mut cancel_clicked = false;
+        egui::Window::new(tr("manifest-check"))
+            .open(&mut open)
+            .default_width(520.0)
+            .show(ctx, |ui| {
+                ui.label(job.path.display().to_string());
+                let Some(check) = &job.outcome else {
+                    let (checked, total) = (
+                        job.checked.load(Ordering::Relaxed),
+                        job.total.load(Ordering::Relaxed),
+                    );
+                    let fraction = match total {
+                        0 => 0.0,
+                        total => checked as f32 / total as f32,
+                    };
+                    ui.add(egui::ProgressBar::new(fraction).text(tr_args(
+                        "checked-of-total-files",
+                        &[("checked", checked.into()), ("total", total.into())],
+                    )));
+                    cancel_clicked = ui.button(tr("cancel")).clicked();
+                    return;
+                };
+                ui.label(tr_args(
+                    "manifest-unchanged",
+                    &[("count", check.unchanged.into())],
+                ));
+                if check.is_identical() {
+                    return;
+                }
+                egui::ScrollArea::vertical()
+                    .max_height(400.0)
+                    .show(ui, |ui| {
+                        let groups = [
+                            ("manifest-changed", &check.changed),
+                            ("manifest-touched", &check.touched),
+                            ("manifest-missing", &check.missing),
+                            ("manifest-added", &check.added),
+                        ];
+                        for (key, files) in groups {
+                            if files.is_empty() {
+                                continue;
+                            }
+                            let header = tr_args(key, &[("count", files.len().into())]);
+                            egui::CollapsingHeader::new(
+                                egui::RichText::new(header).color(Color32::from_rgb(0x8f, 0, 0)),
+                            )
+                            .id_source(key)
+                            .show(ui, |ui| {
+                                for file in files.iter().take(MAX_LISTED) {
+                                    ui.small(file);
+                                }
+                                if files.len() > MAX_LISTED {
+                                    ui.small(tr_args(
+                                        "n-more",
+                                        &[("more", (files.len() - MAX_LISTED).into())],
+                                    ));
+                                }
+                            });
+                        }
+                    });
+            });
+        if cancel_clicked {
+            job.cancel.store(true, Ordering::Relaxed);
+        }
+        if !open {
+            job.cancel.store(true, Ordering::Relaxed);
+            self.manifest_check = None;
+        } else if job.outcome.is_none() {
+            ctx.request_repaint_after(std::time::Duration::from_millis(100));
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{Finding, MatchContent, QuerApp};
+use std::collections::HashMap;
+
+// bytes of a merged region read back from the file for its match column
+const MERGED_CONTENT_LIMIT: usize = 256;
+
+// merges findings of the same file whose regions overlap or are at most `gap` bytes apart,
+// files keep the order they were first found in and their regions come out sorted by offset
+pub fn merge_adjacent(findings: Vec<Finding>, gap: usize) -> Vec<(Finding, usize)> {
+    let mut file_order: Vec<String> = Vec::new();
+    let mut by_file: HashMap<String, Vec<Finding>> = HashMap::new();
+    for finding in findings {
+        by_file
+            .entry(finding.filepath.clone())
+            .or_insert_with(|| {
+                file_order.push(finding.filepath.clone());
+                Vec::new()
+            })
+            .push(finding);
+    }
+
+    let mut merged: Vec<(Finding, usize)> = Vec::new();
+    for path in file_order {
+        let mut file_findings = by_file.remove(&path).unwrap_or_default();
+        file_findings.sort_by_key(|finding| finding.offset);
+        let file_start = merged.len();
+        for finding in file_findings {
+            let last = merged[file_start..].last_mut();
+            match last {
+                // offsets inside decoded containers only line up with others from the same one
+                Some((region, parts))
+                    if region.location == finding.location
+                        && finding.offset <= region.offset + region.match_size + gap =>
+                {
+                    let end = std::cmp::max(
+                        region.offset + region.match_size,
+                        finding.offset + finding.match_size,
+                    );
+                    region.match_size = end - region.offset;
+                    region.hit_count += finding.hit_count;
+                    if region.pattern != finding.pattern {
+                        region.pattern = None;
+                    }
+                    *parts += 1;
+                }
+                _ => merged.push((finding, 1)),
+            }
+        }
+    }
+    merged
+}
+
+impl QuerApp {
+    // run after a content search when merging is enabled
+    pub(crate) fn merge_adjacent_findings(&mut self) {
+        let start = std::cmp::min(self.search_start_index, self.findings.len());
+        let before = self.findings.len() - start;
+        let findings: Vec<Finding> = self.findings.drain(start..).collect();
+        for (mut region, parts) in merge_adjacent(findings, self.merge_gap) {
+            if parts > 1 && !region.decoded {
+                let len = std::cmp::min(region.match_size, MERGED_CONTENT_LIMIT);
+                if let Some(bytes) = self.get_file_contents(&region.filepath, region.offset, len) {
+                    region.match_content = match region.match_content {
+                        MatchContent::Text(_) => {
+                            MatchContent::Text(String::from_utf8_lossy(&bytes).to_string())
+                        }
+                        MatchContent::Bytes(_) => MatchContent::Bytes(bytes.into()),
+                    };
+                }
+            }
+            self.findings.push(region);
+        }
+        self.invalidate_finding_caches();
+
+        let after = self.findings.len() - start;
+        if after < before {
+            self.scan_summary = format!("Merged {} matches into {} regions.", before, after);
+            self.log(self.scan_summary.clone());
+        }
+    }
+}
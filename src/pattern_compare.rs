@@ -0,0 +1,188 @@
+use crate::i18n::tr;
+use crate::QuerApp;
+use eframe::egui::{self, text::LayoutJob, Color32, TextFormat};
+use std::ops::Range;
+use std::sync::Arc;
+
+// backgrounds of bytes matched by the first, the second and both compared patterns
+const COMPARE_COLORS: [Color32; 3] = [
+    Color32::from_rgb(0x2a, 0x7e, 0x43),
+    Color32::from_rgb(0x1f, 0x5a, 0x8a),
+    Color32::from_rgb(0x8a, 0x3f, 0x8a),
+];
+
+// a match of a named pattern in the selected file
+#[derive(Clone)]
+pub struct PatternRegion {
+    pub range: Range<usize>,
+    pub pattern: Arc<str>,
+}
+
+// the color of whichever compared patterns cover `offset`
+fn compare_color(
+    regions: &[PatternRegion],
+    patterns: &[Arc<str>; 2],
+    offset: usize,
+) -> Option<Color32> {
+    let mut hit = [false; 2];
+    for region in regions
+        .iter()
+        .filter(|region| region.range.contains(&offset))
+    {
+        for (idx, pattern) in patterns.iter().enumerate() {
+            hit[idx] |= region.pattern == *pattern;
+        }
+    }
+    match hit {
+        [true, true] => Some(COMPARE_COLORS[2]),
+        [true, false] => Some(COMPARE_COLORS[0]),
+        [false, true] => Some(COMPARE_COLORS[1]),
+        [false, false] => None,
+    }
+}
+
+impl QuerApp {
+    // patterns that matched the selected file, in the order they were first found
+    fn file_view_patterns(&self) -> Vec<Arc<str>> {
+        let mut patterns: Vec<Arc<str>> = Vec::new();
+        for region in self.file_view_regions.iter() {
+            if !patterns.contains(&region.pattern) {
+                patterns.push(Arc::clone(&region.pattern));
+            }
+        }
+        patterns
+    }
+
+    // the two patterns to tell apart, only once the selected file matched at least two,
+    // falling back to the first two found when nothing (or nothing present) was picked
+    pub(crate) fn compared_patterns(&self) -> Option<[Arc<str>; 2]> {
+        if !self.compare_patterns_enabled {
+            return None;
+        }
+        let patterns = self.file_view_patterns();
+        if patterns.len() < 2 {
+            return None;
+        }
+        let pick = |idx: usize, other: Option<&Arc<str>>| {
+            self.compare_patterns[idx]
+                .clone()
+                .filter(|picked| patterns.contains(picked) && Some(picked) != other)
+                .or_else(|| {
+                    patterns
+                        .iter()
+                        .find(|pattern| Some(*pattern) != other)
+                        .cloned()
+                })
+        };
+        let first = pick(0, None)?;
+        let second = pick(1, Some(&first))?;
+        Some([first, second])
+    }
+
+    // regions overlapping `range`, so per-byte lookups only go through the visible ones
+    pub(crate) fn pattern_regions_in(&self, range: Range<usize>) -> Vec<PatternRegion> {
+        self.file_view_regions
+            .iter()
+            .filter(|region| region.range.start < range.end && range.start < region.range.end)
+            .cloned()
+            .collect()
+    }
+
+    pub(crate) fn add_pattern_compare_selector(&mut self, ui: &mut egui::Ui) {
+        let patterns = self.file_view_patterns();
+        if patterns.len() < 2 {
+            return;
+        }
+        let current = self.compared_patterns();
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.compare_patterns_enabled, tr("compare-patterns"))
+                .on_hover_text(tr("compare-patterns-hint"));
+            let Some(current) = current else {
+                return;
+            };
+            for (idx, picked) in current.iter().enumerate() {
+                if idx == 1 {
+                    ui.label(tr("versus"));
+                }
+                let swatch = egui::RichText::new(picked.as_ref())
+                    .color(Color32::WHITE)
+                    .background_color(COMPARE_COLORS[idx]);
+                egui::ComboBox::from_id_source(("compare_pattern", idx))
+                    .selected_text(swatch)
+                    .show_ui(ui, |ui| {
+                        for pattern in patterns.iter() {
+                            if ui
+                                .selectable_label(pattern == picked, pattern.as_ref())
+                                .clicked()
+                            {
+                                self.compare_patterns[idx] = Some(Arc::clone(pattern));
+                            }
+                        }
+                    });
+            }
+            ui.label(
+                egui::RichText::new(tr("matched-by-both"))
+                    .color(Color32::WHITE)
+                    .background_color(COMPARE_COLORS[2]),
+            );
+        });
+    }
+
+    // one row laid out like `bytes_to_hexdump`, with the compared patterns' bytes colored
+    // and everything else on `row_background`
+    pub(crate) fn hexdump_compare_row(
+        &self,
+        ui: &egui::Ui,
+        (row, row_offset): (&[u8], usize),
+        row_background: Color32,
+        regions: &[PatternRegion],
+        patterns: &[Arc<str>; 2],
+    ) -> LayoutJob {
+        let font_id = egui::TextStyle::Monospace.resolve(ui.style());
+        let plain = TextFormat {
+            background: row_background,
+            ..TextFormat::simple(font_id.clone(), ui.visuals().text_color())
+        };
+        let format_for = |color: Option<Color32>| match color {
+            Some(background) => TextFormat {
+                font_id: font_id.clone(),
+                color: Color32::WHITE,
+                background,
+                ..Default::default()
+            },
+            None => plain.clone(),
+        };
+        let colors: Vec<Option<Color32>> = (0..row.len())
+            .map(|pos| compare_color(regions, patterns, row_offset + pos))
+            .collect();
+
+        let width = std::cmp::max(self.hexdump_width, 1);
+        let mut job = LayoutJob::default();
+        job.append(&format!("{:08X}  ", row_offset), 0.0, plain.clone());
+        for pos in 0..width {
+            match row.get(pos).zip(colors.get(pos)) {
+                Some((byte, color)) => {
+                    job.append(&format!("{byte:02X}"), 0.0, format_for(*color));
+                    job.append(" ", 0.0, plain.clone());
+                }
+                None => job.append("   ", 0.0, plain.clone()),
+            }
+            if self.hexdump_grouping > 0
+                && pos % self.hexdump_grouping == self.hexdump_grouping - 1
+                && pos != width - 1
+            {
+                job.append(" ", 0.0, plain.clone());
+            }
+        }
+        job.append("   ", 0.0, plain.clone());
+        for (byte, color) in row.iter().zip(colors) {
+            let ch = if byte.is_ascii() && !byte.is_ascii_whitespace() && !byte.eq(&0) {
+                *byte as char
+            } else {
+                '.'
+            };
+            job.append(&ch.to_string(), 0.0, format_for(color));
+        }
+        job
+    }
+}
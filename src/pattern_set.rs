@@ -0,0 +1,363 @@
+use crate::i18n::{tr, tr_args};
+use crate::{compile_pattern, update_dialog_viewport, ContentEnum, QuerApp, RegexEnum};
+use eframe::egui::{self, RichText, TextStyle};
+use egui_extras::{Column, TableBuilder};
+use egui_file_dialog::FileDialog;
+use regex::bytes::Regex as BytesRegex;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct SearchPattern {
+    pub name: Option<Arc<str>>, // only set when several patterns run at once
+    pub regex: RegexEnum,
+}
+
+impl SearchPattern {
+    // the same pattern, but only matching at the start of the haystack
+    pub fn anchored(&self) -> Option<Self> {
+        let regex = match &self.regex {
+            RegexEnum::Hex(re) => {
+                RegexEnum::Hex(BytesRegex::new(&format!("^(?:{})", re.as_str())).ok()?)
+            }
+            RegexEnum::Text(re) => {
+                RegexEnum::Text(BytesRegex::new(&format!("^(?:{})", re.as_str())).ok()?)
+            }
+            RegexEnum::Auto(text_re, hex_re) => RegexEnum::Auto(
+                BytesRegex::new(&format!("^(?:{})", text_re.as_str())).ok()?,
+                match hex_re {
+                    Some(hex_re) => {
+                        Some(BytesRegex::new(&format!("^(?:{})", hex_re.as_str())).ok()?)
+                    }
+                    None => None,
+                },
+            ),
+        };
+        Some(Self {
+            name: self.name.clone(),
+            regex,
+        })
+    }
+
+    // Auto patterns become Text or Hex depending on the file being searched
+    pub fn resolve_auto(&self, text_file: bool) -> Self {
+        let regex = match &self.regex {
+            RegexEnum::Auto(_text_re, Some(hex_re)) if !text_file => RegexEnum::Hex(hex_re.clone()),
+            RegexEnum::Auto(text_re, _) => RegexEnum::Text(text_re.clone()),
+            regex => regex.clone(),
+        };
+        Self {
+            name: self.name.clone(),
+            regex,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct PatternSet {
+    pub sources: Vec<String>, // pattern files, or the clipboard
+    pub patterns: Vec<SearchPattern>,
+}
+
+// one pattern per line, `#` starts a comment line and `hex:`/`text:` pick the mode
+pub fn parse_patterns(
+    contents: &str,
+    default_type: &ContentEnum,
+) -> (Vec<SearchPattern>, Vec<String>) {
+    let mut patterns = Vec::new();
+    let mut errors = Vec::new();
+    for (idx, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (content_type, pattern) = if let Some(pattern) = line.strip_prefix("hex:") {
+            (ContentEnum::Hex, pattern.trim())
+        } else if let Some(pattern) = line.strip_prefix("text:") {
+            (ContentEnum::Text, pattern.trim())
+        } else {
+            (default_type.clone(), line)
+        };
+        // pattern files set their own flags, e.g. (?i)
+        match compile_pattern(pattern, &content_type, false) {
+            Ok(regex) => patterns.push(SearchPattern {
+                name: Some(Arc::from(line)),
+                regex,
+            }),
+            Err(err) => errors.push(format!("line {}: {}", idx + 1, err)),
+        }
+    }
+    (patterns, errors)
+}
+
+impl QuerApp {
+    pub(crate) fn load_pattern_file(&mut self, path: &Path) {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) => {
+                self.log_error(format!("Could not read {}: {}", path.display(), err));
+                return;
+            }
+        };
+
+        let (patterns, errors) = parse_patterns(&contents, &self.content_type);
+        for err in errors.iter() {
+            self.log_error(format!("Skipped pattern in {}, {}", path.display(), err));
+        }
+        self.log(format!(
+            "Loaded {} patterns from {}",
+            patterns.len(),
+            path.display()
+        ));
+        self.pattern_set = if patterns.is_empty() {
+            None
+        } else {
+            Some(PatternSet {
+                sources: vec![path.display().to_string()],
+                patterns,
+            })
+        };
+    }
+
+    // adds to the patterns already in use instead of replacing them
+    pub(crate) fn paste_pattern_list(&mut self) {
+        let contents = match arboard::Clipboard::new().and_then(|mut cb| cb.get_text()) {
+            Ok(contents) => contents,
+            Err(err) => {
+                self.log_error(format!("Could not read the clipboard: {}", err));
+                return;
+            }
+        };
+
+        let (patterns, errors) = parse_patterns(&contents, &self.content_type);
+        for err in errors.iter() {
+            self.log_error(format!("Skipped pasted pattern, {}", err));
+        }
+        if patterns.is_empty() {
+            self.log("No patterns found in the clipboard".to_string());
+            return;
+        }
+        let pattern_set = self.pattern_set.get_or_insert_with(|| PatternSet {
+            sources: Vec::new(),
+            patterns: Vec::new(),
+        });
+        let known: HashSet<Arc<str>> = pattern_set
+            .patterns
+            .iter()
+            .filter_map(|pattern| pattern.name.clone())
+            .collect();
+        let pasted = patterns.len();
+        pattern_set
+            .patterns
+            .extend(patterns.into_iter().filter(|pattern| match &pattern.name {
+                Some(name) => !known.contains(name),
+                None => true,
+            }));
+        let added = pattern_set.patterns.len() - known.len();
+        if !pattern_set
+            .sources
+            .iter()
+            .any(|source| source == "the clipboard")
+        {
+            pattern_set.sources.push("the clipboard".to_string());
+        }
+        self.log(format!(
+            "Added {} patterns from the clipboard ({} already in use)",
+            added,
+            pasted - added
+        ));
+    }
+
+    pub(crate) fn open_pattern_file_dialog(&mut self) {
+        let mut dialog = FileDialog::new()
+            .as_modal(false)
+            .title_bar(false)
+            .movable(false)
+            .resizable(false)
+            .min_size([800., 600.]);
+        dialog.select_file();
+        self.pattern_file_dialog = Some(dialog);
+    }
+
+    pub(crate) fn add_pattern_file_dialog(&mut self, ctx: &egui::Context) {
+        let Some(dialog) = &mut self.pattern_file_dialog else {
+            return;
+        };
+        let (selected, done) =
+            update_dialog_viewport(ctx, dialog, "pattern_file_dialog", "Load Patterns");
+        if done {
+            self.pattern_file_dialog = None;
+        }
+        if let Some(path) = selected {
+            self.load_pattern_file(&path);
+        }
+    }
+
+    // replaces the regex error line while a pattern file is in use
+    pub(crate) fn add_pattern_set_line(&mut self, ui: &mut egui::Ui) {
+        let Some(pattern_set) = &self.pattern_set else {
+            return;
+        };
+        let mut clear = false;
+        ui.horizontal(|ui| {
+            ui.label(
+                RichText::new(tr_args(
+                    "searching-pattern-set",
+                    &[
+                        ("count", pattern_set.patterns.len().into()),
+                        ("sources", pattern_set.sources.join(", ").into()),
+                    ],
+                ))
+                .text_style(TextStyle::Small),
+            );
+            clear = ui.small_button(tr("clear")).clicked();
+        });
+        if clear {
+            self.pattern_set = None;
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct PatternStat {
+    pub pattern: Arc<str>,
+    pub hits: usize,
+    pub files: usize,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum PatternStatsSort {
+    Pattern,
+    Hits,
+    Files,
+}
+
+impl QuerApp {
+    fn update_pattern_stats(&mut self) {
+        if self.findings.len() == self.pattern_stats_src_len && !self.pattern_stats.is_empty() {
+            return;
+        }
+
+        let mut by_pattern: HashMap<Arc<str>, (usize, HashSet<&str>)> = HashMap::new();
+        // patterns that never fired are listed too
+        if let Some(pattern_set) = &self.pattern_set {
+            for pattern in pattern_set.patterns.iter().filter_map(|p| p.name.clone()) {
+                by_pattern.entry(pattern).or_default();
+            }
+        }
+        for finding in self.findings.iter() {
+            if let Some(pattern) = &finding.pattern {
+                let (hits, files) = by_pattern.entry(Arc::clone(pattern)).or_default();
+                *hits += finding.hit_count;
+                files.insert(finding.filepath.as_str());
+            }
+        }
+
+        self.pattern_stats = by_pattern
+            .into_iter()
+            .map(|(pattern, (hits, files))| PatternStat {
+                pattern,
+                hits,
+                files: files.len(),
+            })
+            .collect();
+        self.pattern_stats_src_len = self.findings.len();
+        self.sort_pattern_stats();
+    }
+
+    fn sort_pattern_stats(&mut self) {
+        let (column, descending) = self.pattern_stats_sort;
+        self.pattern_stats.sort_by(|a, b| {
+            let ordering = match column {
+                PatternStatsSort::Pattern => a.pattern.cmp(&b.pattern),
+                PatternStatsSort::Hits => a.hits.cmp(&b.hits),
+                PatternStatsSort::Files => a.files.cmp(&b.files),
+            };
+            if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+    }
+
+    pub(crate) fn add_pattern_stats_window(&mut self, ctx: &egui::Context) {
+        if !self.show_pattern_stats {
+            return;
+        }
+        self.update_pattern_stats();
+
+        let mut open = self.show_pattern_stats;
+        let mut sort_by = None;
+        egui::Window::new(tr("pattern-statistics-title"))
+            .open(&mut open)
+            .resizable(true)
+            .default_width(480.0)
+            .show(ctx, |ui| {
+                let fired = self.pattern_stats.iter().filter(|s| s.hits > 0).count();
+                ui.label(tr_args(
+                    "patterns-matched",
+                    &[
+                        ("fired", fired.into()),
+                        ("count", self.pattern_stats.len().into()),
+                    ],
+                ));
+                ui.separator();
+
+                let (sorted_column, descending) = self.pattern_stats_sort;
+                let arrow = if descending { " v" } else { " ^" };
+                TableBuilder::new(ui)
+                    .striped(true)
+                    .resizable(true)
+                    .column(Column::remainder().at_least(160.))
+                    .column(Column::auto().at_least(56.))
+                    .column(Column::auto().at_least(56.))
+                    .header(20.0, |mut header| {
+                        for (column, name) in [
+                            (PatternStatsSort::Pattern, "Pattern"),
+                            (PatternStatsSort::Hits, "Hits"),
+                            (PatternStatsSort::Files, "Files"),
+                        ] {
+                            header.col(|ui| {
+                                let mut title = name.to_string();
+                                if column == sorted_column {
+                                    title.push_str(arrow);
+                                }
+                                if ui.button(RichText::new(title).strong()).clicked() {
+                                    sort_by = Some(column);
+                                }
+                            });
+                        }
+                    })
+                    .body(|body| {
+                        body.rows(20.0, self.pattern_stats.len(), |mut row| {
+                            let stat = &self.pattern_stats[row.index()];
+                            row.col(|ui| {
+                                ui.add(egui::Label::new(stat.pattern.as_ref()).truncate())
+                                    .on_hover_text(stat.pattern.as_ref());
+                            });
+                            row.col(|ui| {
+                                ui.label(stat.hits.to_string());
+                            });
+                            row.col(|ui| {
+                                ui.label(stat.files.to_string());
+                            });
+                        });
+                    });
+            });
+        self.show_pattern_stats = open;
+
+        if let Some(column) = sort_by {
+            let (sorted_column, descending) = self.pattern_stats_sort;
+            // numbers are most useful biggest first
+            self.pattern_stats_sort = if column == sorted_column {
+                (column, !descending)
+            } else {
+                (column, column != PatternStatsSort::Pattern)
+            };
+            self.sort_pattern_stats();
+        }
+    }
+}
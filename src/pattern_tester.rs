@@ -0,0 +1,178 @@
+use crate::accessibility::set_accessible_name;
+use crate::i18n::{tr, tr_args};
+use crate::{QuerApp, RegexEnum};
+use eframe::egui;
+use egui::text::{LayoutJob, TextFormat};
+use std::ops::Range;
+
+const MAX_LISTED_MATCHES: usize = 100;
+
+// sample bytes plus, for every byte, the range of the sample text it came from
+fn parse_sample(sample: &str, hex: bool) -> Result<(Vec<u8>, Vec<Range<usize>>), String> {
+    if !hex {
+        let spans = (0..sample.len()).map(|idx| idx..idx + 1).collect();
+        return Ok((sample.as_bytes().to_vec(), spans));
+    }
+
+    let mut bytes = Vec::new();
+    let mut spans = Vec::new();
+    let mut high_nibble: Option<(usize, u8)> = None;
+    for (pos, c) in sample.char_indices() {
+        if c.is_whitespace() {
+            continue;
+        }
+        let Some(nibble) = c.to_digit(16) else {
+            return Err(format!("Invalid hex character '{}' at {}", c, pos));
+        };
+        match high_nibble.take() {
+            Some((start, high)) => {
+                bytes.push(high << 4 | nibble as u8);
+                spans.push(start..pos + 1);
+            }
+            None => high_nibble = Some((pos, nibble as u8)),
+        }
+    }
+    if high_nibble.is_some() {
+        return Err("Odd number of hex digits".to_string());
+    }
+    Ok((bytes, spans))
+}
+
+fn highlight_job(sample: &str, matches: &[Range<usize>], ui: &egui::Ui) -> LayoutJob {
+    let font_id = egui::TextStyle::Monospace.resolve(ui.style());
+    let text_color = ui.visuals().text_color();
+    // alternate colors so adjacent matches can be told apart
+    let match_colors = [
+        egui::Color32::from_rgb(0x2a, 0x7e, 0x43),
+        egui::Color32::from_rgb(0x1f, 0x5a, 0x8a),
+    ];
+
+    let mut job = LayoutJob::default();
+    let mut pos = 0;
+    for (idx, range) in matches.iter().enumerate() {
+        // ranges can be a frame stale while the sample is being edited
+        let valid = range.start >= pos
+            && range.end <= sample.len()
+            && sample.is_char_boundary(range.start)
+            && sample.is_char_boundary(range.end);
+        if !valid {
+            continue;
+        }
+        job.append(
+            &sample[pos..range.start],
+            0.0,
+            TextFormat::simple(font_id.clone(), text_color),
+        );
+        job.append(
+            &sample[range.clone()],
+            0.0,
+            TextFormat {
+                font_id: font_id.clone(),
+                color: egui::Color32::WHITE,
+                background: match_colors[idx % match_colors.len()],
+                ..Default::default()
+            },
+        );
+        pos = range.end;
+    }
+    job.append(&sample[pos..], 0.0, TextFormat::simple(font_id, text_color));
+    job
+}
+
+impl QuerApp {
+    // (byte offset, byte length, range in the sample text) for each match of the current pattern
+    fn tester_matches(
+        &self,
+        bytes: &[u8],
+        spans: &[Range<usize>],
+    ) -> Vec<(usize, usize, Range<usize>)> {
+        let re = match &self.regex_result {
+            Ok(RegexEnum::Hex(re)) | Ok(RegexEnum::Text(re)) | Ok(RegexEnum::Auto(re, _)) => re,
+            Err(_) => return Vec::new(),
+        };
+
+        re.find_iter(bytes)
+            .filter(|m| !m.is_empty())
+            .map(|m| {
+                let text_range = spans[m.start()].start..spans[m.end() - 1].end;
+                (m.start(), m.len(), text_range)
+            })
+            .collect()
+    }
+
+    pub(crate) fn add_pattern_tester_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_pattern_tester;
+        egui::Window::new(tr("pattern-tester-title"))
+            .open(&mut open)
+            .resizable(true)
+            .default_width(480.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr("sample-is"));
+                    ui.selectable_value(&mut self.tester_hex, false, tr("text"));
+                    ui.selectable_value(&mut self.tester_hex, true, tr("hex"));
+                });
+                ui.label(tr("pattern-tester-intro"));
+
+                let (bytes, spans, parse_err) =
+                    match parse_sample(&self.tester_sample, self.tester_hex) {
+                        Ok((bytes, spans)) => (bytes, spans, None),
+                        Err(err) => (Vec::new(), Vec::new(), Some(err)),
+                    };
+                let mut matches = self.tester_matches(&bytes, &spans);
+                // keep highlight ranges on char boundaries for non-utf8 patterns
+                matches.retain(|(_offset, _len, range)| {
+                    self.tester_sample.is_char_boundary(range.start)
+                        && self.tester_sample.is_char_boundary(range.end)
+                });
+
+                let text_ranges: Vec<Range<usize>> = matches
+                    .iter()
+                    .map(|(_offset, _len, range)| range.clone())
+                    .collect();
+                let mut layouter = |ui: &egui::Ui, sample: &str, wrap_width: f32| {
+                    let mut job = highlight_job(sample, &text_ranges, ui);
+                    job.wrap.max_width = wrap_width;
+                    ui.fonts(|fonts| fonts.layout_job(job))
+                };
+                egui::ScrollArea::vertical()
+                    .max_height(240.0)
+                    .show(ui, |ui| {
+                        let resp = ui.add(
+                            egui::TextEdit::multiline(&mut self.tester_sample)
+                                .desired_width(f32::INFINITY)
+                                .desired_rows(8)
+                                .hint_text(tr("paste-sample-data-here"))
+                                .layouter(&mut layouter),
+                        );
+                        set_accessible_name(&resp, &tr("sample-data"));
+                    });
+
+                if let Some(err) = parse_err {
+                    ui.colored_label(egui::Color32::from_rgb(0x8f, 0x0, 0x0), err);
+                    return;
+                }
+                if let Err(err) = &self.regex_result {
+                    ui.colored_label(egui::Color32::from_rgb(0x8f, 0x0, 0x0), err);
+                    return;
+                }
+
+                ui.label(tr_args("n-matches", &[("matches", matches.len().into())]));
+                egui::ScrollArea::vertical()
+                    .id_source("tester_matches")
+                    .max_height(160.0)
+                    .show(ui, |ui| {
+                        for (offset, len, _range) in matches.iter().take(MAX_LISTED_MATCHES) {
+                            let content = &bytes[*offset..offset + len];
+                            let content_str = if self.tester_hex {
+                                self.bytes_to_hex(content, content.len())
+                            } else {
+                                String::from_utf8_lossy(content).to_string()
+                            };
+                            ui.monospace(format!("0x{:x}: {}", offset, content_str));
+                        }
+                    });
+            });
+        self.show_pattern_tester = open;
+    }
+}
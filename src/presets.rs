@@ -0,0 +1,306 @@
+use crate::i18n::{tr, tr_args};
+use crate::{ContentEnum, QuerApp};
+use eframe::egui;
+
+#[derive(Clone)]
+pub struct Preset {
+    pub name: String,
+    pub pattern: String,
+    pub content_type: ContentEnum,
+}
+
+impl Preset {
+    fn new(name: &str, pattern: &str, content_type: ContentEnum) -> Self {
+        Self {
+            name: name.to_string(),
+            pattern: pattern.to_string(),
+            content_type,
+        }
+    }
+}
+
+pub fn builtin_presets() -> Vec<Preset> {
+    vec![
+        Preset::new("ELF header", "7f 45 4c 46 {class:u8}", ContentEnum::Hex),
+        Preset::new("PE header", "4d 5a", ContentEnum::Hex),
+        Preset::new("Pointer (32-bit LE)", "{address:u32le}", ContentEnum::Hex),
+        Preset::new("Pointer (64-bit LE)", "{address:u64le}", ContentEnum::Hex),
+        Preset::new("Magic number", "{magic:hex8}", ContentEnum::Hex),
+        Preset::new(
+            "Key/value",
+            "{key:text}\\s*[=:]\\s*{value}",
+            ContentEnum::Text,
+        ),
+        Preset::new(
+            "Email address",
+            "[\\w.+-]+@[\\w-]+\\.[\\w.]+",
+            ContentEnum::Text,
+        ),
+        Preset::new("URL", "https?://[^\\s\"'<>]+", ContentEnum::Text),
+    ]
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum PlaceholderKind {
+    Raw,              // inserted as typed, e.g. a regex fragment
+    Text,             // regex-escaped literal
+    Hex(usize),       // number as N hex digits
+    Int(usize, bool), // number as N little (true) or big endian bytes
+}
+
+#[derive(Clone)]
+pub struct Placeholder {
+    pub name: String,
+    pub spec: String,
+    kind: PlaceholderKind,
+}
+
+impl Placeholder {
+    pub fn hint(&self) -> &'static str {
+        match self.kind {
+            PlaceholderKind::Raw => "pattern fragment",
+            PlaceholderKind::Text => "literal text",
+            PlaceholderKind::Hex(_) | PlaceholderKind::Int(_, _) => {
+                "number, e.g. 0x400000 or 4194304"
+            }
+        }
+    }
+}
+
+fn parse_kind(spec: &str) -> Option<PlaceholderKind> {
+    let (base, little_endian) = match spec.strip_suffix("le") {
+        Some(base) => (base, true),
+        None => match spec.strip_suffix("be") {
+            Some(base) => (base, false),
+            None => (spec, true),
+        },
+    };
+    match spec {
+        "" | "raw" => return Some(PlaceholderKind::Raw),
+        "text" => return Some(PlaceholderKind::Text),
+        _ => {}
+    }
+    if let Some(digits) = spec.strip_prefix("hex") {
+        return digits
+            .parse()
+            .ok()
+            .filter(|n| (1..=16).contains(n))
+            .map(PlaceholderKind::Hex);
+    }
+    match base {
+        "u8" => Some(PlaceholderKind::Int(1, little_endian)),
+        "u16" => Some(PlaceholderKind::Int(2, little_endian)),
+        "u32" => Some(PlaceholderKind::Int(4, little_endian)),
+        "u64" => Some(PlaceholderKind::Int(8, little_endian)),
+        _ => None,
+    }
+}
+
+// `{name}` or `{name:kind}`; `{3}`, `{2,5}` etc. are regex repetitions and left alone
+pub fn find_placeholders(template: &str) -> Vec<Placeholder> {
+    let mut placeholders: Vec<Placeholder> = Vec::new();
+    for (start, _) in template.match_indices('{') {
+        let Some(len) = template[start..].find('}') else {
+            continue;
+        };
+        let inner = &template[start + 1..start + len];
+        let (name, spec) = inner.split_once(':').unwrap_or((inner, ""));
+        let is_name = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !is_name || placeholders.iter().any(|p| p.name == name) {
+            continue;
+        }
+        if let Some(kind) = parse_kind(spec) {
+            placeholders.push(Placeholder {
+                name: name.to_string(),
+                spec: spec.to_string(),
+                kind,
+            });
+        }
+    }
+    placeholders
+}
+
+pub(crate) fn parse_number(value: &str) -> Result<u64, String> {
+    let value = value.trim().replace('_', "");
+    let parsed = match value.strip_prefix("0x").or(value.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => value.parse(),
+    };
+    parsed.map_err(|_| format!("'{}' is not a number", value))
+}
+
+fn render_value(
+    placeholder: &Placeholder,
+    value: &str,
+    content_type: &ContentEnum,
+) -> Result<String, String> {
+    let hex_mode = *content_type == ContentEnum::Hex;
+    match placeholder.kind {
+        PlaceholderKind::Raw => Ok(value.to_string()),
+        PlaceholderKind::Text if hex_mode => Ok(value
+            .bytes()
+            .map(|b| format!("{:02x}", b))
+            .collect::<Vec<_>>()
+            .join(" ")),
+        PlaceholderKind::Text => Ok(regex::escape(value)),
+        PlaceholderKind::Hex(digits) => {
+            let number = parse_number(value)?;
+            let hex = format!("{:0width$x}", number, width = digits);
+            if hex.len() > digits {
+                return Err(format!("{} doesn't fit in {} hex digits", value, digits));
+            }
+            Ok(hex)
+        }
+        PlaceholderKind::Int(size, little_endian) => {
+            let number = parse_number(value)?;
+            if size < 8 && number >> (size * 8) != 0 {
+                return Err(format!("{} doesn't fit in {} bytes", value, size));
+            }
+            let bytes = if little_endian {
+                number.to_le_bytes()[..size].to_vec()
+            } else {
+                number.to_be_bytes()[8 - size..].to_vec()
+            };
+            let rendered: Vec<String> = if hex_mode {
+                bytes.iter().map(|b| format!("{:02x}", b)).collect()
+            } else {
+                bytes.iter().map(|b| format!("\\x{:02x}", b)).collect()
+            };
+            Ok(rendered.join(if hex_mode { " " } else { "" }))
+        }
+    }
+}
+
+pub fn fill_template(
+    template: &str,
+    placeholders: &[Placeholder],
+    values: &[String],
+    content_type: &ContentEnum,
+) -> Result<String, String> {
+    let mut pattern = template.to_string();
+    for (placeholder, value) in placeholders.iter().zip(values.iter()) {
+        if value.is_empty() {
+            return Err(format!("No value for '{}'", placeholder.name));
+        }
+        let rendered = render_value(placeholder, value, content_type)?;
+        let token = if placeholder.spec.is_empty() {
+            format!("{{{}}}", placeholder.name)
+        } else {
+            format!("{{{}:{}}}", placeholder.name, placeholder.spec)
+        };
+        pattern = pattern.replace(&token, &rendered);
+    }
+    Ok(pattern)
+}
+
+// a preset whose placeholders are being filled in
+#[derive(Clone)]
+pub struct PendingTemplate {
+    pub preset: Preset,
+    pub placeholders: Vec<Placeholder>,
+    pub values: Vec<String>,
+}
+
+impl QuerApp {
+    pub(crate) fn apply_preset(&mut self, preset: &Preset) {
+        let placeholders = find_placeholders(&preset.pattern);
+        if placeholders.is_empty() {
+            self.regex_str = preset.pattern.clone();
+            self.content_type = preset.content_type.clone();
+        } else {
+            self.pending_template = Some(PendingTemplate {
+                preset: preset.clone(),
+                values: vec![String::new(); placeholders.len()],
+                placeholders,
+            });
+        }
+    }
+
+    pub(crate) fn add_presets_menu(&mut self, ui: &mut egui::Ui) {
+        let mut selected = None;
+        for preset in self.presets.iter() {
+            let resp = ui.button(&preset.name).on_hover_text(&preset.pattern);
+            if resp.clicked() {
+                selected = Some(preset.clone());
+                ui.close_menu();
+            }
+        }
+        ui.separator();
+        if ui
+            .button(tr("save-preset"))
+            .on_hover_text(tr("save-preset-hint"))
+            .clicked()
+        {
+            self.presets.push(Preset {
+                name: self.regex_str.clone(),
+                pattern: self.regex_str.clone(),
+                content_type: self.content_type.clone(),
+            });
+            ui.close_menu();
+        }
+
+        if let Some(preset) = selected {
+            self.apply_preset(&preset);
+        }
+    }
+
+    pub(crate) fn add_template_window(&mut self, ctx: &egui::Context) {
+        let Some(pending) = self.pending_template.as_mut() else {
+            return;
+        };
+
+        let mut open = true;
+        let mut apply = None;
+        egui::Window::new(tr_args(
+            "template-title",
+            &[("name", pending.preset.name.as_str().into())],
+        ))
+        .open(&mut open)
+        .resizable(false)
+        .show(ctx, |ui| {
+            ui.monospace(&pending.preset.pattern);
+            ui.separator();
+            egui::Grid::new("template_values").show(ui, |ui| {
+                for (placeholder, value) in
+                    pending.placeholders.iter().zip(pending.values.iter_mut())
+                {
+                    let label = ui.label(&placeholder.name);
+                    ui.add(egui::TextEdit::singleline(value).hint_text(placeholder.hint()))
+                        .labelled_by(label.id);
+                    ui.end_row();
+                }
+            });
+            ui.separator();
+
+            let result = fill_template(
+                &pending.preset.pattern,
+                &pending.placeholders,
+                &pending.values,
+                &pending.preset.content_type,
+            );
+            match &result {
+                Ok(pattern) => {
+                    ui.monospace(pattern);
+                }
+                Err(err) => {
+                    ui.colored_label(egui::Color32::from_rgb(0x8f, 0x0, 0x0), err);
+                }
+            }
+            if ui
+                .add_enabled(result.is_ok(), egui::Button::new(tr("use-pattern")))
+                .clicked()
+            {
+                apply = result.ok();
+            }
+        });
+
+        if let Some(pattern) = apply {
+            self.content_type = pending.preset.content_type.clone();
+            self.regex_str = pattern;
+            self.pending_template = None;
+        } else if !open {
+            self.pending_template = None;
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use crate::i18n::{tr, tr_args};
+use crate::QuerApp;
+use eframe::egui::{self, RichText, TextStyle};
+use std::sync::atomic::Ordering;
+
+// names of files being scanned shown under the bar, the rest are summarized
+const SHOWN_SCANNING: usize = 6;
+
+impl QuerApp {
+    // one bar split into completed / skipped / scanning / waiting files
+    pub(crate) fn add_progress_breakdown(&mut self, ui: &mut egui::Ui) {
+        let scanning: Vec<String> = match self.scanning_files.lock() {
+            Ok(scanning) => scanning
+                .iter()
+                .map(|path| path.to_string_lossy().to_string())
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+        let total = std::cmp::max(self.max_files, 0) as usize;
+        let done = self.files_scanned;
+        let skipped = self.files_skipped;
+        let waiting = total.saturating_sub(done + skipped + scanning.len());
+
+        let segments = [
+            (
+                done,
+                egui::Color32::from_rgb(0x2a, 0x7e, 0x43),
+                "progress-completed",
+            ),
+            (
+                skipped,
+                egui::Color32::from_rgb(0x8a, 0x6d, 0x1f),
+                "progress-skipped",
+            ),
+            (
+                scanning.len(),
+                egui::Color32::from_rgb(0x3a, 0x6e, 0xa5),
+                "progress-scanning",
+            ),
+            (waiting, ui.visuals().extreme_bg_color, "progress-waiting"),
+        ];
+
+        let desired_size = egui::vec2(ui.available_width(), ui.spacing().interact_size.y * 0.6);
+        let (rect, resp) = ui.allocate_exact_size(desired_size, egui::Sense::hover());
+        resp.widget_info(|| {
+            egui::WidgetInfo::labeled(
+                egui::WidgetType::ProgressIndicator,
+                true,
+                tr_args(
+                    "progress-summary",
+                    &[
+                        ("done", (done + skipped).into()),
+                        ("total", total.into()),
+                        ("scanning", scanning.len().into()),
+                    ],
+                ),
+            )
+        });
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+        if total > 0 {
+            let mut left = rect.left();
+            for (count, color, _name) in segments.iter() {
+                let width = rect.width() * *count as f32 / total as f32;
+                let segment = egui::Rect::from_min_max(
+                    egui::pos2(left, rect.top()),
+                    egui::pos2(left + width, rect.bottom()),
+                );
+                painter.rect_filled(segment, 0.0, *color);
+                left += width;
+            }
+        }
+        resp.on_hover_text(tr("progress-bar-hint"));
+
+        if total == 0 {
+            return;
+        }
+        ui.horizontal(|ui| {
+            for (count, color, name) in segments.iter() {
+                ui.label(RichText::new("■").color(*color));
+                ui.label(
+                    RichText::new(format!("{} {}", tr(name), count)).text_style(TextStyle::Small),
+                );
+                ui.add_space(8.0);
+            }
+            let failed = self.read_retry.failed.load(Ordering::Relaxed);
+            let retried = self.read_retry.retried.load(Ordering::Relaxed);
+            if failed > 0 || retried > 0 {
+                ui.label(
+                    RichText::new(tr_args(
+                        "read-retries",
+                        &[("failed", failed.into()), ("retried", retried.into())],
+                    ))
+                    .text_style(TextStyle::Small),
+                )
+                .on_hover_text(tr("read-retries-hint"));
+                ui.add_space(8.0);
+            }
+            let locked = self
+                .read_retry
+                .locked_files
+                .as_ref()
+                .map_or(0, |reader| reader.files_read.load(Ordering::Relaxed));
+            if locked > 0 {
+                ui.label(
+                    RichText::new(tr_args("locked-files-read", &[("count", locked.into())]))
+                        .text_style(TextStyle::Small),
+                )
+                .on_hover_text(tr("read-locked-files-raw-hint"));
+                ui.add_space(8.0);
+            }
+            if waiting > 0 {
+                ui.label(
+                    RichText::new(tr_args(
+                        "queue-depths",
+                        &[
+                            ("queued", self.file_queue.len().into()),
+                            ("read", self.prefetched_files.load(Ordering::Relaxed).into()),
+                        ],
+                    ))
+                    .text_style(TextStyle::Small),
+                )
+                .on_hover_text(tr("queue-depths-hint"));
+            }
+        });
+        if !scanning.is_empty() {
+            let mut names = scanning
+                .iter()
+                .take(SHOWN_SCANNING)
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(", ");
+            if scanning.len() > SHOWN_SCANNING {
+                names = tr_args(
+                    "names-and-more",
+                    &[
+                        ("names", names.into()),
+                        ("more", (scanning.len() - SHOWN_SCANNING).into()),
+                    ],
+                );
+            }
+            ui.add(
+                egui::Label::new(
+                    RichText::new(tr_args("scanning-names", &[("names", names.into())]))
+                        .text_style(TextStyle::Small),
+                )
+                .truncate(),
+            )
+            .on_hover_text(scanning.join("\n"));
+        }
+    }
+}
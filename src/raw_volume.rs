@@ -0,0 +1,335 @@
+use crate::fs_carve::{ntfs_fixups, ntfs_geometry, ntfs_runlist};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Component, Path, PathBuf, Prefix};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+// opening a volume for reading needs admin rights, and only Windows locks files this way
+pub const RAW_VOLUME_SUPPORTED: bool = cfg!(windows);
+
+// raw volume reads have to start and end on sector boundaries
+const ALIGN: u64 = 4096;
+const MAX_FILE_SIZE: u64 = 2 * 1024 * 1024 * 1024;
+const MAX_INDEX_BLOCKS: usize = 65536;
+
+const NTFS_ROOT_RECORD: u64 = 5;
+const NTFS_DATA: u32 = 0x80;
+const NTFS_INDEX_ROOT: u32 = 0x90;
+const NTFS_INDEX_ALLOCATION: u32 = 0xa0;
+const NTFS_END: u32 = 0xffff_ffff;
+const NTFS_LAST_ENTRY: u32 = 0x02;
+
+// ERROR_SHARING_VIOLATION and ERROR_LOCK_VIOLATION
+const LOCKED_ERRORS: [i32; 2] = [32, 33];
+
+fn le_u16(data: &[u8], pos: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(data.get(pos..pos + 2)?.try_into().ok()?))
+}
+
+fn le_u32(data: &[u8], pos: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(data.get(pos..pos + 4)?.try_into().ok()?))
+}
+
+fn le_u64(data: &[u8], pos: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(data.get(pos..pos + 8)?.try_into().ok()?))
+}
+
+// another process holds the file open without sharing it
+pub fn is_locked(err: &io::Error) -> bool {
+    cfg!(windows)
+        && err
+            .raw_os_error()
+            .is_some_and(|code| LOCKED_ERRORS.contains(&code))
+}
+
+// the volume device a path lies on, and the names leading to the file below its root
+fn split_volume_path(path: &Path) -> Option<(u8, PathBuf, Vec<String>)> {
+    let mut components = path.components();
+    let letter = match components.next()? {
+        Component::Prefix(prefix) => match prefix.kind() {
+            Prefix::Disk(letter) | Prefix::VerbatimDisk(letter) => letter.to_ascii_uppercase(),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    let names = components
+        .filter_map(|component| match component {
+            Component::Normal(name) => Some(name.to_string_lossy().to_string()),
+            _ => None,
+        })
+        .collect();
+    let device = PathBuf::from(format!("\\\\.\\{}:", letter as char));
+    Some((letter, device, names))
+}
+
+struct Attribute {
+    kind: u32,
+    named: bool,
+    resident: Option<Vec<u8>>, // the value of a resident attribute
+    runs: Vec<(Option<u64>, u64)>,
+    size: u64, // of the data, runs cover whole clusters
+}
+
+fn attributes(record: &[u8]) -> Vec<Attribute> {
+    let mut attributes = Vec::new();
+    let mut pos = le_u16(record, 20).unwrap_or(0) as usize;
+    while let (Some(kind), Some(len)) = (le_u32(record, pos), le_u32(record, pos + 4)) {
+        let len = len as usize;
+        if kind == NTFS_END || len == 0 || pos + len > record.len() {
+            break;
+        }
+        let non_resident = record.get(pos + 8).copied().unwrap_or(0) != 0;
+        let named = record.get(pos + 9).copied().unwrap_or(0) != 0;
+        let attribute = if non_resident {
+            let runlist = pos + le_u16(record, pos + 32).unwrap_or(0) as usize;
+            Attribute {
+                kind,
+                named,
+                resident: None,
+                // only the first extent, later ones live in other records
+                runs: match le_u64(record, pos + 16) {
+                    Some(0) => ntfs_runlist(record.get(runlist..pos + len).unwrap_or_default()),
+                    _ => Vec::new(),
+                },
+                size: le_u64(record, pos + 48).unwrap_or(0),
+            }
+        } else {
+            let value_len = le_u32(record, pos + 16).unwrap_or(0) as usize;
+            let value = pos + le_u16(record, pos + 20).unwrap_or(0) as usize;
+            let value = record.get(value..value + value_len).unwrap_or_default();
+            Attribute {
+                kind,
+                named,
+                resident: Some(value.to_vec()),
+                runs: Vec::new(),
+                size: value.len() as u64,
+            }
+        };
+        attributes.push(attribute);
+        pos += len;
+    }
+    attributes
+}
+
+// (record number, name) of the index entries between `pos` and `end`
+fn index_entries(data: &[u8], mut pos: usize, end: usize) -> Vec<(u64, String)> {
+    let mut entries = Vec::new();
+    while pos + 16 <= end {
+        let (Some(reference), Some(len), Some(flags)) = (
+            le_u64(data, pos),
+            le_u16(data, pos + 8),
+            le_u32(data, pos + 12),
+        ) else {
+            break;
+        };
+        if flags & NTFS_LAST_ENTRY != 0 || len < 16 {
+            break;
+        }
+        let key = pos + 16;
+        let name_len = data.get(key + 64).copied().unwrap_or(0) as usize;
+        if let Some(name) = data.get(key + 66..key + 66 + name_len * 2) {
+            let units: Vec<u16> = name
+                .chunks_exact(2)
+                .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+                .collect();
+            entries.push((
+                reference & 0xffff_ffff_ffff,
+                String::from_utf16_lossy(&units),
+            ));
+        }
+        pos += len as usize;
+    }
+    entries
+}
+
+struct NtfsVolume {
+    device: File,
+    cluster: u64,
+    record_size: u64,
+    mft: Vec<(Option<u64>, u64)>,
+}
+
+impl NtfsVolume {
+    fn open(device: &Path) -> Option<Self> {
+        let mut volume = Self {
+            device: File::open(device).ok()?,
+            cluster: 0,
+            record_size: 0,
+            mft: Vec::new(),
+        };
+        let (cluster, mft_lcn, record_size) = ntfs_geometry(&volume.read_at(0, ALIGN)?)?;
+        volume.cluster = cluster;
+        volume.record_size = record_size;
+        // $MFT describes where the rest of the MFT is
+        let first = ntfs_fixups(
+            volume.read_at(mft_lcn.checked_mul(cluster)?, record_size)?,
+            b"FILE",
+        )?;
+        let data = attributes(&first)
+            .into_iter()
+            .find(|attribute| attribute.kind == NTFS_DATA && !attribute.named)?;
+        volume.mft = data.runs;
+        Some(volume)
+    }
+
+    fn read_at(&mut self, offset: u64, len: u64) -> Option<Vec<u8>> {
+        let start = offset / ALIGN * ALIGN;
+        let end = offset.checked_add(len)?.checked_add(ALIGN - 1)? / ALIGN * ALIGN;
+        let mut buffer = vec![0; (end - start) as usize];
+        self.device.seek(SeekFrom::Start(start)).ok()?;
+        self.device.read_exact(&mut buffer).ok()?;
+        let skip = (offset - start) as usize;
+        Some(buffer[skip..skip + len as usize].to_vec())
+    }
+
+    // the clusters of a run list as one buffer of `size` bytes, sparse runs as zeros
+    fn read_runs(&mut self, runs: &[(Option<u64>, u64)], size: u64) -> Option<Vec<u8>> {
+        let mut data = Vec::with_capacity(size as usize);
+        for (lcn, clusters) in runs {
+            let len = std::cmp::min(
+                clusters.checked_mul(self.cluster)?,
+                size - data.len() as u64,
+            );
+            match lcn {
+                Some(lcn) => data.extend(self.read_at(lcn.checked_mul(self.cluster)?, len)?),
+                None => data.resize(data.len() + len as usize, 0),
+            }
+            if data.len() as u64 >= size {
+                break;
+            }
+        }
+        // the rest of the runs is in extension records we don't follow
+        (data.len() as u64 == size).then_some(data)
+    }
+
+    fn record(&mut self, number: u64) -> Option<Vec<u8>> {
+        let mut offset = number.checked_mul(self.record_size)?;
+        for (lcn, clusters) in self.mft.clone() {
+            let len = clusters.checked_mul(self.cluster)?;
+            if offset < len {
+                let pos = lcn?.checked_mul(self.cluster)?.checked_add(offset)?;
+                return ntfs_fixups(self.read_at(pos, self.record_size)?, b"FILE");
+            }
+            offset -= len;
+        }
+        None
+    }
+
+    // looks `name` up in the $I30 index of a directory, both in the root and all blocks
+    fn child(&mut self, directory: u64, name: &str) -> Option<u64> {
+        let record = self.record(directory)?;
+        let attributes = attributes(&record);
+        let mut entries = Vec::new();
+        let mut block_size = 0;
+        for attribute in &attributes {
+            if let (NTFS_INDEX_ROOT, Some(root)) = (attribute.kind, &attribute.resident) {
+                block_size = le_u32(root, 8)? as u64;
+                let header = 16;
+                let start = header + le_u32(root, header)? as usize;
+                let end = header + le_u32(root, header + 4)? as usize;
+                entries.extend(index_entries(root, start, end));
+            }
+        }
+        let wanted = |(_, entry): &(u64, String)| entry.to_lowercase() == name.to_lowercase();
+        if let Some((number, _)) = entries.iter().find(|entry| wanted(entry)) {
+            return Some(*number);
+        }
+
+        let allocation = attributes
+            .iter()
+            .find(|attribute| attribute.kind == NTFS_INDEX_ALLOCATION)?;
+        if block_size == 0 || allocation.size > MAX_FILE_SIZE {
+            return None;
+        }
+        let blocks = self.read_runs(&allocation.runs, allocation.size)?;
+        for block in blocks
+            .chunks_exact(block_size as usize)
+            .take(MAX_INDEX_BLOCKS)
+        {
+            let Some(block) = ntfs_fixups(block.to_vec(), b"INDX") else {
+                continue;
+            };
+            let header = 24;
+            let (Some(start), Some(end)) = (le_u32(&block, header), le_u32(&block, header + 4))
+            else {
+                continue;
+            };
+            let entries = index_entries(&block, header + start as usize, header + end as usize);
+            if let Some((number, _)) = entries.iter().find(|entry| wanted(entry)) {
+                return Some(*number);
+            }
+        }
+        None
+    }
+
+    fn read_file(&mut self, names: &[String]) -> Option<Vec<u8>> {
+        let mut number = NTFS_ROOT_RECORD;
+        for name in names {
+            number = self.child(number, name)?;
+        }
+        let record = self.record(number)?;
+        let attributes = attributes(&record);
+        if let Some(data) = attributes
+            .iter()
+            .find(|attribute| attribute.kind == NTFS_DATA && !attribute.named)
+        {
+            if let Some(value) = &data.resident {
+                return Some(value.clone());
+            }
+            if data.size > MAX_FILE_SIZE {
+                return None;
+            }
+            return self.read_runs(&data.runs, data.size);
+        }
+        // a heavily fragmented file keeps its $DATA in extension records, not followed
+        None
+    }
+}
+
+// reads files that are locked for normal opens straight from their NTFS volume, for the
+// I/O workers of one search. volumes are opened once and read one file at a time.
+#[derive(Default)]
+pub struct LockedFileReader {
+    volumes: Mutex<HashMap<u8, Option<NtfsVolume>>>,
+    pub files_read: AtomicUsize, // locked files read this way
+}
+
+impl LockedFileReader {
+    pub fn read(&self, path: &Path) -> Option<Vec<u8>> {
+        let (letter, device, names) = split_volume_path(path)?;
+        let mut volumes = self.volumes.lock().ok()?;
+        let volume = volumes
+            .entry(letter)
+            .or_insert_with(|| NtfsVolume::open(&device))
+            .as_mut()?;
+        let data = volume.read_file(&names)?;
+        self.files_read.fetch_add(1, Ordering::Relaxed);
+        Some(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn attribute_cut_off_by_the_record_end_is_skipped() {
+        let mut record = vec![0; 64];
+        record[20..22].copy_from_slice(&56_u16.to_le_bytes()); // first attribute
+        record[56..60].copy_from_slice(&NTFS_DATA.to_le_bytes());
+        record[60..64].copy_from_slice(&8_u32.to_le_bytes()); // length
+        let attributes = attributes(&record);
+        assert_eq!(attributes.len(), 1);
+        assert!(attributes[0].resident.as_ref().is_some_and(Vec::is_empty));
+    }
+
+    #[test]
+    fn index_entry_names_past_the_end_are_skipped() {
+        let mut data = vec![0; 96];
+        data[8..10].copy_from_slice(&96_u16.to_le_bytes()); // length
+        data[16 + 64] = 0xff; // name length
+        assert!(index_entries(&data, 0, data.len()).is_empty());
+    }
+}
@@ -0,0 +1,152 @@
+use crate::i18n::tr;
+use crate::{ContentEnum, QuerApp};
+use eframe::egui::{self, RichText};
+
+struct Example {
+    pattern: &'static str,
+    description: &'static str,
+}
+
+const HEX_SYNTAX: &[(&str, &str)] = &[
+    ("DE AD", "two hex digits per byte, spaces are ignored"),
+    (".", "any byte except 0A (newline)"),
+    ("[00-1f]", "any byte in the range"),
+    ("(DE|BE)", "either byte sequence"),
+    ("{4} {2,8}", "repeat the previous item"),
+    ("?", "the previous item is optional"),
+];
+
+const HEX_EXAMPLES: &[Example] = &[
+    Example {
+        pattern: "4d 5a",
+        description: "PE/DOS header",
+    },
+    Example {
+        pattern: "7f 45 4c 46 (01|02)",
+        description: "32 or 64-bit ELF header",
+    },
+    Example {
+        pattern: "89 50 4e 47 0d 0a 1a 0a",
+        description: "PNG signature",
+    },
+    Example {
+        pattern: "ff d8 ff [e0-ef]",
+        description: "JPEG start of image",
+    },
+    Example {
+        pattern: "[00-ff] [00-ff] [00-ff] 7f",
+        description: "little endian pointer into 0x7f000000-0x7fffffff",
+    },
+];
+
+const TEXT_SYNTAX: &[(&str, &str)] = &[
+    (r"\d \w \s", "digit, word character, whitespace"),
+    ("[a-z] [^0-9]", "character class, negated class"),
+    ("* + ? {2,5}", "repetition (greedy), append ? for lazy"),
+    ("(a|b) (?:x)", "alternation, non-capturing group"),
+    (r"^ $ \b", "start, end, word boundary"),
+    ("(?i)", "case insensitive from here on"),
+    (r"(?-u:\xff)", "a raw byte, even if it isn't valid UTF-8"),
+];
+
+const TEXT_EXAMPLES: &[Example] = &[
+    Example {
+        pattern: r"(?i)password\s*[=:]\s*\S+",
+        description: "password assignments",
+    },
+    Example {
+        pattern: r"\b(?:\d{1,3}\.){3}\d{1,3}\b",
+        description: "IPv4 addresses",
+    },
+    Example {
+        pattern: r"[\w.+-]+@[\w-]+\.[\w.]+",
+        description: "email addresses",
+    },
+    Example {
+        pattern: r"-----BEGIN [A-Z ]+-----",
+        description: "PEM blocks (keys, certificates)",
+    },
+    Example {
+        pattern: r"[ -~]{16,}",
+        description: "printable ASCII strings of 16+ characters",
+    },
+];
+
+impl QuerApp {
+    pub(crate) fn add_regex_help_panel(&mut self, ctx: &egui::Context) {
+        if !self.show_regex_help {
+            return;
+        }
+
+        let mut picked = None;
+        egui::SidePanel::right("regex_help")
+            .resizable(true)
+            .default_width(300.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.heading(tr("pattern-reference-title"));
+                    if ui.small_button("x").clicked() {
+                        self.show_regex_help = false;
+                    }
+                });
+                ui.label(tr("pattern-reference-intro"));
+                ui.separator();
+
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    egui::CollapsingHeader::new(tr("hex-mode"))
+                        .default_open(self.content_type == ContentEnum::Hex)
+                        .show(ui, |ui| {
+                            add_syntax(ui, "hex_syntax", HEX_SYNTAX);
+                            ui.add_space(4.0);
+                            add_examples(ui, HEX_EXAMPLES, ContentEnum::Hex, &mut picked);
+                        });
+                    egui::CollapsingHeader::new(tr("text-mode"))
+                        .default_open(self.content_type == ContentEnum::Text)
+                        .show(ui, |ui| {
+                            ui.label(tr("text-mode-syntax"));
+                            add_syntax(ui, "text_syntax", TEXT_SYNTAX);
+                            ui.add_space(4.0);
+                            add_examples(ui, TEXT_EXAMPLES, ContentEnum::Text, &mut picked);
+                            ui.hyperlink_to(
+                                "Full syntax reference",
+                                "https://docs.rs/regex/latest/regex/#syntax",
+                            );
+                        });
+                });
+            });
+
+        if let Some((pattern, content_type)) = picked {
+            self.regex_str = pattern;
+            self.content_type = content_type;
+        }
+    }
+}
+
+fn add_syntax(ui: &mut egui::Ui, id: &str, syntax: &[(&str, &str)]) {
+    egui::Grid::new(id).striped(true).show(ui, |ui| {
+        for (token, meaning) in syntax.iter() {
+            ui.monospace(*token);
+            ui.label(*meaning);
+            ui.end_row();
+        }
+    });
+}
+
+fn add_examples(
+    ui: &mut egui::Ui,
+    examples: &[Example],
+    content_type: ContentEnum,
+    picked: &mut Option<(String, ContentEnum)>,
+) {
+    ui.label(RichText::new(tr("examples")).strong());
+    for example in examples.iter() {
+        if ui
+            .link(RichText::new(example.pattern).monospace())
+            .on_hover_text(example.description)
+            .clicked()
+        {
+            *picked = Some((example.pattern.to_string(), content_type.clone()));
+        }
+        ui.label(RichText::new(example.description).small().weak());
+    }
+}
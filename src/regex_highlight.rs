@@ -0,0 +1,222 @@
+use eframe::egui;
+use egui::text::{LayoutJob, TextFormat};
+use egui::{Color32, FontId, Stroke};
+use std::ops::Range;
+
+#[derive(Clone, Copy, PartialEq)]
+enum TokenKind {
+    Literal,
+    Escape,
+    Class,
+    Quantifier,
+    Group,
+    Anchor,
+}
+
+struct Palette {
+    literal: Color32,
+    escape: Color32,
+    class: Color32,
+    quantifier: Color32,
+    group: Color32,
+    anchor: Color32,
+}
+
+impl Palette {
+    fn new(dark_mode: bool) -> Self {
+        if dark_mode {
+            Palette {
+                literal: Color32::from_rgb(0xdd, 0xdd, 0xdd),
+                escape: Color32::from_rgb(0xe0, 0xa0, 0x50),
+                class: Color32::from_rgb(0x6c, 0xb6, 0xff),
+                quantifier: Color32::from_rgb(0xd0, 0x7c, 0xe0),
+                group: Color32::from_rgb(0x7e, 0xc8, 0x7e),
+                anchor: Color32::from_rgb(0xe0, 0xd0, 0x60),
+            }
+        } else {
+            Palette {
+                literal: Color32::from_rgb(0x20, 0x20, 0x20),
+                escape: Color32::from_rgb(0xa0, 0x50, 0x00),
+                class: Color32::from_rgb(0x00, 0x50, 0xb0),
+                quantifier: Color32::from_rgb(0x80, 0x20, 0x90),
+                group: Color32::from_rgb(0x1a, 0x70, 0x2a),
+                anchor: Color32::from_rgb(0x80, 0x70, 0x00),
+            }
+        }
+    }
+
+    fn color(&self, kind: TokenKind) -> Color32 {
+        match kind {
+            TokenKind::Literal => self.literal,
+            TokenKind::Escape => self.escape,
+            TokenKind::Class => self.class,
+            TokenKind::Quantifier => self.quantifier,
+            TokenKind::Group => self.group,
+            TokenKind::Anchor => self.anchor,
+        }
+    }
+}
+
+// byte range of the offending part of the pattern, as reported by the regex parser
+pub fn error_span(pattern: &str) -> Option<Range<usize>> {
+    let err = regex_syntax::ParserBuilder::new()
+        .utf8(false)
+        .build()
+        .parse(pattern)
+        .err()?;
+    let span = match &err {
+        regex_syntax::Error::Parse(err) => err.span(),
+        regex_syntax::Error::Translate(err) => err.span(),
+        _ => return None,
+    };
+    Some(span.start.offset..span.end.offset)
+}
+
+// a rough lexer, good enough for coloring; the real validation is done by the regex crate
+fn tokenize(pattern: &str) -> Vec<(Range<usize>, TokenKind)> {
+    let chars: Vec<(usize, char)> = pattern.char_indices().collect();
+    let end_of = |idx: usize| {
+        chars
+            .get(idx)
+            .map(|(pos, _c)| *pos)
+            .unwrap_or(pattern.len())
+    };
+    let mut tokens = Vec::new();
+    let mut idx = 0;
+
+    while idx < chars.len() {
+        let start = idx;
+        let kind = match chars[idx].1 {
+            '\\' => {
+                idx += 1;
+                // \x{..}, \p{..} and friends span up to the closing brace
+                if idx + 1 < chars.len()
+                    && matches!(chars[idx].1, 'x' | 'u' | 'U' | 'p' | 'P')
+                    && chars[idx + 1].1 == '{'
+                {
+                    while idx < chars.len() && chars[idx].1 != '}' {
+                        idx += 1;
+                    }
+                } else if idx < chars.len() && chars[idx].1 == 'x' {
+                    idx += 2;
+                }
+                idx += 1;
+                TokenKind::Escape
+            }
+            '[' => {
+                idx += 1;
+                let mut depth = 1;
+                // a leading ] (or ^]) is a literal inside the class
+                if idx < chars.len() && chars[idx].1 == '^' {
+                    idx += 1;
+                }
+                if idx < chars.len() && chars[idx].1 == ']' {
+                    idx += 1;
+                }
+                while idx < chars.len() && depth > 0 {
+                    match chars[idx].1 {
+                        '\\' => idx += 1,
+                        '[' => depth += 1,
+                        ']' => depth -= 1,
+                        _ => {}
+                    }
+                    idx += 1;
+                }
+                TokenKind::Class
+            }
+            '{' => {
+                while idx < chars.len() && chars[idx].1 != '}' {
+                    idx += 1;
+                }
+                idx += 1;
+                TokenKind::Quantifier
+            }
+            '*' | '+' | '?' => {
+                idx += 1;
+                // lazy variants
+                if idx < chars.len() && chars[idx].1 == '?' {
+                    idx += 1;
+                }
+                TokenKind::Quantifier
+            }
+            '(' => {
+                idx += 1;
+                // flags and group names belong to the opening paren
+                if idx < chars.len() && chars[idx].1 == '?' {
+                    while idx < chars.len() && !matches!(chars[idx].1, ':' | ')' | '>') {
+                        idx += 1;
+                    }
+                    if idx < chars.len() && chars[idx].1 != ')' {
+                        idx += 1;
+                    }
+                }
+                TokenKind::Group
+            }
+            ')' | '|' => {
+                idx += 1;
+                TokenKind::Group
+            }
+            '^' | '$' | '.' => {
+                idx += 1;
+                TokenKind::Anchor
+            }
+            _ => {
+                idx += 1;
+                TokenKind::Literal
+            }
+        };
+        let idx_end = std::cmp::min(idx, chars.len());
+        tokens.push((end_of(start)..end_of(idx_end), kind));
+        idx = idx_end;
+    }
+
+    tokens
+}
+
+pub fn layout_job(
+    pattern: &str,
+    error_span: Option<&Range<usize>>,
+    font_id: FontId,
+    dark_mode: bool,
+) -> LayoutJob {
+    let palette = Palette::new(dark_mode);
+    let error_stroke = Stroke::new(1.5, Color32::from_rgb(0xe0, 0x20, 0x20));
+    let mut job = LayoutJob::default();
+
+    for (range, kind) in tokenize(pattern) {
+        // split tokens at the error boundaries so exactly the reported part is underlined
+        let mut cuts = vec![range.start, range.end];
+        if let Some(err) = error_span {
+            for cut in [err.start, err.end] {
+                if cut > range.start && cut < range.end && pattern.is_char_boundary(cut) {
+                    cuts.push(cut);
+                }
+            }
+        }
+        cuts.sort_unstable();
+
+        for part in cuts.windows(2) {
+            let (start, end) = (part[0], part[1]);
+            let in_error = match error_span {
+                // zero-width errors (e.g. unexpected end) underline the char before them
+                Some(err) if err.start == err.end => {
+                    start < err.start.max(1) && end >= err.start.max(1)
+                }
+                Some(err) => start >= err.start && end <= err.end,
+                None => false,
+            };
+            job.append(
+                &pattern[start..end],
+                0.0,
+                TextFormat {
+                    font_id: font_id.clone(),
+                    color: palette.color(kind),
+                    underline: if in_error { error_stroke } else { Stroke::NONE },
+                    ..Default::default()
+                },
+            );
+        }
+    }
+
+    job
+}
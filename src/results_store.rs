@@ -0,0 +1,247 @@
+use crate::elevated::{finding_from_json, finding_to_json};
+use crate::history::SearchHistoryEntry;
+use crate::i18n::{tr, tr_args};
+use crate::settings::{load_settings, save_setting, settings_path};
+use crate::{DirTree, Finding, QuerApp};
+use chrono::{DateTime, Local};
+use eframe::egui;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::PathBuf;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+
+const SETTING: &str = "persist_results";
+const COMPRESSION_LEVEL: i32 = 3;
+
+// searches finishing in quick succession would otherwise write the same temporary file
+static WRITING: Mutex<()> = Mutex::new(());
+
+pub fn persist_results_enabled() -> bool {
+    load_settings()[SETTING].as_bool().unwrap_or(true)
+}
+
+fn results_path() -> Option<PathBuf> {
+    Some(settings_path()?.with_file_name("last-results.jsonl.zst"))
+}
+
+// what the stored results came from, read without decoding the findings themselves
+pub struct StoredResults {
+    search: SearchHistoryEntry,
+    finished: Option<DateTime<Local>>,
+    summary: String,
+    count: usize,
+    restoring: bool,
+}
+
+pub type RestoreOutcome = Arc<Mutex<Option<Result<Vec<Finding>, String>>>>;
+
+fn open_results() -> Result<impl BufRead, String> {
+    let path = results_path().ok_or("No config directory found")?;
+    let file = File::open(path).map_err(|err| err.to_string())?;
+    let decoder = zstd::Decoder::new(file).map_err(|err| err.to_string())?;
+    Ok(BufReader::new(decoder))
+}
+
+// None if there's nothing stored or it can't be read, it's only an offer after all
+pub fn load_stored_results() -> Option<StoredResults> {
+    let mut header = String::new();
+    open_results().ok()?.read_line(&mut header).ok()?;
+    let header = json::parse(&header).ok()?;
+    Some(StoredResults {
+        search: SearchHistoryEntry::from_json(&header)?,
+        finished: header["finished"]
+            .as_str()
+            .and_then(|time| DateTime::parse_from_rfc3339(time).ok())
+            .map(|time| time.with_timezone(&Local)),
+        summary: header["summary"].as_str().unwrap_or_default().to_string(),
+        count: header["count"].as_usize()?,
+        restoring: false,
+    })
+}
+
+fn read_findings() -> Result<Vec<Finding>, String> {
+    let mut findings = Vec::new();
+    for line in open_results()?.lines().skip(1) {
+        let line = line.map_err(|err| err.to_string())?;
+        let finding = json::parse(&line)
+            .ok()
+            .and_then(|obj| finding_from_json(&obj))
+            .ok_or("Damaged result line")?;
+        findings.push(finding);
+    }
+    Ok(findings)
+}
+
+fn write_findings(header: json::JsonValue, findings: &[Finding]) -> Result<(), String> {
+    let path = results_path().ok_or("No config directory found")?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|err| err.to_string())?;
+    }
+    let _writing = WRITING.lock().map_err(|err| err.to_string())?;
+    if findings.is_empty() {
+        // an empty search replaces older results as well
+        return match fs::remove_file(&path) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.to_string()),
+            _ => Ok(()),
+        };
+    }
+    // written next to it and renamed, so a crash midway keeps the previous results
+    let temp_path = path.with_extension("zst.part");
+    let file = File::create(&temp_path).map_err(|err| err.to_string())?;
+    let mut encoder = zstd::Encoder::new(file, COMPRESSION_LEVEL).map_err(|err| err.to_string())?;
+    {
+        let mut writer = BufWriter::new(&mut encoder);
+        writeln!(writer, "{}", header.dump()).map_err(|err| err.to_string())?;
+        for finding in findings {
+            writeln!(writer, "{}", finding_to_json(finding).dump())
+                .map_err(|err| err.to_string())?;
+        }
+        writer.flush().map_err(|err| err.to_string())?;
+    }
+    encoder.finish().map_err(|err| err.to_string())?;
+    fs::rename(&temp_path, &path).map_err(|err| err.to_string())
+}
+
+impl QuerApp {
+    pub(crate) fn save_persist_results(&mut self) {
+        if let Err(err) = save_setting(SETTING, self.persist_results.into()) {
+            self.log_error(format!("Couldn't save the keep results setting: {}", err));
+        }
+    }
+
+    // compresses the results on their own thread, big result sets take a moment
+    pub(crate) fn store_results(&mut self) {
+        if !self.persist_results {
+            return;
+        }
+        let mut header = self.current_search_entry().to_json();
+        header["scan_mode"] = crate::export::scan_mode_name(&self.active_scan_mode).into();
+        header["finished"] = self
+            .search_finished_at
+            .unwrap_or_else(Local::now)
+            .to_rfc3339()
+            .into();
+        header["summary"] = self.scan_summary.as_str().into();
+        header["count"] = self.findings.len().into();
+        let findings = self.findings.clone();
+        let errors = Arc::clone(&self.results_store_error);
+        thread::spawn(move || {
+            if let Err(err) = write_findings(header, &findings) {
+                if let Ok(mut errors) = errors.lock() {
+                    *errors = Some(err);
+                }
+            }
+        });
+        // stored results are replaced, there's nothing left to offer
+        self.restorable = None;
+    }
+
+    fn restore_stored_results(&mut self, ctx: &egui::Context) {
+        let Some(stored) = &mut self.restorable else {
+            return;
+        };
+        stored.restoring = true;
+        let outcome = Arc::clone(&self.restore_outcome);
+        let ctx = ctx.clone();
+        thread::spawn(move || {
+            let findings = read_findings();
+            if let Ok(mut outcome) = outcome.lock() {
+                *outcome = Some(findings);
+            }
+            ctx.request_repaint();
+        });
+    }
+
+    fn finish_restoring(&mut self, findings: Result<Vec<Finding>, String>) {
+        let Some(stored) = self.restorable.take() else {
+            return;
+        };
+        let findings = match findings {
+            Ok(findings) => findings,
+            Err(err) => {
+                self.log_error(format!("Couldn't restore the last results: {}", err));
+                return;
+            }
+        };
+        // the search they came from, as if it just ran
+        self.active_scan_mode = stored.search.scan_mode.clone();
+        self.restore_search(stored.search);
+        self.show_line_cols = self.content_type == crate::ContentEnum::Text
+            && self.active_scan_mode == crate::ScanModeEnum::Content;
+        self.dir_tree = DirTree::new(&self.root_folder_path);
+        self.search_start_index = 0;
+        self.search_finished_at = stored.finished;
+        self.scan_summary = stored.summary;
+        self.log(format!(
+            "Restored {} results of the last search",
+            findings.len()
+        ));
+        // they come in like those of a search, so columns and the folder tree are filled
+        let (tx, rx) = mpsc::channel();
+        for finding in findings {
+            let _ = tx.send(finding);
+        }
+        self.rx_handles.push(rx);
+    }
+
+    // offered until something else fills the table
+    pub(crate) fn add_restore_banner(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        let store_error = self
+            .results_store_error
+            .lock()
+            .ok()
+            .and_then(|mut err| err.take());
+        if let Some(err) = store_error {
+            self.log_error(format!("Couldn't keep the results: {}", err));
+        }
+        let restored = self
+            .restore_outcome
+            .lock()
+            .ok()
+            .and_then(|mut outcome| outcome.take());
+        if let Some(findings) = restored {
+            self.finish_restoring(findings);
+        }
+
+        if !self.findings.is_empty() || !self.is_search_finished() {
+            return;
+        }
+        let Some(stored) = &self.restorable else {
+            return;
+        };
+        let finished = stored
+            .finished
+            .map(|time| time.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default();
+        let description = tr_args(
+            "restore-last-results-desc",
+            &[
+                ("count", stored.count.into()),
+                ("pattern", stored.search.regex_str.clone().into()),
+                (
+                    "root",
+                    stored.search.root_folder_path.display().to_string().into(),
+                ),
+                ("time", finished.into()),
+            ],
+        );
+        let restoring = stored.restoring;
+        let (mut restore, mut dismiss) = (false, false);
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.label(description);
+            if restoring {
+                ui.spinner();
+                return;
+            }
+            restore = ui.button(tr("restore-last-results")).clicked();
+            dismiss = ui.button(tr("dismiss")).clicked();
+        });
+        if restore {
+            self.restore_stored_results(ctx);
+        } else if dismiss {
+            self.restorable = None;
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use crate::accessibility::set_accessible_name;
+use crate::i18n::{tr, tr_args};
+use crate::presets::parse_number;
+use crate::settings::save_setting;
+use crate::{Finding, QuerApp};
+use eframe::egui::{self, Color32};
+
+// alpha of the tint painted under a colored row, so text stays readable
+const TINT_ALPHA: u8 = 0x50;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum RuleKind {
+    PathContains,
+    OffsetBelow,
+    PatternIs,
+}
+
+impl RuleKind {
+    const ALL: [RuleKind; 3] = [
+        RuleKind::PathContains,
+        RuleKind::OffsetBelow,
+        RuleKind::PatternIs,
+    ];
+
+    fn key(&self) -> &'static str {
+        match self {
+            RuleKind::PathContains => "path_contains",
+            RuleKind::OffsetBelow => "offset_below",
+            RuleKind::PatternIs => "pattern_is",
+        }
+    }
+
+    fn name(&self) -> String {
+        match self {
+            RuleKind::PathContains => tr("rule-path-contains"),
+            RuleKind::OffsetBelow => tr("rule-offset-below"),
+            RuleKind::PatternIs => tr("rule-pattern-is"),
+        }
+    }
+
+    fn default_color(&self) -> Color32 {
+        match self {
+            RuleKind::PathContains => Color32::from_rgb(0xc0, 0x20, 0x20),
+            RuleKind::OffsetBelow => Color32::from_rgb(0xd0, 0xb0, 0x00),
+            RuleKind::PatternIs => Color32::from_rgb(0x20, 0xa0, 0x40),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RowColorRule {
+    pub kind: RuleKind,
+    pub value: String,
+    pub color: Color32,
+}
+
+impl RowColorRule {
+    fn new(kind: RuleKind) -> Self {
+        Self {
+            kind,
+            value: String::new(),
+            color: kind.default_color(),
+        }
+    }
+
+    // empty or unparsable rules match nothing
+    fn matches(&self, finding: &Finding) -> bool {
+        if self.value.is_empty() {
+            return false;
+        }
+        match self.kind {
+            RuleKind::PathContains => finding.filepath.contains(&self.value),
+            RuleKind::OffsetBelow => parse_number(&self.value)
+                .map(|limit| (finding.offset as u64) < limit)
+                .unwrap_or(false),
+            RuleKind::PatternIs => finding.pattern.as_deref() == Some(self.value.as_str()),
+        }
+    }
+
+    fn to_json(&self) -> json::JsonValue {
+        let mut obj = json::JsonValue::new_object();
+        obj["kind"] = self.kind.key().into();
+        obj["value"] = self.value.as_str().into();
+        obj["color"] = format!(
+            "#{:02x}{:02x}{:02x}",
+            self.color.r(),
+            self.color.g(),
+            self.color.b()
+        )
+        .into();
+        obj
+    }
+
+    fn from_json(obj: &json::JsonValue) -> Option<Self> {
+        let kind = RuleKind::ALL
+            .into_iter()
+            .find(|kind| obj["kind"].as_str() == Some(kind.key()))?;
+        let color = obj["color"].as_str()?.strip_prefix('#')?;
+        let color = u32::from_str_radix(color, 16).ok()?;
+        Some(Self {
+            kind,
+            value: obj["value"].as_str()?.to_string(),
+            color: Color32::from_rgb((color >> 16) as u8, (color >> 8) as u8, color as u8),
+        })
+    }
+}
+
+// rules saved in the settings file, skipping any that don't parse
+pub fn load_row_color_rules(settings: &json::JsonValue) -> Vec<RowColorRule> {
+    settings["row_color_rules"]
+        .members()
+        .filter_map(RowColorRule::from_json)
+        .collect()
+}
+
+// tints the whole visible row, called from its first cell so the text of every cell
+// is drawn on top
+pub fn paint_row_tint(ui: &egui::Ui, color: Color32) {
+    let clip = ui.clip_rect();
+    let rect = egui::Rect::from_x_y_ranges(clip.x_range(), ui.max_rect().y_range());
+    let tint = Color32::from_rgba_unmultiplied(color.r(), color.g(), color.b(), TINT_ALPHA);
+    ui.painter().rect_filled(rect, 0.0, tint);
+}
+
+impl QuerApp {
+    // the first matching rule wins
+    pub(crate) fn row_tint(&self, finding: &Finding) -> Option<Color32> {
+        self.row_color_rules
+            .iter()
+            .find(|rule| rule.matches(finding))
+            .map(|rule| rule.color)
+    }
+
+    fn save_row_color_rules(&mut self) {
+        let rules = json::JsonValue::Array(
+            self.row_color_rules
+                .iter()
+                .map(RowColorRule::to_json)
+                .collect(),
+        );
+        if let Err(err) = save_setting("row_color_rules", rules) {
+            self.log_error(format!("Couldn't save row color rules: {}", err));
+        }
+    }
+
+    pub(crate) fn add_row_colors_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_row_colors;
+        let mut changed = false;
+        egui::Window::new(tr("row-colors-title"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(tr("row-colors-intro"));
+                let mut remove = None;
+                egui::Grid::new("row_color_rules").show(ui, |ui| {
+                    for (idx, rule) in self.row_color_rules.iter_mut().enumerate() {
+                        egui::ComboBox::from_id_source(("row_color_kind", idx))
+                            .selected_text(rule.kind.name())
+                            .show_ui(ui, |ui| {
+                                for kind in RuleKind::ALL {
+                                    changed |= ui
+                                        .selectable_value(&mut rule.kind, kind, kind.name())
+                                        .changed();
+                                }
+                            });
+                        let hint = match rule.kind {
+                            RuleKind::OffsetBelow => "0x400",
+                            _ => "",
+                        };
+                        let resp = ui.add(
+                            egui::TextEdit::singleline(&mut rule.value)
+                                .hint_text(hint)
+                                .desired_width(200.0),
+                        );
+                        set_accessible_name(
+                            &resp,
+                            &tr_args("rule-value", &[("rule", rule.kind.name().into())]),
+                        );
+                        changed |= resp.lost_focus();
+                        changed |= ui.color_edit_button_srgba(&mut rule.color).changed();
+                        if ui.button("🗑").on_hover_text(tr("remove-rule")).clicked() {
+                            remove = Some(idx);
+                        }
+                        ui.end_row();
+                    }
+                });
+                if let Some(idx) = remove {
+                    self.row_color_rules.remove(idx);
+                    changed = true;
+                }
+                ui.horizontal(|ui| {
+                    for kind in RuleKind::ALL {
+                        if ui
+                            .button(tr_args("add-rule", &[("rule", kind.name().into())]))
+                            .clicked()
+                        {
+                            self.row_color_rules.push(RowColorRule::new(kind));
+                            changed = true;
+                        }
+                    }
+                });
+            });
+        self.show_row_colors = open;
+
+        if changed {
+            self.save_row_color_rules();
+        }
+    }
+}
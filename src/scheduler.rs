@@ -0,0 +1,449 @@
+use crate::export::{content_mode_name, expand_export_template};
+use crate::hash_lookup::sha256_bytes;
+use crate::history::SearchHistoryEntry;
+use crate::i18n::{tr, tr_args};
+use crate::settings::{save_setting, settings_path};
+use crate::tray;
+use crate::webhook::{WebhookConfig, WebhookSink};
+use crate::QuerApp;
+use chrono::{Local, NaiveDate, NaiveTime};
+use eframe::egui;
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+// how often the clock is checked while nothing else wakes the UI
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+const DATE_FORMAT: &str = "%Y-%m-%d";
+pub(crate) const DEFAULT_SCHEDULED_EXPORT: &str = "~/quer/scheduled/{pattern-hash}.jsonl";
+
+// a search run once a day, its results appended to a JSON Lines file
+#[derive(Clone)]
+pub struct ScheduledScan {
+    pub name: String,
+    pub search: SearchHistoryEntry,
+    pub hour: u32,
+    pub minute: u32,
+    pub export_path: String, // a quick export template, ~ and {date} work
+    pub enabled: bool,
+    pub last_run: Option<NaiveDate>,
+    pub last_outcome: String,
+    pub id: String,  // names the file with the findings of the last run
+    pub alert: bool, // notify and post to the webhook when a run finds something new
+}
+
+impl ScheduledScan {
+    fn time(&self) -> NaiveTime {
+        NaiveTime::from_hms_opt(self.hour, self.minute, 0).unwrap_or_default()
+    }
+
+    // the next run is the next time the clock gets there, not right away because the time
+    // already passed today
+    fn skip_past_time(&mut self) {
+        let now = Local::now();
+        self.last_run = (now.time() >= self.time()).then(|| now.date_naive());
+    }
+
+    fn is_due(&self) -> bool {
+        let now = Local::now();
+        self.enabled
+            && now.time() >= self.time()
+            && self.last_run.map_or(true, |last| last < now.date_naive())
+    }
+
+    fn to_json(&self) -> json::JsonValue {
+        let mut obj = self.search.to_json();
+        obj["name"] = self.name.as_str().into();
+        obj["hour"] = self.hour.into();
+        obj["minute"] = self.minute.into();
+        obj["export_path"] = self.export_path.as_str().into();
+        obj["enabled"] = self.enabled.into();
+        if let Some(last_run) = self.last_run {
+            obj["last_run"] = last_run.format(DATE_FORMAT).to_string().into();
+        }
+        obj["last_outcome"] = self.last_outcome.as_str().into();
+        obj["id"] = self.id.as_str().into();
+        obj["alert"] = self.alert.into();
+        obj
+    }
+
+    fn from_json(obj: &json::JsonValue) -> Option<Self> {
+        let search = SearchHistoryEntry::from_json(obj)?;
+        Some(Self {
+            name: obj["name"].as_str()?.to_string(),
+            search,
+            hour: obj["hour"].as_u32()?.min(23),
+            minute: obj["minute"].as_u32()?.min(59),
+            export_path: obj["export_path"].as_str()?.to_string(),
+            enabled: obj["enabled"].as_bool().unwrap_or(false),
+            last_run: obj["last_run"]
+                .as_str()
+                .and_then(|date| NaiveDate::parse_from_str(date, DATE_FORMAT).ok()),
+            last_outcome: obj["last_outcome"].as_str().unwrap_or_default().to_string(),
+            // scans saved before they had one get an id here, saved along from then on
+            id: match obj["id"].as_str() {
+                Some(id) => id.to_string(),
+                None => sha256_bytes(obj.dump().as_bytes())[..16].to_string(),
+            },
+            alert: obj["alert"].as_bool().unwrap_or(false),
+        })
+    }
+}
+
+// the (file, offset) of each finding of a scan's last run, None before its first run
+fn baseline_path(id: &str) -> Option<PathBuf> {
+    Some(settings_path()?.with_file_name(format!("scheduled-{}.json", id)))
+}
+
+fn load_baseline(id: &str) -> Option<HashSet<(String, usize)>> {
+    let text = fs::read_to_string(baseline_path(id)?).ok()?;
+    let baseline = json::parse(&text).ok()?;
+    Some(
+        baseline
+            .members()
+            .filter_map(|entry| Some((entry[0].as_str()?.to_string(), entry[1].as_usize()?)))
+            .collect(),
+    )
+}
+
+fn save_baseline(id: &str, findings: &HashSet<(String, usize)>) -> Result<(), String> {
+    let path = baseline_path(id).ok_or("No config directory found")?;
+    let entries = json::JsonValue::Array(
+        findings
+            .iter()
+            .map(|(file, offset)| json::array![file.as_str(), *offset])
+            .collect(),
+    );
+    fs::write(path, entries.dump()).map_err(|err| err.to_string())
+}
+
+fn append_lines(path: &Path, lines: &[json::JsonValue]) -> Result<(), String> {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir).map_err(|err| err.to_string())?;
+    }
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|err| err.to_string())?;
+    let mut writer = BufWriter::new(file);
+    for line in lines {
+        writeln!(writer, "{}", line.dump()).map_err(|err| err.to_string())?;
+    }
+    writer.flush().map_err(|err| err.to_string())
+}
+
+pub fn load_scheduled_scans(settings: &json::JsonValue) -> Vec<ScheduledScan> {
+    settings["scheduled_scans"]
+        .members()
+        .filter_map(ScheduledScan::from_json)
+        .collect()
+}
+
+impl QuerApp {
+    fn save_scheduled_scans(&mut self) {
+        let scans = json::JsonValue::Array(
+            self.scheduled_scans
+                .iter()
+                .map(ScheduledScan::to_json)
+                .collect(),
+        );
+        if let Err(err) = save_setting("scheduled_scans", scans) {
+            self.log_error(format!("Couldn't save the scheduled scans: {}", err));
+        }
+    }
+
+    fn add_current_search_to_schedule(&mut self) {
+        let mut scan = ScheduledScan {
+            name: self.regex_str.clone(),
+            search: self.current_search_entry(),
+            hour: 2,
+            minute: 0,
+            export_path: DEFAULT_SCHEDULED_EXPORT.to_string(),
+            enabled: true,
+            last_run: None,
+            last_outcome: String::new(),
+            id: format!("{:x}", Local::now().timestamp_micros()),
+            alert: false,
+        };
+        scan.skip_past_time();
+        self.scheduled_scans.push(scan);
+        self.save_scheduled_scans();
+    }
+
+    fn start_scheduled_scan(&mut self, idx: usize) {
+        let scan = &mut self.scheduled_scans[idx];
+        scan.last_run = Some(Local::now().date_naive());
+        let (name, search) = (scan.name.clone(), scan.search.clone());
+        self.restore_search(search);
+        if self.scan_mode.needs_pattern() && self.regex_result.is_err() {
+            self.scheduled_scans[idx].last_outcome = tr("scheduled-scan-invalid-pattern");
+            self.log_error(format!(
+                "Scheduled scan \"{}\" has an invalid pattern",
+                name
+            ));
+        } else {
+            self.log(format!("Starting scheduled scan \"{}\"", name));
+            self.progress = 0.0;
+            self.search();
+            self.scheduled_run = Some((idx, Local::now()));
+        }
+        self.save_scheduled_scans();
+    }
+
+    // appends the findings of the run that just finished, each tagged with the scan and the
+    // time it started, so the file builds up a history of runs. findings the previous run
+    // didn't have are marked as new, and reported if the scan asks for it.
+    fn finish_scheduled_scan(&mut self, idx: usize, started: chrono::DateTime<Local>) {
+        let Some(scan) = self.scheduled_scans.get(idx).cloned() else {
+            return;
+        };
+        let path = expand_export_template(
+            &scan.export_path,
+            &scan.search.regex_str,
+            content_mode_name(&scan.search.content_type),
+        );
+        let findings = &self.findings[self.search_start_index.min(self.findings.len())..];
+        let previous = load_baseline(&scan.id);
+        let previous_files: HashSet<&str> = previous
+            .iter()
+            .flatten()
+            .map(|(file, _)| file.as_str())
+            .collect();
+        let run = started.to_rfc3339();
+        let mut lines = Vec::with_capacity(findings.len());
+        let mut new_findings = HashSet::new();
+        let mut new_files = HashSet::new();
+        for finding in findings {
+            let mut obj = finding.to_json();
+            obj["scheduled_scan"] = scan.name.as_str().into();
+            obj["run"] = run.as_str().into();
+            let key = (finding.filepath.clone(), finding.offset);
+            // the first run only sets the baseline
+            if previous
+                .as_ref()
+                .is_some_and(|previous| !previous.contains(&key))
+            {
+                if previous_files.contains(finding.filepath.as_str()) {
+                    obj["new"] = "offset".into();
+                } else {
+                    obj["new"] = "file".into();
+                    new_files.insert(finding.filepath.as_str());
+                }
+                new_findings.insert(key);
+            }
+            lines.push(obj);
+        }
+        let current: HashSet<(String, usize)> = findings
+            .iter()
+            .map(|finding| (finding.filepath.clone(), finding.offset))
+            .collect();
+        let (matches, new_files) = (findings.len(), new_files.len());
+
+        // a stopped run only saw part of the files, what it missed isn't gone. the baseline
+        // stays that of the last complete run so the next one compares against it.
+        let completed = !self.file_queue.is_closed() && self.file_queue.is_empty();
+        let written = append_lines(&path, &lines).and_then(|()| match completed {
+            true => save_baseline(&scan.id, &current),
+            false => Ok(()),
+        });
+        let outcome = match written {
+            Ok(()) => tr_args(
+                "scheduled-scan-outcome",
+                &[
+                    ("matches", matches.into()),
+                    ("new", new_findings.len().into()),
+                    ("files", new_files.into()),
+                    ("time", started.format("%Y-%m-%d %H:%M").to_string().into()),
+                ],
+            ),
+            Err(err) => {
+                let message = format!("Couldn't write to {}: {}", path.display(), err);
+                self.log_error(format!("Scheduled scan \"{}\": {}", scan.name, message));
+                message
+            }
+        };
+        self.log(format!(
+            "Scheduled scan \"{}\" found {} matches ({} new), appended to {}",
+            scan.name,
+            matches,
+            new_findings.len(),
+            path.display()
+        ));
+        if !completed {
+            self.log(format!(
+                "Scheduled scan \"{}\" was stopped, its matches don't replace the baseline",
+                scan.name
+            ));
+        }
+        if scan.alert && !new_findings.is_empty() {
+            self.alert_new_findings(
+                &scan,
+                &outcome,
+                lines.into_iter().filter(|obj| obj.has_key("new")),
+            );
+        }
+        self.new_findings = new_findings;
+        self.scheduled_scans[idx].last_outcome = outcome;
+        self.save_scheduled_scans();
+    }
+
+    fn alert_new_findings(
+        &mut self,
+        scan: &ScheduledScan,
+        outcome: &str,
+        new_findings: impl Iterator<Item = json::JsonValue>,
+    ) {
+        tray::notify(&format!("quer: {}", scan.name), outcome);
+        if !self.webhook_enabled || self.webhook_url.is_empty() {
+            return;
+        }
+        // the sink of an earlier alert flushes and exits once replaced
+        let sink = WebhookSink::spawn(WebhookConfig {
+            url: self.webhook_url.clone(),
+            headers: WebhookConfig::parse_headers(&self.webhook_headers),
+        });
+        for finding in new_findings {
+            sink.send(finding);
+        }
+        self.alert_sink = Some(sink);
+    }
+
+    // runs scans that are due one after another, never while another search is going on
+    pub(crate) fn poll_scheduled_scans(&mut self, ctx: &egui::Context) {
+        if self.scheduled_scans.is_empty() {
+            return;
+        }
+        ctx.request_repaint_after(CHECK_INTERVAL);
+        if let Some(sink) = &self.alert_sink {
+            for err in sink.take_errors() {
+                self.log_error(format!("Scheduled scan alert: {}", err));
+            }
+        }
+        if let Some((idx, started)) = self.scheduled_run {
+            if self.search_running {
+                return;
+            }
+            self.scheduled_run = None;
+            self.finish_scheduled_scan(idx, started);
+        }
+        if self.search_running || !self.is_search_finished() {
+            return;
+        }
+        if let Some(idx) = self.scheduled_scans.iter().position(ScheduledScan::is_due) {
+            self.start_scheduled_scan(idx);
+        }
+    }
+
+    pub(crate) fn add_scheduled_scans_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_scheduled_scans;
+        let mut changed = false;
+        let mut removed = None;
+        let mut run_now = None;
+        egui::Window::new(tr("scheduled-scans-title"))
+            .open(&mut open)
+            .default_width(640.0)
+            .show(ctx, |ui| {
+                ui.label(tr("scheduled-scans-intro"));
+                ui.separator();
+                if self.scheduled_scans.is_empty() {
+                    ui.label(tr("no-scheduled-scans"));
+                }
+                let idle = self.is_search_finished() && self.scheduled_run.is_none();
+                // indices of the scans can't shift while one of them runs
+                let can_remove = self.scheduled_run.is_none();
+                egui::Grid::new("scheduled_scans")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (idx, scan) in self.scheduled_scans.iter_mut().enumerate() {
+                            changed |= ui.checkbox(&mut scan.enabled, "").changed();
+                            changed |= ui
+                                .add(
+                                    egui::TextEdit::singleline(&mut scan.name).desired_width(120.0),
+                                )
+                                .on_hover_text(tr_args(
+                                    "scheduled-scan-details",
+                                    &[
+                                        ("pattern", scan.search.regex_str.clone().into()),
+                                        (
+                                            "root",
+                                            scan.search
+                                                .root_folder_path
+                                                .display()
+                                                .to_string()
+                                                .into(),
+                                        ),
+                                    ],
+                                ))
+                                .lost_focus();
+                            let retimed = ui
+                                .horizontal(|ui| {
+                                    let hour = ui
+                                        .add(egui::DragValue::new(&mut scan.hour).range(0..=23))
+                                        .changed();
+                                    ui.label(":");
+                                    let minute = ui
+                                        .add(egui::DragValue::new(&mut scan.minute).range(0..=59))
+                                        .changed();
+                                    hour || minute
+                                })
+                                .inner;
+                            if retimed {
+                                scan.skip_past_time();
+                                changed = true;
+                            }
+                            changed |= ui
+                                .add(
+                                    egui::TextEdit::singleline(&mut scan.export_path)
+                                        .desired_width(220.0),
+                                )
+                                .on_hover_text(tr("scheduled-scan-export-hint"))
+                                .lost_focus();
+                            changed |= ui
+                                .checkbox(&mut scan.alert, tr("alert-on-new-findings"))
+                                .on_hover_text(tr("alert-on-new-findings-hint"))
+                                .changed();
+                            ui.label(&scan.last_outcome);
+                            if ui
+                                .add_enabled(idle, egui::Button::new(tr("run-now")))
+                                .clicked()
+                            {
+                                run_now = Some(idx);
+                            }
+                            if ui
+                                .add_enabled(
+                                    can_remove,
+                                    egui::Button::new(tr("remove-scheduled-scan")),
+                                )
+                                .clicked()
+                            {
+                                removed = Some(idx);
+                            }
+                            ui.end_row();
+                        }
+                    });
+                ui.separator();
+                if ui
+                    .button(tr("schedule-current-search"))
+                    .on_hover_text(tr("schedule-current-search-hint"))
+                    .clicked()
+                {
+                    self.add_current_search_to_schedule();
+                }
+            });
+        self.show_scheduled_scans = open;
+
+        if let Some(idx) = removed {
+            self.scheduled_scans.remove(idx);
+            changed = true;
+        }
+        if changed {
+            self.save_scheduled_scans();
+        }
+        if let Some(idx) = run_now {
+            self.start_scheduled_scan(idx);
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// settings kept between runs, one JSON object in the platform's config directory
+pub fn settings_path() -> Option<PathBuf> {
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("APPDATA").map(PathBuf::from))
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+    Some(config_dir.join("quer").join("settings.json"))
+}
+
+// an empty object if nothing was saved yet or the file is unreadable
+pub fn load_settings() -> json::JsonValue {
+    settings_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|text| json::parse(&text).ok())
+        .filter(|settings| settings.is_object())
+        .unwrap_or_else(json::JsonValue::new_object)
+}
+
+// replaces one top-level entry, leaving the others as they were on disk
+pub fn save_setting(key: &str, value: json::JsonValue) -> Result<PathBuf, String> {
+    let path = settings_path().ok_or("No config directory found")?;
+    let mut settings = load_settings();
+    settings[key] = value;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|err| err.to_string())?;
+    }
+    fs::write(&path, settings.pretty(2)).map_err(|err| err.to_string())?;
+    Ok(path)
+}
@@ -0,0 +1,197 @@
+use crate::i18n::{tr, tr_args};
+use crate::QuerApp;
+use eframe::egui;
+use std::path::{Component, Path, PathBuf, Prefix};
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+// volume shadow copies only exist on Windows
+pub const SHADOW_COPIES_SUPPORTED: bool = cfg!(windows);
+
+// lists the snapshots with the drive letter of their volume and when they were taken
+const LIST_SCRIPT: &str = "$vols = @{}; \
+    Get-CimInstance Win32_Volume | ForEach-Object { $vols[$_.DeviceID] = $_.DriveLetter }; \
+    Get-CimInstance Win32_ShadowCopy | ForEach-Object { \
+    '{0}|{1}|{2}' -f $_.DeviceObject, $vols[$_.VolumeName], $_.InstallDate.ToString('yyyy-MM-dd HH:mm') }";
+
+#[derive(Clone)]
+pub struct ShadowCopy {
+    pub device: String, // \\?\GLOBALROOT\Device\HarddiskVolumeShadowCopyN
+    pub volume: String, // drive letter of the snapshotted volume, e.g. "C:"
+    pub created: String,
+    pub selected: bool,
+}
+
+pub type ShadowCopyListing = Arc<Mutex<Option<Result<Vec<ShadowCopy>, String>>>>;
+
+impl ShadowCopy {
+    // where `path` on the live volume lies inside the snapshot, None for other volumes
+    pub fn map_path(&self, path: &Path) -> Option<PathBuf> {
+        let mut components = path.components();
+        let drive = match components.next()? {
+            Component::Prefix(prefix) => match prefix.kind() {
+                Prefix::Disk(letter) | Prefix::VerbatimDisk(letter) => letter,
+                _ => return None,
+            },
+            _ => return None,
+        };
+        if !self.volume.as_bytes().first()?.eq_ignore_ascii_case(&drive) {
+            return None;
+        }
+        let mut mapped = PathBuf::from(format!("{}\\", self.device));
+        mapped.extend(components.filter(|c| !matches!(c, Component::RootDir)));
+        Some(mapped)
+    }
+}
+
+fn parse_listing(output: &str) -> Vec<ShadowCopy> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.trim().split('|');
+            let device = fields.next()?.trim();
+            let volume = fields.next()?.trim();
+            let created = fields.next().unwrap_or("").trim();
+            // snapshots of volumes without a drive letter can't be reached from a search path
+            if device.is_empty() || volume.is_empty() {
+                return None;
+            }
+            Some(ShadowCopy {
+                device: device.to_string(),
+                volume: volume.to_string(),
+                created: created.to_string(),
+                selected: false,
+            })
+        })
+        .collect()
+}
+
+// asks WMI for the snapshots, which needs admin rights
+pub fn list_shadow_copies() -> Result<Vec<ShadowCopy>, String> {
+    if !SHADOW_COPIES_SUPPORTED {
+        return Err(tr("shadow-copies-unsupported"));
+    }
+    let output = Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", LIST_SCRIPT])
+        .output()
+        .map_err(|err| format!("powershell: {}", err))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(stderr.lines().next().unwrap_or_default().to_string());
+    }
+    Ok(parse_listing(&String::from_utf8_lossy(&output.stdout)))
+}
+
+impl QuerApp {
+    // roots to walk besides the search folder, with the excluded paths mapped alike
+    pub(crate) fn shadow_copy_roots(&self, skip_paths: &[PathBuf]) -> Vec<(PathBuf, Vec<PathBuf>)> {
+        if !self.scan_shadow_copies {
+            return Vec::new();
+        }
+        self.shadow_copies
+            .iter()
+            .filter(|copy| copy.selected)
+            .filter_map(|copy| {
+                let root = copy.map_path(&self.root_folder_path)?;
+                let skips = skip_paths
+                    .iter()
+                    .filter_map(|path| copy.map_path(path))
+                    .collect();
+                Some((root, skips))
+            })
+            .collect()
+    }
+
+    fn refresh_shadow_copies(&mut self, ctx: &egui::Context) {
+        self.shadow_copies_listing = true;
+        let listing = Arc::clone(&self.shadow_copy_listing);
+        let ctx = ctx.clone();
+        thread::spawn(move || {
+            let result = list_shadow_copies();
+            if let Ok(mut listing) = listing.lock() {
+                *listing = Some(result);
+            }
+            ctx.request_repaint();
+        });
+    }
+
+    fn poll_shadow_copies(&mut self) {
+        let result = match self.shadow_copy_listing.lock() {
+            Ok(mut listing) => listing.take(),
+            Err(_) => None,
+        };
+        let Some(result) = result else {
+            return;
+        };
+        self.shadow_copies_listing = false;
+        match result {
+            Ok(copies) => {
+                self.log(format!("Found {} volume shadow copies", copies.len()));
+                self.shadow_copies = copies;
+                self.shadow_copy_error = None;
+            }
+            Err(err) => {
+                self.log(format!("Listing shadow copies failed: {}", err));
+                self.shadow_copy_error = Some(err);
+            }
+        }
+    }
+
+    pub(crate) fn add_shadow_copy_options(&mut self, ui: &mut egui::Ui) {
+        self.poll_shadow_copies();
+        let checkbox = ui
+            .add_enabled(
+                SHADOW_COPIES_SUPPORTED,
+                egui::Checkbox::new(&mut self.scan_shadow_copies, tr("search-shadow-copies")),
+            )
+            .on_hover_text(tr("search-shadow-copies-hint"))
+            .on_disabled_hover_text(tr("shadow-copies-unsupported"));
+        if checkbox.changed()
+            && self.scan_shadow_copies
+            && self.shadow_copies.is_empty()
+            && !self.shadow_copies_listing
+        {
+            self.refresh_shadow_copies(ui.ctx());
+        }
+        if !self.scan_shadow_copies {
+            return;
+        }
+        ui.indent("shadow_copies", |ui| {
+            if self.shadow_copies_listing {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(tr("listing-shadow-copies"));
+                });
+                return;
+            }
+            if let Some(err) = &self.shadow_copy_error {
+                ui.colored_label(egui::Color32::from_rgb(0x8f, 0, 0), err);
+            } else if self.shadow_copies.is_empty() {
+                ui.label(tr("no-shadow-copies"));
+            }
+            for copy in self.shadow_copies.iter_mut() {
+                let name = copy
+                    .device
+                    .rsplit('\\')
+                    .next()
+                    .unwrap_or_default()
+                    .to_string();
+                ui.checkbox(
+                    &mut copy.selected,
+                    tr_args(
+                        "shadow-copy-entry",
+                        &[
+                            ("volume", copy.volume.clone().into()),
+                            ("created", copy.created.clone().into()),
+                        ],
+                    ),
+                )
+                .on_hover_text(name);
+            }
+            if ui.small_button(tr("refresh-shadow-copies")).clicked() {
+                self.refresh_shadow_copies(ui.ctx());
+            }
+        });
+    }
+}
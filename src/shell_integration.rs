@@ -0,0 +1,350 @@
+use crate::i18n::{tr, tr_args};
+use crate::{ContentEnum, QuerApp, ScanModeEnum};
+use eframe::egui;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+pub const URL_SCHEME: &str = "quer";
+// registering is done per user, so neither needs admin rights
+pub const SHELL_INTEGRATION_SUPPORTED: bool = cfg!(any(windows, target_os = "linux"));
+
+// what quer was opened with: a quer:// URL from another tool, or a folder or file from the
+// shell's context menu
+#[derive(Default, Clone, PartialEq)]
+pub struct LaunchRequest {
+    pub root: Option<PathBuf>,
+    pub pattern: Option<(String, ContentEnum)>,
+    pub run: bool, // search right away rather than just filling in the search, after asking
+}
+
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut pos = 0;
+    while pos < bytes.len() {
+        let escaped = (bytes[pos] == b'%')
+            .then(|| bytes.get(pos + 1..pos + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                pos += 3;
+            }
+            None => {
+                decoded.push(bytes[pos]);
+                pos += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+// quer://search?dir=/some/folder&hex=de%20ad&run=1, also with text= (literal) or regex=.
+// "+" is kept as is, regexes need it more than query strings need it for spaces.
+fn parse_url(url: &str) -> Result<LaunchRequest, String> {
+    let rest = url
+        .strip_prefix(URL_SCHEME)
+        .and_then(|rest| rest.strip_prefix(':'))
+        .ok_or("not a quer:// URL")?
+        .trim_start_matches('/');
+    let (action, query) = rest.split_once('?').unwrap_or((rest, ""));
+    if action.trim_end_matches('/') != "search" {
+        return Err(format!("unknown action \"{}\"", action));
+    }
+    let mut request = LaunchRequest::default();
+    for (key, value) in query.split('&').filter_map(|pair| pair.split_once('=')) {
+        let value = percent_decode(value);
+        match key {
+            "dir" | "path" => request.root = Some(PathBuf::from(value)),
+            "hex" => request.pattern = Some((value, ContentEnum::Hex)),
+            "text" => request.pattern = Some((regex::escape(&value), ContentEnum::Text)),
+            "regex" => request.pattern = Some((value, ContentEnum::Text)),
+            "run" => request.run = matches!(value.as_str(), "1" | "true" | "yes"),
+            _ => return Err(format!("unknown parameter \"{}\"", key)),
+        }
+    }
+    Ok(request)
+}
+
+// the command line past the program name, None when there's nothing to open
+pub fn parse_args(args: &[OsString]) -> Option<Result<LaunchRequest, String>> {
+    let arg = args.first()?;
+    let url = arg
+        .to_str()
+        .filter(|arg| arg.starts_with(&format!("{}:", URL_SCHEME)));
+    if let Some(url) = url {
+        return Some(parse_url(url));
+    }
+    Some(Ok(LaunchRequest {
+        root: Some(PathBuf::from(arg)),
+        ..Default::default()
+    }))
+}
+
+fn run(command: &mut Command) -> Result<(), String> {
+    let status = command
+        .status()
+        .map_err(|err| format!("{}: {}", command.get_program().to_string_lossy(), err))?;
+    match status.success() {
+        true => Ok(()),
+        false => Err(format!("{:?} failed ({})", command, status)),
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use super::{run, URL_SCHEME};
+    use std::path::Path;
+    use std::process::Command;
+
+    const CLASSES: &str = r"HKCU\Software\Classes";
+    // folders, the background of an open folder, and any file
+    const MENU_KEYS: [(&str, &str); 3] = [
+        ("Directory", "%1"),
+        ("Directory\\Background", "%V"),
+        ("*", "%1"),
+    ];
+
+    fn reg_set(key: &str, name: Option<&str>, value: &str) -> Result<(), String> {
+        let mut command = Command::new("reg");
+        command.args(["add", key]);
+        match name {
+            Some(name) => command.args(["/v", name]),
+            None => command.arg("/ve"),
+        };
+        run(command.args(["/d", value, "/f"]))
+    }
+
+    pub fn register(exe: &Path, menu_label: &str) -> Result<(), String> {
+        let exe = exe.display();
+        let scheme = format!(r"{}\{}", CLASSES, URL_SCHEME);
+        reg_set(&scheme, None, "URL:quer")?;
+        reg_set(&scheme, Some("URL Protocol"), "")?;
+        reg_set(
+            &format!(r"{}\shell\open\command", scheme),
+            None,
+            &format!("\"{}\" \"%1\"", exe),
+        )?;
+        for (class, arg) in MENU_KEYS {
+            let key = format!(r"{}\{}\shell\quer", CLASSES, class);
+            reg_set(&key, None, menu_label)?;
+            reg_set(&key, Some("Icon"), &exe.to_string())?;
+            reg_set(
+                &format!(r"{}\command", key),
+                None,
+                &format!("\"{}\" \"{}\"", exe, arg),
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn unregister() -> Result<(), String> {
+        let mut keys = vec![format!(r"{}\{}", CLASSES, URL_SCHEME)];
+        keys.extend(
+            MENU_KEYS
+                .iter()
+                .map(|(class, _)| format!(r"{}\{}\shell\quer", CLASSES, class)),
+        );
+        for key in keys {
+            // already gone is fine
+            let _ = run(Command::new("reg").args(["delete", &key, "/f"]));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use super::{run, URL_SCHEME};
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::process::Command;
+
+    const URL_DESKTOP_FILE: &str = "quer-url-handler.desktop";
+    const OPEN_DESKTOP_FILE: &str = "quer-search.desktop";
+    // a fixed name, a translated one would be left behind after switching languages
+    const SCRIPT_FILE: &str = "quer-search";
+
+    fn data_dir() -> Result<PathBuf, String> {
+        std::env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".local/share")))
+            .ok_or_else(|| "No home directory found".to_string())
+    }
+
+    // file managers list this under "Open With" for folders and files, Nautilus and Nemo
+    // also under "Scripts"
+    fn script_paths(data_dir: &Path) -> [PathBuf; 2] {
+        [
+            data_dir.join("nautilus/scripts").join(SCRIPT_FILE),
+            data_dir.join("nemo/scripts").join(SCRIPT_FILE),
+        ]
+    }
+
+    pub fn register(exe: &Path, menu_label: &str) -> Result<(), String> {
+        let data_dir = data_dir()?;
+        let applications = data_dir.join("applications");
+        fs::create_dir_all(&applications).map_err(|err| err.to_string())?;
+        let exe = exe.display();
+        let url_entry = format!(
+            "[Desktop Entry]\nType=Application\nName=quer\nExec=\"{}\" %u\nNoDisplay=true\n\
+             MimeType=x-scheme-handler/{};\n",
+            exe, URL_SCHEME
+        );
+        let open_entry = format!(
+            "[Desktop Entry]\nType=Application\nName={}\nExec=\"{}\" %f\nNoDisplay=true\n\
+             MimeType=inode/directory;application/octet-stream;\n",
+            menu_label, exe
+        );
+        fs::write(applications.join(URL_DESKTOP_FILE), url_entry).map_err(|err| err.to_string())?;
+        fs::write(applications.join(OPEN_DESKTOP_FILE), open_entry)
+            .map_err(|err| err.to_string())?;
+
+        // scripts run in the open folder with the selected names as arguments
+        let script = format!(
+            "#!/bin/sh\nexec \"{}\" \"$(realpath \"${{1:-.}}\")\"\n",
+            exe
+        );
+        for path in script_paths(&data_dir) {
+            // only for file managers that are installed
+            let Some(dir) = path
+                .parent()
+                .filter(|dir| dir.parent().is_some_and(Path::exists))
+            else {
+                continue;
+            };
+            fs::create_dir_all(dir).map_err(|err| err.to_string())?;
+            fs::write(&path, &script).map_err(|err| err.to_string())?;
+            run(Command::new("chmod").arg("+x").arg(&path))?;
+        }
+        run(Command::new("xdg-mime").args([
+            "default",
+            URL_DESKTOP_FILE,
+            &format!("x-scheme-handler/{}", URL_SCHEME),
+        ]))
+    }
+
+    pub fn unregister() -> Result<(), String> {
+        let data_dir = data_dir()?;
+        let applications = data_dir.join("applications");
+        let mut paths = vec![
+            applications.join(URL_DESKTOP_FILE),
+            applications.join(OPEN_DESKTOP_FILE),
+        ];
+        paths.extend(script_paths(&data_dir));
+        for path in paths {
+            if path.exists() {
+                fs::remove_file(&path).map_err(|err| err.to_string())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn register() -> Result<(), String> {
+    let exe = std::env::current_exe().map_err(|err| err.to_string())?;
+    #[cfg(any(windows, target_os = "linux"))]
+    {
+        platform::register(&exe, &tr("search-with-quer"))
+    }
+    #[cfg(not(any(windows, target_os = "linux")))]
+    {
+        let _ = exe;
+        Err("Not supported on this platform".to_string())
+    }
+}
+
+fn unregister() -> Result<(), String> {
+    #[cfg(any(windows, target_os = "linux"))]
+    {
+        platform::unregister()
+    }
+    #[cfg(not(any(windows, target_os = "linux")))]
+    {
+        Err("Not supported on this platform".to_string())
+    }
+}
+
+impl QuerApp {
+    pub(crate) fn apply_launch_request(&mut self, request: LaunchRequest) {
+        if let Some(root) = request.root {
+            self.root_folder_path = fs::canonicalize(&root).unwrap_or(root);
+        }
+        if let Some((pattern, content_type)) = request.pattern {
+            self.regex_str = pattern;
+            self.content_type = content_type;
+            self.scan_mode = ScanModeEnum::Content;
+        }
+        self.update_regex();
+        // any web page can open a quer:// link, so it only fills in the search until confirmed
+        self.confirm_launch_search =
+            request.run && self.regex_result.is_ok() && Path::exists(&self.root_folder_path);
+    }
+
+    pub(crate) fn add_launch_search_confirmation(&mut self, ctx: &egui::Context) {
+        if !self.confirm_launch_search {
+            return;
+        }
+        let mut start = false;
+        let mut cancel = false;
+        egui::Window::new(tr("start-linked-search-title"))
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(tr_args(
+                    "start-linked-search-prompt",
+                    &[("path", self.root_folder_path.display().to_string().into())],
+                ));
+                ui.monospace(&self.regex_str);
+                ui.horizontal(|ui| {
+                    start = ui.button(tr("search")).clicked();
+                    cancel = ui.button(tr("cancel")).clicked();
+                });
+            });
+        if start || cancel {
+            self.confirm_launch_search = false;
+        }
+        if start && self.regex_result.is_ok() && Path::exists(&self.root_folder_path) {
+            self.progress = 0.0;
+            self.search();
+        }
+    }
+
+    pub(crate) fn add_shell_integration_options(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            let register_clicked = ui
+                .add_enabled(
+                    SHELL_INTEGRATION_SUPPORTED,
+                    egui::Button::new(tr("register-shell-integration")),
+                )
+                .on_hover_text(tr("register-shell-integration-hint"))
+                .on_disabled_hover_text(tr("shell-integration-unsupported"))
+                .clicked();
+            if register_clicked {
+                match register() {
+                    Ok(()) => self.log(
+                        "Registered the quer:// URL scheme and the shell menu entries".to_string(),
+                    ),
+                    Err(err) => self.log_error(format!("Couldn't register quer: {}", err)),
+                }
+            }
+            let unregister_clicked = ui
+                .add_enabled(
+                    SHELL_INTEGRATION_SUPPORTED,
+                    egui::Button::new(tr("unregister-shell-integration")),
+                )
+                .clicked();
+            if unregister_clicked {
+                match unregister() {
+                    Ok(()) => self.log(
+                        "Removed the quer:// URL scheme and the shell menu entries".to_string(),
+                    ),
+                    Err(err) => self.log_error(format!("Couldn't unregister quer: {}", err)),
+                }
+            }
+        });
+    }
+}
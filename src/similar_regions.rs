@@ -0,0 +1,194 @@
+use crate::i18n::{tr, tr_args};
+use crate::{ContentEnum, QuerApp, ScanModeEnum};
+use eframe::egui::{self, RichText};
+
+const MAX_CONTEXT: usize = 256;
+const BYTES_PER_ROW: usize = 16;
+
+#[derive(Clone)]
+pub struct SimilarRegion {
+    pub filepath: String,
+    pub match_offset: usize,
+    pub match_size: usize,
+    pub before: usize,
+    pub after: usize,
+    // bytes of the region starting at `start()`, with the matching wildcard flags
+    pub bytes: Vec<u8>,
+    pub wildcards: Vec<bool>,
+}
+
+impl SimilarRegion {
+    pub fn start(&self) -> usize {
+        self.match_offset.saturating_sub(self.before)
+    }
+
+    // simplified hex pattern for the region, wildcarded bytes match anything
+    pub fn pattern(&self) -> String {
+        self.bytes
+            .iter()
+            .zip(self.wildcards.iter())
+            .map(|(byte, wildcard)| {
+                if *wildcard {
+                    // '.' wouldn't match newlines
+                    "[00-ff]".to_string()
+                } else {
+                    format!("{:02X}", byte)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl QuerApp {
+    pub(crate) fn open_similar_regions(&mut self, path: &str, offset: usize, match_size: usize) {
+        let mut region = SimilarRegion {
+            filepath: path.to_string(),
+            match_offset: offset,
+            match_size,
+            before: 0,
+            after: 0,
+            bytes: Vec::new(),
+            wildcards: Vec::new(),
+        };
+        self.load_similar_region(&mut region);
+        self.similar_region = Some(region);
+    }
+
+    fn load_similar_region(&self, region: &mut SimilarRegion) {
+        let start = region.start();
+        let len = region.match_offset - start + region.match_size + region.after;
+        region.bytes = self
+            .get_file_contents(&region.filepath, start, len)
+            .unwrap_or_default();
+        region.wildcards = vec![false; region.bytes.len()];
+    }
+
+    pub(crate) fn add_similar_regions_window(&mut self, ctx: &egui::Context) {
+        let Some(mut region) = self.similar_region.take() else {
+            return;
+        };
+
+        let mut open = true;
+        let mut reload = false;
+        let mut search = false;
+        egui::Window::new(tr("find-similar-regions-title"))
+            .open(&mut open)
+            .resizable(true)
+            .default_width(560.0)
+            .show(ctx, |ui| {
+                ui.label(tr_args(
+                    "region-at",
+                    &[
+                        ("path", region.filepath.as_str().into()),
+                        ("offset", format!("0x{:X}", region.match_offset).into()),
+                        ("size", region.match_size.into()),
+                    ],
+                ));
+                ui.horizontal(|ui| {
+                    let label = ui.label(tr("bytes-before"));
+                    reload |= ui
+                        .add(egui::DragValue::new(&mut region.before).range(0..=MAX_CONTEXT))
+                        .labelled_by(label.id)
+                        .changed();
+                    let label = ui.label(tr("bytes-after"));
+                    reload |= ui
+                        .add(egui::DragValue::new(&mut region.after).range(0..=MAX_CONTEXT))
+                        .labelled_by(label.id)
+                        .changed();
+                });
+                ui.horizontal(|ui| {
+                    ui.label(tr("similar-regions-intro"));
+                    if ui.button(tr("wildcard-all")).clicked() {
+                        region.wildcards.iter_mut().for_each(|w| *w = true);
+                    }
+                    if ui.button(tr("wildcard-none")).clicked() {
+                        region.wildcards.iter_mut().for_each(|w| *w = false);
+                    }
+                    if ui.button(tr("wildcard-context")).clicked() {
+                        let match_start = region.match_offset - region.start();
+                        let match_end = match_start + region.match_size;
+                        for (i, w) in region.wildcards.iter_mut().enumerate() {
+                            *w = i < match_start || i >= match_end;
+                        }
+                    }
+                });
+                ui.separator();
+
+                let start = region.start();
+                let match_range = region.match_offset..region.match_offset + region.match_size;
+                egui::ScrollArea::vertical()
+                    .max_height(320.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("similar_region_bytes")
+                            .spacing([2.0, 2.0])
+                            .show(ui, |ui| {
+                                for (row, chunk) in region.bytes.chunks(BYTES_PER_ROW).enumerate() {
+                                    let row_offset = start + row * BYTES_PER_ROW;
+                                    ui.monospace(format!("{:08X}", row_offset));
+                                    for (i, byte) in chunk.iter().enumerate() {
+                                        let index = row * BYTES_PER_ROW + i;
+                                        let wildcard = &mut region.wildcards[index];
+                                        let mut text = if *wildcard {
+                                            RichText::new("??").monospace().weak()
+                                        } else {
+                                            RichText::new(format!("{:02X}", byte)).monospace()
+                                        };
+                                        if match_range.contains(&(row_offset + i)) {
+                                            text = text.strong().underline();
+                                        }
+                                        if ui.selectable_label(*wildcard, text).clicked() {
+                                            *wildcard = !*wildcard;
+                                        }
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                    });
+
+                ui.separator();
+                let pattern = region.pattern();
+                let label = ui.label(tr("pattern-label"));
+                ui.add(
+                    egui::TextEdit::multiline(&mut pattern.as_str())
+                        .font(egui::TextStyle::Monospace)
+                        .desired_width(f32::INFINITY)
+                        .desired_rows(2),
+                )
+                .labelled_by(label.id);
+                ui.horizontal(|ui| {
+                    let can_search = !region.bytes.is_empty() && self.is_search_finished();
+                    if ui
+                        .add_enabled(can_search, egui::Button::new(tr("search")))
+                        .on_hover_text(tr("similar-regions-search-hint"))
+                        .clicked()
+                    {
+                        search = true;
+                    }
+                    if ui.button(tr("use-as-pattern")).clicked() {
+                        self.regex_str = pattern.clone();
+                        self.content_type = ContentEnum::Hex;
+                    }
+                });
+            });
+
+        if reload {
+            self.load_similar_region(&mut region);
+        }
+
+        if search {
+            self.regex_str = region.pattern();
+            self.content_type = ContentEnum::Hex;
+            self.scan_mode = ScanModeEnum::Content;
+            self.update_regex();
+            if self.regex_result.is_ok() {
+                self.progress = 0.0;
+                self.search();
+            }
+        }
+
+        if open {
+            self.similar_region = Some(region);
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use fuzzyhash::FuzzyHash;
+use memmap2::Mmap;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+pub struct ClusterMember {
+    pub filepath: String,
+    pub ssdeep: String,
+}
+
+pub struct FileCluster {
+    pub members: Vec<ClusterMember>,
+}
+
+pub fn fuzzy_hash_file(path: &Path) -> io::Result<String> {
+    let file = File::open(path)?;
+    if file.metadata()?.len() == 0 {
+        return Ok(FuzzyHash::new(b"").to_string());
+    }
+
+    // same caveat as the search itself: the mapping may change underneath us
+    let data = unsafe { Mmap::map(&file)? };
+    Ok(FuzzyHash::new(&data[..]).to_string())
+}
+
+// single-linkage clustering: any pair scoring >= threshold ends up in the same cluster
+pub fn cluster_files(filepaths: &[String], threshold: u32) -> Vec<FileCluster> {
+    let hashed: Vec<ClusterMember> = filepaths
+        .iter()
+        .filter_map(|filepath| {
+            fuzzy_hash_file(Path::new(filepath))
+                .ok()
+                .map(|ssdeep| ClusterMember {
+                    filepath: filepath.clone(),
+                    ssdeep,
+                })
+        })
+        .collect();
+
+    let mut parents: Vec<usize> = (0..hashed.len()).collect();
+    for i in 0..hashed.len() {
+        for j in (i + 1)..hashed.len() {
+            let score = FuzzyHash::compare(&hashed[i].ssdeep, &hashed[j].ssdeep).unwrap_or(0);
+            if score >= threshold {
+                let (root_i, root_j) = (find_root(&mut parents, i), find_root(&mut parents, j));
+                parents[root_j] = root_i;
+            }
+        }
+    }
+
+    let mut clusters: Vec<Vec<ClusterMember>> = (0..hashed.len()).map(|_| Vec::new()).collect();
+    for (idx, member) in hashed.into_iter().enumerate() {
+        let root = find_root(&mut parents, idx);
+        clusters[root].push(member);
+    }
+
+    let mut clusters: Vec<FileCluster> = clusters
+        .into_iter()
+        .filter(|members| !members.is_empty())
+        .map(|members| FileCluster { members })
+        .collect();
+    clusters.sort_by_key(|c| std::cmp::Reverse(c.members.len()));
+    clusters
+}
+
+fn find_root(parents: &mut [usize], mut idx: usize) -> usize {
+    while parents[idx] != idx {
+        parents[idx] = parents[parents[idx]];
+        idx = parents[idx];
+    }
+    idx
+}
@@ -0,0 +1,251 @@
+use crate::settings::{load_settings, save_setting, settings_path};
+use crate::shell_integration;
+use crate::{tray, QuerApp};
+use eframe::egui;
+use std::ffi::OsString;
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+#[cfg(unix)]
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+const SETTING: &str = "single_instance";
+
+// whether a new launch hands its request over to a running instance
+pub fn single_instance_enabled() -> bool {
+    load_settings()[SETTING].as_bool().unwrap_or(false)
+}
+
+// where the running instance leaves its port and the token other launches have to send.
+// it is written for the owner only on Unix, on Windows the per-user AppData folder keeps
+// other users out
+fn instance_file() -> Option<PathBuf> {
+    let settings = settings_path()?;
+    Some(settings.with_file_name("instance.json"))
+}
+
+fn write_private(path: &Path, text: &str) -> std::io::Result<()> {
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+    let mut file = options.open(path)?;
+    // the mode only applies to new files, one left behind may have been readable
+    #[cfg(unix)]
+    file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    file.write_all(text.as_bytes())
+}
+
+// paths made absolute, the running instance resolves relative ones against its own working
+// directory. None if one isn't UTF-8, this launch opens it itself then.
+fn forwarded_args(args: &[OsString]) -> Option<Vec<String>> {
+    let cwd = std::env::current_dir().ok()?;
+    args.iter()
+        .map(|arg| {
+            let text = arg.to_str()?;
+            if text.starts_with(&format!("{}:", shell_integration::URL_SCHEME)) {
+                return Some(text.to_string());
+            }
+            let path = cwd.join(arg);
+            // Windows would get a \\?\ path, which isn't what the user typed
+            #[cfg(not(windows))]
+            let path = fs::canonicalize(&path).unwrap_or(path);
+            path.into_os_string().into_string().ok()
+        })
+        .collect()
+}
+
+// passes the command line to the running instance, false if there's none to take it
+pub fn forward_to_running_instance(args: &[OsString]) -> bool {
+    let Some(args) = forwarded_args(args) else {
+        return false;
+    };
+    let Some(info) = instance_file()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|text| json::parse(&text).ok())
+    else {
+        return false;
+    };
+    let (Some(port), Some(token)) = (info["port"].as_u16(), info["token"].as_str()) else {
+        return false;
+    };
+    let address = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+    let Ok(mut stream) = TcpStream::connect_timeout(&address, CONNECT_TIMEOUT) else {
+        // left behind by an instance that didn't shut down cleanly
+        return false;
+    };
+    let _ = stream.set_read_timeout(Some(CONNECT_TIMEOUT));
+    let mut message = json::JsonValue::new_object();
+    message["token"] = token.into();
+    message["args"] = args.into();
+    if writeln!(stream, "{}", message.dump()).is_err() {
+        return false;
+    }
+    let mut reply = String::new();
+    BufReader::new(stream).read_line(&mut reply).is_ok() && reply.trim() == "ok"
+}
+
+// the command lines handed over by later launches, waiting to be applied
+type Forwarded = Arc<Mutex<Vec<Vec<String>>>>;
+
+// accepts requests from later launches until dropped
+pub struct InstanceServer {
+    forwarded: Forwarded,
+    stop: Arc<AtomicBool>,
+    token: String,
+}
+
+impl InstanceServer {
+    pub fn start(ctx: &egui::Context) -> Result<Self, String> {
+        let listener =
+            TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).map_err(|err| err.to_string())?;
+        listener
+            .set_nonblocking(true)
+            .map_err(|err| err.to_string())?;
+        let port = listener.local_addr().map_err(|err| err.to_string())?.port();
+        let mut secret = [0_u8; 32];
+        getrandom::getrandom(&mut secret).map_err(|err| err.to_string())?;
+        let token: String = secret.iter().map(|byte| format!("{:02x}", byte)).collect();
+
+        let path = instance_file().ok_or("No config directory found")?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|err| err.to_string())?;
+        }
+        let mut info = json::JsonValue::new_object();
+        info["port"] = port.into();
+        info["token"] = token.as_str().into();
+        write_private(&path, &info.dump()).map_err(|err| err.to_string())?;
+
+        let server = Self {
+            forwarded: Arc::new(Mutex::new(Vec::new())),
+            stop: Arc::new(AtomicBool::new(false)),
+            token: token.clone(),
+        };
+        let (forwarded, stop) = (Arc::clone(&server.forwarded), Arc::clone(&server.stop));
+        let ctx = ctx.clone();
+        thread::spawn(move || {
+            while !stop.load(Ordering::Relaxed) {
+                let Ok((stream, _)) = listener.accept() else {
+                    thread::sleep(POLL_INTERVAL);
+                    continue;
+                };
+                // one at a time, a connection that never sends anything would hold up the rest
+                let (forwarded, token, ctx) = (Arc::clone(&forwarded), token.clone(), ctx.clone());
+                thread::spawn(move || {
+                    if let Some(args) = receive(stream, &token) {
+                        if let Ok(mut forwarded) = forwarded.lock() {
+                            forwarded.push(args);
+                        }
+                        ctx.request_repaint();
+                    }
+                });
+            }
+        });
+        Ok(server)
+    }
+
+    fn take(&self) -> Vec<Vec<String>> {
+        match self.forwarded.lock() {
+            Ok(mut forwarded) => std::mem::take(&mut *forwarded),
+            Err(_) => Vec::new(),
+        }
+    }
+}
+
+impl Drop for InstanceServer {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        // only if no other instance took over in the meantime
+        let Some(path) = instance_file() else {
+            return;
+        };
+        let ours = fs::read_to_string(&path)
+            .ok()
+            .and_then(|text| json::parse(&text).ok())
+            .is_some_and(|info| info["token"].as_str() == Some(self.token.as_str()));
+        if ours {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+fn receive(stream: TcpStream, token: &str) -> Option<Vec<String>> {
+    stream.set_nonblocking(false).ok()?;
+    stream.set_read_timeout(Some(CONNECT_TIMEOUT)).ok()?;
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line).ok()?;
+    let message = json::parse(&line).ok()?;
+    if message["token"].as_str() != Some(token) {
+        return None;
+    }
+    let args = message["args"]
+        .members()
+        .filter_map(|arg| arg.as_str().map(str::to_string))
+        .collect();
+    let _ = writeln!(reader.get_mut(), "ok");
+    Some(args)
+}
+
+impl QuerApp {
+    pub(crate) fn set_single_instance(&mut self, ctx: &egui::Context, enabled: bool) {
+        self.instance_server = None;
+        if enabled {
+            match InstanceServer::start(ctx) {
+                Ok(server) => self.instance_server = Some(Arc::new(server)),
+                Err(err) => self.log_error(format!(
+                    "Couldn't take requests from other launches: {}",
+                    err
+                )),
+            }
+        }
+    }
+
+    pub(crate) fn save_single_instance(&mut self) {
+        if let Err(err) = save_setting(SETTING, self.single_instance.into()) {
+            self.log_error(format!(
+                "Couldn't save the single instance setting: {}",
+                err
+            ));
+        }
+    }
+
+    // requests from later launches are applied one at a time, each once the search before
+    // it is done, so they don't compete for the same disks
+    pub(crate) fn poll_forwarded_launches(&mut self, ctx: &egui::Context) {
+        if let Some(server) = &self.instance_server {
+            let forwarded = server.take();
+            if !forwarded.is_empty() {
+                tray::show_window(ctx, &self.tray_hidden);
+            }
+            for args in forwarded {
+                let args: Vec<OsString> = args.into_iter().map(OsString::from).collect();
+                match shell_integration::parse_args(&args) {
+                    Some(Ok(request)) => self.pending_launches.push_back(request),
+                    Some(Err(err)) => self.log_error(format!(
+                        "Couldn't open {}: {}",
+                        args[0].to_string_lossy(),
+                        err
+                    )),
+                    // launched again without arguments, coming to the front is all it takes
+                    None => {}
+                }
+            }
+        }
+        if self.is_search_finished() {
+            if let Some(request) = self.pending_launches.pop_front() {
+                if let Some(root) = &request.root {
+                    self.log(format!("Opening {} from another launch", root.display()));
+                }
+                self.apply_launch_request(request);
+            }
+        }
+    }
+}
@@ -0,0 +1,256 @@
+use std::fmt::Write;
+
+// nested messages are tried this deep, and long bytes fields are cut short when shown
+const MAX_DEPTH: usize = 8;
+const MAX_SHOWN_BYTES: usize = 32;
+
+const PROTO_VARINT: u64 = 0;
+const PROTO_FIXED64: u64 = 1;
+const PROTO_LENGTH_DELIMITED: u64 = 2;
+const PROTO_FIXED32: u64 = 5;
+const PROTO_MAX_FIELD: u64 = (1 << 29) - 1;
+
+const DER_CONSTRUCTED: u8 = 0x20;
+const DER_CLASS_MASK: u8 = 0xc0;
+const DER_CONTEXT: u8 = 0x80;
+
+// a field or element of a decoded structure, shown as a tree
+pub struct DecodedNode {
+    pub label: String,
+    pub children: Vec<DecodedNode>,
+}
+
+impl DecodedNode {
+    fn leaf(label: String) -> Self {
+        Self {
+            label,
+            children: Vec::new(),
+        }
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    let mut text: String = bytes
+        .iter()
+        .take(MAX_SHOWN_BYTES)
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    if bytes.len() > MAX_SHOWN_BYTES {
+        text.push_str("...");
+    }
+    text
+}
+
+// text that reads like a string rather than binary data that happens to be valid UTF-8
+fn printable(bytes: &[u8]) -> Option<&str> {
+    let text = std::str::from_utf8(bytes).ok()?;
+    (!text.is_empty() && !text.chars().any(|c| c.is_control() && !c.is_whitespace()))
+        .then_some(text)
+}
+
+fn varint(data: &[u8], pos: &mut usize) -> Option<u64> {
+    let mut value = 0_u64;
+    for shift in (0..64).step_by(7) {
+        let byte = *data.get(*pos)?;
+        *pos += 1;
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+    None
+}
+
+// the fields of a protobuf message, None unless all of `data` parses as one
+fn protobuf(data: &[u8], depth: usize) -> Option<Vec<DecodedNode>> {
+    let mut fields = Vec::new();
+    let mut pos = 0;
+    while pos < data.len() {
+        let key = varint(data, &mut pos)?;
+        let (field, wire_type) = (key >> 3, key & 7);
+        if field == 0 || field > PROTO_MAX_FIELD {
+            return None;
+        }
+        let node = match wire_type {
+            PROTO_VARINT => {
+                let value = varint(data, &mut pos)?;
+                let signed = ((value >> 1) as i64) ^ -((value & 1) as i64);
+                DecodedNode::leaf(format!("{}: {} (zigzag {})", field, value, signed))
+            }
+            PROTO_FIXED64 => {
+                let bytes: [u8; 8] = data.get(pos..pos + 8)?.try_into().ok()?;
+                pos += 8;
+                let value = u64::from_le_bytes(bytes);
+                DecodedNode::leaf(format!(
+                    "{}: 0x{:016x} (double {})",
+                    field,
+                    value,
+                    f64::from_bits(value)
+                ))
+            }
+            PROTO_FIXED32 => {
+                let bytes: [u8; 4] = data.get(pos..pos + 4)?.try_into().ok()?;
+                pos += 4;
+                let value = u32::from_le_bytes(bytes);
+                DecodedNode::leaf(format!(
+                    "{}: 0x{:08x} (float {})",
+                    field,
+                    value,
+                    f32::from_bits(value)
+                ))
+            }
+            PROTO_LENGTH_DELIMITED => {
+                let len = usize::try_from(varint(data, &mut pos)?).ok()?;
+                let bytes = data.get(pos..pos.checked_add(len)?)?;
+                pos += len;
+                // strings are tried first, short ones often parse as messages as well
+                if let Some(text) = printable(bytes) {
+                    DecodedNode::leaf(format!("{}: \"{}\"", field, text))
+                } else if let Some(children) = (depth < MAX_DEPTH && !bytes.is_empty())
+                    .then(|| protobuf(bytes, depth + 1))
+                    .flatten()
+                {
+                    DecodedNode {
+                        label: format!("{}: message ({} bytes)", field, len),
+                        children,
+                    }
+                } else {
+                    DecodedNode::leaf(format!("{}: bytes {}", field, hex(bytes)))
+                }
+            }
+            // groups are deprecated and rare enough to rule a guess out
+            _ => return None,
+        };
+        fields.push(node);
+    }
+    (!fields.is_empty()).then_some(fields)
+}
+
+fn der_tag_name(tag: u8) -> String {
+    if tag & DER_CLASS_MASK == DER_CONTEXT {
+        return format!("[{}]", tag & 0x1f);
+    }
+    let name = match tag {
+        0x01 => "BOOLEAN",
+        0x02 => "INTEGER",
+        0x03 => "BIT STRING",
+        0x04 => "OCTET STRING",
+        0x05 => "NULL",
+        0x06 => "OBJECT IDENTIFIER",
+        0x0a => "ENUMERATED",
+        0x0c => "UTF8String",
+        0x13 => "PrintableString",
+        0x14 => "T61String",
+        0x16 => "IA5String",
+        0x17 => "UTCTime",
+        0x18 => "GeneralizedTime",
+        0x1e => "BMPString",
+        0x30 => "SEQUENCE",
+        0x31 => "SET",
+        tag => return format!("tag 0x{:02x}", tag),
+    };
+    name.to_string()
+}
+
+fn der_oid(bytes: &[u8]) -> Option<String> {
+    let first = *bytes.first()?;
+    let mut text = format!("{}.{}", first / 40, first % 40);
+    let mut pos = 1;
+    while pos < bytes.len() {
+        let mut arc = 0_u64;
+        loop {
+            let byte = *bytes.get(pos)?;
+            pos += 1;
+            arc = arc.checked_mul(128)? | (byte & 0x7f) as u64;
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+        let _ = write!(text, ".{}", arc);
+    }
+    Some(text)
+}
+
+fn der_value(tag: u8, bytes: &[u8]) -> String {
+    match tag {
+        0x01 => (bytes.first().copied().unwrap_or(0) != 0).to_string(),
+        0x02 | 0x0a if bytes.len() <= 8 => {
+            let value = bytes
+                .iter()
+                .fold(0_i64, |acc, byte| (acc << 8) | *byte as i64);
+            // sign-extend from the length of the encoding
+            let shift = 64 - bytes.len() as u32 * 8;
+            value
+                .checked_shl(shift)
+                .map_or(value, |v| v >> shift)
+                .to_string()
+        }
+        0x05 => String::new(),
+        0x06 => der_oid(bytes).unwrap_or_else(|| hex(bytes)),
+        0x0c | 0x13 | 0x14 | 0x16 | 0x17 | 0x18 => String::from_utf8_lossy(bytes).into_owned(),
+        _ => match printable(bytes) {
+            Some(text) => format!("\"{}\"", text),
+            None => hex(bytes),
+        },
+    }
+}
+
+// DER elements, None unless all of `data` parses as a run of them
+fn der(data: &[u8], depth: usize) -> Option<Vec<DecodedNode>> {
+    let mut elements = Vec::new();
+    let mut pos = 0;
+    while pos < data.len() {
+        let tag = *data.get(pos)?;
+        // multi-byte tags are too rare to be worth a guess
+        if tag & 0x1f == 0x1f {
+            return None;
+        }
+        let first = *data.get(pos + 1)?;
+        pos += 2;
+        let len = if first & 0x80 == 0 {
+            first as usize
+        } else {
+            let count = (first & 0x7f) as usize;
+            if count == 0 || count > 4 {
+                return None;
+            }
+            let len = data
+                .get(pos..pos + count)?
+                .iter()
+                .fold(0_usize, |acc, byte| (acc << 8) | *byte as usize);
+            pos += count;
+            len
+        };
+        let bytes = data.get(pos..pos.checked_add(len)?)?;
+        pos += len;
+        let node = if tag & DER_CONSTRUCTED != 0 {
+            if depth >= MAX_DEPTH {
+                return None;
+            }
+            DecodedNode {
+                label: format!("{} ({} bytes)", der_tag_name(tag), len),
+                children: if bytes.is_empty() {
+                    Vec::new()
+                } else {
+                    der(bytes, depth + 1)?
+                },
+            }
+        } else {
+            DecodedNode::leaf(format!("{} {}", der_tag_name(tag), der_value(tag, bytes)))
+        };
+        elements.push(node);
+    }
+    (!elements.is_empty()).then_some(elements)
+}
+
+// the matched bytes as DER or protobuf, whichever parses completely, with the format's name
+pub fn decode(data: &[u8]) -> Option<(&'static str, Vec<DecodedNode>)> {
+    // a DER structure starts with a constructed element, short plain protobuf would too often
+    // parse as DER otherwise
+    if data.first().is_some_and(|tag| tag & DER_CONSTRUCTED != 0) {
+        if let Some(nodes) = der(data, 0) {
+            return Some(("DER / ASN.1", nodes));
+        }
+    }
+    protobuf(data, 0).map(|nodes| ("protobuf", nodes))
+}
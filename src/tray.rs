@@ -0,0 +1,178 @@
+use eframe::egui;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+pub const TRAY_SUPPORTED: bool = cfg!(all(feature = "tray", target_os = "linux"));
+
+// updated by the worker threads directly, so progress is known even while the UI isn't drawing
+#[derive(Default)]
+pub struct ScanProgress {
+    pub files_done: AtomicUsize,
+    pub files_total: AtomicUsize,
+}
+
+impl ScanProgress {
+    pub fn new(files_total: usize) -> Self {
+        Self {
+            files_done: AtomicUsize::new(0),
+            files_total: AtomicUsize::new(files_total),
+        }
+    }
+
+    fn status(&self) -> (usize, usize) {
+        (
+            self.files_done.load(Ordering::Relaxed),
+            self.files_total.load(Ordering::Relaxed),
+        )
+    }
+}
+
+pub fn notify(summary: &str, body: &str) {
+    let _ = notify_rust::Notification::new()
+        .appname("quer")
+        .summary(summary)
+        .body(body)
+        .show();
+}
+
+// `hidden` is cleared once the window is shown again, which stops the monitor thread
+pub fn hide_to_tray(ctx: &egui::Context, progress: Arc<ScanProgress>, hidden: Arc<AtomicBool>) {
+    hidden.store(true, Ordering::Relaxed);
+    if TRAY_SUPPORTED {
+        ctx.send_viewport_cmd(egui::ViewportCommand::Visible(false));
+    } else {
+        // no tray to come back from, so at least get out of the way
+        ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(true));
+    }
+    let tray = TrayIcon::spawn(ctx, Arc::clone(&hidden));
+
+    thread::spawn(move || {
+        let (done, total) = progress.status();
+        let mut running = total > 0 && done < total;
+        // without a tray there's nothing to wait for once the notification went out
+        while hidden.load(Ordering::Relaxed) && (tray.is_some() || running) {
+            let (done, total) = progress.status();
+            if let Some(tray) = &tray {
+                tray.set_tooltip(if running {
+                    format!("Scanning: {} / {} files", done, total)
+                } else {
+                    "Idle".to_string()
+                });
+            }
+            if running && done >= total {
+                running = false;
+                notify("quer: scan finished", &format!("Searched {} files", total));
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+
+        if let Some(tray) = tray {
+            tray.shutdown();
+        }
+    });
+}
+
+pub fn show_window(ctx: &egui::Context, hidden: &AtomicBool) {
+    hidden.store(false, Ordering::Relaxed);
+    ctx.send_viewport_cmd(egui::ViewportCommand::Visible(true));
+    ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(false));
+    ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+    ctx.request_repaint();
+}
+
+#[cfg(all(feature = "tray", target_os = "linux"))]
+struct TrayIcon(ksni::Handle<QuerTray>);
+
+#[cfg(all(feature = "tray", target_os = "linux"))]
+struct QuerTray {
+    ctx: egui::Context,
+    hidden: Arc<AtomicBool>,
+    tooltip: String,
+}
+
+#[cfg(all(feature = "tray", target_os = "linux"))]
+impl ksni::Tray for QuerTray {
+    fn id(&self) -> String {
+        "quer".to_string()
+    }
+
+    fn title(&self) -> String {
+        "quer".to_string()
+    }
+
+    fn icon_name(&self) -> String {
+        "system-search".to_string()
+    }
+
+    fn tool_tip(&self) -> ksni::ToolTip {
+        ksni::ToolTip {
+            title: "quer".to_string(),
+            description: self.tooltip.clone(),
+            ..Default::default()
+        }
+    }
+
+    fn activate(&mut self, _x: i32, _y: i32) {
+        show_window(&self.ctx, &self.hidden);
+    }
+
+    // nothing to show the icon, bring the window back rather than leaving it hidden
+    fn watcher_offine(&self) -> bool {
+        show_window(&self.ctx, &self.hidden);
+        false
+    }
+
+    fn menu(&self) -> Vec<ksni::MenuItem<Self>> {
+        vec![ksni::menu::StandardItem {
+            label: "Show quer".to_string(),
+            activate: Box::new(|tray: &mut Self| show_window(&tray.ctx, &tray.hidden)),
+            ..Default::default()
+        }
+        .into()]
+    }
+}
+
+#[cfg(all(feature = "tray", target_os = "linux"))]
+impl TrayIcon {
+    fn spawn(ctx: &egui::Context, hidden: Arc<AtomicBool>) -> Option<Self> {
+        let service = ksni::TrayService::new(QuerTray {
+            ctx: ctx.clone(),
+            hidden: Arc::clone(&hidden),
+            tooltip: String::new(),
+        });
+        let handle = service.handle();
+        let ctx = ctx.clone();
+        thread::spawn(move || {
+            // e.g. no session bus
+            if service.run().is_err() {
+                show_window(&ctx, &hidden);
+            }
+        });
+        Some(TrayIcon(handle))
+    }
+
+    fn set_tooltip(&self, tooltip: String) {
+        self.0.update(|tray| tray.tooltip = tooltip);
+    }
+
+    fn shutdown(&self) {
+        self.0.shutdown();
+    }
+}
+
+#[cfg(not(all(feature = "tray", target_os = "linux")))]
+struct TrayIcon;
+
+#[cfg(not(all(feature = "tray", target_os = "linux")))]
+impl TrayIcon {
+    fn spawn(_ctx: &egui::Context, _hidden: Arc<AtomicBool>) -> Option<Self> {
+        None
+    }
+
+    fn set_tooltip(&self, _tooltip: String) {}
+
+    fn shutdown(&self) {}
+}
@@ -0,0 +1,90 @@
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const BATCH_SIZE: usize = 256;
+const BATCH_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Clone)]
+pub struct WebhookConfig {
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+}
+
+impl WebhookConfig {
+    // headers are entered one per line as "Name: value"
+    pub fn parse_headers(headers_str: &str) -> Vec<(String, String)> {
+        headers_str
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+            .filter(|(name, _value)| !name.is_empty())
+            .collect()
+    }
+}
+
+pub struct WebhookSink {
+    tx: mpsc::Sender<json::JsonValue>,
+    errors: mpsc::Receiver<String>,
+}
+
+impl WebhookSink {
+    // spawns the background poster; dropping the sink flushes what's left and stops it
+    pub fn spawn(config: WebhookConfig) -> Self {
+        let (tx, rx) = mpsc::channel::<json::JsonValue>();
+        let (err_tx, err_rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let mut batch = Vec::with_capacity(BATCH_SIZE);
+            let mut last_post = Instant::now();
+            loop {
+                let disconnected = match rx.recv_timeout(BATCH_INTERVAL) {
+                    Ok(finding) => {
+                        batch.push(finding);
+                        false
+                    }
+                    Err(mpsc::RecvTimeoutError::Timeout) => false,
+                    Err(mpsc::RecvTimeoutError::Disconnected) => true,
+                };
+
+                let should_post = batch.len() >= BATCH_SIZE
+                    || last_post.elapsed() >= BATCH_INTERVAL
+                    || disconnected;
+                if should_post && !batch.is_empty() {
+                    if let Err(err) = post_batch(&config, std::mem::take(&mut batch)) {
+                        let _ = err_tx.send(format!("Webhook POST failed: {}", err));
+                    }
+                    last_post = Instant::now();
+                }
+
+                if disconnected {
+                    return;
+                }
+            }
+        });
+
+        Self { tx, errors: err_rx }
+    }
+
+    pub fn send(&self, finding: json::JsonValue) {
+        let _ = self.tx.send(finding);
+    }
+
+    pub fn take_errors(&self) -> Vec<String> {
+        self.errors.try_iter().collect()
+    }
+}
+
+fn post_batch(config: &WebhookConfig, findings: Vec<json::JsonValue>) -> Result<(), String> {
+    let mut body = json::JsonValue::new_object();
+    body["findings"] = findings.into();
+
+    let mut req = ureq::post(&config.url).set("Content-Type", "application/json");
+    for (name, value) in config.headers.iter() {
+        req = req.set(name, value);
+    }
+
+    req.send_string(&body.dump())
+        .map(|_resp| ())
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,229 @@
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+pub const SCHEDULING_SUPPORTED: bool = cfg!(any(target_os = "linux", windows));
+
+// how the pool's threads are scheduled, applied once as each thread starts
+#[derive(Clone, PartialEq, Default)]
+pub struct WorkerScheduling {
+    pub low_priority: bool,
+    pub cores: Vec<usize>, // workers are spread over these, empty lets the OS decide
+}
+
+impl WorkerScheduling {
+    fn apply(&self, worker_idx: usize, errors: &mpsc::Sender<String>) {
+        if self.low_priority && !lower_priority() {
+            let _ = errors.send(format!(
+                "Couldn't lower the priority of worker {}",
+                worker_idx
+            ));
+        }
+        if !self.cores.is_empty() {
+            let core = self.cores[worker_idx % self.cores.len()];
+            if !pin_to_core(core) {
+                let _ = errors.send(format!(
+                    "Couldn't pin worker {} to core {}",
+                    worker_idx, core
+                ));
+            }
+        }
+    }
+}
+
+// "0-3, 6" style lists, empty for no pinning
+pub fn parse_core_list(text: &str) -> Result<Vec<usize>, String> {
+    let parse = |num: &str| {
+        num.trim()
+            .parse::<usize>()
+            .map_err(|_| format!("'{}' is not a core number", num.trim()))
+    };
+    let mut cores = Vec::new();
+    for part in text
+        .split(',')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+    {
+        match part.split_once('-') {
+            Some((first, last)) => {
+                let (first, last) = (parse(first)?, parse(last)?);
+                if first > last {
+                    return Err(format!("'{}' is an empty range", part));
+                }
+                cores.extend(first..=last);
+            }
+            None => cores.push(parse(part)?),
+        }
+    }
+    Ok(cores)
+}
+
+// both calls only affect the calling thread when given a thread id of 0
+#[cfg(target_os = "linux")]
+fn lower_priority() -> bool {
+    const IOPRIO_WHO_PROCESS: libc::c_int = 1;
+    const IOPRIO_CLASS_IDLE: libc::c_int = 3;
+    const IOPRIO_CLASS_SHIFT: libc::c_int = 13;
+    let niced = unsafe { libc::setpriority(libc::PRIO_PROCESS, 0, 19) } == 0;
+    let idle_io = unsafe {
+        libc::syscall(
+            libc::SYS_ioprio_set,
+            IOPRIO_WHO_PROCESS,
+            0,
+            IOPRIO_CLASS_IDLE << IOPRIO_CLASS_SHIFT,
+        )
+    } == 0;
+    niced && idle_io
+}
+
+#[cfg(target_os = "linux")]
+fn pin_to_core(core: usize) -> bool {
+    if core >= libc::CPU_SETSIZE as usize {
+        return false;
+    }
+    unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        libc::CPU_SET(core, &mut set);
+        libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) == 0
+    }
+}
+
+// background mode lowers the I/O and memory priority along with the CPU priority
+#[cfg(windows)]
+fn lower_priority() -> bool {
+    use windows_sys::Win32::System::Threading::{
+        GetCurrentThread, SetThreadPriority, THREAD_MODE_BACKGROUND_BEGIN,
+    };
+    unsafe { SetThreadPriority(GetCurrentThread(), THREAD_MODE_BACKGROUND_BEGIN) != 0 }
+}
+
+#[cfg(windows)]
+fn pin_to_core(core: usize) -> bool {
+    use windows_sys::Win32::System::Threading::{GetCurrentThread, SetThreadAffinityMask};
+    if core >= usize::BITS as usize {
+        return false;
+    }
+    unsafe { SetThreadAffinityMask(GetCurrentThread(), 1 << core) != 0 }
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn lower_priority() -> bool {
+    false
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn pin_to_core(_core: usize) -> bool {
+    false
+}
+
+// long-lived search threads, searches hand them jobs instead of spawning their own
+pub struct WorkerPool {
+    sender: Mutex<mpsc::Sender<Job>>,
+    busy: Arc<AtomicUsize>, // jobs queued or running
+    size: usize,
+    scheduling: WorkerScheduling,
+    errors: Mutex<mpsc::Receiver<String>>, // for the app's log, workers have no other way there
+}
+
+// counts a job as done even if it panicked
+struct BusyGuard(Arc<AtomicUsize>);
+
+impl Drop for BusyGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+impl WorkerPool {
+    pub fn new(size: usize, scheduling: WorkerScheduling) -> Self {
+        let size = std::cmp::max(size, 1);
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let (err_tx, err_rx) = mpsc::channel();
+        for idx in 0..size {
+            let receiver = Arc::clone(&receiver);
+            let thread_scheduling = scheduling.clone();
+            let errors = err_tx.clone();
+            let spawned = thread::Builder::new()
+                .name(format!("quer-worker-{}", idx))
+                .spawn(move || {
+                    thread_scheduling.apply(idx, &errors);
+                    worker_loop(&receiver, &errors);
+                });
+            if let Err(err) = spawned {
+                let _ = err_tx.send(format!("Couldn't start worker thread {}: {}", idx, err));
+            }
+        }
+
+        Self {
+            sender: Mutex::new(sender),
+            busy: Arc::new(AtomicUsize::new(0)),
+            size,
+            scheduling,
+            errors: Mutex::new(err_rx),
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn scheduling(&self) -> &WorkerScheduling {
+        &self.scheduling
+    }
+
+    pub fn take_errors(&self) -> Vec<String> {
+        match self.errors.lock() {
+            Ok(errors) => errors.try_iter().collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.busy.load(Ordering::Acquire) == 0
+    }
+
+    pub fn submit<F: FnOnce() + Send + 'static>(&self, job: F) {
+        self.busy.fetch_add(1, Ordering::AcqRel);
+        let guard = BusyGuard(Arc::clone(&self.busy));
+        let job: Job = Box::new(move || {
+            let _guard = guard;
+            job();
+        });
+        if let Ok(sender) = self.sender.lock() {
+            // a failed send drops the job, and the guard with it
+            let _ = sender.send(job);
+        }
+    }
+}
+
+// the text a panic was raised with, for the log
+pub fn panic_message(payload: &Box<dyn Any + Send>) -> String {
+    match payload.downcast_ref::<&str>() {
+        Some(message) => message.to_string(),
+        None => match payload.downcast_ref::<String>() {
+            Some(message) => message.clone(),
+            None => "unknown panic".to_string(),
+        },
+    }
+}
+
+fn worker_loop(receiver: &Mutex<mpsc::Receiver<Job>>, errors: &mpsc::Sender<String>) {
+    loop {
+        // the lock is only held while waiting, not while the job runs
+        let job = match receiver.lock() {
+            Ok(receiver) => receiver.recv(),
+            Err(_) => break,
+        };
+        let Ok(job) = job else {
+            break; // the pool was dropped
+        };
+        if let Err(err) = panic::catch_unwind(AssertUnwindSafe(job)) {
+            let _ = errors.send(format!("Worker job panicked: {}", panic_message(&err)));
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use crate::address_map::AddressMap;
+use iced_x86::{Decoder, DecoderOptions, Formatter, Instruction, IntelFormatter, OpKind};
+use memmap2::Mmap;
+use std::fs::File;
+use std::ops::Range;
+use std::sync::{Arc, Mutex};
+
+const MAX_XREFS: usize = 1000;
+
+// an instruction referring to the address of a match
+pub struct Xref {
+    pub offset: u64, // of the instruction in the file
+    pub address: u64,
+    pub text: String,
+}
+
+// filled in by the thread looking for references
+pub type XrefSearch = Arc<Mutex<Option<Result<Vec<Xref>, String>>>>;
+
+// the addresses an instruction refers to: RIP-relative and absolute memory operands, and
+// immediates such as `push offset string` in 32-bit code
+fn referenced(instruction: &Instruction) -> impl Iterator<Item = u64> + '_ {
+    let memory = if instruction.is_ip_rel_memory_operand() {
+        Some(instruction.ip_rel_memory_address())
+    } else if instruction.memory_base() == iced_x86::Register::None {
+        Some(instruction.memory_displacement64()).filter(|address| *address != 0)
+    } else {
+        None
+    };
+    let immediates =
+        (0..instruction.op_count()).filter_map(|operand| match instruction.op_kind(operand) {
+            OpKind::Immediate32
+            | OpKind::Immediate64
+            | OpKind::Immediate32to64
+            | OpKind::Immediate8to32
+            | OpKind::Immediate8to64 => instruction.try_immediate(operand).ok(),
+            _ => None,
+        });
+    memory.into_iter().chain(immediates)
+}
+
+// decodes every x86 code section of an executable front to back, a linear sweep that can
+// miss references hidden behind data mixed into the code
+pub fn find_xrefs(path: &str, offset: u64, match_size: usize) -> Result<Vec<Xref>, String> {
+    let file = File::open(path).map_err(|err| err.to_string())?;
+    let data = unsafe { Mmap::map(&file) }.map_err(|err| err.to_string())?;
+    let map = AddressMap::parse(&data).ok_or("not an executable")?;
+    let address = map
+        .virtual_address(offset)
+        .ok_or("the match isn't loaded into memory")?;
+    let target: Range<u64> = address..address + match_size.max(1) as u64;
+    let sections = map.code_sections();
+    if sections.is_empty() {
+        return Err("no x86 code to search".to_string());
+    }
+
+    let mut formatter = IntelFormatter::new();
+    let mut xrefs = Vec::new();
+    for section in sections {
+        let end = std::cmp::min(section.file_end, data.len() as u64);
+        let Some(code) = data.get(section.file_start as usize..end as usize) else {
+            continue;
+        };
+        let mut decoder =
+            Decoder::with_ip(section.bitness, code, section.address, DecoderOptions::NONE);
+        let mut instruction = Instruction::default();
+        while decoder.can_decode() {
+            decoder.decode_out(&mut instruction);
+            if !referenced(&instruction).any(|address| target.contains(&address)) {
+                continue;
+            }
+            let mut text = String::new();
+            formatter.format(&instruction, &mut text);
+            xrefs.push(Xref {
+                offset: section.file_start + (instruction.ip() - section.address),
+                address: instruction.ip(),
+                text,
+            });
+            if xrefs.len() >= MAX_XREFS {
+                return Ok(xrefs);
+            }
+        }
+    }
+    Ok(xrefs)
+}